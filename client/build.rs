@@ -26,6 +26,8 @@ fn main() {
             .include_item("casper_deploy_params_t")
             .include_item("casper_payment_params_t")
             .include_item("casper_session_params_t")
+            .include_item("casper_payment_params_v2_t")
+            .include_item("casper_session_params_v2_t")
             .generate()
             .expect("Unable to generate bindings")
             .write_to_file(&output_file);
@@ -123,6 +123,26 @@ pub enum Error {
     #[error("Invalid response: {0}")]
     InvalidResponse(#[from] ValidateResponseError),
 
+    /// The contract has no entry point with the given name.
+    #[error("Contract has no entry point '{entry_point}', it has {available:?}")]
+    NoSuchEntryPoint {
+        /// The requested entry point.
+        entry_point: String,
+        /// The names of the contract's entry points.
+        available: Vec<String>,
+    },
+
+    /// An arg of a contract call doesn't match the entry point's parameters.
+    #[error("Invalid arg '{name}' for entry point '{entry_point}': {reason}")]
+    InvalidContractCallArg {
+        /// The entry point being called.
+        entry_point: String,
+        /// The name of the arg.
+        name: String,
+        /// Why the arg is invalid.
+        reason: String,
+    },
+
     /// Failed to create a DictionaryIdentifier
     #[error("Failed to parse the dictionary identifier")]
     FailedToParseDictionaryIdentifier,
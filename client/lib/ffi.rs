@@ -267,14 +267,66 @@ pub extern "C" fn casper_put_deploy(
     payment_params: *const casper_payment_params_t,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
+) -> casper_error_t {
+    let session_params = try_arg_into!(session_params);
+    let payment_params = try_arg_into!(payment_params);
+    put_deploy(
+        maybe_rpc_id,
+        node_address,
+        verbosity_level,
+        deploy_params,
+        session_params,
+        payment_params,
+        response_buf,
+        response_buf_len,
+    )
+}
+
+/// Creates a `Deploy` whose session and payment args may be JSON-encoded and sends it to the
+/// network for execution.
+///
+/// See [super::put_deploy](super::put_deploy) for more details.
+#[no_mangle]
+pub extern "C" fn casper_put_deploy_v2(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbosity_level: u64,
+    deploy_params: *const casper_deploy_params_t,
+    session_params: *const casper_session_params_v2_t,
+    payment_params: *const casper_payment_params_v2_t,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let session_params = try_arg_into!(session_params);
+    let payment_params = try_arg_into!(payment_params);
+    put_deploy(
+        maybe_rpc_id,
+        node_address,
+        verbosity_level,
+        deploy_params,
+        session_params,
+        payment_params,
+        response_buf,
+        response_buf_len,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn put_deploy(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbosity_level: u64,
+    deploy_params: *const casper_deploy_params_t,
+    session_params: super::SessionStrParams<'static>,
+    payment_params: super::PaymentStrParams<'static>,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
 ) -> casper_error_t {
     let mut runtime = RUNTIME.lock().expect("should lock");
     let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
     let deploy_params = try_arg_into!(deploy_params);
-    let session_params = try_arg_into!(session_params);
-    let payment_params = try_arg_into!(payment_params);
     runtime.block_on(async move {
         let result = super::put_deploy(
             maybe_rpc_id,
@@ -301,10 +353,49 @@ pub extern "C" fn casper_make_deploy(
     payment_params: *const casper_payment_params_t,
     force: bool,
 ) -> casper_error_t {
-    let maybe_output_path = try_unsafe_arg!(maybe_output_path);
-    let deploy_params = try_arg_into!(deploy_params);
     let session_params = try_arg_into!(session_params);
     let payment_params = try_arg_into!(payment_params);
+    make_deploy(
+        maybe_output_path,
+        deploy_params,
+        session_params,
+        payment_params,
+        force,
+    )
+}
+
+/// Creates a `Deploy` whose session and payment args may be JSON-encoded and outputs it to a file
+/// or stdout.
+///
+/// See [super::make_deploy](super::make_deploy) for more details.
+#[no_mangle]
+pub extern "C" fn casper_make_deploy_v2(
+    maybe_output_path: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    session_params: *const casper_session_params_v2_t,
+    payment_params: *const casper_payment_params_v2_t,
+    force: bool,
+) -> casper_error_t {
+    let session_params = try_arg_into!(session_params);
+    let payment_params = try_arg_into!(payment_params);
+    make_deploy(
+        maybe_output_path,
+        deploy_params,
+        session_params,
+        payment_params,
+        force,
+    )
+}
+
+fn make_deploy(
+    maybe_output_path: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    session_params: super::SessionStrParams<'static>,
+    payment_params: super::PaymentStrParams<'static>,
+    force: bool,
+) -> casper_error_t {
+    let maybe_output_path = try_unsafe_arg!(maybe_output_path);
+    let deploy_params = try_arg_into!(deploy_params);
     let result = super::make_deploy(
         maybe_output_path,
         deploy_params,
@@ -376,6 +467,65 @@ pub extern "C" fn casper_transfer(
     payment_params: *const casper_payment_params_t,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
+) -> casper_error_t {
+    let payment_params = try_arg_into!(payment_params);
+    transfer(
+        maybe_rpc_id,
+        node_address,
+        verbosity_level,
+        amount,
+        maybe_target_account,
+        transfer_id,
+        deploy_params,
+        payment_params,
+        response_buf,
+        response_buf_len,
+    )
+}
+
+/// Transfers funds between purses, with payment args which may be JSON-encoded.
+///
+/// See [super::transfer](super::transfer) for more details.
+#[no_mangle]
+pub extern "C" fn casper_transfer_v2(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbosity_level: u64,
+    amount: *const c_char,
+    maybe_target_account: *const c_char,
+    transfer_id: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: *const casper_payment_params_v2_t,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let payment_params = try_arg_into!(payment_params);
+    transfer(
+        maybe_rpc_id,
+        node_address,
+        verbosity_level,
+        amount,
+        maybe_target_account,
+        transfer_id,
+        deploy_params,
+        payment_params,
+        response_buf,
+        response_buf_len,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn transfer(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbosity_level: u64,
+    amount: *const c_char,
+    maybe_target_account: *const c_char,
+    transfer_id: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: super::PaymentStrParams<'static>,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
 ) -> casper_error_t {
     let mut runtime = RUNTIME.lock().expect("should lock");
     let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
@@ -385,7 +535,6 @@ pub extern "C" fn casper_transfer(
     let maybe_target_account = try_unsafe_arg!(maybe_target_account);
     let transfer_id = try_unsafe_arg!(transfer_id);
     let deploy_params = try_arg_into!(deploy_params);
-    let payment_params = try_arg_into!(payment_params);
     runtime.block_on(async move {
         let result = super::transfer(
             maybe_rpc_id,
@@ -415,13 +564,59 @@ pub extern "C" fn casper_make_transfer(
     deploy_params: *const casper_deploy_params_t,
     payment_params: *const casper_payment_params_t,
     force: bool,
+) -> casper_error_t {
+    let payment_params = try_arg_into!(payment_params);
+    make_transfer(
+        maybe_output_path,
+        amount,
+        maybe_target_account,
+        transfer_id,
+        deploy_params,
+        payment_params,
+        force,
+    )
+}
+
+/// Creates a transfer `Deploy` with payment args which may be JSON-encoded and outputs it to a
+/// file or stdout.
+///
+/// See [super::make_transfer](super::make_transfer) for more details.
+#[no_mangle]
+pub extern "C" fn casper_make_transfer_v2(
+    maybe_output_path: *const c_char,
+    amount: *const c_char,
+    maybe_target_account: *const c_char,
+    transfer_id: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: *const casper_payment_params_v2_t,
+    force: bool,
+) -> casper_error_t {
+    let payment_params = try_arg_into!(payment_params);
+    make_transfer(
+        maybe_output_path,
+        amount,
+        maybe_target_account,
+        transfer_id,
+        deploy_params,
+        payment_params,
+        force,
+    )
+}
+
+fn make_transfer(
+    maybe_output_path: *const c_char,
+    amount: *const c_char,
+    maybe_target_account: *const c_char,
+    transfer_id: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: super::PaymentStrParams<'static>,
+    force: bool,
 ) -> casper_error_t {
     let maybe_output_path = try_unsafe_arg!(maybe_output_path);
     let amount = try_unsafe_arg!(amount);
     let maybe_target_account = try_unsafe_arg!(maybe_target_account);
     let transfer_id = try_unsafe_arg!(transfer_id);
     let deploy_params = try_arg_into!(deploy_params);
-    let payment_params = try_arg_into!(payment_params);
     let result = super::make_transfer(
         maybe_output_path,
         amount,
@@ -771,6 +966,7 @@ impl TryInto<super::PaymentStrParams<'static>> for casper_payment_params_t {
             payment_path,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json: "",
             payment_version,
             payment_entry_point,
         })
@@ -840,9 +1036,58 @@ impl TryInto<super::SessionStrParams<'static>> for casper_session_params_t {
             session_path,
             session_args_simple,
             session_args_complex,
+            session_args_json: "",
             session_version,
             session_entry_point,
             is_session_transfer: self.is_session_transfer,
         })
     }
 }
+
+/// Container for `Payment` construction options, including JSON-encoded payment args.
+///
+/// See [PaymentStrParams](super::PaymentStrParams) for more info.
+#[allow(non_snake_case)]
+#[repr(C)]
+#[derive(Clone)]
+pub struct casper_payment_params_v2_t {
+    payment_params: casper_payment_params_t,
+    payment_args_json: *const c_char,
+}
+
+impl TryInto<super::PaymentStrParams<'static>> for casper_payment_params_v2_t {
+    type Error = Error;
+
+    fn try_into(self) -> Result<super::PaymentStrParams<'static>> {
+        let mut payment_params: super::PaymentStrParams = self.payment_params.try_into()?;
+        payment_params.payment_args_json = unsafe_str_arg(
+            self.payment_args_json,
+            "casper_payment_params_v2_t.payment_args_json",
+        )?;
+        Ok(payment_params)
+    }
+}
+
+/// Container for `Session` construction options, including JSON-encoded session args.
+///
+/// See [SessionStrParams](super::SessionStrParams) for more info.
+#[allow(non_snake_case)]
+#[repr(C)]
+#[derive(Clone)]
+pub struct casper_session_params_v2_t {
+    session_params: casper_session_params_t,
+    session_args_json: *const c_char,
+}
+
+impl TryInto<super::SessionStrParams<'static>> for casper_session_params_v2_t {
+    type Error = Error;
+
+    fn try_into(self) -> Result<super::SessionStrParams<'static>> {
+        let mut session_params: super::SessionStrParams = self.session_params.try_into()?;
+        session_params.session_args_json = unsafe_str_arg(
+            self.session_args_json,
+            "casper_session_params_v2_t.session_args_json",
+        )?;
+        Ok(session_params)
+    }
+}
@@ -28,7 +28,10 @@ use jsonrpc_lite::JsonRpc;
 use serde::Serialize;
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_node::{rpcs::state::DictionaryIdentifier, types::Deploy};
+use casper_node::{
+    rpcs::state::{ContractIdentifier, DictionaryIdentifier},
+    types::Deploy,
+};
 use casper_types::{Key, UIntParseError, U512};
 
 pub use cl_type::help;
@@ -398,6 +401,54 @@ pub fn get_item(
     RpcCall::new(maybe_rpc_id, node_address, verbosity_level).get_item(state_root_hash, key, path)
 }
 
+/// Retrieves a stored value from the network, verifying it against a trusted switch block.
+///
+/// Rather than trusting the node to report the correct state root hash, the value is looked up in
+/// the global state of a block which has been finalized by the validators of its era.  The
+/// validators of the era following a trusted switch block are named in that block, and those of
+/// each later era in the previous era's switch block, which is verified in turn.  The block must be
+/// in an era after the trusted switch block's, and more than `finality_threshold_fraction` of its
+/// era's validators' total weight must have signed it.  Every Merkle proof in the response must
+/// lead to the block's state root hash, so a forged response is rejected with
+/// [`Error::InvalidResponse`](enum.Error.html#variant.InvalidResponse).
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `trusted_switch_block_hash` must be a hex-encoded, 32-byte hash digest of a switch block
+///   obtained through a trustworthy channel.
+/// * `finality_threshold_fraction` is the fraction of the validators' total weight which must be
+///   exceeded by the weight of those who signed a block, e.g. `"1/3"` as in the production
+///   chainspec.  It must be less than 1.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty. If empty, the latest `Block` will be used.
+/// * `key` and `path` are as for [`get_item`](fn.get_item.html).
+pub fn get_item_verified(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    trusted_switch_block_hash: &str,
+    finality_threshold_fraction: &str,
+    maybe_block_id: &str,
+    key: &str,
+    path: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level).get_item_verified(
+        trusted_switch_block_hash,
+        finality_threshold_fraction,
+        maybe_block_id,
+        key,
+        path,
+    )
+}
+
 /// Retrieves a purse's balance from the network.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -500,6 +551,35 @@ pub fn get_account_info(
         .get_account_info(public_key, maybe_block_id)
 }
 
+/// Retrieves the deploys and transfers which touched an Account, oldest first.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `account` is either the hex-encoded public key associated with the `Account` or its formatted
+///   account hash, e.g. `"account-hash-<HEX STRING>"`.
+/// * `offset` must be a `u64` giving the number of history entries to skip.
+/// * `limit` must be a `u64` giving the maximum number of history entries to return.  The node caps
+///   this at 100 entries per request.
+pub fn get_account_history(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    account: &str,
+    offset: &str,
+    limit: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
+        .get_account_history(account, offset, limit)
+}
+
 /// Retrieves information and examples for all currently supported RPCs.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -541,6 +621,76 @@ pub fn get_dictionary(
         .get_dictionary_item(state_root_hash, dictionary_str_params)
 }
 
+/// Retrieves the schema of a stored contract: its entry points with their typed parameters, and the
+/// versions and user groups of its contract package.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty. If empty, the latest `Block` will be used.
+/// * `contract_identifier` identifies the contract. See
+///   [`ContractIdentifierStrParams`](struct.ContractIdentifierStrParams.html) for more details.
+pub fn get_contract_schema(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    maybe_block_id: &str,
+    contract_identifier: ContractIdentifierStrParams<'_>,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
+        .get_contract_schema(maybe_block_id, contract_identifier.try_into()?)
+}
+
+/// Creates a `Deploy` calling an entry point of a stored contract, and sends it to the network for
+/// execution.
+///
+/// The contract's schema is retrieved from the node first, and the args are validated against and
+/// typed from the entry point's parameters.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty, and identifies the `Block` as of which the contract's schema is
+///   retrieved. If empty, the latest `Block` will be used.
+/// * `deploy_params` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `contract_call` identifies the contract, entry point and args of the call. See
+///   [`ContractCallStrParams`](struct.ContractCallStrParams.html) for more details.
+/// * `payment_params` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+pub fn call_contract(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    maybe_block_id: &str,
+    deploy_params: DeployStrParams<'_>,
+    contract_call: ContractCallStrParams<'_>,
+    payment_params: PaymentStrParams<'_>,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level).call_contract(
+        maybe_block_id,
+        deploy_params.try_into()?,
+        contract_call,
+        payment_params.try_into()?,
+    )
+}
+
 /// Container for `Deploy` construction options.
 #[derive(Default, Debug)]
 pub struct DeployStrParams<'a> {
@@ -654,10 +804,32 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// For methods taking `payment_args_complex`, this parameter is the payment contract arguments, in
 /// the form of a `ToBytes`-encoded file.
 ///
+/// ## `payment_args_json`
+///
+/// For methods taking `payment_args_json`, this parameter is the payment contract arguments, in
+/// the form of a JSON array of typed args.  Each arg is an object holding its `name`, its `type`
+/// as a JSON-encoded `CLType`, and its `value` in the form produced by
+/// [`casper_types::cl_value_to_json`], e.g.
+///
+/// ```json
+/// [
+///   {"name": "amount", "type": "U512", "value": "100"},
+///   {"name": "ids", "type": {"List": "U64"}, "value": [1, 2]},
+///   {"name": "maybe_key", "type": {"Option": "Key"}, "value": null},
+///   {"name": "pair", "type": {"Tuple2": ["String", "Bool"]}, "value": ["a", true]},
+///   {"name": "result", "type": {"Result": {"ok": "U8", "err": "String"}}, "value": {"Ok": 1}},
+///   {
+///     "name": "balances",
+///     "type": {"Map": {"key": "String", "value": "U512"}},
+///     "value": [{"key": "alice", "value": "10"}]
+///   }
+/// ]
+/// ```
+///
 /// ---
 ///
 /// **Note** while multiple payment args can be specified for a single payment code instance, only
-/// one of `payment_args_simple` and `payment_args_complex` may be used.
+/// one of `payment_args_simple`, `payment_args_complex` and `payment_args_json` may be used.
 #[derive(Default)]
 pub struct PaymentStrParams<'a> {
     payment_amount: &'a str,
@@ -668,6 +840,7 @@ pub struct PaymentStrParams<'a> {
     payment_path: &'a str,
     payment_args_simple: Vec<&'a str>,
     payment_args_complex: &'a str,
+    payment_args_json: &'a str,
     payment_version: &'a str,
     payment_entry_point: &'a str,
 }
@@ -685,6 +858,7 @@ impl<'a> TryInto<ExecutableDeployItem> for PaymentStrParams<'a> {
             payment_path,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             payment_version,
             payment_entry_point,
         } = self;
@@ -698,6 +872,7 @@ impl<'a> TryInto<ExecutableDeployItem> for PaymentStrParams<'a> {
             payment_path,
             &payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             payment_version,
             payment_entry_point,
         )
@@ -708,17 +883,20 @@ impl<'a> PaymentStrParams<'a> {
     /// Constructs a `PaymentStrParams` using a payment smart contract file.
     ///
     /// * `payment_path` is the path to the compiled Wasm payment code.
-    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple) and
-    ///   [`payment_args_complex`](#payment_args_complex).
+    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple),
+    ///   [`payment_args_complex`](#payment_args_complex) and
+    ///   [`payment_args_json`](#payment_args_json).
     pub fn with_path(
         payment_path: &'a str,
         payment_args_simple: Vec<&'a str>,
         payment_args_complex: &'a str,
+        payment_args_json: &'a str,
     ) -> Self {
         Self {
             payment_path,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             ..Default::default()
         }
     }
@@ -740,19 +918,22 @@ impl<'a> PaymentStrParams<'a> {
     ///   to be called as the payment.
     /// * `payment_entry_point` is the name of the method that will be used when calling the payment
     ///   contract.
-    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple) and
-    ///   [`payment_args_complex`](#payment_args_complex).
+    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple),
+    ///   [`payment_args_complex`](#payment_args_complex) and
+    ///   [`payment_args_json`](#payment_args_json).
     pub fn with_name(
         payment_name: &'a str,
         payment_entry_point: &'a str,
         payment_args_simple: Vec<&'a str>,
         payment_args_complex: &'a str,
+        payment_args_json: &'a str,
     ) -> Self {
         Self {
             payment_name,
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             ..Default::default()
         }
     }
@@ -762,19 +943,22 @@ impl<'a> PaymentStrParams<'a> {
     /// * `payment_hash` is the hex-encoded hash of the stored contract to be called as the payment.
     /// * `payment_entry_point` is the name of the method that will be used when calling the payment
     ///   contract.
-    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple) and
-    ///   [`payment_args_complex`](#payment_args_complex).
+    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple),
+    ///   [`payment_args_complex`](#payment_args_complex) and
+    ///   [`payment_args_json`](#payment_args_json).
     pub fn with_hash(
         payment_hash: &'a str,
         payment_entry_point: &'a str,
         payment_args_simple: Vec<&'a str>,
         payment_args_complex: &'a str,
+        payment_args_json: &'a str,
     ) -> Self {
         Self {
             payment_hash,
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             ..Default::default()
         }
     }
@@ -786,14 +970,16 @@ impl<'a> PaymentStrParams<'a> {
     ///   if `payment_version` is empty.
     /// * `payment_entry_point` is the name of the method that will be used when calling the payment
     ///   contract.
-    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple) and
-    ///   [`payment_args_complex`](#payment_args_complex).
+    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple),
+    ///   [`payment_args_complex`](#payment_args_complex) and
+    ///   [`payment_args_json`](#payment_args_json).
     pub fn with_package_name(
         payment_package_name: &'a str,
         payment_version: &'a str,
         payment_entry_point: &'a str,
         payment_args_simple: Vec<&'a str>,
         payment_args_complex: &'a str,
+        payment_args_json: &'a str,
     ) -> Self {
         Self {
             payment_package_name,
@@ -801,6 +987,7 @@ impl<'a> PaymentStrParams<'a> {
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             ..Default::default()
         }
     }
@@ -813,14 +1000,16 @@ impl<'a> PaymentStrParams<'a> {
     ///   if `payment_version` is empty.
     /// * `payment_entry_point` is the name of the method that will be used when calling the payment
     ///   contract.
-    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple) and
-    ///   [`payment_args_complex`](#payment_args_complex).
+    /// * See the struct docs for a description of [`payment_args_simple`](#payment_args_simple),
+    ///   [`payment_args_complex`](#payment_args_complex) and
+    ///   [`payment_args_json`](#payment_args_json).
     pub fn with_package_hash(
         payment_package_hash: &'a str,
         payment_version: &'a str,
         payment_entry_point: &'a str,
         payment_args_simple: Vec<&'a str>,
         payment_args_complex: &'a str,
+        payment_args_json: &'a str,
     ) -> Self {
         Self {
             payment_package_hash,
@@ -828,6 +1017,7 @@ impl<'a> PaymentStrParams<'a> {
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             ..Default::default()
         }
     }
@@ -845,6 +1035,7 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
             session_path,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             session_version,
             session_entry_point,
             is_session_transfer,
@@ -858,6 +1049,7 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
             session_path,
             &session_args_simple,
             session_args_complex,
+            session_args_json,
             session_version,
             session_entry_point,
             is_session_transfer,
@@ -881,10 +1073,19 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
 /// For methods taking `session_args_complex`, this parameter is the session contract arguments, in
 /// the form of a `ToBytes`-encoded file.
 ///
+/// ## `session_args_json`
+///
+/// For methods taking `session_args_json`, this parameter is the session contract arguments, in
+/// the form of a JSON array of typed args.
+///
+/// There are further details in
+/// [the docs for the equivalent
+/// `payment_args_json`](struct.PaymentStrParams.html#payment_args_json).
+///
 /// ---
 ///
 /// **Note** while multiple payment args can be specified for a single session code instance, only
-/// one of `session_args_simple` and `session_args_complex` may be used.
+/// one of `session_args_simple`, `session_args_complex` and `session_args_json` may be used.
 #[derive(Default)]
 pub struct SessionStrParams<'a> {
     session_hash: &'a str,
@@ -894,6 +1095,7 @@ pub struct SessionStrParams<'a> {
     session_path: &'a str,
     session_args_simple: Vec<&'a str>,
     session_args_complex: &'a str,
+    session_args_json: &'a str,
     session_version: &'a str,
     session_entry_point: &'a str,
     is_session_transfer: bool,
@@ -903,17 +1105,20 @@ impl<'a> SessionStrParams<'a> {
     /// Constructs a `SessionStrParams` using a session smart contract file.
     ///
     /// * `session_path` is the path to the compiled Wasm session code.
-    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple) and
-    ///   [`session_args_complex`](#session_args_complex).
+    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple),
+    ///   [`session_args_complex`](#session_args_complex) and
+    ///   [`session_args_json`](#session_args_json).
     pub fn with_path(
         session_path: &'a str,
         session_args_simple: Vec<&'a str>,
         session_args_complex: &'a str,
+        session_args_json: &'a str,
     ) -> Self {
         Self {
             session_path,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             ..Default::default()
         }
     }
@@ -924,19 +1129,22 @@ impl<'a> SessionStrParams<'a> {
     ///   to be called as the session.
     /// * `session_entry_point` is the name of the method that will be used when calling the session
     ///   contract.
-    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple) and
-    ///   [`session_args_complex`](#session_args_complex).
+    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple),
+    ///   [`session_args_complex`](#session_args_complex) and
+    ///   [`session_args_json`](#session_args_json).
     pub fn with_name(
         session_name: &'a str,
         session_entry_point: &'a str,
         session_args_simple: Vec<&'a str>,
         session_args_complex: &'a str,
+        session_args_json: &'a str,
     ) -> Self {
         Self {
             session_name,
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             ..Default::default()
         }
     }
@@ -946,19 +1154,22 @@ impl<'a> SessionStrParams<'a> {
     /// * `session_hash` is the hex-encoded hash of the stored contract to be called as the session.
     /// * `session_entry_point` is the name of the method that will be used when calling the session
     ///   contract.
-    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple) and
-    ///   [`session_args_complex`](#session_args_complex).
+    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple),
+    ///   [`session_args_complex`](#session_args_complex) and
+    ///   [`session_args_json`](#session_args_json).
     pub fn with_hash(
         session_hash: &'a str,
         session_entry_point: &'a str,
         session_args_simple: Vec<&'a str>,
         session_args_complex: &'a str,
+        session_args_json: &'a str,
     ) -> Self {
         Self {
             session_hash,
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             ..Default::default()
         }
     }
@@ -970,14 +1181,16 @@ impl<'a> SessionStrParams<'a> {
     ///   if `session_version` is empty.
     /// * `session_entry_point` is the name of the method that will be used when calling the session
     ///   contract.
-    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple) and
-    ///   [`session_args_complex`](#session_args_complex).
+    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple),
+    ///   [`session_args_complex`](#session_args_complex) and
+    ///   [`session_args_json`](#session_args_json).
     pub fn with_package_name(
         session_package_name: &'a str,
         session_version: &'a str,
         session_entry_point: &'a str,
         session_args_simple: Vec<&'a str>,
         session_args_complex: &'a str,
+        session_args_json: &'a str,
     ) -> Self {
         Self {
             session_package_name,
@@ -985,6 +1198,7 @@ impl<'a> SessionStrParams<'a> {
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             ..Default::default()
         }
     }
@@ -997,14 +1211,16 @@ impl<'a> SessionStrParams<'a> {
     ///   if `session_version` is empty.
     /// * `session_entry_point` is the name of the method that will be used when calling the session
     ///   contract.
-    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple) and
-    ///   [`session_args_complex`](#session_args_complex).
+    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple),
+    ///   [`session_args_complex`](#session_args_complex) and
+    ///   [`session_args_json`](#session_args_json).
     pub fn with_package_hash(
         session_package_hash: &'a str,
         session_version: &'a str,
         session_entry_point: &'a str,
         session_args_simple: Vec<&'a str>,
         session_args_complex: &'a str,
+        session_args_json: &'a str,
     ) -> Self {
         Self {
             session_package_hash,
@@ -1012,24 +1228,87 @@ impl<'a> SessionStrParams<'a> {
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             ..Default::default()
         }
     }
 
     /// Constructs a `SessionStrParams` representing a `Transfer` type of `Deploy`.
     ///
-    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple) and
-    ///   [`session_args_complex`](#session_args_complex).
-    pub fn with_transfer(session_args_simple: Vec<&'a str>, session_args_complex: &'a str) -> Self {
+    /// * See the struct docs for a description of [`session_args_simple`](#session_args_simple),
+    ///   [`session_args_complex`](#session_args_complex) and
+    ///   [`session_args_json`](#session_args_json).
+    pub fn with_transfer(
+        session_args_simple: Vec<&'a str>,
+        session_args_complex: &'a str,
+        session_args_json: &'a str,
+    ) -> Self {
         Self {
             is_session_transfer: true,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             ..Default::default()
         }
     }
 }
 
+/// Options identifying a stored contract.
+#[derive(Default, Debug)]
+pub struct ContractIdentifierStrParams<'a> {
+    /// Hex-encoded hash of the contract, or the same formatted as a key, e.g. `"hash-<HEX>"`.
+    ///
+    /// Must be empty if `contract_package_hash` is given.
+    pub contract_hash: &'a str,
+    /// Hex-encoded hash of the contract package, or the same formatted as a key.
+    ///
+    /// Must be empty if `contract_hash` is given.
+    pub contract_package_hash: &'a str,
+    /// Version of the contract within the package.  If empty, the latest enabled version is used.
+    ///
+    /// Can only be given together with `contract_package_hash`.
+    pub contract_version: &'a str,
+}
+
+impl<'a> TryInto<ContractIdentifier> for ContractIdentifierStrParams<'a> {
+    type Error = Error;
+
+    fn try_into(self) -> Result<ContractIdentifier> {
+        parsing::parse_contract_identifier(
+            self.contract_hash,
+            self.contract_package_hash,
+            self.contract_version,
+        )
+    }
+}
+
+/// Container for the options of a call to a stored contract's entry point.
+///
+/// ## `args`
+///
+/// Each arg has the form `NAME=VALUE`, where `NAME` is one of the entry point's parameters and
+/// `VALUE` is the JSON representation of a value of the parameter's type, as given by the
+/// contract's schema.  As the type is known, the quotes around a JSON string can be omitted, and a
+/// `Key` can be given as a formatted string.  Parameters of an `Option` type may be left out, in
+/// which case `None` is passed.
+///
+/// Example inputs for an entry point taking a `Key`, a `U512` and a `List` of `String`s are:
+///
+/// ```text
+/// recipient=account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20
+/// amount=1000000
+/// memos=["first","second"]
+/// ```
+#[derive(Default, Debug)]
+pub struct ContractCallStrParams<'a> {
+    /// Identifies the contract to be called.
+    pub contract_identifier: ContractIdentifierStrParams<'a>,
+    /// Name of the entry point to be called.
+    pub entry_point: &'a str,
+    /// The args of the call.
+    pub args: Vec<&'a str>,
+}
+
 /// Various ways of uniquely identifying a dictionary entry.
 pub enum DictionaryItemStrParams<'a> {
     /// Lookup a dictionary item via an Account's named keys.
@@ -8,12 +8,14 @@ use serde::{self, Deserialize};
 use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
 use casper_node::{
     crypto::{hash::Digest, AsymmetricKeyExt},
-    types::{DeployHash, TimeDiff, Timestamp},
+    rpcs::state::ContractIdentifier,
+    types::{json_compatibility::ContractSchema, DeployHash, TimeDiff, Timestamp},
 };
 use casper_types::{
-    bytesrepr, AsymmetricType, CLType, CLValue, HashAddr, Key, NamedArg, PublicKey, RuntimeArgs,
-    SecretKey, UIntParseError, U512,
+    bytesrepr, AsymmetricType, CLType, CLValue, ContractPackageHash, HashAddr, Key, NamedArg,
+    PublicKey, RuntimeArgs, SecretKey, UIntParseError, U512,
 };
+use serde_json::Value;
 
 use crate::{
     cl_type,
@@ -209,6 +211,69 @@ mod args_complex {
     }
 }
 
+/// Handles providing the arg for and retrieval of JSON-encoded session and payment args.  These are
+/// given as a JSON array of objects, each holding the arg's `name`, its `type` as a JSON-encoded
+/// `CLType`, and its `value` in the form produced by `casper_types::cl_value_to_json`.
+mod args_json {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct JsonArg {
+        /// Deploy argument's name.
+        name: String,
+        #[serde(rename = "type")]
+        cl_type: CLType,
+        value: Value,
+    }
+
+    pub(crate) mod session {
+        use super::*;
+
+        pub fn parse(json: &str) -> Result<Option<RuntimeArgs>> {
+            Ok(if json.is_empty() {
+                None
+            } else {
+                Some(get(json)?)
+            })
+        }
+    }
+
+    pub(crate) mod payment {
+        use super::*;
+
+        pub fn parse(json: &str) -> Result<Option<RuntimeArgs>> {
+            Ok(if json.is_empty() {
+                None
+            } else {
+                Some(get(json)?)
+            })
+        }
+    }
+
+    fn get(json: &str) -> Result<RuntimeArgs> {
+        let args: Vec<JsonArg> = serde_json::from_str(json).map_err(|error| {
+            Error::InvalidCLValue(format!("failed to parse JSON args: {}", error))
+        })?;
+        let mut runtime_args = RuntimeArgs::new();
+        for JsonArg {
+            name,
+            cl_type,
+            value,
+        } in args
+        {
+            let cl_value = casper_types::cl_value_from_json(&cl_type, &value).ok_or_else(|| {
+                Error::InvalidCLValue(format!(
+                    "value {} of arg {} is not a valid {:?}",
+                    value, name, cl_type
+                ))
+            })?;
+            runtime_args.insert_cl_value(name, cl_value);
+        }
+        Ok(runtime_args)
+    }
+}
+
 const STANDARD_PAYMENT_ARG_NAME: &str = "amount";
 fn standard_payment(value: &str) -> Result<RuntimeArgs> {
     if value.is_empty() {
@@ -229,18 +294,35 @@ pub(crate) fn secret_key(value: &str) -> Result<SecretKey> {
     })
 }
 
-fn args_from_simple_or_complex(
+fn args_from_simple_complex_or_json(
     simple: Option<RuntimeArgs>,
     complex: Option<RuntimeArgs>,
+    json: Option<RuntimeArgs>,
 ) -> RuntimeArgs {
-    // We can have exactly zero or one of the two as `Some`.
-    match (simple, complex) {
-        (Some(args), None) | (None, Some(args)) => args,
-        (None, None) => RuntimeArgs::new(),
-        (Some(_), Some(_)) => unreachable!("should not have both simple and complex args"),
+    // We can have exactly zero or one of the three as `Some`.
+    match (simple, complex, json) {
+        (Some(args), None, None) | (None, Some(args), None) | (None, None, Some(args)) => args,
+        (None, None, None) => RuntimeArgs::new(),
+        _ => unreachable!("should not have more than one of simple, complex and json args"),
     }
 }
 
+/// Returns an error if more than one style of args was provided.
+fn check_args_styles(context: &'static str, args: &[(&str, bool)]) -> Result<()> {
+    let provided = args
+        .iter()
+        .filter(|(_, is_empty)| !*is_empty)
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<_>>();
+    if provided.len() > 1 {
+        return Err(Error::ConflictingArguments {
+            context,
+            args: provided,
+        });
+    }
+    Ok(())
+}
+
 /// Private macro for enforcing parameter validity.
 /// e.g. check_exactly_one_not_empty!(
 ///   (field1) requires[another_field],
@@ -366,6 +448,7 @@ pub(super) fn parse_session_info(
     session_path: &str,
     session_args: &[&str],
     session_args_complex: &str,
+    session_args_json: &str,
     session_version: &str,
     session_entry_point: &str,
     session_transfer: bool,
@@ -388,16 +471,19 @@ pub(super) fn parse_session_info(
         (is_session_transfer)
             requires[] requires_empty[session_entry_point, session_version]
     );
-    if !session_args.is_empty() && !session_args_complex.is_empty() {
-        return Err(Error::ConflictingArguments {
-            context: "parse_session_info",
-            args: vec!["session_args".to_owned(), "session_args_complex".to_owned()],
-        });
-    }
-
-    let session_args = args_from_simple_or_complex(
+    check_args_styles(
+        "parse_session_info",
+        &[
+            ("session_args", session_args.is_empty()),
+            ("session_args_complex", session_args_complex.is_empty()),
+            ("session_args_json", session_args_json.is_empty()),
+        ],
+    )?;
+
+    let session_args = args_from_simple_complex_or_json(
         arg_simple::session::parse(session_args)?,
         args_complex::session::parse(session_args_complex).ok(),
+        args_json::session::parse(session_args_json)?,
     );
     if session_transfer {
         if session_args.is_empty() {
@@ -465,6 +551,7 @@ pub(super) fn parse_payment_info(
     payment_path: &str,
     payment_args: &[&str],
     payment_args_complex: &str,
+    payment_args_json: &str,
     payment_version: &str,
     payment_entry_point: &str,
 ) -> Result<ExecutableDeployItem> {
@@ -482,12 +569,14 @@ pub(super) fn parse_payment_info(
             requires[payment_entry_point] requires_empty[],
         (payment_path) requires[] requires_empty[payment_entry_point, payment_version],
     );
-    if !payment_args.is_empty() && !payment_args_complex.is_empty() {
-        return Err(Error::ConflictingArguments {
-            context: "parse_payment_info",
-            args: vec!["payment_args".to_owned(), "payment_args_complex".to_owned()],
-        });
-    }
+    check_args_styles(
+        "parse_payment_info",
+        &[
+            ("payment_args", payment_args.is_empty()),
+            ("payment_args_complex", payment_args_complex.is_empty()),
+            ("payment_args_json", payment_args_json.is_empty()),
+        ],
+    )?;
 
     if let Ok(payment_args) = standard_payment(payment_amount) {
         return Ok(ExecutableDeployItem::ModuleBytes {
@@ -499,9 +588,10 @@ pub(super) fn parse_payment_info(
     let invalid_entry_point =
         || Error::InvalidArgument("payment_entry_point", payment_entry_point.to_string());
 
-    let payment_args = args_from_simple_or_complex(
+    let payment_args = args_from_simple_complex_or_json(
         arg_simple::payment::parse(payment_args)?,
         args_complex::payment::parse(payment_args_complex).ok(),
+        args_json::payment::parse(payment_args_json)?,
     );
 
     if let Some(payment_name) = name(payment_name) {
@@ -549,6 +639,156 @@ pub(super) fn parse_payment_info(
     })
 }
 
+pub(super) fn parse_contract_identifier(
+    contract_hash: &str,
+    contract_package_hash: &str,
+    contract_version: &str,
+) -> Result<ContractIdentifier> {
+    let contract_version = match none_if_empty(contract_version) {
+        Some(contract_version) => Some(version(contract_version)?),
+        None => None,
+    };
+    match (
+        parse_contract_hash(contract_hash)?,
+        parse_contract_hash(contract_package_hash)?,
+    ) {
+        (Some(_), None) if contract_version.is_some() => Err(Error::InvalidArgument(
+            "contract_version",
+            "can only be given together with a contract package hash".to_string(),
+        )),
+        (Some(contract_hash), None) => Ok(ContractIdentifier::ContractHash(contract_hash.into())),
+        (None, Some(contract_package_hash)) => Ok(ContractIdentifier::ContractPackageHash {
+            contract_package_hash: ContractPackageHash::new(contract_package_hash),
+            contract_version,
+        }),
+        _ => Err(Error::InvalidArgument(
+            "parse_contract_identifier",
+            "exactly one of contract_hash and contract_package_hash must be provided".to_string(),
+        )),
+    }
+}
+
+/// Builds the session code calling `entry_point` of the contract described by `schema`.
+///
+/// Each of `args` has the form `NAME=VALUE`, where `NAME` is a parameter of the entry point, and
+/// `VALUE` is the JSON representation of a value of the parameter's type.  Since the type is known,
+/// the quotes around a JSON string can be omitted, e.g. `amount=100` works for a `U512` param, and
+/// a `Key` can be given as a formatted string.  Params of an `Option` type which are not given are
+/// passed as `None`.
+pub(super) fn parse_contract_call(
+    contract_identifier: &ContractIdentifier,
+    schema: &ContractSchema,
+    entry_point: &str,
+    args: &[&str],
+) -> Result<ExecutableDeployItem> {
+    let entry_point_schema =
+        schema
+            .entry_point(entry_point)
+            .ok_or_else(|| Error::NoSuchEntryPoint {
+                entry_point: entry_point.to_string(),
+                available: schema
+                    .entry_points()
+                    .iter()
+                    .map(|entry_point| entry_point.name().to_string())
+                    .collect(),
+            })?;
+    let invalid_arg = |name: &str, reason: String| Error::InvalidContractCallArg {
+        entry_point: entry_point.to_string(),
+        name: name.to_string(),
+        reason,
+    };
+
+    let mut given_values = Vec::new();
+    for &arg in args {
+        let (name, value) = match arg.find('=') {
+            Some(index) => (&arg[..index], &arg[index + 1..]),
+            None => {
+                return Err(invalid_arg(
+                    arg,
+                    "should be of the form NAME=VALUE".to_string(),
+                ))
+            }
+        };
+        if entry_point_schema
+            .args()
+            .iter()
+            .all(|parameter| parameter.name() != name)
+        {
+            return Err(invalid_arg(
+                name,
+                "not a parameter of the entry point".to_string(),
+            ));
+        }
+        if given_values
+            .iter()
+            .any(|(given_name, _)| *given_name == name)
+        {
+            return Err(invalid_arg(name, "given more than once".to_string()));
+        }
+        given_values.push((name, value));
+    }
+
+    // Insert the args in the order of the entry point's parameters.
+    let mut runtime_args = RuntimeArgs::new();
+    for parameter in entry_point_schema.args() {
+        let cl_type = parameter.cl_type();
+        let maybe_value = given_values
+            .iter()
+            .find(|(name, _)| *name == parameter.name())
+            .map(|(_, value)| *value);
+        let cl_value = match (maybe_value, cl_type) {
+            (Some(value), _) => typed_arg_value(cl_type, value).ok_or_else(|| {
+                invalid_arg(
+                    parameter.name(),
+                    format!("'{}' is not a valid {:?}", value, cl_type),
+                )
+            })?,
+            (None, CLType::Option(_)) => casper_types::cl_value_from_json(cl_type, &Value::Null)
+                .expect("null should be a valid option"),
+            (None, _) => {
+                return Err(invalid_arg(
+                    parameter.name(),
+                    format!("missing required arg of type {:?}", cl_type),
+                ))
+            }
+        };
+        runtime_args.insert_cl_value(parameter.name(), cl_value);
+    }
+
+    let entry_point = entry_point.to_string();
+    Ok(match contract_identifier {
+        ContractIdentifier::ContractHash(_) => ExecutableDeployItem::StoredContractByHash {
+            hash: schema.contract_hash(),
+            entry_point,
+            args: runtime_args,
+        },
+        ContractIdentifier::ContractPackageHash {
+            contract_package_hash,
+            contract_version,
+        } => ExecutableDeployItem::StoredVersionedContractByHash {
+            hash: *contract_package_hash,
+            version: *contract_version,
+            entry_point,
+            args: runtime_args,
+        },
+    })
+}
+
+/// Parses `value` as JSON of the given type, falling back to treating it as an unquoted JSON
+/// string.
+fn typed_arg_value(cl_type: &CLType, value: &str) -> Option<CLValue> {
+    // Keys are usually given in their formatted form rather than as JSON.
+    if *cl_type == CLType::Key {
+        if let Ok(key) = Key::from_formatted_str(value) {
+            return CLValue::from_t(key).ok();
+        }
+    }
+    serde_json::from_str(value)
+        .ok()
+        .and_then(|json_value| casper_types::cl_value_from_json(cl_type, &json_value))
+        .or_else(|| casper_types::cl_value_from_json(cl_type, &Value::String(value.to_string())))
+}
+
 pub(crate) fn get_transfer_target(target_account: &str) -> Result<TransferTarget> {
     let account = PublicKey::from_hex(target_account).map_err(|error| {
         Error::InvalidArgument(
@@ -879,6 +1119,7 @@ mod tests {
                 &["something:u32='0'"],
                 "path_to/file",
                 "",
+                "",
                 "entrypoint",
                 false
             )
@@ -901,6 +1142,7 @@ mod tests {
                 &["something:u32='0'"],
                 "path_to/file",
                 "",
+                "",
                 "entrypoint",
             )
             .map(|_| ())
@@ -913,6 +1155,266 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_parse_json_args() {
+        let json = r#"[
+            {"name": "amount", "type": "U512", "value": "100"},
+            {"name": "list", "type": {"List": "U8"}, "value": [1, 2]},
+            {"name": "maybe", "type": {"Option": "String"}, "value": null},
+            {"name": "pair", "type": {"Tuple2": ["Bool", "I32"]}, "value": [true, -1]},
+            {
+                "name": "map",
+                "type": {"Map": {"key": "String", "value": "U64"}},
+                "value": [{"key": "a", "value": 1}]
+            }
+        ]"#;
+        let mut map = std::collections::BTreeMap::new();
+        map.insert("a".to_string(), 1u64);
+        let expected = Some(RuntimeArgs::from(vec![
+            NamedArg::new(
+                "amount".to_string(),
+                CLValue::from_t(U512::from(100)).unwrap(),
+            ),
+            NamedArg::new("list".to_string(), CLValue::from_t(vec![1u8, 2]).unwrap()),
+            NamedArg::new(
+                "maybe".to_string(),
+                CLValue::from_t(None::<String>).unwrap(),
+            ),
+            NamedArg::new("pair".to_string(), CLValue::from_t((true, -1i32)).unwrap()),
+            NamedArg::new("map".to_string(), CLValue::from_t(map).unwrap()),
+        ]));
+
+        assert_eq!(
+            args_json::payment::parse(json).expect("should parse"),
+            expected
+        );
+        assert_eq!(
+            args_json::session::parse(json).expect("should parse"),
+            expected
+        );
+        assert_eq!(args_json::session::parse("").expect("should parse"), None);
+    }
+
+    #[test]
+    fn should_fail_to_parse_bad_json_args() {
+        let bad_args = [
+            "not json",
+            r#"[{"name": "x", "type": "U8"}]"#,
+            r#"[{"name": "x", "type": "U8", "value": 256}]"#,
+            r#"[{"name": "x", "type": "Wat", "value": 1}]"#,
+            r#"[{"name": "x", "type": {"ByteArray": 2}, "value": "01"}]"#,
+        ];
+        for json in &bad_args {
+            assert!(
+                args_json::session::parse(json).is_err(),
+                "{} should be an error",
+                json
+            );
+            assert!(
+                args_json::payment::parse(json).is_err(),
+                "{} should be an error",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_conflicting_json_args() {
+        let json = r#"[{"name": "x", "type": "U32", "value": 0}]"#;
+        assert_eq!(
+            parse_session_info(
+                "",
+                "name",
+                "",
+                "",
+                "",
+                &["something:u32='0'"],
+                "",
+                json,
+                "",
+                "entrypoint",
+                false
+            )
+            .map(|_| ())
+            .map_err(ErrWrapper),
+            Err(Error::ConflictingArguments {
+                context: "parse_session_info",
+                args: vec!["session_args".to_owned(), "session_args_json".to_owned()]
+            }
+            .into())
+        );
+        assert_eq!(
+            parse_payment_info(
+                "",
+                "name",
+                "",
+                "",
+                "",
+                "",
+                &[],
+                "path_to/file",
+                json,
+                "",
+                "entrypoint",
+            )
+            .map(|_| ())
+            .map_err(ErrWrapper),
+            Err(Error::ConflictingArguments {
+                context: "parse_payment_info",
+                args: vec![
+                    "payment_args_complex".to_owned(),
+                    "payment_args_json".to_owned()
+                ]
+            }
+            .into())
+        );
+    }
+
+    fn contract_schema() -> ContractSchema {
+        use casper_types::{
+            contracts::NamedKeys, Contract, ContractHash, ContractPackage, ContractWasmHash,
+            EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter, ProtocolVersion,
+        };
+
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("recipient", CLType::Key),
+                Parameter::new("amount", CLType::U512),
+                Parameter::new("memo", CLType::Option(Box::new(CLType::String))),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        let contract = Contract::new(
+            ContractPackageHash::new([2; 32]),
+            ContractWasmHash::new([3; 32]),
+            NamedKeys::new(),
+            entry_points,
+            ProtocolVersion::V1_0_0,
+        );
+        ContractSchema::new(
+            ContractHash::new([1; 32]),
+            &contract,
+            &ContractPackage::default(),
+        )
+    }
+
+    #[test]
+    fn should_parse_contract_identifier() {
+        let hash = "0101010101010101010101010101010101010101010101010101010101010101";
+        assert!(matches!(
+            parse_contract_identifier(hash, "", ""),
+            Ok(ContractIdentifier::ContractHash(_))
+        ));
+        assert!(matches!(
+            parse_contract_identifier("", hash, "2"),
+            Ok(ContractIdentifier::ContractPackageHash {
+                contract_version: Some(2),
+                ..
+            })
+        ));
+        assert!(parse_contract_identifier("", "", "").is_err());
+        assert!(parse_contract_identifier(hash, hash, "").is_err());
+        assert!(parse_contract_identifier(hash, "", "2").is_err());
+        assert!(parse_contract_identifier("", hash, "two").is_err());
+    }
+
+    #[test]
+    fn should_parse_contract_call_args_from_schema() {
+        let schema = contract_schema();
+        let contract_identifier = ContractIdentifier::ContractHash(schema.contract_hash());
+        let account_hash = AccountHash::new([4; 32]);
+        let recipient = format!(
+            "recipient={}",
+            Key::from(account_hash).to_formatted_string()
+        );
+        let recipient = recipient.as_str();
+
+        let session = parse_contract_call(
+            &contract_identifier,
+            &schema,
+            "transfer",
+            &["amount=100", recipient],
+        )
+        .expect("should parse");
+        let expected_args = RuntimeArgs::from(vec![
+            NamedArg::new(
+                "recipient".to_string(),
+                CLValue::from_t(Key::from(account_hash)).unwrap(),
+            ),
+            NamedArg::new(
+                "amount".to_string(),
+                CLValue::from_t(U512::from(100)).unwrap(),
+            ),
+            NamedArg::new("memo".to_string(), CLValue::from_t(None::<String>).unwrap()),
+        ]);
+        match session {
+            ExecutableDeployItem::StoredContractByHash {
+                hash,
+                entry_point,
+                args,
+            } => {
+                assert_eq!(hash, schema.contract_hash());
+                assert_eq!(entry_point, "transfer");
+                assert_eq!(args, expected_args);
+            }
+            other => panic!("unexpected session {:?}", other),
+        }
+
+        let session = parse_contract_call(
+            &contract_identifier,
+            &schema,
+            "transfer",
+            &["amount=\"100\"", recipient, "memo=hello"],
+        )
+        .expect("should parse");
+        assert_eq!(
+            session.args().get("memo"),
+            Some(&CLValue::from_t(Some("hello".to_string())).unwrap())
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_contract_call_args_not_matching_schema() {
+        let schema = contract_schema();
+        let contract_identifier = ContractIdentifier::ContractHash(schema.contract_hash());
+        let recipient = format!(
+            "recipient={}",
+            Key::from(AccountHash::new([4; 32])).to_formatted_string()
+        );
+        let recipient = recipient.as_str();
+
+        assert!(matches!(
+            parse_contract_call(&contract_identifier, &schema, "mint", &[]),
+            Err(Error::NoSuchEntryPoint { .. })
+        ));
+        let bad_args: &[&[&str]] = &[
+            // Missing required `amount`.
+            &[recipient],
+            // Not a U512.
+            &[recipient, "amount=-1"],
+            // Not a parameter.
+            &[recipient, "amount=1", "fee=1"],
+            // Given twice.
+            &[recipient, "amount=1", "amount=2"],
+            // Not of the form NAME=VALUE.
+            &[recipient, "amount"],
+        ];
+        for args in bad_args {
+            assert!(
+                matches!(
+                    parse_contract_call(&contract_identifier, &schema, "transfer", args),
+                    Err(Error::InvalidContractCallArg { .. })
+                ),
+                "{:?} should be an error",
+                args
+            );
+        }
+    }
+
     #[test]
     fn should_fail_to_parse_conflicting_session_parameters() {
         assert_eq!(
@@ -926,6 +1428,7 @@ mod tests {
                 "",
                 "",
                 "",
+                "",
                 false
             )
             .map(|_| ())
@@ -947,7 +1450,7 @@ mod tests {
     #[test]
     fn should_fail_to_parse_conflicting_payment_parameters() {
         assert_eq!(
-            parse_payment_info("12345", happy::HASH, happy::NAME, happy::PACKAGE_HASH, happy::PACKAGE_NAME, happy::PATH, &[], "", "", "",)
+            parse_payment_info("12345", happy::HASH, happy::NAME, happy::PACKAGE_HASH, happy::PACKAGE_NAME, happy::PATH, &[], "", "", "", "",)
                 .map(|_| ())
                 .map_err(ErrWrapper),
             Err(Error::ConflictingArguments {
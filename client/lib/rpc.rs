@@ -2,6 +2,7 @@ use std::{convert::TryInto, fs::File};
 
 use futures::executor;
 use jsonrpc_lite::{Id, JsonRpc, Params};
+use num_rational::Ratio;
 use rand::Rng;
 use reqwest::Client;
 use serde::Serialize;
@@ -17,21 +18,25 @@ use casper_node::{
             GetEraInfoBySwitchBlock, GetEraInfoParams, GetStateRootHash, GetStateRootHashParams,
         },
         docs::ListRpcs,
-        info::{GetDeploy, GetDeployParams},
+        info::{GetAccountHistory, GetAccountHistoryParams, GetDeploy, GetDeployParams},
         state::{
-            GetAccountInfo, GetAccountInfoParams, GetAuctionInfo, GetAuctionInfoParams, GetBalance,
-            GetBalanceParams, GetDictionaryItem, GetDictionaryItemParams, GetItem, GetItemParams,
+            ContractIdentifier, GetAccountInfo, GetAccountInfoParams, GetAuctionInfo,
+            GetAuctionInfoParams, GetBalance, GetBalanceParams, GetContractSchema,
+            GetContractSchemaParams, GetContractSchemaResult, GetDictionaryItem,
+            GetDictionaryItemParams, GetItem, GetItemParams,
         },
         RpcWithOptionalParams, RpcWithParams, RpcWithoutParams, RPC_API_PATH,
     },
     types::{BlockHash, Deploy, DeployHash},
 };
-use casper_types::{AsymmetricType, Key, PublicKey, URef, U512};
+use casper_types::{account::AccountHash, AsymmetricType, EraId, Key, PublicKey, URef, U512};
 
 use crate::{
     deploy::{DeployExt, DeployParams, SendDeploy, Transfer},
     error::{Error, Result},
-    validation, DictionaryItemStrParams,
+    parsing,
+    validation::{self, ValidateResponseError},
+    ContractCallStrParams, DictionaryItemStrParams,
 };
 
 /// Target for a given transfer.
@@ -41,7 +46,7 @@ pub(crate) enum TransferTarget {
 }
 
 /// Struct representing a single JSON-RPC call to the casper node.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct RpcCall {
     rpc_id: Id,
     node_address: String,
@@ -97,15 +102,7 @@ impl RpcCall {
                 error,
             })?;
 
-        let key = {
-            if let Ok(key) = Key::from_formatted_str(key) {
-                key
-            } else if let Ok(public_key) = PublicKey::from_hex(key) {
-                Key::Account(public_key.to_account_hash())
-            } else {
-                return Err(Error::FailedToParseKey);
-            }
-        };
+        let key = Self::key(key)?;
 
         let path = if path.is_empty() {
             vec![]
@@ -123,6 +120,63 @@ impl RpcCall {
         Ok(response)
     }
 
+    pub(crate) fn get_item_verified(
+        self,
+        trusted_switch_block_hash: &str,
+        finality_threshold_fraction: &str,
+        maybe_block_identifier: &str,
+        key: &str,
+        path: &str,
+    ) -> Result<JsonRpc> {
+        // Only a block hash, not a height, identifies the trusted block unambiguously.
+        let _ =
+            Digest::from_hex(trusted_switch_block_hash).map_err(|error| Error::CryptoError {
+                context: "trusted_switch_block_hash",
+                error,
+            })?;
+        let finality_threshold_fraction =
+            Self::finality_threshold_fraction(finality_threshold_fraction)?;
+        let response = self.clone().get_block(trusted_switch_block_hash)?;
+        let (switch_block_era_id, mut validator_weights) =
+            validation::validate_trusted_switch_block_response(&response)?;
+        let mut switch_block_height = validation::get_block_response_header(&response)?.height();
+
+        let response = self.clone().get_block(maybe_block_identifier)?;
+        let unverified_header = validation::get_block_response_header(&response)?;
+
+        // Walk the switch blocks from the trusted one to the block's era, so that the validators of
+        // each era are vouched for by those of the previous one.
+        let mut era_id = switch_block_era_id.successor();
+        while era_id < unverified_header.era_id() {
+            let switch_block_response =
+                self.get_switch_block(era_id, switch_block_height, unverified_header.height())?;
+            let switch_block_header = validation::validate_get_block_response_finality(
+                &switch_block_response,
+                era_id,
+                &validator_weights,
+                finality_threshold_fraction,
+            )?;
+            validator_weights = switch_block_header
+                .next_era_validator_weights()
+                .cloned()
+                .ok_or(ValidateResponseError::NotASwitchBlock)?;
+            switch_block_height = switch_block_header.height();
+            era_id = era_id.successor();
+        }
+
+        let header = validation::validate_get_block_response_finality(
+            &response,
+            era_id,
+            &validator_weights,
+            finality_threshold_fraction,
+        )?;
+
+        let state_root_hash = hex::encode(header.state_root_hash());
+        let response = self.get_item(&state_root_hash, key, path)?;
+        validation::validate_query_response_against_block(&response, &header, &Self::key(key)?)?;
+        Ok(response)
+    }
+
     pub(crate) fn get_dictionary_item(
         self,
         state_root_hash: &str,
@@ -145,6 +199,47 @@ impl RpcCall {
         Ok(response)
     }
 
+    pub(crate) fn get_contract_schema(
+        self,
+        maybe_block_identifier: &str,
+        contract_identifier: ContractIdentifier,
+    ) -> Result<JsonRpc> {
+        let block_identifier = Self::block_identifier(maybe_block_identifier)?;
+        let params = GetContractSchemaParams {
+            block_identifier,
+            contract_identifier,
+        };
+        GetContractSchema::request_with_map_params(self, params)
+    }
+
+    /// Fetches the schema of the contract to be called, and uses it to type the call's args before
+    /// sending the `Deploy`.
+    pub(crate) fn call_contract(
+        self,
+        maybe_block_identifier: &str,
+        deploy_params: DeployParams,
+        contract_call: ContractCallStrParams<'_>,
+        payment: ExecutableDeployItem,
+    ) -> Result<JsonRpc> {
+        let contract_identifier: ContractIdentifier =
+            contract_call.contract_identifier.try_into()?;
+        let response = self
+            .clone()
+            .get_contract_schema(maybe_block_identifier, contract_identifier.clone())?;
+        let result: GetContractSchemaResult = match response.get_result() {
+            Some(value) => serde_json::from_value(value.clone())?,
+            None => return Err(Error::InvalidRpcResponse(response)),
+        };
+        let session = parsing::parse_contract_call(
+            &contract_identifier,
+            &result.contract_schema,
+            contract_call.entry_point,
+            &contract_call.args,
+        )?;
+        let deploy = Deploy::with_payment_and_session(deploy_params, payment, session)?;
+        self.put_deploy(deploy)
+    }
+
     pub(crate) fn get_state_root_hash(self, maybe_block_identifier: &str) -> Result<JsonRpc> {
         match Self::block_identifier(maybe_block_identifier)? {
             Some(block_identifier) => {
@@ -283,6 +378,83 @@ impl RpcCall {
         GetAccountInfo::request_with_map_params(self, params)
     }
 
+    pub(crate) fn get_account_history(
+        self,
+        account: &str,
+        offset: &str,
+        limit: &str,
+    ) -> Result<JsonRpc> {
+        let account_hash = if let Ok(account_hash) = AccountHash::from_formatted_str(account) {
+            account_hash
+        } else if let Ok(public_key) = PublicKey::from_hex(account) {
+            public_key.to_account_hash()
+        } else {
+            return Err(Error::FailedToParseKey);
+        };
+        let offset = offset
+            .parse()
+            .map_err(|error| Error::FailedToParseInt("offset", error))?;
+        let limit = limit
+            .parse()
+            .map_err(|error| Error::FailedToParseInt("limit", error))?;
+        let params = GetAccountHistoryParams {
+            account_hash,
+            offset,
+            limit,
+        };
+        GetAccountHistory::request_with_map_params(self, params)
+    }
+
+    fn key(key: &str) -> Result<Key> {
+        if let Ok(key) = Key::from_formatted_str(key) {
+            Ok(key)
+        } else if let Ok(public_key) = PublicKey::from_hex(key) {
+            Ok(Key::Account(public_key.to_account_hash()))
+        } else {
+            Err(Error::FailedToParseKey)
+        }
+    }
+
+    /// Returns the last block of `era_id`, found by a binary search over the heights between
+    /// `lower_height`, whose block is in an earlier era, and `upper_height`, whose block is in a
+    /// later era.
+    ///
+    /// The eras of the blocks visited by the search are unverified, so the returned block must be
+    /// verified to be the switch block of `era_id` by the caller.
+    fn get_switch_block(
+        &self,
+        era_id: EraId,
+        mut lower_height: u64,
+        mut upper_height: u64,
+    ) -> Result<JsonRpc> {
+        let mut maybe_switch_block_response = None;
+        while lower_height + 1 < upper_height {
+            let height = lower_height + (upper_height - lower_height) / 2;
+            let response = self.clone().get_block(&height.to_string())?;
+            if validation::get_block_response_header(&response)?.era_id() <= era_id {
+                lower_height = height;
+                maybe_switch_block_response = Some(response);
+            } else {
+                upper_height = height;
+            }
+        }
+        maybe_switch_block_response
+            .ok_or_else(|| ValidateResponseError::NoSwitchBlock(era_id).into())
+    }
+
+    fn finality_threshold_fraction(finality_threshold_fraction: &str) -> Result<Ratio<u64>> {
+        let fraction: Ratio<u64> = finality_threshold_fraction.parse().map_err(|error| {
+            Error::InvalidArgument("finality_threshold_fraction", format!("{}", error))
+        })?;
+        if fraction >= Ratio::from_integer(1) {
+            return Err(Error::InvalidArgument(
+                "finality_threshold_fraction",
+                format!("{} is not less than 1", fraction),
+            ));
+        }
+        Ok(fraction)
+    }
+
     fn block_identifier(maybe_block_identifier: &str) -> Result<Option<BlockIdentifier>> {
         if maybe_block_identifier.is_empty() {
             return Ok(None);
@@ -415,6 +587,14 @@ impl RpcClient for GetDictionaryItem {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
 
+impl RpcClient for GetContractSchema {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetAccountHistory {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 pub(crate) trait IntoJsonMap: Serialize {
     fn into_json_map(self) -> Map<String, Value>
     where
@@ -439,3 +619,5 @@ impl IntoJsonMap for ListRpcs {}
 impl IntoJsonMap for GetAuctionInfoParams {}
 impl IntoJsonMap for GetAccountInfoParams {}
 impl IntoJsonMap for GetDictionaryItemParams {}
+impl IntoJsonMap for GetAccountHistoryParams {}
+impl IntoJsonMap for GetContractSchemaParams {}
@@ -1,6 +1,7 @@
-use std::convert::TryFrom;
+use std::{collections::BTreeMap, convert::TryFrom};
 
 use jsonrpc_lite::JsonRpc;
+use num_rational::Ratio;
 use thiserror::Error;
 
 use casper_execution_engine::{
@@ -12,7 +13,11 @@ use casper_node::{
     rpcs::chain::{BlockIdentifier, EraSummary, GetEraInfoResult},
     types::{json_compatibility, Block, BlockValidationError, JsonBlock},
 };
-use casper_types::{bytesrepr, Key, U512};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    light_client::{self, LightBlockHeader},
+    EraId, Key, PublicKey, U512,
+};
 
 const GET_ITEM_RESULT_BALANCE_VALUE: &str = "balance_value";
 const GET_ITEM_RESULT_STORED_VALUE: &str = "stored_value";
@@ -56,6 +61,29 @@ pub enum ValidateResponseError {
     /// Block height was not as requested.
     #[error("block height was not as requested")]
     UnexpectedBlockHeight,
+
+    /// The trusted block is not a switch block.
+    #[error("trusted block is not a switch block")]
+    NotASwitchBlock,
+
+    /// No switch block was found for an era between the trusted switch block and the block.
+    #[error("no switch block found for era {0}")]
+    NoSwitchBlock(EraId),
+
+    /// The block is not in the era whose validators were used to verify it.
+    #[error(
+        "block is in era {actual}, but the validators of era {expected} were used to verify it"
+    )]
+    UntrustedEra {
+        /// The era of the validators.
+        expected: EraId,
+        /// The era of the block.
+        actual: EraId,
+    },
+
+    /// Light client verification of the response failed.
+    #[error("light client verification failed: {0}")]
+    LightClient(light_client::Error),
 }
 
 impl From<bytesrepr::Error> for ValidateResponseError {
@@ -64,6 +92,12 @@ impl From<bytesrepr::Error> for ValidateResponseError {
     }
 }
 
+impl From<light_client::Error> for ValidateResponseError {
+    fn from(e: light_client::Error) -> Self {
+        ValidateResponseError::LightClient(e)
+    }
+}
+
 impl From<BlockValidationError> for ValidateResponseError {
     fn from(e: BlockValidationError) -> Self {
         ValidateResponseError::BlockValidationError(e)
@@ -219,16 +253,19 @@ pub(crate) fn validate_get_balance_response(
     .map_err(Into::into)
 }
 
-pub(crate) fn validate_get_block_response(
-    response: &JsonRpc,
-    maybe_block_identifier: &Option<BlockIdentifier>,
-) -> Result<(), ValidateResponseError> {
+fn json_block(response: &JsonRpc) -> Result<Option<JsonBlock>, ValidateResponseError> {
     let maybe_result = response.get_result();
     let json_block_value = maybe_result
         .and_then(|value| value.get("block"))
         .ok_or(ValidateResponseError::NoBlockInResponse)?;
-    let maybe_json_block: Option<JsonBlock> = serde_json::from_value(json_block_value.to_owned())?;
-    let json_block = if let Some(json_block) = maybe_json_block {
+    serde_json::from_value(json_block_value.to_owned()).map_err(Into::into)
+}
+
+pub(crate) fn validate_get_block_response(
+    response: &JsonRpc,
+    maybe_block_identifier: &Option<BlockIdentifier>,
+) -> Result<(), ValidateResponseError> {
+    let json_block = if let Some(json_block) = json_block(response)? {
         json_block
     } else {
         return Ok(());
@@ -254,3 +291,194 @@ pub(crate) fn validate_get_block_response(
     }
     Ok(())
 }
+
+/// Returns the header of the block in the response, without verifying its finality.
+///
+/// The response must already have been checked by `validate_get_block_response`.
+pub(crate) fn get_block_response_header(
+    response: &JsonRpc,
+) -> Result<LightBlockHeader, ValidateResponseError> {
+    let json_block = json_block(response)?.ok_or(ValidateResponseError::NoBlockInResponse)?;
+    light_block_header(json_block)
+}
+
+/// Returns the era of a trusted switch block, along with the validator weights of the following
+/// era.
+///
+/// The response must already have been checked by `validate_get_block_response`.
+pub(crate) fn validate_trusted_switch_block_response(
+    response: &JsonRpc,
+) -> Result<(EraId, BTreeMap<PublicKey, U512>), ValidateResponseError> {
+    let header = get_block_response_header(response)?;
+    let validator_weights = header
+        .next_era_validator_weights()
+        .cloned()
+        .ok_or(ValidateResponseError::NotASwitchBlock)?;
+    Ok((header.era_id(), validator_weights))
+}
+
+/// Verifies that the block in the response is in era `era_id` and was finalized by that era's
+/// validators, i.e. that the signatures by `validator_weights` account for more than
+/// `finality_threshold_fraction` of their total weight.  Returns the block's header.
+///
+/// The response must already have been checked by `validate_get_block_response`.
+pub(crate) fn validate_get_block_response_finality(
+    response: &JsonRpc,
+    era_id: EraId,
+    validator_weights: &BTreeMap<PublicKey, U512>,
+    finality_threshold_fraction: Ratio<u64>,
+) -> Result<LightBlockHeader, ValidateResponseError> {
+    let json_block = json_block(response)?.ok_or(ValidateResponseError::NoBlockInResponse)?;
+    let signatures = json_block.proofs();
+    let header = light_block_header(json_block)?;
+    if header.era_id() != era_id {
+        return Err(ValidateResponseError::UntrustedEra {
+            expected: era_id,
+            actual: header.era_id(),
+        });
+    }
+    light_client::verify_finality_signatures(
+        &header,
+        &signatures,
+        validator_weights,
+        finality_threshold_fraction,
+    )?;
+    Ok(header)
+}
+
+fn light_block_header(json_block: JsonBlock) -> Result<LightBlockHeader, ValidateResponseError> {
+    let serialized_header = Block::from(json_block).take_header().to_bytes()?;
+    LightBlockHeader::from_serialized_header(&serialized_header).map_err(Into::into)
+}
+
+/// Verifies that every Merkle proof in the response leads to the state root hash of the finalized
+/// block header, and that the first proof is for the queried key.
+///
+/// The response must already have been checked by `validate_query_response`, using the state root
+/// hash of `header`.
+pub(crate) fn validate_query_response_against_block(
+    response: &JsonRpc,
+    header: &LightBlockHeader,
+    key: &Key,
+) -> Result<(), ValidateResponseError> {
+    let proof_str = response
+        .get_result()
+        .and_then(|value| value.get(GET_ITEM_RESULT_MERKLE_PROOF))
+        .and_then(|proof| proof.as_str())
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let proof_bytes =
+        hex::decode(proof_str).map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
+    let proofs: Vec<light_client::TrieMerkleProof<Key, StoredValue>> =
+        bytesrepr::deserialize(proof_bytes)?;
+
+    let (first_proof, other_proofs) = proofs.split_first().ok_or(light_client::Error::NoProofs)?;
+    light_client::verify_trie_merkle_proof(header, first_proof, key, first_proof.value())?;
+    for proof in other_proofs {
+        light_client::verify_trie_merkle_proof(header, proof, proof.key(), proof.value())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_lite::Id;
+    use serde_json::{json, Value};
+
+    use casper_node::{crypto, rpcs::docs::DocExample};
+    use casper_types::SecretKey;
+
+    use super::*;
+
+    /// Returns a response containing the example switch block.
+    fn switch_block_response() -> JsonRpc {
+        let json_block = serde_json::to_value(JsonBlock::doc_example()).unwrap();
+        JsonRpc::success(Id::Num(1), &json!({ "block": json_block }))
+    }
+
+    /// Returns a response containing the example block, changed to be a non-switch block in
+    /// `era_id` and signed by the given validators.
+    fn non_switch_block_response(era_id: EraId, signers: &[SecretKey]) -> JsonRpc {
+        let mut json_block = serde_json::to_value(JsonBlock::doc_example()).unwrap();
+        json_block["header"]["era_end"] = Value::Null;
+        json_block["header"]["era_id"] = json!(era_id);
+        json_block["header"]["height"] = json!(11);
+        json_block["proofs"] = json!([]);
+
+        let header = light_block_header(serde_json::from_value(json_block.clone()).unwrap())
+            .expect("should parse header");
+        let mut signed_message = header.block_hash().to_vec();
+        signed_message.extend_from_slice(&era_id.to_le_bytes());
+        let proofs: Vec<Value> = signers
+            .iter()
+            .map(|secret_key| {
+                let public_key = PublicKey::from(secret_key);
+                let signature = crypto::sign(&signed_message, secret_key, &public_key);
+                json!({ "public_key": public_key, "signature": signature })
+            })
+            .collect();
+        json_block["proofs"] = json!(proofs);
+
+        JsonRpc::success(Id::Num(1), &json!({ "block": json_block }))
+    }
+
+    #[test]
+    fn should_verify_non_switch_block_in_trusted_era() {
+        let (switch_block_era_id, validator_weights) =
+            validate_trusted_switch_block_response(&switch_block_response())
+                .expect("should be a switch block");
+        let era_id = switch_block_era_id.successor();
+        let finality_threshold_fraction = Ratio::new(1, 3);
+
+        // The validators with weights 456 and 789 out of 1368 have signed the block.
+        let signers = [
+            SecretKey::ed25519_from_bytes([5u8; SecretKey::ED25519_LENGTH]).unwrap(),
+            SecretKey::ed25519_from_bytes([6u8; SecretKey::ED25519_LENGTH]).unwrap(),
+        ];
+        let response = non_switch_block_response(era_id, &signers);
+        let header = validate_get_block_response_finality(
+            &response,
+            era_id,
+            &validator_weights,
+            finality_threshold_fraction,
+        )
+        .expect("should verify block");
+        assert_eq!(header.era_id(), era_id);
+        assert_eq!(header.height(), 11);
+        assert!(header.next_era_validator_weights().is_none());
+
+        // The block's own era must be the one whose validators are given.
+        let result = validate_get_block_response_finality(
+            &response,
+            switch_block_era_id,
+            &validator_weights,
+            finality_threshold_fraction,
+        );
+        assert!(matches!(
+            result,
+            Err(ValidateResponseError::UntrustedEra { expected, actual })
+                if expected == switch_block_era_id && actual == era_id
+        ));
+
+        // Exactly a third of the total weight doesn't exceed a threshold of one third.
+        let response = non_switch_block_response(era_id, &signers[..1]);
+        let result = validate_get_block_response_finality(
+            &response,
+            era_id,
+            &validator_weights,
+            finality_threshold_fraction,
+        );
+        assert!(matches!(
+            result,
+            Err(ValidateResponseError::LightClient(
+                light_client::Error::InsufficientFinalitySignatures
+            ))
+        ));
+        validate_get_block_response_finality(
+            &response,
+            era_id,
+            &validator_weights,
+            Ratio::new(1, 4),
+        )
+        .expect("should verify block with a lower threshold");
+    }
+}
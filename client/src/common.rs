@@ -147,7 +147,7 @@ pub mod force {
 pub mod state_root_hash {
     use super::*;
 
-    const ARG_NAME: &str = "state-root-hash";
+    pub(crate) const ARG_NAME: &str = "state-root-hash";
     const ARG_SHORT: &str = "s";
     const ARG_VALUE_NAME: &str = super::ARG_HEX_STRING;
     const ARG_HELP: &str = "Hex-encoded hash of the state root";
@@ -246,3 +246,71 @@ pub mod public_key {
         Ok(value.to_string())
     }
 }
+
+/// Handles providing the arg for and retrieval of the hash of a stored contract.
+pub mod contract_hash {
+    use super::*;
+
+    pub(crate) const ARG_NAME: &str = "contract-hash";
+    const ARG_VALUE_NAME: &str = super::ARG_HEX_STRING;
+    const ARG_HELP: &str = "Hex-encoded hash of the stored contract";
+
+    pub(crate) fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub(crate) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the hash of a stored contract package.
+pub mod contract_package_hash {
+    use super::*;
+
+    pub(crate) const ARG_NAME: &str = "contract-package-hash";
+    const ARG_VALUE_NAME: &str = super::ARG_HEX_STRING;
+    const ARG_HELP: &str = "Hex-encoded hash of the stored contract package";
+
+    pub(crate) fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub(crate) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the version of a contract within its package.
+pub mod contract_version {
+    use super::*;
+
+    const ARG_NAME: &str = "contract-version";
+    const ARG_VALUE_NAME: &str = super::ARG_INTEGER;
+    const ARG_HELP: &str =
+        "Version of the contract within the contract package. Latest will be used by default";
+
+    pub(crate) fn arg(order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(contract_package_hash::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+
+    pub(crate) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
@@ -1,3 +1,4 @@
+mod call_contract;
 mod creation_common;
 mod get;
 mod list;
@@ -8,6 +9,7 @@ mod send;
 mod sign;
 mod transfer;
 
+pub use call_contract::CallContract;
 pub use list::ListDeploys;
 pub use make::MakeDeploy;
 pub use make_transfer::MakeTransfer;
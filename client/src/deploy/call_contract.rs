@@ -0,0 +1,147 @@
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+
+use casper_client::{ContractCallStrParams, ContractIdentifierStrParams, DeployStrParams, Error};
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+/// Handles providing the arg for and retrieval of the entry point to be called.
+pub(super) mod entry_point {
+    use super::*;
+
+    const ARG_NAME: &str = "entry-point";
+    const ARG_VALUE_NAME: &str = "NAME";
+    const ARG_HELP: &str = "Name of the entry point of the stored contract to be called";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractEntryPoint as usize)
+    }
+
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the args of the call.
+pub(super) mod arg {
+    use super::*;
+
+    const ARG_NAME: &str = "arg";
+    const ARG_SHORT: &str = "a";
+    const ARG_VALUE_NAME: &str = "NAME=VALUE";
+    const ARG_HELP: &str =
+        "An arg of the call, where NAME is a parameter of the entry point and VALUE is the JSON \
+        representation of a value of the parameter's type as given by the contract's schema, e.g. \
+        'amount=1000' or 'recipients=[\"first\",\"second\"]'. Quotes around JSON strings can be \
+        omitted, and keys can be given as formatted strings. This arg can be repeated to pass \
+        multiple args, and args for Option parameters can be left out to pass None";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractArg as usize)
+    }
+
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches
+            .values_of(ARG_NAME)
+            .map(|values| values.collect())
+            .unwrap_or_default()
+    }
+}
+
+pub struct CallContract {}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for CallContract {
+    const NAME: &'static str = "call-contract";
+    const ABOUT: &'static str =
+        "Calls an entry point of a stored contract, with the args typed from the contract's \
+        schema, and sends the deploy to the network for execution";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(common::block_identifier::arg(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+            .arg(common::contract_hash::arg(
+                DisplayOrder::ContractHash as usize,
+            ))
+            .arg(common::contract_package_hash::arg(
+                DisplayOrder::ContractPackageHash as usize,
+            ))
+            .arg(common::contract_version::arg(
+                DisplayOrder::ContractVersion as usize,
+            ))
+            .group(
+                ArgGroup::with_name("contract")
+                    .arg(common::contract_hash::ARG_NAME)
+                    .arg(common::contract_package_hash::ARG_NAME)
+                    .arg(creation_common::show_arg_examples::ARG_NAME)
+                    .required(true),
+            )
+            .arg(entry_point::arg())
+            .arg(arg::arg());
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    fn run(matches: &ArgMatches<'_>) -> Result<Success, Error> {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+        let block_identifier = common::block_identifier::get(matches);
+
+        let secret_key = common::secret_key::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+
+        let contract_call = ContractCallStrParams {
+            contract_identifier: ContractIdentifierStrParams {
+                contract_hash: common::contract_hash::get(matches),
+                contract_package_hash: common::contract_package_hash::get(matches),
+                contract_version: common::contract_version::get(matches),
+            },
+            entry_point: entry_point::get(matches),
+            args: arg::get(matches),
+        };
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        casper_client::call_contract(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            block_identifier,
+            DeployStrParams {
+                secret_key,
+                timestamp,
+                ttl,
+                dependencies,
+                gas_price,
+                chain_name,
+            },
+            contract_call,
+            payment_str_params,
+        )
+        .map(Success::from)
+    }
+}
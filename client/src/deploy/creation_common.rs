@@ -21,6 +21,12 @@ pub(super) enum DisplayOrder {
     TransferAmount,
     TransferTargetAccount,
     TransferId,
+    BlockIdentifier,
+    ContractHash,
+    ContractPackageHash,
+    ContractVersion,
+    ContractEntryPoint,
+    ContractArg,
     Timestamp,
     Ttl,
     GasPrice,
@@ -29,6 +35,7 @@ pub(super) enum DisplayOrder {
     SessionCode,
     SessionArgSimple,
     SessionArgsComplex,
+    SessionArgsJson,
     SessionHash,
     SessionName,
     SessionPackageHash,
@@ -40,6 +47,7 @@ pub(super) enum DisplayOrder {
     PaymentCode,
     PaymentArgSimple,
     PaymentArgsComplex,
+    PaymentArgsJson,
     PaymentHash,
     PaymentName,
     PaymentPackageHash,
@@ -83,14 +91,20 @@ pub(super) mod show_arg_examples {
 pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParams<'a> {
     let session_args_simple = arg_simple::session::get(matches);
     let session_args_complex = args_complex::session::get(matches);
+    let session_args_json = args_json::session::get(matches);
     if is_session_transfer::get(matches) {
-        return SessionStrParams::with_transfer(session_args_simple, session_args_complex);
+        return SessionStrParams::with_transfer(
+            session_args_simple,
+            session_args_complex,
+            session_args_json,
+        );
     }
     if let Some(session_path) = session_path::get(matches) {
         return SessionStrParams::with_path(
             session_path,
             session_args_simple,
             session_args_complex,
+            session_args_json,
         );
     }
     let session_entry_point = session_entry_point::get(matches);
@@ -100,6 +114,7 @@ pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParam
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
         );
     }
     if let Some(session_name) = session_name::get(matches) {
@@ -108,6 +123,7 @@ pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParam
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
         );
     }
     let session_version = session_version::get(matches);
@@ -118,6 +134,7 @@ pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParam
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
         );
     }
     if let Some(session_package_name) = session_package_name::get(matches) {
@@ -127,6 +144,7 @@ pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParam
             session_entry_point,
             session_args_simple,
             session_args_complex,
+            session_args_json,
         );
     }
     unreachable!("clap arg groups and parsing should prevent this")
@@ -138,11 +156,13 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
     }
     let payment_args_simple = arg_simple::payment::get(matches);
     let payment_args_complex = args_complex::payment::get(matches);
+    let payment_args_json = args_json::payment::get(matches);
     if let Some(payment_path) = payment_path::get(matches) {
         return PaymentStrParams::with_path(
             payment_path,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
         );
     }
     let payment_entry_point = payment_entry_point::get(matches);
@@ -152,6 +172,7 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
         );
     }
     if let Some(payment_name) = payment_name::get(matches) {
@@ -160,6 +181,7 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
         );
     }
     let payment_version = payment_version::get(matches);
@@ -170,6 +192,7 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
         );
     }
     if let Some(payment_package_name) = payment_package_name::get(matches) {
@@ -179,6 +202,7 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
             payment_entry_point,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
         );
     }
     unreachable!("clap arg groups and parsing should prevent this")
@@ -463,6 +487,56 @@ pub(super) mod args_complex {
     }
 }
 
+/// Handles providing the arg for and retrieval of JSON-encoded session and payment args.
+pub(super) mod args_json {
+    use super::*;
+
+    const ARG_VALUE_NAME: &str = "JSON";
+    const ARG_HELP: &str =
+        "JSON array of named and typed args for passing to the Wasm code, e.g. '[{\"name\": \
+        \"amount\", \"type\": \"U512\", \"value\": \"100\"}]'. The type of each arg is a \
+        JSON-encoded CLType, e.g. {\"List\": {\"Option\": \"U64\"}}, and so can describe \
+        values of any type, including maps, tuples and results. See the docs of \
+        'PaymentStrParams' in the casper-client library for further examples";
+
+    pub(in crate::deploy) mod session {
+        use super::*;
+
+        pub const ARG_NAME: &str = "session-args-json";
+
+        pub fn arg() -> Arg<'static, 'static> {
+            super::arg(ARG_NAME, DisplayOrder::SessionArgsJson as usize)
+        }
+
+        pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+            matches.value_of(ARG_NAME).unwrap_or_default()
+        }
+    }
+
+    pub(in crate::deploy) mod payment {
+        use super::*;
+
+        pub const ARG_NAME: &str = "payment-args-json";
+
+        pub fn arg() -> Arg<'static, 'static> {
+            super::arg(ARG_NAME, DisplayOrder::PaymentArgsJson as usize)
+        }
+
+        pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+            matches.value_of(ARG_NAME).unwrap_or_default()
+        }
+    }
+
+    fn arg(name: &'static str, order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(name)
+            .long(name)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(order)
+    }
+}
+
 /// Handles providing the arg for and retrieval of the payment code bytes.
 pub(super) mod payment_path {
     use super::*;
@@ -550,11 +624,13 @@ pub(super) fn apply_common_session_options<'a, 'b>(subcommand: App<'a, 'b>) -> A
         .arg(session_name::arg())
         .arg(arg_simple::session::arg())
         .arg(args_complex::session::arg())
+        .arg(args_json::session::arg())
         // Group the session-arg args so only one style is used to ensure consistent ordering.
         .group(
             ArgGroup::with_name("session-args")
                 .arg(arg_simple::session::ARG_NAME)
                 .arg(args_complex::session::ARG_NAME)
+                .arg(args_json::session::ARG_NAME)
                 .required(false),
         )
         .arg(session_entry_point::arg())
@@ -584,11 +660,13 @@ pub(crate) fn apply_common_payment_options(
         .arg(payment_name::arg())
         .arg(arg_simple::payment::arg())
         .arg(args_complex::payment::arg())
+        .arg(args_json::payment::arg())
         // Group the payment-arg args so only one style is used to ensure consistent ordering.
         .group(
             ArgGroup::with_name("payment-args")
                 .arg(arg_simple::payment::ARG_NAME)
                 .arg(args_complex::payment::ARG_NAME)
+                .arg(args_json::payment::ARG_NAME)
                 .required(false),
         )
         .arg(payment_entry_point::arg())
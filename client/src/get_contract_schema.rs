@@ -0,0 +1,74 @@
+use std::str;
+
+use clap::{App, ArgGroup, ArgMatches, SubCommand};
+
+use casper_client::{ContractIdentifierStrParams, Error};
+use casper_node::rpcs::state::GetContractSchema;
+
+use crate::{command::ClientCommand, common, Success};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    BlockIdentifier,
+    ContractHash,
+    ContractPackageHash,
+    ContractVersion,
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetContractSchema {
+    const NAME: &'static str = "get-contract-schema";
+    const ABOUT: &'static str =
+        "Retrieve the entry points, versions and user groups of a stored contract";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(common::block_identifier::arg(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+            .arg(common::contract_hash::arg(
+                DisplayOrder::ContractHash as usize,
+            ))
+            .arg(common::contract_package_hash::arg(
+                DisplayOrder::ContractPackageHash as usize,
+            ))
+            .arg(common::contract_version::arg(
+                DisplayOrder::ContractVersion as usize,
+            ))
+            .group(
+                ArgGroup::with_name("contract")
+                    .arg(common::contract_hash::ARG_NAME)
+                    .arg(common::contract_package_hash::ARG_NAME)
+                    .required(true),
+            )
+    }
+
+    fn run(matches: &ArgMatches<'_>) -> Result<Success, Error> {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+        let block_identifier = common::block_identifier::get(matches);
+
+        casper_client::get_contract_schema(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            block_identifier,
+            ContractIdentifierStrParams {
+                contract_hash: common::contract_hash::get(matches),
+                contract_package_hash: common::contract_package_hash::get(matches),
+                contract_version: common::contract_version::get(matches),
+            },
+        )
+        .map(Success::from)
+    }
+}
@@ -0,0 +1,137 @@
+use std::str;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+use casper_node::rpcs::info::GetAccountHistory;
+
+use crate::{command::ClientCommand, common, Success};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    Account,
+    Offset,
+    Limit,
+}
+
+/// Handles providing the arg for and retrieval of the account identifier.
+mod account {
+    use super::*;
+
+    const ARG_NAME: &str = "account";
+    const ARG_SHORT: &str = "a";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "The account whose history is to be listed. This must be either a hex-encoded public key \
+        or a properly formatted account hash. The format for account hash is \
+        \"account-hash-<HEX STRING>\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Account as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the number of entries to skip.
+mod offset {
+    use super::*;
+
+    const ARG_NAME: &str = "offset";
+    const ARG_DEFAULT: &str = "0";
+    const ARG_HELP: &str = "The number of entries to skip, starting from the oldest";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .default_value(ARG_DEFAULT)
+            .value_name(common::ARG_INTEGER)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Offset as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the maximum number of entries to list.
+mod limit {
+    use super::*;
+
+    const ARG_NAME: &str = "limit";
+    const ARG_DEFAULT: &str = "10";
+    const ARG_HELP: &str =
+        "The maximum number of entries to list. The node returns at most 100 entries per request";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .default_value(ARG_DEFAULT)
+            .value_name(common::ARG_INTEGER)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Limit as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetAccountHistory {
+    const NAME: &'static str = "list-account-history";
+    const ABOUT: &'static str =
+        "Retrieves the deploys and transfers which touched a given account, oldest first";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(account::arg())
+            .arg(offset::arg())
+            .arg(limit::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) -> Result<Success, Error> {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+        let account = account::get(matches);
+        let offset = offset::get(matches);
+        let limit = limit::get(matches);
+
+        casper_client::get_account_history(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            account,
+            offset,
+            limit,
+        )
+        .map(Success::from)
+    }
+}
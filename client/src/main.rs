@@ -8,9 +8,11 @@ mod generate_completion;
 mod get_account_info;
 mod get_auction_info;
 mod get_balance;
+mod get_contract_schema;
 mod get_era_info_by_switch_block;
 mod get_state_hash;
 mod keygen;
+mod list_account_history;
 mod query_dictionary;
 mod query_state;
 
@@ -23,13 +25,18 @@ use casper_node::rpcs::{
     account::PutDeploy,
     chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
     docs::ListRpcs,
-    info::GetDeploy,
-    state::{GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem as QueryState},
+    info::{GetAccountHistory, GetDeploy},
+    state::{
+        GetAccountInfo, GetAuctionInfo, GetBalance, GetContractSchema, GetDictionaryItem,
+        GetItem as QueryState,
+    },
 };
 
 use account_address::GenerateAccountHash as AccountAddress;
 use command::{ClientCommand, Success};
-use deploy::{ListDeploys, MakeDeploy, MakeTransfer, SendDeploy, SignDeploy, Transfer};
+use deploy::{
+    CallContract, ListDeploys, MakeDeploy, MakeTransfer, SendDeploy, SignDeploy, Transfer,
+};
 use generate_completion::GenerateCompletion;
 use keygen::Keygen;
 
@@ -43,6 +50,7 @@ enum DisplayOrder {
     SendDeploy,
     Transfer,
     MakeTransfer,
+    CallContract,
     GetDeploy,
     GetBlock,
     GetBlockTransfers,
@@ -51,6 +59,7 @@ enum DisplayOrder {
     QueryState,
     GetBalance,
     GetAccountInfo,
+    ListAccountHistory,
     GetEraInfo,
     GetAuctionInfo,
     Keygen,
@@ -58,6 +67,7 @@ enum DisplayOrder {
     GetRpcs,
     AccountAddress,
     GetDictionaryItem,
+    GetContractSchema,
 }
 
 fn cli<'a, 'b>() -> App<'a, 'b> {
@@ -70,6 +80,7 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
         .subcommand(SendDeploy::build(DisplayOrder::SendDeploy as usize))
         .subcommand(Transfer::build(DisplayOrder::Transfer as usize))
         .subcommand(MakeTransfer::build(DisplayOrder::MakeTransfer as usize))
+        .subcommand(CallContract::build(DisplayOrder::CallContract as usize))
         .subcommand(GetDeploy::build(DisplayOrder::GetDeploy as usize))
         .subcommand(GetBlock::build(DisplayOrder::GetBlock as usize))
         .subcommand(GetBlockTransfers::build(
@@ -78,6 +89,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
         .subcommand(ListDeploys::build(DisplayOrder::ListDeploys as usize))
         .subcommand(GetBalance::build(DisplayOrder::GetBalance as usize))
         .subcommand(GetAccountInfo::build(DisplayOrder::GetAccountInfo as usize))
+        .subcommand(GetAccountHistory::build(
+            DisplayOrder::ListAccountHistory as usize,
+        ))
         .subcommand(GetStateRootHash::build(
             DisplayOrder::GetStateRootHash as usize,
         ))
@@ -95,6 +109,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
         .subcommand(GetDictionaryItem::build(
             DisplayOrder::GetDictionaryItem as usize,
         ))
+        .subcommand(GetContractSchema::build(
+            DisplayOrder::GetContractSchema as usize,
+        ))
 }
 
 #[tokio::main]
@@ -107,12 +124,14 @@ async fn main() {
         (SendDeploy::NAME, Some(matches)) => (SendDeploy::run(matches), matches),
         (Transfer::NAME, Some(matches)) => (Transfer::run(matches), matches),
         (MakeTransfer::NAME, Some(matches)) => (MakeTransfer::run(matches), matches),
+        (CallContract::NAME, Some(matches)) => (CallContract::run(matches), matches),
         (GetDeploy::NAME, Some(matches)) => (GetDeploy::run(matches), matches),
         (GetBlock::NAME, Some(matches)) => (GetBlock::run(matches), matches),
         (GetBlockTransfers::NAME, Some(matches)) => (GetBlockTransfers::run(matches), matches),
         (ListDeploys::NAME, Some(matches)) => (ListDeploys::run(matches), matches),
         (GetBalance::NAME, Some(matches)) => (GetBalance::run(matches), matches),
         (GetAccountInfo::NAME, Some(matches)) => (GetAccountInfo::run(matches), matches),
+        (GetAccountHistory::NAME, Some(matches)) => (GetAccountHistory::run(matches), matches),
         (GetStateRootHash::NAME, Some(matches)) => (GetStateRootHash::run(matches), matches),
         (QueryState::NAME, Some(matches)) => (QueryState::run(matches), matches),
         (GetEraInfoBySwitchBlock::NAME, Some(matches)) => {
@@ -124,6 +143,7 @@ async fn main() {
         (ListRpcs::NAME, Some(matches)) => (ListRpcs::run(matches), matches),
         (AccountAddress::NAME, Some(matches)) => (AccountAddress::run(matches), matches),
         (GetDictionaryItem::NAME, Some(matches)) => (GetDictionaryItem::run(matches), matches),
+        (GetContractSchema::NAME, Some(matches)) => (GetContractSchema::run(matches), matches),
         _ => {
             let _ = cli().print_long_help();
             println!();
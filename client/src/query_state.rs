@@ -16,6 +16,10 @@ enum DisplayOrder {
     StateRootHash,
     Key,
     Path,
+    Verify,
+    TrustedSwitchBlockHash,
+    FinalityThresholdFraction,
+    BlockIdentifier,
 }
 
 /// Handles providing the arg for and retrieval of the key.
@@ -98,6 +102,89 @@ mod path {
     }
 }
 
+/// Handles providing the arg for and retrieval of the verify flag.
+mod verify {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "verify";
+    const ARG_HELP: &str =
+        "If this flag is passed, the value is not looked up under --state-root-hash, but in the \
+        global state of the block given by --block-identifier. The block must be in an era after \
+        that of the switch block given by --trusted-switch-block-hash, and must have been \
+        finalized by the validators of its era, which are verified via the switch blocks of the \
+        eras in between. Responses whose blocks or Merkle proofs fail to verify are rejected";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(trusted_switch_block_hash::ARG_NAME)
+            .conflicts_with(common::state_root_hash::ARG_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Verify as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the trusted switch block hash.
+mod trusted_switch_block_hash {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "trusted-switch-block-hash";
+    const ARG_VALUE_NAME: &str = "HEX STRING";
+    const ARG_HELP: &str =
+        "Hex-encoded hash of a switch block obtained through a trusted channel. Only used with \
+        --verify";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .requires(verify::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::TrustedSwitchBlockHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the finality threshold fraction.
+mod finality_threshold_fraction {
+    use super::*;
+
+    const ARG_NAME: &str = "finality-threshold-fraction";
+    const ARG_VALUE_NAME: &str = "FRACTION";
+    const ARG_DEFAULT: &str = "1/3";
+    const ARG_HELP: &str =
+        "The fraction of the validators' total weight which must be exceeded by the weight of \
+        those who signed a block for it to be considered finalized, as in the network's chainspec. \
+        Only used with --verify";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .default_value(ARG_DEFAULT)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::FinalityThresholdFraction as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
 impl<'a, 'b> ClientCommand<'a, 'b> for GetItem {
     const NAME: &'static str = "query-state";
     const ABOUT: &'static str = "Retrieves a stored value from the network";
@@ -111,21 +198,46 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GetItem {
                 DisplayOrder::NodeAddress as usize,
             ))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
-            .arg(common::state_root_hash::arg(
-                DisplayOrder::StateRootHash as usize,
-            ))
+            .arg(
+                common::state_root_hash::arg(DisplayOrder::StateRootHash as usize)
+                    .required_unless(verify::ARG_NAME),
+            )
             .arg(key::arg())
             .arg(path::arg())
+            .arg(verify::arg())
+            .arg(trusted_switch_block_hash::arg())
+            .arg(finality_threshold_fraction::arg())
+            .arg(
+                common::block_identifier::arg(DisplayOrder::BlockIdentifier as usize)
+                    .requires(verify::ARG_NAME),
+            )
     }
 
     fn run(matches: &ArgMatches<'_>) -> Result<Success, Error> {
         let maybe_rpc_id = common::rpc_id::get(matches);
         let node_address = common::node_address::get(matches);
         let verbosity_level = common::verbose::get(matches);
-        let state_root_hash = common::state_root_hash::get(matches);
         let key = key::get(matches)?;
         let path = path::get(matches);
 
+        if verify::get(matches) {
+            let trusted_switch_block_hash = trusted_switch_block_hash::get(matches);
+            let finality_threshold_fraction = finality_threshold_fraction::get(matches);
+            let maybe_block_id = common::block_identifier::get(matches);
+            return casper_client::get_item_verified(
+                maybe_rpc_id,
+                node_address,
+                verbosity_level,
+                trusted_switch_block_hash,
+                finality_threshold_fraction,
+                maybe_block_id,
+                &key,
+                path,
+            )
+            .map(Success::from);
+        }
+
+        let state_root_hash = common::state_root_hash::get(matches);
         casper_client::get_item(
             maybe_rpc_id,
             node_address,
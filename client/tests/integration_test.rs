@@ -253,7 +253,7 @@ mod payment_params {
     }
 
     pub fn test_data_with_name() -> PaymentStrParams<'static> {
-        PaymentStrParams::with_name(NAME, ENTRYPOINT, args_simple(), "")
+        PaymentStrParams::with_name(NAME, ENTRYPOINT, args_simple(), "", "")
     }
 }
 
@@ -270,7 +270,7 @@ mod session_params {
     }
 
     pub fn test_data_with_package_hash() -> SessionStrParams<'static> {
-        SessionStrParams::with_package_hash(PKG_HASH, VERSION, ENTRYPOINT, args_simple(), "")
+        SessionStrParams::with_package_hash(PKG_HASH, VERSION, ENTRYPOINT, args_simple(), "", "")
     }
 }
 
@@ -1,22 +1,47 @@
-const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
+use super::wasm_module_cache::DEFAULT_WASM_MODULE_CACHE_SIZE;
+
+/// The default limit of depth of recursive global state queries.
+pub const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 
 /// The runtime configuration of the execution engine
 #[derive(Debug, Copy, Clone)]
 pub struct EngineConfig {
     pub(crate) max_query_depth: u64,
+    pub(crate) enable_call_tracing: bool,
+    pub(crate) wasm_module_cache_size: usize,
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         EngineConfig {
             max_query_depth: DEFAULT_MAX_QUERY_DEPTH,
+            enable_call_tracing: false,
+            wasm_module_cache_size: DEFAULT_WASM_MODULE_CACHE_SIZE,
         }
     }
 }
 
 impl EngineConfig {
     /// Creates a new engine configuration with provided parameters.
-    pub fn new(max_query_depth: u64) -> EngineConfig {
-        EngineConfig { max_query_depth }
+    pub fn new(
+        max_query_depth: u64,
+        enable_call_tracing: bool,
+        wasm_module_cache_size: usize,
+    ) -> EngineConfig {
+        EngineConfig {
+            max_query_depth,
+            enable_call_tracing,
+            wasm_module_cache_size,
+        }
+    }
+
+    /// Returns `true` if the contract calls made by executed deploys should be traced.
+    pub fn call_tracing_enabled(&self) -> bool {
+        self.enable_call_tracing
+    }
+
+    /// Returns the maximum number of stored contract modules to keep in the engine's cache.
+    pub fn wasm_module_cache_size(&self) -> usize {
+        self.wasm_module_cache_size
     }
 }
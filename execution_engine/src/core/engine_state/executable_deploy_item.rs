@@ -30,12 +30,12 @@ use casper_types::{
 use super::error;
 use crate::{
     core::{
-        engine_state::{Error, ExecError, MAX_PAYMENT_AMOUNT},
+        engine_state::{wasm_module_cache::WasmModuleCache, Error, ExecError, MAX_PAYMENT_AMOUNT},
         execution,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{
-        account::Account, newtypes::CorrelationId, stored_value::StoredValue, wasm, wasm_prep,
+        account::Account, newtypes::CorrelationId, stored_value::StoredValue, wasm,
         wasm_prep::Preprocessor,
     },
     storage::{global_state::StateReader, protocol_data::ProtocolData},
@@ -127,6 +127,7 @@ impl ExecutableDeployItem {
         account: &Account,
         correlation_id: CorrelationId,
         preprocessor: &Preprocessor,
+        wasm_module_cache: &WasmModuleCache,
         protocol_version: &ProtocolVersion,
         protocol_data: &ProtocolData,
         phase: Phase,
@@ -339,7 +340,11 @@ impl ExecutableDeployItem {
             .borrow_mut()
             .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
 
-        let module = wasm_prep::deserialize(contract_wasm.bytes())?;
+        let module = wasm_module_cache.get_or_deserialize(
+            contract.contract_wasm_hash(),
+            protocol_data.wasm_config(),
+            &contract_wasm,
+        )?;
 
         match entry_point.entry_point_type() {
             EntryPointType::Session => {
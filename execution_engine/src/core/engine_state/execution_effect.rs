@@ -1,4 +1,4 @@
-use casper_types::Key;
+use casper_types::{CallTrace, ContractEvent, Key};
 
 use super::op::Op;
 use crate::shared::{additive_map::AdditiveMap, transform::Transform};
@@ -7,14 +7,35 @@ use crate::shared::{additive_map::AdditiveMap, transform::Transform};
 pub struct ExecutionEffect {
     pub ops: AdditiveMap<Key, Op>,
     pub transforms: AdditiveMap<Key, Transform>,
+    pub events: Vec<ContractEvent>,
+    /// The contract calls made during execution.  Only recorded if call tracing is enabled in the
+    /// [`EngineConfig`](super::EngineConfig).
+    pub call_trace: CallTrace,
 }
 
 impl ExecutionEffect {
-    pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
-        ExecutionEffect { ops, transforms }
+    pub fn new(
+        ops: AdditiveMap<Key, Op>,
+        transforms: AdditiveMap<Key, Transform>,
+        events: Vec<ContractEvent>,
+    ) -> Self {
+        ExecutionEffect {
+            ops,
+            transforms,
+            events,
+            call_trace: CallTrace::default(),
+        }
+    }
+
+    /// Returns the effect with the given call trace attached.
+    pub fn with_call_trace(self, call_trace: CallTrace) -> Self {
+        ExecutionEffect { call_trace, ..self }
     }
 }
 
+// The call trace is deliberately left out, as it is only recorded by nodes which opted into call
+// tracing.  The events are left out too, as they are not persisted as part of the execution
+// results, but only streamed to event stream clients.
 impl From<&ExecutionEffect> for casper_types::ExecutionEffect {
     fn from(effect: &ExecutionEffect) -> Self {
         casper_types::ExecutionEffect {
@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
 
-use casper_types::{bytesrepr::FromBytes, CLTyped, CLValue, CLValueError, Key, TransferAddr};
+use casper_types::{
+    bytesrepr::FromBytes, CLTyped, CLValue, CLValueError, CallTrace, ContractEvent, Key,
+    TransferAddr,
+};
 
 use super::{error, execution_effect::ExecutionEffect, op::Op};
 use crate::{
@@ -40,7 +43,7 @@ fn make_payment_error_effects(
         Transform::AddUInt512(max_payment_cost.value()),
     );
 
-    Ok(ExecutionEffect::new(ops, transforms))
+    Ok(ExecutionEffect::new(ops, transforms, Vec::new()))
 }
 
 #[derive(Clone, Debug)]
@@ -206,6 +209,12 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the result with the given call trace attached to its effect.
+    pub fn with_call_trace(self, call_trace: CallTrace) -> Self {
+        let effect = self.effect().clone().with_call_trace(call_trace);
+        self.with_effect(effect)
+    }
+
     pub fn as_error(&self) -> Option<&error::Error> {
         match self {
             ExecutionResult::Failure { error, .. } => Some(error),
@@ -388,8 +397,10 @@ impl ExecutionResultBuilder {
     ) -> Result<ExecutionResult, ExecutionResultBuilderError> {
         let transfers = self.transfers();
         let cost = self.total_cost();
+        let call_trace = self.call_trace();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
+        let mut events = Vec::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
@@ -402,7 +413,7 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     return Ok(result);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -415,7 +426,7 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     ret = result.with_cost(cost);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
@@ -429,21 +440,42 @@ impl ExecutionResultBuilder {
                         error::Error::Finalization,
                     ));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
         }
 
         // Remove redundant writes to allow more opportunity to commute
-        let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
+        let reduced_effect =
+            Self::reduce_identity_writes(ops, transforms, events, reader, correlation_id);
+
+        Ok(ret.with_effect(reduced_effect.with_call_trace(call_trace)))
+    }
 
-        Ok(ret.with_effect(reduced_effect))
+    /// Returns the calls traced during payment and session execution, including those of a
+    /// failed session whose effects are discarded.
+    fn call_trace(&self) -> CallTrace {
+        let payment_trace = self
+            .payment_execution_result
+            .as_ref()
+            .map(|result| &result.effect().call_trace);
+        let session_trace = self
+            .session_execution_result
+            .as_ref()
+            .map(|result| &result.effect().call_trace);
+        CallTrace {
+            payment: payment_trace.and_then(|call_trace| call_trace.payment.clone()),
+            session: session_trace.and_then(|call_trace| call_trace.session.clone()),
+            truncated: payment_trace.map_or(false, |call_trace| call_trace.truncated)
+                || session_trace.map_or(false, |call_trace| call_trace.truncated),
+        }
     }
 
     fn add_effects(
         ops: &mut AdditiveMap<Key, Op>,
         transforms: &mut AdditiveMap<Key, Transform>,
+        events: &mut Vec<ContractEvent>,
         effect: &ExecutionEffect,
     ) {
         for (k, op) in effect.ops.iter() {
@@ -452,6 +484,7 @@ impl ExecutionResultBuilder {
         for (k, t) in effect.transforms.iter() {
             transforms.insert_add(*k, t.clone())
         }
+        events.extend(effect.events.iter().cloned());
     }
 
     /// In the case we are writing the same value as was there originally,
@@ -460,6 +493,7 @@ impl ExecutionResultBuilder {
     fn reduce_identity_writes<R: StateReader<Key, StoredValue>>(
         mut ops: AdditiveMap<Key, Op>,
         mut transforms: AdditiveMap<Key, Transform>,
+        events: Vec<ContractEvent>,
         reader: &R,
         correlation_id: CorrelationId,
     ) -> ExecutionEffect {
@@ -485,6 +519,6 @@ impl ExecutionResultBuilder {
             }
         }
 
-        ExecutionEffect::new(ops, transforms)
+        ExecutionEffect::new(ops, transforms, events)
     }
 }
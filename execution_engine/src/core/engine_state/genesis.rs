@@ -18,9 +18,10 @@ use casper_types::{
     system::{
         auction::{
             self, Bid, Bids, DelegationRate, Delegator, SeigniorageRecipient,
-            SeigniorageRecipients, SeigniorageRecipientsSnapshot, AUCTION_DELAY_KEY,
-            DELEGATION_RATE_DENOMINATOR, ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY,
-            INITIAL_ERA_END_TIMESTAMP_MILLIS, INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY,
+            SeigniorageRecipients, SeigniorageRecipientsSnapshot, UnbondingPurse,
+            AUCTION_DELAY_KEY, DELEGATION_RATE_DENOMINATOR, ERA_END_TIMESTAMP_MILLIS_KEY,
+            ERA_ID_KEY, INITIAL_ERA_END_TIMESTAMP_MILLIS, INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY,
+            MAX_DELEGATORS_PER_VALIDATOR_KEY, MINIMUM_DELEGATION_AMOUNT_KEY, REDELEGATIONS_KEY,
             SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment::{self},
@@ -536,6 +537,8 @@ pub struct ExecConfig {
     round_seigniorage_rate: Ratio<u64>,
     unbonding_delay: u64,
     genesis_timestamp_millis: u64,
+    minimum_delegation_amount: u64,
+    max_delegators_per_validator: u32,
 }
 
 impl ExecConfig {
@@ -550,6 +553,8 @@ impl ExecConfig {
         round_seigniorage_rate: Ratio<u64>,
         unbonding_delay: u64,
         genesis_timestamp_millis: u64,
+        minimum_delegation_amount: u64,
+        max_delegators_per_validator: u32,
     ) -> ExecConfig {
         ExecConfig {
             accounts,
@@ -561,6 +566,8 @@ impl ExecConfig {
             round_seigniorage_rate,
             unbonding_delay,
             genesis_timestamp_millis,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
         }
     }
 
@@ -617,6 +624,14 @@ impl ExecConfig {
     pub fn genesis_timestamp_millis(&self) -> u64 {
         self.genesis_timestamp_millis
     }
+
+    pub fn minimum_delegation_amount(&self) -> u64 {
+        self.minimum_delegation_amount
+    }
+
+    pub fn max_delegators_per_validator(&self) -> u32 {
+        self.max_delegators_per_validator
+    }
 }
 
 impl Distribution<ExecConfig> for Standard {
@@ -644,6 +659,10 @@ impl Distribution<ExecConfig> for Standard {
 
         let genesis_timestamp_millis = rng.gen();
 
+        let minimum_delegation_amount = rng.gen();
+
+        let max_delegators_per_validator = rng.gen();
+
         ExecConfig {
             accounts,
             wasm_config,
@@ -654,6 +673,8 @@ impl Distribution<ExecConfig> for Standard {
             round_seigniorage_rate,
             unbonding_delay,
             genesis_timestamp_millis,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
         }
     }
 }
@@ -1085,6 +1106,54 @@ where
         );
         named_keys.insert(UNBONDING_DELAY_KEY.into(), unbonding_delay_uref.into());
 
+        let minimum_delegation_amount = self.exec_config.minimum_delegation_amount();
+        let minimum_delegation_amount_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            minimum_delegation_amount_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(minimum_delegation_amount).map_err(|_| {
+                    GenesisError::CLValue(MINIMUM_DELEGATION_AMOUNT_KEY.to_string())
+                })?,
+            ),
+        );
+        named_keys.insert(
+            MINIMUM_DELEGATION_AMOUNT_KEY.into(),
+            minimum_delegation_amount_uref.into(),
+        );
+
+        let max_delegators_per_validator = self.exec_config.max_delegators_per_validator();
+        let max_delegators_per_validator_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            max_delegators_per_validator_uref.into(),
+            StoredValue::CLValue(CLValue::from_t(max_delegators_per_validator).map_err(|_| {
+                GenesisError::CLValue(MAX_DELEGATORS_PER_VALIDATOR_KEY.to_string())
+            })?),
+        );
+        named_keys.insert(
+            MAX_DELEGATORS_PER_VALIDATOR_KEY.into(),
+            max_delegators_per_validator_uref.into(),
+        );
+
+        let redelegations: Vec<(UnbondingPurse, PublicKey)> = Vec::new();
+        let redelegations_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            redelegations_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(redelegations)
+                    .map_err(|_| GenesisError::CLValue(REDELEGATIONS_KEY.to_string()))?,
+            ),
+        );
+        named_keys.insert(REDELEGATIONS_KEY.into(), redelegations_uref.into());
+
         let entry_points = auction::auction_entry_points();
 
         let access_key = self
@@ -1210,6 +1279,7 @@ where
                 Phase::System,
                 self.protocol_data,
                 Default::default(),
+                Default::default(),
                 call_stack,
             )
             .map_err(|_| GenesisError::UnableToCreateRuntime)?;
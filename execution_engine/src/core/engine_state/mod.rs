@@ -10,11 +10,13 @@ pub mod execution_result;
 pub mod genesis;
 pub mod op;
 pub mod query;
+pub mod read_set;
 pub mod run_genesis_request;
 pub mod step;
 pub mod system_contract_cache;
 mod transfer;
 pub mod upgrade;
+pub mod wasm_module_cache;
 
 use std::{
     cell::RefCell,
@@ -34,16 +36,18 @@ use casper_types::{
     contracts::NamedKeys,
     system::{
         auction::{
-            EraValidators, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
+            EraValidators, UnbondingPurse, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
             ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY,
-            LOCKED_FUNDS_PERIOD_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+            LOCKED_FUNDS_PERIOD_KEY, MAX_DELEGATORS_PER_VALIDATOR_KEY,
+            MINIMUM_DELEGATION_AMOUNT_KEY, REDELEGATIONS_KEY, UNBONDING_DELAY_KEY,
+            VALIDATOR_SLOTS_KEY,
         },
         handle_payment,
         mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
         CallStackElement,
     },
-    AccessRights, ApiError, BlockTime, CLValue, Contract, DeployHash, DeployInfo, Key, KeyTag,
-    Phase, ProtocolVersion, PublicKey, RuntimeArgs, URef, U512,
+    AccessRights, ApiError, BlockTime, CLTyped, CLValue, Contract, ContractHash, DeployHash,
+    DeployInfo, Key, KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs, URef, U512,
 };
 
 pub use self::{
@@ -58,18 +62,20 @@ pub use self::{
     execution_result::{ExecutionResult, ExecutionResults, ForcedTransferResult},
     genesis::{ExecConfig, GenesisAccount, GenesisResult},
     query::{GetBidsRequest, GetBidsResult, QueryRequest, QueryResult},
+    read_set::ReadSet,
     step::{RewardItem, SlashItem, StepRequest, StepResult},
     system_contract_cache::SystemContractCache,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
     upgrade::{UpgradeConfig, UpgradeResult},
+    wasm_module_cache::WasmModuleCache,
 };
 use crate::{
     core::{
         engine_state::{
             executable_deploy_item::DeployKind, execution_result::ExecutionResultBuilder,
-            genesis::GenesisInstaller, upgrade::SystemUpgrader,
+            genesis::GenesisInstaller, read_set::RecordingReader, upgrade::SystemUpgrader,
         },
-        execution::{self, DirectSystemContractCall, Executor},
+        execution::{self, AddressGenerator, DirectSystemContractCall, Executor},
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{
@@ -83,7 +89,7 @@ use crate::{
         wasm_prep::Preprocessor,
     },
     storage::{
-        global_state::{CommitResult, StateProvider},
+        global_state::{CommitResult, StateProvider, StateReader},
         protocol_data::ProtocolData,
         trie::Trie,
     },
@@ -100,6 +106,7 @@ pub const WASMLESS_TRANSFER_FIXED_GAS_PRICE: u64 = 1;
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
+    wasm_module_cache: WasmModuleCache,
     state: S,
 }
 
@@ -110,9 +117,11 @@ where
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
         let system_contract_cache = Default::default();
+        let wasm_module_cache = WasmModuleCache::new(config.wasm_module_cache_size());
         EngineState {
             config,
             system_contract_cache,
+            wasm_module_cache,
             state,
         }
     }
@@ -121,6 +130,11 @@ where
         &self.config
     }
 
+    /// Returns the cache of stored contract modules.
+    pub fn wasm_module_cache(&self) -> &WasmModuleCache {
+        &self.wasm_module_cache
+    }
+
     pub fn get_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -217,6 +231,42 @@ where
         Ok(genesis_result)
     }
 
+    /// Writes `value` under the auction contract's named key `name`, adding the named key to the
+    /// auction contract if it doesn't have it yet.
+    fn write_auction_named_value<T: CLTyped + ToBytes>(
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        address_generator: &mut AddressGenerator,
+        auction_hash: ContractHash,
+        name: &str,
+        value: T,
+    ) -> Result<(), Error> {
+        let mut auction_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, auction_hash)?;
+
+        let key = match auction_contract.named_keys().get(name) {
+            Some(key) => *key,
+            None => {
+                let key = Key::from(address_generator.new_uref(AccessRights::READ_ADD_WRITE));
+                let mut named_keys = NamedKeys::new();
+                named_keys.insert(name.to_string(), key);
+                auction_contract.named_keys_append(&mut named_keys);
+                tracking_copy.borrow_mut().write(
+                    Key::from(auction_hash),
+                    StoredValue::Contract(auction_contract),
+                );
+                key
+            }
+        };
+
+        let value = StoredValue::CLValue(
+            CLValue::from_t(value).map_err(|_| Error::Bytesrepr(name.to_string()))?,
+        );
+        tracking_copy.borrow_mut().write(key, value);
+        Ok(())
+    }
+
     pub fn commit_upgrade(
         &self,
         correlation_id: CorrelationId,
@@ -350,6 +400,54 @@ where
             tracking_copy.borrow_mut().write(unbonding_delay_key, value);
         }
 
+        // Auction contracts installed before the delegation limits were introduced lack the named
+        // keys holding them, so they are created as needed.
+        let mut address_generator = AddressGenerator::new(pre_state_hash.as_ref(), Phase::System);
+
+        if let Some(new_minimum_delegation_amount) = upgrade_config.new_minimum_delegation_amount()
+        {
+            Self::write_auction_named_value(
+                correlation_id,
+                &tracking_copy,
+                &mut address_generator,
+                new_protocol_data.auction(),
+                MINIMUM_DELEGATION_AMOUNT_KEY,
+                new_minimum_delegation_amount,
+            )?;
+        }
+
+        if let Some(new_max_delegators_per_validator) =
+            upgrade_config.new_max_delegators_per_validator()
+        {
+            Self::write_auction_named_value(
+                correlation_id,
+                &tracking_copy,
+                &mut address_generator,
+                new_protocol_data.auction(),
+                MAX_DELEGATORS_PER_VALIDATOR_KEY,
+                new_max_delegators_per_validator,
+            )?;
+        }
+
+        // Likewise, redelegations are tracked under a named key of their own, which starts out
+        // empty.
+        let has_redelegations = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, new_protocol_data.auction())?
+            .named_keys()
+            .contains_key(REDELEGATIONS_KEY);
+        if !has_redelegations {
+            let redelegations: Vec<(UnbondingPurse, PublicKey)> = Vec::new();
+            Self::write_auction_named_value(
+                correlation_id,
+                &tracking_copy,
+                &mut address_generator,
+                new_protocol_data.auction(),
+                REDELEGATIONS_KEY,
+                redelegations,
+            )?;
+        }
+
         if let Some(new_round_seigniorage_rate) = upgrade_config.new_round_seigniorage_rate() {
             let new_round_seigniorage_rate: Ratio<U512> = {
                 let (numer, denom) = new_round_seigniorage_rate.into();
@@ -401,6 +499,22 @@ where
         }
     }
 
+    /// Returns a tracking copy of the state under `hash` which records what it reads into
+    /// `read_set`, if given.
+    fn recording_tracking_copy(
+        &self,
+        hash: Blake2bHash,
+        read_set: Option<&ReadSet>,
+    ) -> Result<Option<TrackingCopy<RecordingReader<S::Reader>>>, Error> {
+        match self.state.checkout(hash).map_err(Into::into)? {
+            Some(reader) => Ok(Some(TrackingCopy::new(RecordingReader::new(
+                reader,
+                read_set.cloned(),
+            )))),
+            None => Ok(None),
+        }
+    }
+
     pub fn run_query(
         &self,
         correlation_id: CorrelationId,
@@ -435,26 +549,13 @@ where
         let mut results = ExecutionResults::with_capacity(deploys.len());
 
         for deploy_item in deploys {
-            let result = match deploy_item.session {
-                ExecutableDeployItem::Transfer { .. } => self.transfer(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-                _ => self.deploy(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-            };
+            let result = self.execute_deploy_item(
+                correlation_id,
+                &executor,
+                &exec_request,
+                deploy_item,
+                None,
+            );
             match result {
                 Ok(result) => results.push_back(result),
                 Err(error) => {
@@ -466,13 +567,131 @@ where
         Ok(results)
     }
 
-    fn get_authorized_account(
+    /// Executes each deploy of `exec_request` against the state under its parent state hash, like
+    /// `run_execute()`, additionally returning the parts of global state each execution depended
+    /// on.
+    ///
+    /// Since every deploy is executed independently of the others, the results are only valid for
+    /// deploys whose read sets don't conflict with the effects of the deploys preceding them, see
+    /// `has_read_conflict()`.
+    pub fn run_execute_recording_reads(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, ReadSet)>, Error> {
+        let executor = Executor::new(self.config);
+
+        let deploys = exec_request.take_deploys();
+        let mut results = Vec::with_capacity(deploys.len());
+
+        for deploy_item in deploys {
+            let read_set = ReadSet::default();
+            let result = self.execute_deploy_item(
+                correlation_id,
+                &executor,
+                &exec_request,
+                deploy_item,
+                Some(&read_set),
+            )?;
+            results.push((result, read_set));
+        }
+
+        Ok(results)
+    }
+
+    /// Returns `true` if an execution which depended on `read_set` of the state under
+    /// `prestate_hash` might have had a different result against the state under `state_hash`,
+    /// given that the latter was produced by applying `transforms` to the former.
+    ///
+    /// If this returns `false`, the effects of the execution can be committed on top of the state
+    /// under `state_hash` just as if it had been executed against that state.
+    pub fn has_read_conflict(
+        &self,
+        correlation_id: CorrelationId,
+        read_set: &ReadSet,
+        prestate_hash: Blake2bHash,
+        state_hash: Blake2bHash,
+        transforms: &AdditiveMap<Key, Transform>,
+    ) -> Result<bool, Error> {
+        let prestate = match self.state.checkout(prestate_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Err(Error::RootNotFound(prestate_hash)),
+        };
+        let state = match self.state.checkout(state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Err(Error::RootNotFound(state_hash)),
+        };
+
+        for (key, transform) in transforms.iter() {
+            // Additions leave both the existence and the type of a value unchanged.
+            let is_addition = matches!(
+                transform,
+                Transform::AddInt32(_)
+                    | Transform::AddUInt64(_)
+                    | Transform::AddUInt128(_)
+                    | Transform::AddUInt256(_)
+                    | Transform::AddUInt512(_)
+                    | Transform::AddKeys(_)
+            );
+            let is_depended_on = read_set.contains_value(key)
+                || (read_set.contains_type(key) && !is_addition)
+                || read_set.contains_prefix_of(key);
+            if *transform == Transform::Identity || !is_depended_on {
+                continue;
+            }
+            let value_before = prestate.read(correlation_id, key).map_err(Into::into)?;
+            let value_after = state.read(correlation_id, key).map_err(Into::into)?;
+            if value_before != value_after {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn execute_deploy_item(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        exec_request: &ExecuteRequest,
+        deploy_item: DeployItem,
+        read_set: Option<&ReadSet>,
+    ) -> Result<ExecutionResult, Error> {
+        match deploy_item.session {
+            ExecutableDeployItem::Transfer { .. } => self.transfer(
+                correlation_id,
+                executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+                read_set,
+            ),
+            _ => self.deploy(
+                correlation_id,
+                executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+                read_set,
+            ),
+        }
+    }
+
+    fn get_authorized_account<R>(
         &self,
         correlation_id: CorrelationId,
         account_hash: AccountHash,
         authorization_keys: &BTreeSet<AccountHash>,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
-    ) -> Result<Account, Error> {
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> Result<Account, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let account: Account = match tracking_copy
             .borrow_mut()
             .get_account(correlation_id, account_hash)
@@ -525,6 +744,7 @@ where
         blocktime: BlockTime,
         deploy_item: DeployItem,
         proposer: PublicKey,
+        read_set: Option<&ReadSet>,
     ) -> Result<ExecutionResult, Error> {
         let protocol_data = match self.state.get_protocol_data(protocol_version) {
             Ok(Some(protocol_data)) => protocol_data,
@@ -539,7 +759,7 @@ where
             }
         };
 
-        let tracking_copy = match self.tracking_copy(prestate_hash) {
+        let tracking_copy = match self.recording_tracking_copy(prestate_hash, read_set) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
@@ -734,6 +954,7 @@ where
                             Phase::Session,
                             protocol_data,
                             SystemContractCache::clone(&self.system_contract_cache),
+                            WasmModuleCache::clone(&self.wasm_module_cache),
                             create_purse_call_stack,
                         );
                     match maybe_uref {
@@ -835,6 +1056,7 @@ where
                     Phase::Payment,
                     protocol_data,
                     SystemContractCache::clone(&self.system_contract_cache),
+                    WasmModuleCache::clone(&self.wasm_module_cache),
                     get_payment_purse_call_stack,
                 );
 
@@ -889,6 +1111,7 @@ where
                     Phase::Payment,
                     protocol_data,
                     SystemContractCache::clone(&self.system_contract_cache),
+                    WasmModuleCache::clone(&self.wasm_module_cache),
                     transfer_to_payment_purse_call_stack,
                 );
 
@@ -985,6 +1208,7 @@ where
                 Phase::Session,
                 protocol_data,
                 SystemContractCache::clone(&self.system_contract_cache),
+                WasmModuleCache::clone(&self.wasm_module_cache),
                 transfer_call_stack,
             );
 
@@ -1059,6 +1283,7 @@ where
                     Phase::FinalizePayment,
                     protocol_data,
                     SystemContractCache::clone(&self.system_contract_cache),
+                    WasmModuleCache::clone(&self.wasm_module_cache),
                     finalize_payment_call_stack,
                 );
 
@@ -1108,6 +1333,7 @@ where
         blocktime: BlockTime,
         deploy_item: DeployItem,
         proposer: PublicKey,
+        read_set: Option<&ReadSet>,
     ) -> Result<ExecutionResult, Error> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
@@ -1134,7 +1360,7 @@ where
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         // do this second; as there is no reason to proceed if the prestate hash is invalid
-        let tracking_copy = match self.tracking_copy(prestate_hash) {
+        let tracking_copy = match self.recording_tracking_copy(prestate_hash, read_set) {
             Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
@@ -1185,6 +1411,7 @@ where
             &account,
             correlation_id,
             &preprocessor,
+            &self.wasm_module_cache,
             &protocol_version,
             &protocol_data,
             Phase::Session,
@@ -1264,6 +1491,7 @@ where
                 &account,
                 correlation_id,
                 &preprocessor,
+                &self.wasm_module_cache,
                 &protocol_version,
                 &protocol_data,
                 phase,
@@ -1290,6 +1518,7 @@ where
 
             let payment_args = payment.args().clone();
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let wasm_module_cache = WasmModuleCache::clone(&self.wasm_module_cache);
 
             if is_standard_payment {
                 executor.exec_standard_payment(
@@ -1308,6 +1537,7 @@ where
                     phase,
                     protocol_data,
                     system_contract_cache,
+                    wasm_module_cache,
                     payment_call_stack,
                 )
             } else {
@@ -1328,6 +1558,7 @@ where
                     phase,
                     protocol_data,
                     system_contract_cache,
+                    wasm_module_cache,
                     &payment_package,
                     payment_call_stack,
                 )
@@ -1415,6 +1646,7 @@ where
                 }
             };
 
+            let call_trace = payment_result.effect().call_trace.clone();
             let error = match forced_transfer {
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPayment,
                 ForcedTransferResult::GasConversionOverflow => Error::GasConversionOverflow,
@@ -1440,7 +1672,7 @@ where
                 account_main_purse_balance_key,
                 proposer_main_purse_balance_key,
             ) {
-                Ok(execution_result) => return Ok(execution_result),
+                Ok(execution_result) => return Ok(execution_result.with_call_trace(call_trace)),
                 Err(error) => {
                     let exec_error = ExecError::from(error);
                     return Ok(ExecutionResult::precondition_failure(exec_error.into()));
@@ -1486,6 +1718,7 @@ where
                     }
                 };
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let wasm_module_cache = WasmModuleCache::clone(&self.wasm_module_cache);
 
             executor.exec(
                 session_module,
@@ -1504,6 +1737,7 @@ where
                 Phase::Session,
                 protocol_data,
                 system_contract_cache,
+                wasm_module_cache,
                 &session_package,
                 session_call_stack,
             )
@@ -1583,6 +1817,7 @@ where
 
             let gas_limit = Gas::new(U512::from(std::u64::MAX));
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+            let wasm_module_cache = WasmModuleCache::clone(&self.wasm_module_cache);
 
             let handle_payment_call_stack = {
                 let deploy_account = CallStackElement::session(deploy_item.address);
@@ -1616,6 +1851,7 @@ where
                     Phase::FinalizePayment,
                     protocol_data,
                     system_contract_cache,
+                    wasm_module_cache,
                     handle_payment_call_stack,
                 );
 
@@ -1677,6 +1913,23 @@ where
         Ok(missing_descendant_trie_keys)
     }
 
+    /// Stores the given tries in a single transaction, returning the keys of their descendants
+    /// which are missing from global state.
+    pub fn put_tries_and_find_missing_descendant_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[Trie<Key, StoredValue>],
+    ) -> Result<Vec<Blake2bHash>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let inserted_trie_keys = self.state.put_tries(correlation_id, tries)?;
+        let missing_descendant_trie_keys = self
+            .state
+            .missing_trie_keys(correlation_id, inserted_trie_keys)?;
+        Ok(missing_descendant_trie_keys)
+    }
+
     pub fn missing_trie_keys(
         &self,
         correlation_id: CorrelationId,
@@ -1690,6 +1943,36 @@ where
             .map_err(Error::from)
     }
 
+    /// Starts recording the state roots committed from now on, which the following call to
+    /// [`prune`](EngineState::prune) retains.
+    ///
+    /// Returns `false` if a previously begun pruning hasn't completed yet, in which case `prune`
+    /// must not be called.
+    pub fn begin_prune(&self) -> Result<bool, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state.begin_prune().map_err(Error::from)
+    }
+
+    /// Removes all global state which is not reachable from any of `state_roots_to_keep`, or from
+    /// any state root committed since [`begin_prune`](EngineState::begin_prune), from the
+    /// underlying trie store, returning the number of removed tries.
+    ///
+    /// Queries against any other state root will subsequently report that the root was not found.
+    pub fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        state_roots_to_keep: Vec<Blake2bHash>,
+    ) -> Result<usize, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .prune(correlation_id, state_roots_to_keep)
+            .map_err(Error::from)
+    }
+
     /// Obtains validator weights for given era.
     pub fn get_era_validators(
         &self,
@@ -1776,6 +2059,7 @@ where
                 Phase::Session,
                 protocol_data,
                 SystemContractCache::clone(&self.system_contract_cache),
+                WasmModuleCache::clone(&self.wasm_module_cache),
                 get_era_validators_call_stack,
             );
 
@@ -1937,6 +2221,7 @@ where
             Phase::Session,
             protocol_data,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmModuleCache::clone(&self.wasm_module_cache),
             distribute_rewards_call_stack,
         );
 
@@ -1989,6 +2274,7 @@ where
             Phase::Session,
             protocol_data,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmModuleCache::clone(&self.wasm_module_cache),
             slash_call_stack,
         );
 
@@ -2047,6 +2333,7 @@ where
                     Phase::Session,
                     protocol_data,
                     SystemContractCache::clone(&self.system_contract_cache),
+                    WasmModuleCache::clone(&self.wasm_module_cache),
                     run_auction_call_stack,
                 );
 
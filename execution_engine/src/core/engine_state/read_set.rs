@@ -0,0 +1,129 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use casper_types::{bytesrepr::ToBytes, Key};
+
+use crate::{
+    shared::{newtypes::CorrelationId, stored_value::StoredValue},
+    storage::{global_state::StateReader, trie::merkle_proof::TrieMerkleProof},
+};
+
+#[derive(Debug, Default)]
+struct Reads {
+    values: BTreeSet<Key>,
+    types: BTreeSet<Key>,
+    prefixes: BTreeSet<Vec<u8>>,
+}
+
+/// The parts of global state which an execution depended on.
+///
+/// An execution which ran against one state is known to have the same result against another
+/// state if the two states agree on every key whose value was read, on the existence and type of
+/// every key which was only peeked at, and on the set of keys under every prefix which was listed.
+#[derive(Clone, Debug, Default)]
+pub struct ReadSet {
+    reads: Arc<Mutex<Reads>>,
+}
+
+impl ReadSet {
+    /// Returns `true` if the value under `key` was read.
+    pub fn contains_value(&self, key: &Key) -> bool {
+        self.reads.lock().unwrap().values.contains(key)
+    }
+
+    /// Returns `true` if the existence and type of the value under `key` were depended upon.
+    pub fn contains_type(&self, key: &Key) -> bool {
+        self.reads.lock().unwrap().types.contains(key)
+    }
+
+    /// Returns `true` if `key` falls under any prefix whose keys were listed.
+    pub fn contains_prefix_of(&self, key: &Key) -> bool {
+        let key_bytes = match key.to_bytes() {
+            Ok(key_bytes) => key_bytes,
+            // Be conservative if the key can't be serialized.
+            Err(_) => return true,
+        };
+        self.reads
+            .lock()
+            .unwrap()
+            .prefixes
+            .iter()
+            .any(|prefix| key_bytes.starts_with(prefix))
+    }
+
+    fn record_value(&self, key: &Key) {
+        self.reads.lock().unwrap().values.insert(*key);
+    }
+
+    fn record_type(&self, key: &Key) {
+        self.reads.lock().unwrap().types.insert(*key);
+    }
+
+    fn record_prefix(&self, prefix: &[u8]) {
+        self.reads.lock().unwrap().prefixes.insert(prefix.to_vec());
+    }
+}
+
+/// A reader of global state which records what it reads into a [`ReadSet`], if it has one.
+pub struct RecordingReader<R> {
+    reader: R,
+    read_set: Option<ReadSet>,
+}
+
+impl<R> RecordingReader<R> {
+    /// Creates a reader which records into `read_set`, or which records nothing if `read_set` is
+    /// `None`.
+    pub fn new(reader: R, read_set: Option<ReadSet>) -> Self {
+        RecordingReader { reader, read_set }
+    }
+}
+
+impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for RecordingReader<R> {
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        if let Some(read_set) = &self.read_set {
+            read_set.record_value(key);
+        }
+        self.reader.read(correlation_id, key)
+    }
+
+    fn peek(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        if let Some(read_set) = &self.read_set {
+            read_set.record_type(key);
+        }
+        self.reader.peek(correlation_id, key)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        if let Some(read_set) = &self.read_set {
+            read_set.record_value(key);
+        }
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        if let Some(read_set) = &self.read_set {
+            read_set.record_prefix(prefix);
+        }
+        self.reader.keys_with_prefix(correlation_id, prefix)
+    }
+}
@@ -114,10 +114,12 @@ impl TransferRuntimeArgsBuilder {
             Ok(key) => key,
             Err(_) => return false,
         };
-        tracking_copy
-            .borrow_mut()
-            .get_purse_balance(correlation_id, key)
-            .is_ok()
+        // Only the existence of the balance is depended upon, not its value.
+        let tracking_copy = tracking_copy.borrow();
+        match StateReader::peek(&&*tracking_copy, correlation_id, &key) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => *cl_value.cl_type() == CLType::U512,
+            _ => false,
+        }
     }
 
     fn resolve_source_uref<R>(
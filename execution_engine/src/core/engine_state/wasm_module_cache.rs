@@ -0,0 +1,195 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use linked_hash_map::LinkedHashMap;
+use parity_wasm::elements::Module;
+
+use crate::shared::{
+    wasm_config::WasmConfig,
+    wasm_prep::{self, PreprocessingError},
+};
+use casper_types::{contracts::ContractWasmHash, ContractWasm};
+
+/// The default maximum number of modules held by a [`WasmModuleCache`].
+pub const DEFAULT_WASM_MODULE_CACHE_SIZE: usize = 256;
+
+/// A bounded cache of the deserialized modules of stored contracts.
+///
+/// Stored contract Wasm is gas-instrumented when the contract is installed, so a cached module is
+/// ready to be instantiated.  Modules are cached alongside the `WasmConfig` they were loaded under
+/// and are only returned for an identical config.  Once full, the least recently used module is
+/// evicted.
+#[derive(Clone, Debug)]
+pub struct WasmModuleCache {
+    max_size: usize,
+    modules: Arc<Mutex<LinkedHashMap<ContractWasmHash, (WasmConfig, Module)>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl Default for WasmModuleCache {
+    fn default() -> Self {
+        WasmModuleCache::new(DEFAULT_WASM_MODULE_CACHE_SIZE)
+    }
+}
+
+impl WasmModuleCache {
+    /// Creates an empty cache holding at most `max_size` modules.  A `max_size` of 0 disables
+    /// caching.
+    pub fn new(max_size: usize) -> Self {
+        WasmModuleCache {
+            max_size,
+            modules: Default::default(),
+            hits: Default::default(),
+            misses: Default::default(),
+        }
+    }
+
+    /// Returns a clone of the module cached under `contract_wasm_hash` and `wasm_config`, marking
+    /// it as the most recently used one.
+    pub fn get(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        wasm_config: &WasmConfig,
+    ) -> Option<Module> {
+        let mut modules = self.modules.lock().unwrap();
+        let maybe_module = match modules.get_refresh(&contract_wasm_hash) {
+            Some((cached_wasm_config, module)) if cached_wasm_config == wasm_config => {
+                Some(module.clone())
+            }
+            _ => None,
+        };
+        let counter = if maybe_module.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        maybe_module
+    }
+
+    /// Inserts `module` into the cache under `contract_wasm_hash` and `wasm_config`, evicting the
+    /// least recently used module if the cache is full.
+    pub fn insert(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        wasm_config: WasmConfig,
+        module: Module,
+    ) {
+        if self.max_size == 0 {
+            return;
+        }
+        let mut modules = self.modules.lock().unwrap();
+        modules.insert(contract_wasm_hash, (wasm_config, module));
+        while modules.len() > self.max_size {
+            modules.pop_front();
+        }
+    }
+
+    /// Returns the module of `contract_wasm`, deserializing and caching it unless it's already
+    /// cached under `contract_wasm_hash` and `wasm_config`.
+    pub fn get_or_deserialize(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        wasm_config: &WasmConfig,
+        contract_wasm: &ContractWasm,
+    ) -> Result<Module, PreprocessingError> {
+        if let Some(module) = self.get(contract_wasm_hash, wasm_config) {
+            return Ok(module);
+        }
+        let module = wasm_prep::deserialize(contract_wasm.bytes())?;
+        self.insert(contract_wasm_hash, *wasm_config, module.clone());
+        Ok(module)
+    }
+
+    /// Returns the number of modules in the cache.
+    pub fn len(&self) -> usize {
+        self.modules.lock().unwrap().len()
+    }
+
+    /// Returns `true` if the cache holds no modules.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups which found a cached module since the last call, resetting
+    /// the count to 0.
+    pub fn take_hits(&self) -> u64 {
+        self.hits.swap(0, Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups which didn't find a cached module since the last call,
+    /// resetting the count to 0.
+    pub fn take_misses(&self) -> u64 {
+        self.misses.swap(0, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{Module, ModuleNameSubsection, NameSection, Section};
+
+    use super::WasmModuleCache;
+    use crate::shared::wasm_config::WasmConfig;
+    use casper_types::contracts::ContractWasmHash;
+
+    fn module(name: &str) -> Module {
+        let module_name_section = ModuleNameSubsection::new(name);
+        let name_section = NameSection::new(Some(module_name_section), None, None);
+        let section = Section::Name(name_section);
+        Module::new(vec![section])
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let wasm_config = WasmConfig::default();
+        let cache = WasmModuleCache::new(2);
+        let (hash_1, hash_2, hash_3) = (
+            ContractWasmHash::new([1; 32]),
+            ContractWasmHash::new([2; 32]),
+            ContractWasmHash::new([3; 32]),
+        );
+
+        cache.insert(hash_1, wasm_config, module("1"));
+        cache.insert(hash_2, wasm_config, module("2"));
+        // Reading the first module makes the second one the least recently used.
+        assert_eq!(cache.get(hash_1, &wasm_config), Some(module("1")));
+        cache.insert(hash_3, wasm_config, module("3"));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(hash_2, &wasm_config), None);
+        assert_eq!(cache.get(hash_1, &wasm_config), Some(module("1")));
+        assert_eq!(cache.get(hash_3, &wasm_config), Some(module("3")));
+        assert_eq!(cache.take_hits(), 3);
+        assert_eq!(cache.take_misses(), 1);
+        assert_eq!(cache.take_hits(), 0);
+        assert_eq!(cache.take_misses(), 0);
+    }
+
+    #[test]
+    fn should_not_return_module_loaded_under_different_wasm_config() {
+        let wasm_config = WasmConfig::default();
+        let mut other_wasm_config = wasm_config;
+        other_wasm_config.max_stack_height += 1;
+        let cache = WasmModuleCache::new(1);
+        let hash = ContractWasmHash::new([1; 32]);
+
+        cache.insert(hash, wasm_config, module("1"));
+        assert_eq!(cache.get(hash, &other_wasm_config), None);
+        assert_eq!(cache.get(hash, &wasm_config), Some(module("1")));
+    }
+
+    #[test]
+    fn should_not_cache_when_disabled() {
+        let wasm_config = WasmConfig::default();
+        let cache = WasmModuleCache::new(0);
+        let hash = ContractWasmHash::new([1; 32]);
+
+        cache.insert(hash, wasm_config, module("1"));
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(hash, &wasm_config), None);
+        assert_eq!(cache.take_misses(), 1);
+    }
+}
@@ -17,7 +17,8 @@ use crate::{
     core::{
         engine_state::{
             execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-            system_contract_cache::SystemContractCache, EngineConfig,
+            system_contract_cache::SystemContractCache, wasm_module_cache::WasmModuleCache,
+            EngineConfig,
         },
         execution::{address_generator::AddressGenerator, Error},
         runtime::{extract_access_rights_from_keys, instance_and_memory, Runtime},
@@ -103,6 +104,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        wasm_module_cache: WasmModuleCache,
         contract_package: &ContractPackage,
         call_stack: Vec<CallStackElement>,
     ) -> ExecutionResult
@@ -170,6 +172,7 @@ impl Executor {
         let mut runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            wasm_module_cache,
             memory,
             module,
             context,
@@ -265,15 +268,21 @@ impl Executor {
                 }
             }
         }
+        runtime.start_call_trace(entry_point_name, &args);
+        let result = instance
+            .invoke_export(entry_point_name, &[], &mut runtime)
+            .map_err(Error::from);
+        let call_trace = runtime.finish_call_trace(result.as_ref().err());
+
         on_fail_charge!(
-            instance.invoke_export(entry_point_name, &[], &mut runtime),
+            result,
             runtime.context().gas_counter(),
-            effects_snapshot,
+            effects_snapshot.with_call_trace(call_trace),
             runtime.context().transfers().to_owned()
         );
 
         ExecutionResult::Success {
-            effect: runtime.context().effect(),
+            effect: runtime.context().effect().with_call_trace(call_trace),
             transfers: runtime.context().transfers().to_owned(),
             cost: runtime.context().gas_counter(),
         }
@@ -296,6 +305,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        wasm_module_cache: WasmModuleCache,
         call_stack: Vec<CallStackElement>,
     ) -> ExecutionResult
     where
@@ -337,6 +347,7 @@ impl Executor {
             phase,
             protocol_data,
             system_contract_cache,
+            wasm_module_cache,
             call_stack,
         ) {
             Ok((_instance, runtime)) => runtime,
@@ -386,6 +397,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        wasm_module_cache: WasmModuleCache,
         call_stack: Vec<CallStackElement>,
     ) -> (Option<T>, ExecutionResult)
     where
@@ -472,6 +484,7 @@ impl Executor {
             phase,
             protocol_data,
             system_contract_cache,
+            wasm_module_cache,
             call_stack,
         ) {
             Ok((instance, runtime)) => (instance, runtime),
@@ -520,6 +533,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        wasm_module_cache: WasmModuleCache,
         call_stack: Vec<CallStackElement>,
     ) -> Result<T, Error>
     where
@@ -551,6 +565,7 @@ impl Executor {
             phase,
             protocol_data,
             system_contract_cache,
+            wasm_module_cache,
             call_stack,
         )?;
 
@@ -609,6 +624,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        wasm_module_cache: WasmModuleCache,
         call_stack: Vec<CallStackElement>,
     ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
     where
@@ -656,6 +672,7 @@ impl Executor {
         let runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            wasm_module_cache,
             memory,
             module,
             runtime_context,
@@ -54,6 +54,11 @@ pub enum FunctionIndex {
     DictionaryGetFuncIndex,
     DictionaryPutFuncIndex,
     LoadCallStack,
+    EmitEventIndex,
+    VerifySignatureIndex,
+    DictionaryRemoveFuncIndex,
+    DictionaryKeysFuncIndex,
+    NewIndexedDictionaryFuncIndex,
 }
 
 impl From<FunctionIndex> for usize {
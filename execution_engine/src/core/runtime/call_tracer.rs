@@ -0,0 +1,243 @@
+use casper_types::{
+    bytesrepr::ToBytes, CallFrame, ContractHash, ContractPackageHash, HostFunctionCall, RuntimeArgs,
+};
+
+use crate::shared::gas::Gas;
+
+/// The maximum number of frames and host function calls recorded by a single execution.
+const MAX_ENTRY_COUNT: usize = 10_000;
+
+/// The maximum total size in bytes of the entry point names, arguments and host function names
+/// recorded by a single execution.
+const MAX_BYTE_COUNT: usize = 1024 * 1024;
+
+/// A call which has been entered but not yet exited, together with the gas counter's value when
+/// it was entered.
+struct OpenFrame {
+    frame: CallFrame,
+    initial_gas: Gas,
+    /// Whether the frame is part of the trace, as opposed to having been left out once the trace
+    /// reached its maximum size.
+    is_recorded: bool,
+    /// Whether the frame's most recent host function call is part of the trace.
+    is_last_host_function_call_recorded: bool,
+}
+
+/// Records the tree of contract calls made by a single execution, i.e. by the payment or session
+/// code of a deploy.
+///
+/// Each call is recorded by entering a frame before it's executed and exiting it afterwards, so
+/// frames are kept on a stack mirroring the runtime's call stack.
+///
+/// Once the recorded frames and host function calls reach `MAX_ENTRY_COUNT` entries or
+/// `MAX_BYTE_COUNT` bytes, further ones are left out and the trace is marked as truncated.
+pub(super) struct CallTracer {
+    open_frames: Vec<OpenFrame>,
+    root: Option<CallFrame>,
+    max_entry_count: usize,
+    max_byte_count: usize,
+    entry_count: usize,
+    byte_count: usize,
+    is_truncated: bool,
+}
+
+impl Default for CallTracer {
+    fn default() -> Self {
+        CallTracer::with_limits(MAX_ENTRY_COUNT, MAX_BYTE_COUNT)
+    }
+}
+
+impl CallTracer {
+    fn with_limits(max_entry_count: usize, max_byte_count: usize) -> Self {
+        CallTracer {
+            open_frames: Vec::new(),
+            root: None,
+            max_entry_count,
+            max_byte_count,
+            entry_count: 0,
+            byte_count: 0,
+            is_truncated: false,
+        }
+    }
+
+    /// Reserves room in the trace for an entry of `byte_count` bytes, returning `false` and marking
+    /// the trace as truncated if there is none left.
+    fn try_reserve(&mut self, byte_count: usize) -> bool {
+        if self.entry_count >= self.max_entry_count
+            || self.byte_count.saturating_add(byte_count) > self.max_byte_count
+        {
+            self.is_truncated = true;
+            return false;
+        }
+        self.entry_count += 1;
+        self.byte_count += byte_count;
+        true
+    }
+
+    /// Enters a new frame as a child of the current one.
+    pub(super) fn enter(
+        &mut self,
+        contract_hash: Option<ContractHash>,
+        contract_package_hash: Option<ContractPackageHash>,
+        entry_point: &str,
+        args: &RuntimeArgs,
+        initial_gas: Gas,
+    ) {
+        let is_parent_recorded = self
+            .open_frames
+            .last()
+            .map_or(true, |open_frame| open_frame.is_recorded);
+        let is_recorded =
+            is_parent_recorded && self.try_reserve(entry_point.len() + args.serialized_length());
+        let frame = CallFrame {
+            contract_hash,
+            contract_package_hash,
+            entry_point: entry_point.to_string(),
+            args: if is_recorded {
+                args.clone()
+            } else {
+                RuntimeArgs::new()
+            },
+            gas_used: Default::default(),
+            host_function_calls: Vec::new(),
+            calls: Vec::new(),
+            error: None,
+        };
+        self.open_frames.push(OpenFrame {
+            frame,
+            initial_gas,
+            is_recorded,
+            is_last_host_function_call_recorded: false,
+        });
+    }
+
+    /// Records a call of the named host function by the current frame.
+    pub(super) fn record_host_function_call(&mut self, name: &str) {
+        let is_frame_recorded = match self.open_frames.last() {
+            Some(open_frame) => open_frame.is_recorded,
+            None => return,
+        };
+        let is_recorded = is_frame_recorded && self.try_reserve(name.len());
+        if let Some(open_frame) = self.open_frames.last_mut() {
+            open_frame.is_last_host_function_call_recorded = is_recorded;
+            if is_recorded {
+                open_frame.frame.host_function_calls.push(HostFunctionCall {
+                    name: name.to_string(),
+                    cost: Default::default(),
+                });
+            }
+        }
+    }
+
+    /// Adds `cost` to the cost of the current frame's most recent host function call.
+    pub(super) fn charge_host_function_call(&mut self, cost: Gas) {
+        if let Some(host_function_call) = self
+            .open_frames
+            .last_mut()
+            .filter(|open_frame| open_frame.is_last_host_function_call_recorded)
+            .and_then(|open_frame| open_frame.frame.host_function_calls.last_mut())
+        {
+            host_function_call.cost += cost.value();
+        }
+    }
+
+    /// Exits the current frame, recording the gas it used and the error it failed with, if any.
+    pub(super) fn exit(&mut self, final_gas: Gas, error: Option<String>) {
+        let OpenFrame {
+            mut frame,
+            initial_gas,
+            is_recorded,
+            ..
+        } = match self.open_frames.pop() {
+            Some(open_frame) => open_frame,
+            None => return,
+        };
+        if !is_recorded {
+            return;
+        }
+        frame.gas_used = final_gas
+            .checked_sub(initial_gas)
+            .unwrap_or_default()
+            .value();
+        frame.error = error;
+        match self.open_frames.last_mut() {
+            Some(parent) => parent.frame.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+
+    /// Returns the outermost frame, once it has been exited.
+    pub(super) fn take_root(&mut self) -> Option<CallFrame> {
+        self.root.take()
+    }
+
+    /// Returns `true` if any frames or host function calls were left out of the trace.
+    pub(super) fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{runtime_args, RuntimeArgs, U512};
+
+    use super::*;
+
+    #[test]
+    fn should_truncate_trace_exceeding_entry_count() {
+        let mut call_tracer = CallTracer::with_limits(3, MAX_BYTE_COUNT);
+        let args = RuntimeArgs::new();
+
+        call_tracer.enter(None, None, "call", &args, Gas::new(U512::zero()));
+        call_tracer.record_host_function_call("host_function_read_value");
+        call_tracer.charge_host_function_call(Gas::new(U512::from(10)));
+        call_tracer.enter(None, None, "first", &args, Gas::new(U512::from(10)));
+        // Neither the second call nor anything it does fits in the trace.
+        call_tracer.record_host_function_call("host_function_write");
+        call_tracer.charge_host_function_call(Gas::new(U512::from(20)));
+        call_tracer.exit(Gas::new(U512::from(30)), None);
+        call_tracer.enter(None, None, "second", &args, Gas::new(U512::from(30)));
+        call_tracer.enter(None, None, "nested", &args, Gas::new(U512::from(30)));
+        call_tracer.exit(Gas::new(U512::from(40)), None);
+        call_tracer.exit(Gas::new(U512::from(50)), None);
+        call_tracer.record_host_function_call("host_function_ret");
+        call_tracer.charge_host_function_call(Gas::new(U512::from(5)));
+        call_tracer.exit(Gas::new(U512::from(55)), None);
+
+        assert!(call_tracer.is_truncated());
+        let root = call_tracer.take_root().expect("should have root frame");
+        assert_eq!(root.gas_used, U512::from(55));
+        assert_eq!(root.host_function_calls.len(), 1);
+        assert_eq!(root.host_function_calls[0].cost, U512::from(10));
+        assert_eq!(root.calls.len(), 1);
+        assert_eq!(root.calls[0].entry_point, "first");
+        assert!(root.calls[0].host_function_calls.is_empty());
+    }
+
+    #[test]
+    fn should_truncate_trace_exceeding_byte_count() {
+        let args = runtime_args! { "payload" => vec![0u8; 100] };
+        let mut call_tracer = CallTracer::with_limits(MAX_ENTRY_COUNT, 100);
+
+        call_tracer.enter(None, None, "call", &RuntimeArgs::new(), Gas::default());
+        call_tracer.enter(None, None, "large", &args, Gas::default());
+        call_tracer.exit(Gas::default(), None);
+        call_tracer.exit(Gas::default(), None);
+
+        assert!(call_tracer.is_truncated());
+        let root = call_tracer.take_root().expect("should have root frame");
+        assert!(root.calls.is_empty());
+
+        let mut call_tracer = CallTracer::default();
+        call_tracer.enter(None, None, "call", &args, Gas::default());
+        call_tracer.exit(Gas::default(), None);
+        assert!(!call_tracer.is_truncated());
+        assert_eq!(
+            call_tracer
+                .take_root()
+                .expect("should have root frame")
+                .args,
+            args
+        );
+    }
+}
@@ -35,6 +35,7 @@ where
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
         let mut scoped_instrumenter = ScopedInstrumenter::new(func);
+        self.trace_host_function_call(func);
 
         let host_function_costs = self
             .protocol_data()
@@ -980,7 +981,7 @@ where
                     &DEFAULT_HOST_FUNCTION_NEW_DICTIONARY,
                     [output_size_ptr],
                 )?;
-                let ret = self.new_dictionary(output_size_ptr)?;
+                let ret = self.new_dictionary(output_size_ptr, false)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
             FunctionIndex::DictionaryGetFuncIndex => {
@@ -1046,6 +1047,114 @@ where
                 let ret = self.load_call_stack(call_stack_len_ptr, result_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to event name in Wasm memory
+                // args(1) = size of event name in Wasm memory
+                // args(2) = pointer to event value in Wasm memory
+                // args(3) = size of event value in Wasm memory
+                let (name_ptr, name_size, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.emit_event,
+                    [name_ptr, name_size, value_ptr, value_size],
+                )?;
+                scoped_instrumenter.add_property("name_size", name_size);
+                scoped_instrumenter.add_property("value_size", value_size);
+                self.emit_event(name_ptr, name_size, value_ptr, value_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::VerifySignatureIndex => {
+                // args(0) = pointer to message in Wasm memory
+                // args(1) = size of message in Wasm memory
+                // args(2) = pointer to serialized signature in Wasm memory
+                // args(3) = size of serialized signature in Wasm memory
+                // args(4) = pointer to serialized public key in Wasm memory
+                // args(5) = size of serialized public key in Wasm memory
+                let (
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.verify_signature,
+                    [
+                        message_ptr,
+                        message_size,
+                        signature_ptr,
+                        signature_size,
+                        public_key_ptr,
+                        public_key_size,
+                    ],
+                )?;
+                scoped_instrumenter.add_property("message_size", message_size);
+                let verified = self.verify_signature(
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(i32::from(verified))))
+            }
+
+            FunctionIndex::DictionaryRemoveFuncIndex => {
+                // args(0) = pointer to uref in Wasm memory
+                // args(1) = size of uref in Wasm memory
+                // args(2) = pointer to key bytes pointer in Wasm memory
+                // args(3) = pointer to key bytes size in Wasm memory
+                let (uref_ptr, uref_size, key_bytes_ptr, key_bytes_size): (_, u32, _, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.dictionary_remove,
+                    [uref_ptr, uref_size, key_bytes_ptr, key_bytes_size],
+                )?;
+                scoped_instrumenter.add_property("key_bytes_size", key_bytes_size);
+                let ret =
+                    self.dictionary_remove(uref_ptr, uref_size, key_bytes_ptr, key_bytes_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::DictionaryKeysFuncIndex => {
+                // args(0) = pointer to uref in Wasm memory
+                // args(1) = size of uref in Wasm memory
+                // args(2) = number of keys to skip
+                // args(3) = maximum number of keys to read
+                // args(4) = pointer to output size (output param)
+                let (uref_ptr, uref_size, offset, limit, output_size_ptr): (_, u32, u32, u32, _) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.dictionary_keys,
+                    [uref_ptr, uref_size, offset, limit, output_size_ptr],
+                )?;
+                scoped_instrumenter.add_property("offset", offset);
+                scoped_instrumenter.add_property("limit", limit);
+                let ret = self.dictionary_keys(
+                    &host_function_costs.dictionary_keys,
+                    uref_ptr,
+                    uref_size,
+                    offset,
+                    limit,
+                    output_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::NewIndexedDictionaryFuncIndex => {
+                // args(0) = pointer to output size (output param)
+                let (output_size_ptr,): (u32,) = Args::parse(args)?;
+
+                self.charge_host_function_call(
+                    &DEFAULT_HOST_FUNCTION_NEW_DICTIONARY,
+                    [output_size_ptr],
+                )?;
+                let ret = self.new_dictionary(output_size_ptr, true)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
         }
     }
 }
@@ -110,6 +110,18 @@ where
         }
     }
 
+    fn balance_exists(&mut self, uref: URef) -> Result<bool, Error> {
+        let maybe_value = self
+            .context
+            .peek_gs_direct(&Key::Balance(uref.addr()))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::CLValue(value)) if *value.cl_type() == U512::cl_type() => Ok(true),
+            Some(_) => Err(Error::CLValue),
+            None => Ok(false),
+        }
+    }
+
     fn write_balance(&mut self, uref: URef, balance: U512) -> Result<(), Error> {
         let cl_value = CLValue::from_t(balance).map_err(|_| Error::CLValue)?;
         self.context
@@ -1,5 +1,6 @@
 mod args;
 mod auction_internal;
+mod call_tracer;
 mod externals;
 mod handle_payment_internal;
 mod mint_internal;
@@ -7,10 +8,12 @@ mod scoped_instrumenter;
 mod standard_payment_internal;
 
 use std::{
+    cell::RefCell,
     cmp,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    rc::Rc,
 };
 
 use itertools::Itertools;
@@ -24,6 +27,7 @@ use casper_types::{
         self, Contract, ContractPackage, ContractPackageStatus, ContractVersion, ContractVersions,
         DisabledVersions, EntryPoint, EntryPointAccess, EntryPoints, Group, Groups, NamedKeys,
     },
+    crypto,
     system::{
         self,
         auction::{self, Auction, EraInfo},
@@ -32,18 +36,27 @@ use casper_types::{
         standard_payment::{self, StandardPayment},
         CallStackElement, SystemContractType,
     },
-    AccessRights, ApiError, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash,
+    AccessRights, ApiError, CLType, CLTyped, CLValue, CallTrace, ContractHash, ContractPackageHash,
     ContractVersionKey, ContractWasm, DeployHash, EntryPointType, EraId, Key, NamedArg, Parameter,
-    Phase, ProtocolVersion, PublicKey, RuntimeArgs, Transfer, TransferResult, TransferredTo, URef,
-    DICTIONARY_ITEM_KEY_MAX_LENGTH, U128, U256, U512,
+    Phase, ProtocolVersion, PublicKey, RuntimeArgs, Signature, Transfer, TransferResult,
+    TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U128, U256, U512,
 };
 
 use crate::{
     core::{
-        engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
+        engine_state::{
+            system_contract_cache::SystemContractCache, wasm_module_cache::WasmModuleCache,
+            EngineConfig,
+        },
         execution::{self, Error},
-        resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
-        runtime::scoped_instrumenter::ScopedInstrumenter,
+        resolvers::{
+            create_module_resolver, memory_resolver::MemoryResolver,
+            v1_function_index::FunctionIndex,
+        },
+        runtime::{
+            call_tracer::CallTracer,
+            scoped_instrumenter::{self, ScopedInstrumenter},
+        },
         runtime_context::{self, RuntimeContext},
         Address,
     },
@@ -59,12 +72,14 @@ use crate::{
 
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
+    wasm_module_cache: WasmModuleCache,
     config: EngineConfig,
     memory: MemoryRef,
     module: Module,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
     call_stack: Vec<CallStackElement>,
+    call_tracer: Option<Rc<RefCell<CallTracer>>>,
 }
 
 pub fn instance_and_memory(
@@ -971,6 +986,7 @@ where
     pub fn new(
         config: EngineConfig,
         system_contract_cache: SystemContractCache,
+        wasm_module_cache: WasmModuleCache,
         memory: MemoryRef,
         module: Module,
         context: RuntimeContext<'a, R>,
@@ -979,11 +995,13 @@ where
         Runtime {
             config,
             system_contract_cache,
+            wasm_module_cache,
             memory,
             module,
             host_buffer: None,
             context,
             call_stack,
+            call_tracer: None,
         }
     }
 
@@ -1026,6 +1044,91 @@ where
         &self.call_stack
     }
 
+    /// Starts tracing the contract calls made by this runtime if call tracing is enabled, with the
+    /// call of `entry_point_name` as the outermost frame.
+    pub(crate) fn start_call_trace(&mut self, entry_point_name: &str, args: &RuntimeArgs) {
+        if !self.config.call_tracing_enabled() {
+            return;
+        }
+        self.call_tracer = Some(Rc::new(RefCell::new(CallTracer::default())));
+        let (contract_hash, contract_package_hash) = match self.call_stack.last() {
+            Some(CallStackElement::StoredSession {
+                contract_hash,
+                contract_package_hash,
+                ..
+            })
+            | Some(CallStackElement::StoredContract {
+                contract_hash,
+                contract_package_hash,
+            }) => (Some(*contract_hash), Some(*contract_package_hash)),
+            Some(CallStackElement::Session { .. }) | None => (None, None),
+        };
+        self.enter_call_frame(contract_hash, contract_package_hash, entry_point_name, args);
+    }
+
+    /// Finishes the call trace started by [`Runtime::start_call_trace`], recording `error` as the
+    /// outcome of the outermost frame.
+    ///
+    /// The returned trace is empty if call tracing is disabled.
+    pub(crate) fn finish_call_trace(&mut self, error: Option<&Error>) -> CallTrace {
+        self.exit_call_frame(error);
+        let (root, truncated) = match self.call_tracer.take() {
+            Some(call_tracer) => {
+                let mut call_tracer = call_tracer.borrow_mut();
+                (call_tracer.take_root(), call_tracer.is_truncated())
+            }
+            None => return CallTrace::default(),
+        };
+        match self.context.phase() {
+            Phase::Payment => CallTrace {
+                payment: root,
+                session: None,
+                truncated,
+            },
+            Phase::Session => CallTrace {
+                payment: None,
+                session: root,
+                truncated,
+            },
+            Phase::System | Phase::FinalizePayment => CallTrace::default(),
+        }
+    }
+
+    fn enter_call_frame(
+        &self,
+        contract_hash: Option<ContractHash>,
+        contract_package_hash: Option<ContractPackageHash>,
+        entry_point_name: &str,
+        args: &RuntimeArgs,
+    ) {
+        if let Some(call_tracer) = &self.call_tracer {
+            call_tracer.borrow_mut().enter(
+                contract_hash,
+                contract_package_hash,
+                entry_point_name,
+                args,
+                self.context.gas_counter(),
+            );
+        }
+    }
+
+    fn exit_call_frame(&self, error: Option<&Error>) {
+        if let Some(call_tracer) = &self.call_tracer {
+            call_tracer
+                .borrow_mut()
+                .exit(self.context.gas_counter(), error.map(ToString::to_string));
+        }
+    }
+
+    fn trace_host_function_call(&self, function_index: FunctionIndex) {
+        if let (Some(call_tracer), Some(name)) = (
+            &self.call_tracer,
+            scoped_instrumenter::host_function_name(function_index),
+        ) {
+            call_tracer.borrow_mut().record_host_function_call(name);
+        }
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -1411,6 +1514,7 @@ where
         let mut mint_runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmModuleCache::clone(&self.wasm_module_cache),
             self.memory.clone(),
             self.module.clone(),
             mint_context,
@@ -1558,6 +1662,7 @@ where
         let mut runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmModuleCache::clone(&self.wasm_module_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
@@ -1687,6 +1792,7 @@ where
         let mut runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmModuleCache::clone(&self.wasm_module_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
@@ -1760,6 +1866,22 @@ where
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_REDELEGATE => (|| {
+                runtime.charge_system_contract_call(auction_costs.redelegate)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
+                let new_validator =
+                    Self::get_named_argument(runtime_args, auction::ARG_NEW_VALIDATOR)?;
+
+                let result = runtime
+                    .redelegate(delegator, validator, amount, new_validator)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+
             auction::METHOD_RUN_AUCTION => (|| {
                 runtime.charge_system_contract_call(auction_costs.run_auction)?;
 
@@ -1994,7 +2116,13 @@ where
         // else the called contract's context
         let context_key = self.get_context_key_for_contract_call(contract_hash, &entry_point)?;
 
-        self.execute_contract(
+        self.enter_call_frame(
+            Some(contract_hash),
+            Some(contract.contract_package_hash()),
+            entry_point.name(),
+            &args,
+        );
+        let result = self.execute_contract(
             context_key,
             context_key,
             contract_hash,
@@ -2002,7 +2130,9 @@ where
             args,
             entry_point,
             self.context.protocol_version(),
-        )
+        );
+        self.exit_call_frame(result.as_ref().err());
+        result
     }
 
     fn get_context_key_for_contract_call(
@@ -2124,6 +2254,8 @@ where
                 .and_then(|hash_addr| self.system_contract_cache.get(hash_addr.into()));
             let wasm_key = contract.contract_wasm_key();
 
+            // The contract Wasm is read even if its module is cached, as the read is recorded in
+            // the execution effects.
             let contract_wasm: ContractWasm = match self.context.read_gs(&wasm_key)? {
                 Some(StoredValue::ContractWasm(contract_wasm)) => contract_wasm,
                 Some(_) => return Err(Error::InvalidContractWasm(contract.contract_wasm_hash())),
@@ -2131,7 +2263,11 @@ where
             };
             match maybe_module {
                 Some(module) => module,
-                None => parity_wasm::deserialize_buffer(contract_wasm.bytes())?,
+                None => self.wasm_module_cache.get_or_deserialize(
+                    contract.contract_wasm_hash(),
+                    self.protocol_data().wasm_config(),
+                    &contract_wasm,
+                )?,
             }
         };
 
@@ -2152,6 +2288,7 @@ where
         };
 
         let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+        let wasm_module_cache = WasmModuleCache::clone(&self.wasm_module_cache);
 
         let config = self.config;
 
@@ -2197,12 +2334,14 @@ where
 
         let mut runtime = Runtime {
             system_contract_cache,
+            wasm_module_cache,
             config,
             memory,
             module,
             host_buffer,
             context,
             call_stack,
+            call_tracer: self.call_tracer.clone(),
         };
 
         let result = instance.invoke_export(entry_point_name, &[], &mut runtime);
@@ -3485,11 +3624,18 @@ where
     {
         let cost = host_function.calculate_gas_cost(weights);
         self.gas(cost)?;
+        if let Some(call_tracer) = &self.call_tracer {
+            call_tracer.borrow_mut().charge_host_function_call(cost);
+        }
         Ok(())
     }
 
-    /// Creates a dictionary
-    fn new_dictionary(&mut self, output_size_ptr: u32) -> Result<Result<(), ApiError>, Error> {
+    /// Creates a dictionary, whose item keys can be read with `dictionary_keys` if it's `indexed`
+    fn new_dictionary(
+        &mut self,
+        output_size_ptr: u32,
+        indexed: bool,
+    ) -> Result<Result<(), ApiError>, Error> {
         // check we can write to the host buffer
         if let Err(err) = self.check_host_buffer() {
             return Ok(Err(err));
@@ -3497,6 +3643,9 @@ where
 
         // Create new URef
         let new_uref = self.context.new_unit_uref()?;
+        if indexed {
+            self.context.enable_dictionary_index(new_uref)?;
+        }
 
         // create CLValue for return value
         let new_uref_value = CLValue::from_t(new_uref)?;
@@ -3589,6 +3738,115 @@ where
         }
         Ok(Ok(()))
     }
+
+    /// Removes a `key` and its value from a dictionary
+    fn dictionary_remove(
+        &mut self,
+        uref_ptr: u32,
+        uref_size: u32,
+        key_ptr: u32,
+        key_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let uref: URef = self.t_from_mem(uref_ptr, uref_size)?;
+        let dictionary_item_key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        if dictionary_item_key_bytes.len() > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+            return Ok(Err(ApiError::DictionaryItemKeyExceedsLength));
+        }
+        let dictionary_item_key = if let Ok(item_key) = String::from_utf8(dictionary_item_key_bytes)
+        {
+            item_key
+        } else {
+            return Ok(Err(ApiError::InvalidDictionaryItemKey));
+        };
+        match self.context.dictionary_remove(uref, &dictionary_item_key) {
+            Ok(true) => Ok(Ok(())),
+            Ok(false) => Ok(Err(ApiError::ValueNotFound)),
+            Err(error) => Err(Trap::from(error)),
+        }
+    }
+
+    /// Reads at most `limit` keys of an indexed dictionary, skipping the first `offset` ones
+    ///
+    /// Finding the keys requires scanning all the index entries of the dictionary, each of which is
+    /// charged the same as a skipped one.
+    fn dictionary_keys(
+        &mut self,
+        host_function: &HostFunction<[Cost; 5]>,
+        uref_ptr: u32,
+        uref_size: u32,
+        offset: u32,
+        limit: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        // check we can write to the host buffer
+        if let Err(err) = self.check_host_buffer() {
+            return Ok(Err(err));
+        }
+
+        let uref: URef = self.t_from_mem(uref_ptr, uref_size)?;
+        let (dictionary_item_keys, scanned_count) =
+            match self
+                .context
+                .dictionary_keys(uref, offset as usize, limit as usize)?
+            {
+                Some(result) => result,
+                None => return Ok(Err(ApiError::DictionaryNotIndexed)),
+            };
+
+        // The skipped entries have already been charged for as part of the `offset` argument.
+        let unskipped_count = scanned_count.saturating_sub(offset as usize);
+        let scan_cost = HostFunction::new(0, [0, 0, host_function.arguments()[2], 0, 0]);
+        self.charge_host_function_call(&scan_cost, [0, 0, unskipped_count as u32, 0, 0])?;
+
+        let cl_value = CLValue::from_t(dictionary_item_keys).map_err(Error::CLValue)?;
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buffer(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Emits an event named by the string at `name_ptr` with the `CLValue` at `value_ptr` as its
+    /// payload.
+    fn emit_event(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.context.emit_event(name, value)?;
+        Ok(())
+    }
+
+    /// Checks whether the serialized `Signature` at `signature_ptr` is a valid signature of the
+    /// message at `message_ptr` by the serialized `PublicKey` at `public_key_ptr`.
+    fn verify_signature(
+        &mut self,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        public_key_ptr: u32,
+        public_key_size: u32,
+    ) -> Result<bool, Trap> {
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature_bytes = self.bytes_from_mem(signature_ptr, signature_size as usize)?;
+        let signature: Signature =
+            bytesrepr::deserialize(signature_bytes).map_err(Error::BytesRepr)?;
+        let public_key_bytes = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+        let public_key: PublicKey =
+            bytesrepr::deserialize(public_key_bytes).map_err(Error::BytesRepr)?;
+        Ok(crypto::verify(message, &signature, &public_key).is_ok())
+    }
 }
 
 #[cfg(test)]
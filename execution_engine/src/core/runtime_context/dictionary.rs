@@ -1,9 +1,19 @@
 use casper_types::{
     bytesrepr::{self, Bytes, FromBytes, ToBytes},
-    CLType, CLTyped, CLValue, CLValueError, Key,
+    CLType, CLTyped, CLValue, CLValueError, DictionaryAddr, Key, KeyTag, URef,
+    KEY_DICTIONARY_LENGTH,
 };
 
-use crate::shared::stored_value::StoredValue;
+use crate::shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+
+/// Length of the address prefix shared by all index entries of a single dictionary.
+const INDEX_PREFIX_LENGTH: usize = KEY_DICTIONARY_LENGTH / 2;
+
+/// Domain separator hashed together with the seed [`URef`] to derive the index prefix.
+const INDEX_PREFIX_DOMAIN: &[u8] = b"dictionary_index";
+
+/// Domain separator hashed together with the seed [`URef`] to derive the index marker address.
+const INDEX_MARKER_DOMAIN: &[u8] = b"dictionary_index_marker";
 
 /// Wraps a [`CLValue`] for storage in a dictionary.
 ///
@@ -37,6 +47,11 @@ impl DictionaryValue {
     pub fn into_cl_value(self) -> CLValue {
         self.cl_value
     }
+
+    /// Get the original key bytes of the dictionary item.
+    pub fn dictionary_item_key_bytes(&self) -> &[u8] {
+        self.dictionary_item_key_bytes.as_ref()
+    }
 }
 
 impl CLTyped for DictionaryValue {
@@ -75,6 +90,52 @@ impl ToBytes for DictionaryValue {
     }
 }
 
+/// Returns the serialized prefix shared by the keys of all index entries of the dictionary
+/// accessed by `seed_uref`.
+///
+/// Items of a dictionary are stored under a hash of the seed and the item key, so they can't be
+/// enumerated by the seed.  Each item therefore also has an index entry, whose address starts with
+/// a hash of the seed alone, allowing the items to be found with a prefix search of the trie.
+pub(crate) fn index_key_prefix(seed_uref: URef) -> Vec<u8> {
+    let mut hash_input = INDEX_PREFIX_DOMAIN.to_vec();
+    hash_input.extend_from_slice(&seed_uref.addr());
+    let hash = Blake2bHash::new(&hash_input);
+
+    let mut prefix = vec![KeyTag::Dictionary as u8];
+    prefix.extend_from_slice(&hash.as_ref()[..INDEX_PREFIX_LENGTH]);
+    prefix
+}
+
+/// Returns the key of the entry marking the dictionary accessed by `seed_uref` as indexed.
+///
+/// Only dictionaries created as indexed ones have this entry, and only their items get index
+/// entries, so writing to any other dictionary costs the same as it did before indexing existed.
+pub(crate) fn index_marker_key(seed_uref: URef) -> Key {
+    let mut hash_input = INDEX_MARKER_DOMAIN.to_vec();
+    hash_input.extend_from_slice(&seed_uref.addr());
+    let hash = Blake2bHash::new(&hash_input);
+
+    let mut addr = DictionaryAddr::default();
+    addr.copy_from_slice(hash.as_ref());
+    Key::Dictionary(addr)
+}
+
+/// Returns the key of the index entry of `dictionary_item_key_bytes` in the dictionary accessed by
+/// `seed_uref`.
+pub(crate) fn index_key(seed_uref: URef, dictionary_item_key_bytes: &[u8]) -> Key {
+    let prefix = index_key_prefix(seed_uref);
+    let item_key = Key::dictionary(seed_uref, dictionary_item_key_bytes);
+    let item_addr = item_key
+        .as_dictionary()
+        .expect("should be a dictionary key");
+
+    let mut addr = DictionaryAddr::default();
+    addr[..INDEX_PREFIX_LENGTH].copy_from_slice(&prefix[1..]);
+    addr[INDEX_PREFIX_LENGTH..]
+        .copy_from_slice(&item_addr[..KEY_DICTIONARY_LENGTH - INDEX_PREFIX_LENGTH]);
+    Key::Dictionary(addr)
+}
+
 /// Inspects `key` argument whether it contains a dictionary variant, and checks if `stored_value`
 /// contains a [`CLValue`], then it will attempt a conversion from the held clvalue into
 /// [`DictionaryValue`] and returns the real [`CLValue`] held by it.
@@ -15,10 +15,10 @@ use casper_types::{
     bytesrepr::ToBytes,
     contracts::NamedKeys,
     system::auction::EraInfo,
-    AccessRights, BlockTime, CLType, CLValue, Contract, ContractPackage, ContractPackageHash,
-    DeployHash, DeployInfo, EntryPointAccess, EntryPointType, Key, KeyTag, Phase, ProtocolVersion,
-    PublicKey, RuntimeArgs, Transfer, TransferAddr, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH,
-    KEY_HASH_LENGTH,
+    AccessRights, BlockTime, CLType, CLValue, Contract, ContractEvent, ContractHash,
+    ContractPackage, ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess, EntryPointType,
+    Key, KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs, Transfer, TransferAddr, URef,
+    DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH,
 };
 
 use crate::{
@@ -415,6 +415,17 @@ where
             .map_err(Into::into)
     }
 
+    /// Like `read_gs_direct()`, but for callers which only depend on whether the value exists and
+    /// on its type.
+    ///
+    /// DO NOT EXPOSE THIS VIA THE FFI
+    pub fn peek_gs_direct(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
+        self.tracking_copy
+            .borrow_mut()
+            .peek(self.correlation_id, key)
+            .map_err(Into::into)
+    }
+
     /// This method is a wrapper over `read_gs` in the sense that it extracts the type held by a
     /// `StoredValue` stored in the global state in a type safe manner.
     ///
@@ -1116,6 +1127,160 @@ where
 
         let dictionary_key = Key::dictionary(seed_uref, dictionary_item_key_bytes);
         self.metered_write_gs_unsafe(dictionary_key, wrapped_cl_value)?;
+
+        if !self.is_dictionary_indexed(seed_uref)? {
+            return Ok(());
+        }
+
+        let index_key = dictionary::index_key(seed_uref, dictionary_item_key_bytes);
+        let has_index_entry = self
+            .tracking_copy
+            .borrow_mut()
+            .read(self.correlation_id, &index_key)
+            .map_err(Into::into)?
+            .is_some();
+        if !has_index_entry {
+            let index_value = {
+                let dictionary_value = DictionaryValue::new(
+                    CLValue::from_t(dictionary_item_key.to_string()).map_err(Error::from)?,
+                    seed_uref.addr().to_vec(),
+                    dictionary_item_key_bytes.to_vec(),
+                );
+                CLValue::from_t(dictionary_value).map_err(Error::from)?
+            };
+            self.metered_write_gs_unsafe(index_key, index_value)?;
+        }
+        Ok(())
+    }
+
+    /// Marks the newly created dictionary accessed by `seed_uref` as indexed, so that the keys of
+    /// the items written to it can be read with [`RuntimeContext::dictionary_keys`].
+    pub(crate) fn enable_dictionary_index(&mut self, seed_uref: URef) -> Result<(), Error> {
+        let marker_value = {
+            let dictionary_value =
+                DictionaryValue::new(CLValue::unit(), seed_uref.addr().to_vec(), Vec::new());
+            CLValue::from_t(dictionary_value).map_err(Error::from)?
+        };
+        self.metered_write_gs_unsafe(dictionary::index_marker_key(seed_uref), marker_value)
+    }
+
+    /// Returns `true` if the dictionary accessed by `seed_uref` was created as an indexed one.
+    fn is_dictionary_indexed(&mut self, seed_uref: URef) -> Result<bool, Error> {
+        let is_indexed = self
+            .tracking_copy
+            .borrow_mut()
+            .read(
+                self.correlation_id,
+                &dictionary::index_marker_key(seed_uref),
+            )
+            .map_err(Into::into)?
+            .is_some();
+        Ok(is_indexed)
+    }
+
+    /// Removes the item under `dictionary_item_key` from the dictionary accessed by `seed_uref`,
+    /// returning `false` if there was no such item.
+    pub(crate) fn dictionary_remove(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Result<bool, Error> {
+        let dictionary_item_key_bytes = dictionary_item_key.as_bytes();
+
+        if dictionary_item_key_bytes.len() > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+            return Err(Error::DictionaryItemKeyExceedsLength);
+        }
+
+        self.validate_writeable(&seed_uref.into())?;
+        self.validate_uref(&seed_uref)?;
+
+        let dictionary_key = Key::dictionary(seed_uref, dictionary_item_key_bytes);
+        let index_key = dictionary::index_key(seed_uref, dictionary_item_key_bytes);
+
+        let mut tracking_copy = self.tracking_copy.borrow_mut();
+        if tracking_copy
+            .read(self.correlation_id, &dictionary_key)
+            .map_err(Into::into)?
+            .is_none()
+        {
+            return Ok(false);
+        }
+        tracking_copy.prune(dictionary_key);
+
+        // Items of dictionaries which aren't indexed have no index entry.
+        if tracking_copy
+            .read(self.correlation_id, &index_key)
+            .map_err(Into::into)?
+            .is_some()
+        {
+            tracking_copy.prune(index_key);
+        }
+        Ok(true)
+    }
+
+    /// Returns at most `limit` item keys of the indexed dictionary accessed by `seed_uref`,
+    /// skipping the first `offset` ones, along with the number of index entries scanned to find
+    /// them.  Returns `None` if the dictionary isn't indexed.
+    ///
+    /// The item keys are ordered by the address of their index entries, which is stable for as
+    /// long as the dictionary isn't modified.
+    pub(crate) fn dictionary_keys(
+        &mut self,
+        seed_uref: URef,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Option<(Vec<String>, usize)>, Error> {
+        self.validate_readable(&seed_uref.into())?;
+        self.validate_key(&seed_uref.into())?;
+
+        if !self.is_dictionary_indexed(seed_uref)? {
+            return Ok(None);
+        }
+
+        let prefix = dictionary::index_key_prefix(seed_uref);
+        let index_keys = self
+            .tracking_copy
+            .borrow_mut()
+            .get_keys_with_prefix(self.correlation_id, &prefix)
+            .map_err(Into::into)?;
+        let scanned_count = index_keys.len();
+
+        let mut dictionary_item_keys = Vec::new();
+        for index_key in index_keys.into_iter().skip(offset).take(limit) {
+            let stored_value = self
+                .tracking_copy
+                .borrow_mut()
+                .read(self.correlation_id, &index_key)
+                .map_err(Into::into)?
+                .ok_or(Error::KeyNotFound(index_key))?;
+            let cl_value: CLValue = stored_value.try_into().map_err(Error::TypeMismatch)?;
+            let dictionary_value: DictionaryValue = cl_value.into_t().map_err(Error::from)?;
+            let dictionary_item_key =
+                String::from_utf8(dictionary_value.dictionary_item_key_bytes().to_vec())
+                    .map_err(|_| Error::BytesRepr(bytesrepr::Error::Formatting))?;
+            dictionary_item_keys.push(dictionary_item_key);
+        }
+        Ok(Some((dictionary_item_keys, scanned_count)))
+    }
+
+    /// Records an event emitted by the currently executing contract.
+    ///
+    /// Events can only be emitted from within a stored contract, as they are attributed to the
+    /// emitting contract's hash.
+    pub fn emit_event(&mut self, name: String, value: CLValue) -> Result<(), Error> {
+        let contract_hash = match self.base_key {
+            Key::Hash(hash_addr) => ContractHash::new(hash_addr),
+            _ => return Err(Error::InvalidContext),
+        };
+
+        self.validate_cl_value(&value)?;
+
+        let event = ContractEvent {
+            contract_hash,
+            name,
+            value,
+        };
+        self.tracking_copy.borrow_mut().emit_event(event);
         Ok(())
     }
 }
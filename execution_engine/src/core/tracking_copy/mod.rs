@@ -13,7 +13,10 @@ use std::{
 use linked_hash_map::LinkedHashMap;
 use thiserror::Error;
 
-use casper_types::{bytesrepr, CLType, CLValue, CLValueError, Key, KeyTag, Tagged, U512};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    CLType, CLValue, CLValueError, ContractEvent, Key, KeyTag, Tagged, U512,
+};
 
 pub use self::ext::TrackingCopyExt;
 use self::meter::{heap_meter::HeapSize, Meter};
@@ -127,6 +130,7 @@ pub struct TrackingCopyCache<M> {
     muts_cached: HashMap<Key, StoredValue>,
     key_tag_reads_cached: LinkedHashMap<KeyTag, BTreeSet<Key>>,
     key_tag_muts_cached: HashMap<KeyTag, BTreeSet<Key>>,
+    prunes_cached: HashSet<Key>,
     meter: M,
 }
 
@@ -143,6 +147,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
             muts_cached: HashMap::new(),
             key_tag_reads_cached: LinkedHashMap::new(),
             key_tag_muts_cached: HashMap::new(),
+            prunes_cached: HashSet::new(),
             meter,
         }
     }
@@ -181,6 +186,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
     /// Inserts `key` and `value` pair to Write/Add cache.
     pub fn insert_write(&mut self, key: Key, value: StoredValue) {
+        self.prunes_cached.remove(&key);
         self.muts_cached.insert(key, value);
 
         let key_set = self
@@ -191,6 +197,20 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
         key_set.insert(key);
     }
 
+    /// Marks `key` as pruned, discarding any value written to it.
+    pub fn insert_prune(&mut self, key: Key) {
+        self.muts_cached.remove(&key);
+        if let Some(keys) = self.key_tag_muts_cached.get_mut(&key.tag()) {
+            keys.remove(&key);
+        }
+        self.prunes_cached.insert(key);
+    }
+
+    /// Returns `true` if `key` has been pruned.
+    pub fn is_pruned(&self, key: &Key) -> bool {
+        self.prunes_cached.contains(key)
+    }
+
     /// Gets value from `key` in the cache.
     pub fn get(&mut self, key: &Key) -> Option<&StoredValue> {
         if let Some(value) = self.muts_cached.get(key) {
@@ -214,6 +234,10 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    events: Vec<ContractEvent>,
+    /// Keys whose cached values were only peeked at, so no caller has depended on their values
+    /// yet.
+    unobserved_values: HashSet<Key>,
 }
 
 #[derive(Debug)]
@@ -247,6 +271,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            events: Vec::new(),
+            unobserved_values: HashSet::new(),
         }
     }
 
@@ -275,8 +301,17 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.get(key) {
-            return Ok(Some(value.to_owned()));
+            let value = value.to_owned();
+            if self.unobserved_values.remove(key) {
+                // The cached value was only peeked at so far; read it again so that the reader
+                // sees the dependency on it.
+                self.reader.read(correlation_id, key)?;
+            }
+            return Ok(Some(value));
         }
         if let Some(value) = self.reader.read(correlation_id, key)? {
             self.cache.insert_read(*key, value.to_owned());
@@ -286,6 +321,28 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Like `get()`, but only peeks at the underlying state, since the caller depends only on
+    /// whether the value exists and on its type.
+    fn get_unobserved(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
+        if let Some(value) = self.cache.get(key) {
+            return Ok(Some(value.to_owned()));
+        }
+        if let Some(value) = self.reader.peek(correlation_id, key)? {
+            self.cache.insert_read(*key, value.to_owned());
+            self.unobserved_values.insert(*key);
+            Ok(Some(value))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn get_keys(
         &mut self,
         correlation_id: CorrelationId,
@@ -306,6 +363,30 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         if let Some(keys) = self.cache.get_key_tag_muts_cached(key_tag) {
             ret.extend(keys)
         }
+        let cache = &self.cache;
+        ret.retain(|key| !cache.is_pruned(key));
+        Ok(ret)
+    }
+
+    /// Returns the keys whose serialized form starts with `prefix`, including keys written and
+    /// excluding keys pruned in this `TrackingCopy`.
+    pub fn get_keys_with_prefix(
+        &mut self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<BTreeSet<Key>, R::Error> {
+        let mut ret: BTreeSet<Key> = self
+            .reader
+            .keys_with_prefix(correlation_id, prefix)?
+            .into_iter()
+            .collect();
+        ret.extend(self.cache.muts_cached.keys().filter(|key| {
+            key.to_bytes()
+                .map(|key_bytes| key_bytes.starts_with(prefix))
+                .unwrap_or(false)
+        }));
+        let cache = &self.cache;
+        ret.retain(|key| !cache.is_pruned(key));
         Ok(ret)
     }
 
@@ -324,13 +405,40 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Reads the value under `key` for a caller which only depends on whether the value exists and
+    /// on its type, but not on the value itself.
+    pub fn peek(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        let normalized_key = key.normalize();
+        if let Some(value) = self.get_unobserved(correlation_id, &normalized_key)? {
+            self.ops.insert_add(normalized_key, Op::Read);
+            self.fns.insert_add(normalized_key, Transform::Identity);
+            Ok(Some(value))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        self.unobserved_values.remove(&normalized_key);
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
     }
 
+    /// Removes the value under `key` from global state.
+    pub fn prune(&mut self, key: Key) {
+        let normalized_key = key.normalize();
+        self.unobserved_values.remove(&normalized_key);
+        self.cache.insert_prune(normalized_key);
+        self.ops.insert_add(normalized_key, Op::Write);
+        self.fns.insert_add(normalized_key, Transform::Prune);
+    }
+
     /// Ok(None) represents missing key to which we want to "add" some value.
     /// Ok(Some(unit)) represents successful operation.
     /// Err(error) is reserved for unexpected errors when accessing global
@@ -342,7 +450,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         value: StoredValue,
     ) -> Result<AddResult, R::Error> {
         let normalized_key = key.normalize();
-        let current_value = match self.get(correlation_id, &normalized_key)? {
+        // Adding only depends on the type of the current value, not on the value itself.
+        let current_value = match self.get_unobserved(correlation_id, &normalized_key)? {
             None => return Ok(AddResult::KeyNotFound(normalized_key)),
             Some(current_value) => current_value,
         };
@@ -408,8 +517,14 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Records an event emitted by a contract.  Like any other change, it only becomes part of
+    /// the effect of this `TrackingCopy`, not of the one it was forked from.
+    pub fn emit_event(&mut self, event: ContractEvent) {
+        self.events.push(event);
+    }
+
     pub fn effect(&self) -> ExecutionEffect {
-        ExecutionEffect::new(self.ops.clone(), self.fns.clone())
+        ExecutionEffect::new(self.ops.clone(), self.fns.clone(), self.events.clone())
     }
 
     /// Calling `query()` avoids calling into `self.cache`, so this will not return any values
@@ -547,7 +662,13 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.muts_cached.get(key) {
+            if self.unobserved_values.contains(key) {
+                self.reader.read(correlation_id, key)?;
+            }
             return Ok(Some(value.to_owned()));
         }
         if let Some(value) = self.reader.read(correlation_id, key)? {
@@ -557,6 +678,20 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         }
     }
 
+    fn peek(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
+        if let Some(value) = self.cache.muts_cached.get(key) {
+            return Ok(Some(value.to_owned()));
+        }
+        self.reader.peek(correlation_id, key)
+    }
+
     fn read_with_proof(
         &self,
         correlation_id: CorrelationId,
@@ -16,7 +16,11 @@ use super::{
 };
 use crate::{
     core::{
-        engine_state::{op::Op, EngineConfig},
+        engine_state::{
+            op::Op,
+            read_set::{ReadSet, RecordingReader},
+            EngineConfig,
+        },
         runtime_context::dictionary,
         ValidationError,
     },
@@ -169,6 +173,42 @@ fn tracking_copy_write() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_prune() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+
+    // pruning hides the value stored in the DB
+    tc.prune(k);
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    assert_eq!(counter.get(), 0);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Prune));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Write));
+
+    // writing after pruning makes the value visible again
+    tc.write(k, one.clone());
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one.clone()));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(one)));
+    assert!(tc
+        .get_keys_with_prefix(correlation_id, &[KeyTag::Hash as u8])
+        .unwrap()
+        .contains(&k));
+
+    // pruning a written value discards it
+    tc.prune(k);
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Prune));
+    assert!(tc
+        .get_keys_with_prefix(correlation_id, &[KeyTag::Hash as u8])
+        .unwrap()
+        .is_empty());
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
@@ -477,6 +517,33 @@ proptest! {
     }
 }
 
+#[test]
+fn tracking_copy_should_record_reads() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let read_set = ReadSet::default();
+    let db = RecordingReader::new(CountingDb::new(counter), Some(read_set.clone()));
+    let mut tc = TrackingCopy::new(db);
+    let (k1, k2) = (Key::Hash([1u8; 32]), Key::Hash([2u8; 32]));
+    let value = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+
+    // adding only depends on the type of the current value
+    let add_result = tc.add(correlation_id, k1, value.clone());
+    assert_matches!(add_result, Ok(AddResult::Success));
+    assert!(read_set.contains_type(&k1));
+    assert!(!read_set.contains_value(&k1));
+
+    // reading the sum depends on the current value
+    let _ = tc.read(correlation_id, &k1);
+    assert!(read_set.contains_value(&k1));
+
+    // reading a written value doesn't depend on the current value
+    tc.write(k2, value);
+    let _ = tc.read(correlation_id, &k2);
+    assert!(!read_set.contains_type(&k2));
+    assert!(!read_set.contains_value(&k2));
+}
+
 #[test]
 fn cache_reads_invalidation() {
     let mut tc_cache = TrackingCopyCache::new(2, Count);
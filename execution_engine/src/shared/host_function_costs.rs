@@ -74,6 +74,19 @@ const DEFAULT_DICTIONARY_PUT_COST: u32 = 9_500;
 const DEFAULT_DICTIONARY_PUT_KEY_BYTES_SIZE_WEIGHT: u32 = 1_800;
 const DEFAULT_DICTIONARY_PUT_VALUE_SIZE_WEIGHT: u32 = 520;
 
+const DEFAULT_EMIT_EVENT_COST: u32 = 9_500;
+const DEFAULT_EMIT_EVENT_NAME_SIZE_WEIGHT: u32 = 1_800;
+const DEFAULT_EMIT_EVENT_VALUE_SIZE_WEIGHT: u32 = 520;
+
+const DEFAULT_VERIFY_SIGNATURE_COST: u32 = 1_300_000;
+const DEFAULT_VERIFY_SIGNATURE_MESSAGE_SIZE_WEIGHT: u32 = 320;
+
+const DEFAULT_DICTIONARY_REMOVE_COST: u32 = 9_500;
+const DEFAULT_DICTIONARY_REMOVE_KEY_BYTES_SIZE_WEIGHT: u32 = 1_800;
+
+const DEFAULT_DICTIONARY_KEYS_COST: u32 = 5_500;
+const DEFAULT_DICTIONARY_KEYS_ITEM_WEIGHT: u32 = 6_000;
+
 const DEFAULT_NEW_DICTIONARY_COST: u32 = DEFAULT_NEW_UREF_COST;
 
 pub(crate) const DEFAULT_HOST_FUNCTION_NEW_DICTIONARY: HostFunction<[Cost; 1]> =
@@ -234,6 +247,10 @@ pub struct HostFunctionCosts {
     pub remove_contract_user_group_urefs: HostFunction<[Cost; 6]>,
     pub print: HostFunction<[Cost; 2]>,
     pub blake2b: HostFunction<[Cost; 4]>,
+    pub emit_event: HostFunction<[Cost; 4]>,
+    pub verify_signature: HostFunction<[Cost; 6]>,
+    pub dictionary_remove: HostFunction<[Cost; 4]>,
+    pub dictionary_keys: HostFunction<[Cost; 5]>,
 }
 
 impl Default for HostFunctionCosts {
@@ -351,6 +368,45 @@ impl Default for HostFunctionCosts {
                 [NOT_USED, DEFAULT_PRINT_TEXT_SIZE_WEIGHT],
             ),
             blake2b: HostFunction::default(),
+            emit_event: HostFunction::new(
+                DEFAULT_EMIT_EVENT_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_EMIT_EVENT_NAME_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_EMIT_EVENT_VALUE_SIZE_WEIGHT,
+                ],
+            ),
+            verify_signature: HostFunction::new(
+                DEFAULT_VERIFY_SIGNATURE_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_VERIFY_SIGNATURE_MESSAGE_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            dictionary_remove: HostFunction::new(
+                DEFAULT_DICTIONARY_REMOVE_COST,
+                [
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    DEFAULT_DICTIONARY_REMOVE_KEY_BYTES_SIZE_WEIGHT,
+                ],
+            ),
+            dictionary_keys: HostFunction::new(
+                DEFAULT_DICTIONARY_KEYS_COST,
+                [
+                    NOT_USED,
+                    NOT_USED,
+                    DEFAULT_DICTIONARY_KEYS_ITEM_WEIGHT,
+                    DEFAULT_DICTIONARY_KEYS_ITEM_WEIGHT,
+                    NOT_USED,
+                ],
+            ),
         }
     }
 }
@@ -400,6 +456,10 @@ impl ToBytes for HostFunctionCosts {
         ret.append(&mut self.remove_contract_user_group_urefs.to_bytes()?);
         ret.append(&mut self.print.to_bytes()?);
         ret.append(&mut self.blake2b.to_bytes()?);
+        ret.append(&mut self.emit_event.to_bytes()?);
+        ret.append(&mut self.verify_signature.to_bytes()?);
+        ret.append(&mut self.dictionary_remove.to_bytes()?);
+        ret.append(&mut self.dictionary_keys.to_bytes()?);
         Ok(ret)
     }
 
@@ -446,11 +506,19 @@ impl ToBytes for HostFunctionCosts {
             + self.remove_contract_user_group_urefs.serialized_length()
             + self.print.serialized_length()
             + self.blake2b.serialized_length()
+            + self.emit_event.serialized_length()
+            + self.verify_signature.serialized_length()
+            + self.dictionary_remove.serialized_length()
+            + self.dictionary_keys.serialized_length()
     }
 }
 
-impl FromBytes for HostFunctionCosts {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+impl HostFunctionCosts {
+    /// Deserializes host function costs as stored in the protocol data of protocol versions before
+    /// any of `emit_event`, `verify_signature`, `dictionary_remove` and `dictionary_keys` existed.
+    ///
+    /// The costs of those host functions are set to their defaults.
+    pub(crate) fn legacy_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (read_value, rem) = FromBytes::from_bytes(bytes)?;
         let (dictionary_get, rem) = FromBytes::from_bytes(rem)?;
         let (write, rem) = FromBytes::from_bytes(rem)?;
@@ -537,6 +605,27 @@ impl FromBytes for HostFunctionCosts {
                 remove_contract_user_group_urefs,
                 print,
                 blake2b,
+                ..Default::default()
+            },
+            rem,
+        ))
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (legacy_costs, rem) = HostFunctionCosts::legacy_from_bytes(bytes)?;
+        let (emit_event, rem) = FromBytes::from_bytes(rem)?;
+        let (verify_signature, rem) = FromBytes::from_bytes(rem)?;
+        let (dictionary_remove, rem) = FromBytes::from_bytes(rem)?;
+        let (dictionary_keys, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            HostFunctionCosts {
+                emit_event,
+                verify_signature,
+                dictionary_remove,
+                dictionary_keys,
+                ..legacy_costs
             },
             rem,
         ))
@@ -588,6 +677,10 @@ impl Distribution<HostFunctionCosts> for Standard {
             remove_contract_user_group_urefs: rng.gen(),
             print: rng.gen(),
             blake2b: rng.gen(),
+            emit_event: rng.gen(),
+            verify_signature: rng.gen(),
+            dictionary_remove: rng.gen(),
+            dictionary_keys: rng.gen(),
         }
     }
 }
@@ -646,6 +739,10 @@ pub mod gens {
             remove_contract_user_group_urefs in host_function_cost_arb(),
             print in host_function_cost_arb(),
             blake2b in host_function_cost_arb(),
+            emit_event in host_function_cost_arb(),
+            verify_signature in host_function_cost_arb(),
+            dictionary_remove in host_function_cost_arb(),
+            dictionary_keys in host_function_cost_arb(),
         ) -> HostFunctionCosts {
             HostFunctionCosts {
                 read_value,
@@ -690,6 +787,10 @@ pub mod gens {
                 remove_contract_user_group_urefs,
                 print,
                 blake2b,
+                emit_event,
+                verify_signature,
+                dictionary_remove,
+                dictionary_keys,
             }
         }
     }
@@ -16,6 +16,7 @@ pub const DEFAULT_WITHDRAW_DELEGATOR_REWARD_COST: u32 = 10_000;
 pub const DEFAULT_WITHDRAW_VALIDATOR_REWARD_COST: u32 = 10_000;
 pub const DEFAULT_READ_ERA_ID_COST: u32 = 10_000;
 pub const DEFAULT_ACTIVATE_BID_COST: u32 = 10_000;
+pub const DEFAULT_REDELEGATE_COST: u32 = 10_000;
 
 /// Description of costs of calling auction entrypoints.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -33,6 +34,7 @@ pub struct AuctionCosts {
     pub withdraw_validator_reward: u32,
     pub read_era_id: u32,
     pub activate_bid: u32,
+    pub redelegate: u32,
 }
 
 impl Default for AuctionCosts {
@@ -51,6 +53,7 @@ impl Default for AuctionCosts {
             withdraw_validator_reward: DEFAULT_WITHDRAW_VALIDATOR_REWARD_COST,
             read_era_id: DEFAULT_READ_ERA_ID_COST,
             activate_bid: DEFAULT_ACTIVATE_BID_COST,
+            redelegate: DEFAULT_REDELEGATE_COST,
         }
     }
 }
@@ -72,6 +75,7 @@ impl ToBytes for AuctionCosts {
         ret.append(&mut self.withdraw_validator_reward.to_bytes()?);
         ret.append(&mut self.read_era_id.to_bytes()?);
         ret.append(&mut self.activate_bid.to_bytes()?);
+        ret.append(&mut self.redelegate.to_bytes()?);
 
         Ok(ret)
     }
@@ -90,11 +94,16 @@ impl ToBytes for AuctionCosts {
             + self.withdraw_validator_reward.serialized_length()
             + self.read_era_id.serialized_length()
             + self.activate_bid.serialized_length()
+            + self.redelegate.serialized_length()
     }
 }
 
-impl FromBytes for AuctionCosts {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+impl AuctionCosts {
+    /// Deserializes auction costs as stored in the protocol data of protocol versions before the
+    /// `redelegate` entry point existed.
+    ///
+    /// The cost of `redelegate` is set to its default.
+    pub(crate) fn legacy_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (get_era_validators, rem) = FromBytes::from_bytes(bytes)?;
         let (read_seigniorage_recipients, rem) = FromBytes::from_bytes(rem)?;
         let (add_bid, rem) = FromBytes::from_bytes(rem)?;
@@ -123,6 +132,21 @@ impl FromBytes for AuctionCosts {
                 withdraw_validator_reward,
                 read_era_id,
                 activate_bid,
+                ..Default::default()
+            },
+            rem,
+        ))
+    }
+}
+
+impl FromBytes for AuctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (legacy_costs, rem) = AuctionCosts::legacy_from_bytes(bytes)?;
+        let (redelegate, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            Self {
+                redelegate,
+                ..legacy_costs
             },
             rem,
         ))
@@ -145,6 +169,7 @@ impl Distribution<AuctionCosts> for Standard {
             withdraw_validator_reward: rng.gen(),
             read_era_id: rng.gen(),
             activate_bid: rng.gen(),
+            redelegate: rng.gen(),
         }
     }
 }
@@ -170,6 +195,7 @@ pub mod gens {
             withdraw_validator_reward in num::u32::ANY,
             read_era_id in num::u32::ANY,
             activate_bid in num::u32::ANY,
+            redelegate in num::u32::ANY,
         ) -> AuctionCosts {
             AuctionCosts {
                 get_era_validators,
@@ -185,6 +211,7 @@ pub mod gens {
                 withdraw_validator_reward,
                 read_era_id,
                 activate_bid,
+                redelegate,
             }
         }
     }
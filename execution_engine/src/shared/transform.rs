@@ -70,6 +70,8 @@ pub enum Transform {
     AddUInt512(U512),
     AddKeys(NamedKeys),
     Failure(Error),
+    /// Removes the value under the key from global state.
+    Prune,
 }
 
 macro_rules! from_try_from_impl {
@@ -206,6 +208,13 @@ impl Transform {
                 }
             },
             Transform::Failure(error) => Err(error),
+            // Pruning removes the value rather than transforming it, so it is handled by the
+            // caller when committing.
+            Transform::Prune => Err(TypeMismatch::new(
+                "Transform other than Prune".to_string(),
+                "Prune".to_string(),
+            )
+            .into()),
         }
     }
 }
@@ -252,6 +261,7 @@ impl Add for Transform {
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
             (_, b @ Transform::Write(_)) => b,
+            (_, b @ Transform::Prune) => b,
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -291,6 +301,10 @@ impl Add for Transform {
                     TypeMismatch::new("AddKeys".to_owned(), format!("{:?}", other)).into(),
                 ),
             },
+            // Nothing can be added to a value which has been pruned.
+            (Transform::Prune, other) => Transform::Failure(
+                TypeMismatch::new("Write".to_owned(), format!("{:?}", other)).into(),
+            ),
         }
     }
 }
@@ -360,6 +374,7 @@ impl From<&Transform> for casper_types::Transform {
                     .collect(),
             ),
             Transform::Failure(error) => casper_types::Transform::Failure(error.to_string()),
+            Transform::Prune => casper_types::Transform::Prune,
         }
     }
 }
@@ -374,6 +389,7 @@ pub mod gens {
     pub fn transform_arb() -> impl Strategy<Value = Transform> {
         prop_oneof![
             Just(Transform::Identity),
+            Just(Transform::Prune),
             stored_value_arb().prop_map(Transform::Write),
             any::<i32>().prop_map(Transform::AddInt32),
             any::<u64>().prop_map(Transform::AddUInt64),
@@ -89,6 +89,30 @@ impl ToBytes for WasmConfig {
     }
 }
 
+impl WasmConfig {
+    /// Deserializes a `WasmConfig` as stored in the protocol data of earlier protocol versions.
+    ///
+    /// See [`HostFunctionCosts::legacy_from_bytes`].
+    pub(crate) fn legacy_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_memory, rem) = FromBytes::from_bytes(bytes)?;
+        let (max_stack_height, rem) = FromBytes::from_bytes(rem)?;
+        let (opcode_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (storage_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (host_function_costs, rem) = HostFunctionCosts::legacy_from_bytes(rem)?;
+
+        Ok((
+            WasmConfig {
+                max_memory,
+                max_stack_height,
+                opcode_costs,
+                storage_costs,
+                host_function_costs,
+            },
+            rem,
+        ))
+    }
+}
+
 impl FromBytes for WasmConfig {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (max_memory, rem) = FromBytes::from_bytes(bytes)?;
@@ -1,4 +1,7 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
 
 use crate::shared::{
     additive_map::AdditiveMap,
@@ -10,7 +13,7 @@ use casper_types::{Key, ProtocolVersion};
 
 use crate::storage::{
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{self, commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
     store::Store,
@@ -33,6 +36,8 @@ pub struct InMemoryGlobalState {
     pub trie_store: Arc<InMemoryTrieStore>,
     pub protocol_data_store: Arc<InMemoryProtocolDataStore>,
     pub empty_root_hash: Blake2bHash,
+    /// The roots committed since pruning began, or `None` if not pruning.
+    committed_roots: Mutex<Option<Vec<Blake2bHash>>>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -76,6 +81,7 @@ impl InMemoryGlobalState {
             trie_store,
             protocol_data_store,
             empty_root_hash,
+            committed_roots: Mutex::new(None),
         }
     }
 
@@ -217,6 +223,9 @@ impl StateProvider for InMemoryGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
+        // Held for the whole commit, so that pruning can't remove any trie the new root refers to
+        // before the root is recorded.
+        let mut committed_roots = self.committed_roots.lock()?;
         let commit_result = commit::<InMemoryEnvironment, InMemoryTrieStore, _, Self::Error>(
             &self.environment,
             &self.trie_store,
@@ -224,6 +233,11 @@ impl StateProvider for InMemoryGlobalState {
             prestate_hash,
             effects,
         )?;
+        if let (Some(committed_roots), CommitResult::Success { state_root }) =
+            (committed_roots.as_mut(), &commit_result)
+        {
+            committed_roots.push(*state_root);
+        }
         Ok(commit_result)
     }
 
@@ -280,6 +294,28 @@ impl StateProvider for InMemoryGlobalState {
         Ok(trie_hash)
     }
 
+    fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[Trie<Key, StoredValue>],
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let trie_hashes = tries
+            .iter()
+            .map(|trie| {
+                put_trie::<
+                    Key,
+                    StoredValue,
+                    InMemoryReadWriteTransaction,
+                    InMemoryTrieStore,
+                    Self::Error,
+                >(correlation_id, &mut txn, &self.trie_store, trie)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        txn.commit()?;
+        Ok(trie_hashes)
+    }
+
     /// Finds all of the keys of missing descendant `Trie<Key,StoredValue>` values
     fn missing_trie_keys(
         &self,
@@ -298,12 +334,31 @@ impl StateProvider for InMemoryGlobalState {
         txn.commit()?;
         Ok(missing_descendants)
     }
+
+    fn begin_prune(&self) -> Result<bool, Self::Error> {
+        global_state::begin_prune(&self.committed_roots)
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        mut roots_to_keep: Vec<Blake2bHash>,
+    ) -> Result<usize, Self::Error> {
+        roots_to_keep.push(self.empty_root_hash);
+        global_state::prune::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &self.committed_roots,
+            correlation_id,
+            roots_to_keep,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::shared::newtypes::Blake2bHash;
-    use casper_types::{account::AccountHash, CLValue};
+    use casper_types::{account::AccountHash, CLValue, KeyTag};
 
     use super::*;
 
@@ -402,6 +457,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn commit_prunes_values() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let missing_key = Key::Account(AccountHash::new([3u8; 32]));
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = vec![
+            (test_pairs[0].key, Transform::Prune),
+            (missing_key, Transform::Prune),
+        ]
+        .into_iter()
+        .collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![test_pairs[1].key],
+            updated_checkout
+                .keys_with_prefix(correlation_id, &[KeyTag::Account as u8])
+                .unwrap()
+        );
+    }
+
     #[test]
     fn commit_updates_state_and_original_state_stays_intact() {
         let correlation_id = CorrelationId::new();
@@ -1,4 +1,7 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
 
 use crate::shared::{
     additive_map::AdditiveMap,
@@ -10,7 +13,7 @@ use casper_types::{Key, ProtocolVersion};
 
 use crate::storage::{
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{self, commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     store::Store,
@@ -29,6 +32,8 @@ pub struct LmdbGlobalState {
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
     pub empty_root_hash: Blake2bHash,
+    /// The roots committed since pruning began, or `None` if not pruning.
+    committed_roots: Mutex<Option<Vec<Blake2bHash>>>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -73,6 +78,7 @@ impl LmdbGlobalState {
             trie_store,
             protocol_data_store,
             empty_root_hash,
+            committed_roots: Mutex::new(None),
         }
     }
 }
@@ -176,6 +182,9 @@ impl StateProvider for LmdbGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
+        // Held for the whole commit, so that pruning can't remove any trie the new root refers to
+        // before the root is recorded.
+        let mut committed_roots = self.committed_roots.lock()?;
         let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
             &self.environment,
             &self.trie_store,
@@ -183,6 +192,11 @@ impl StateProvider for LmdbGlobalState {
             prestate_hash,
             effects,
         )?;
+        if let (Some(committed_roots), CommitResult::Success { state_root }) =
+            (committed_roots.as_mut(), &commit_result)
+        {
+            committed_roots.push(*state_root);
+        }
         Ok(commit_result)
     }
 
@@ -239,6 +253,27 @@ impl StateProvider for LmdbGlobalState {
         Ok(trie_hash)
     }
 
+    fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[Trie<Key, StoredValue>],
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let trie_hashes = tries
+            .iter()
+            .map(|trie| {
+                put_trie::<Key, StoredValue, lmdb::RwTransaction, LmdbTrieStore, Self::Error>(
+                    correlation_id,
+                    &mut txn,
+                    &self.trie_store,
+                    trie,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        txn.commit()?;
+        Ok(trie_hashes)
+    }
+
     /// Finds all of the keys of missing descendant `Trie<K,V>` values
     fn missing_trie_keys(
         &self,
@@ -256,6 +291,25 @@ impl StateProvider for LmdbGlobalState {
         txn.commit()?;
         Ok(missing_descendants)
     }
+
+    fn begin_prune(&self) -> Result<bool, Self::Error> {
+        global_state::begin_prune(&self.committed_roots)
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        mut roots_to_keep: Vec<Blake2bHash>,
+    ) -> Result<usize, Self::Error> {
+        roots_to_keep.push(self.empty_root_hash);
+        global_state::prune::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &self.committed_roots,
+            correlation_id,
+            roots_to_keep,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -443,4 +497,42 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn prune_retains_state_committed_since_pruning_began() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+        assert!(state.begin_prune().unwrap());
+        assert!(!state.begin_prune().unwrap());
+
+        // Committed on top of state which is about to be pruned.
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let removed = state.prune(correlation_id, vec![]).unwrap();
+        assert!(removed > 0);
+        assert!(state.checkout(root_hash).unwrap().is_none());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        // Pruning has completed, so it can begin again.
+        assert!(state.begin_prune().unwrap());
+    }
 }
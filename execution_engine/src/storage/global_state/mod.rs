@@ -1,7 +1,12 @@
 pub mod in_memory;
 pub mod lmdb;
 
-use std::{fmt, hash::BuildHasher};
+use std::{
+    fmt,
+    hash::BuildHasher,
+    mem,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
 
 use crate::shared::{
     additive_map::AdditiveMap,
@@ -10,18 +15,27 @@ use crate::shared::{
     transform::{self, Transform},
     TypeMismatch,
 };
-use casper_types::{bytesrepr, Key, ProtocolVersion};
+use casper_types::{
+    bytesrepr::{self, Bytes},
+    Key, ProtocolVersion,
+};
 
 use crate::storage::{
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
+    transaction_source::{Deletable, Enumerable, Transaction, TransactionSource},
     trie::{merkle_proof::TrieMerkleProof, Trie},
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        operations::{
+            delete, mark_reachable_tries, read, sweep_tries, write, DeleteResult, ReadResult,
+            WriteResult,
+        },
         TrieStore,
     },
 };
 
+/// The number of tries removed in a single transaction when pruning global state.
+pub const PRUNE_BATCH_SIZE: usize = 10_000;
+
 /// A reader of state
 pub trait StateReader<K, V> {
     /// An error which occurs when reading state
@@ -30,6 +44,12 @@ pub trait StateReader<K, V> {
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
 
+    /// Returns the state value from the corresponding key, for callers which only depend on
+    /// whether the value exists and on its type, but not on the value itself.
+    fn peek(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error> {
+        self.read(correlation_id, key)
+    }
+
     /// Returns the merkle proof of the state value from the corresponding key
     fn read_with_proof(
         &self,
@@ -124,12 +144,179 @@ pub trait StateProvider {
         trie: &Trie<Key, StoredValue>,
     ) -> Result<Blake2bHash, Self::Error>;
 
+    /// Inserts the given trie nodes into the trie in a single transaction, returning their keys.
+    fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[Trie<Key, StoredValue>],
+    ) -> Result<Vec<Blake2bHash>, Self::Error>;
+
     /// Finds all of the missing or corrupt keys of which are descendants of `trie_key`
     fn missing_trie_keys(
         &self,
         correlation_id: CorrelationId,
         trie_keys: Vec<Blake2bHash>,
     ) -> Result<Vec<Blake2bHash>, Self::Error>;
+
+    /// Starts recording the roots of all subsequent commits, which the following call to
+    /// [`prune`](StateProvider::prune) retains along with its `roots_to_keep`.
+    ///
+    /// Returns `false` if a previously begun pruning hasn't completed yet, in which case `prune`
+    /// must not be called.
+    fn begin_prune(&self) -> Result<bool, Self::Error>;
+
+    /// Removes all tries which are not reachable from any of `roots_to_keep`, from the empty root
+    /// or from any root committed since [`begin_prune`](StateProvider::begin_prune), returning the
+    /// number of removed tries.
+    ///
+    /// The reachable tries are marked in the store rather than collected in memory, and tries are
+    /// marked and removed in batches of [`PRUNE_BATCH_SIZE`], each in its own transaction, so that
+    /// neither memory usage nor the time commits are held up grows with the size of global state.
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        roots_to_keep: Vec<Blake2bHash>,
+    ) -> Result<usize, Self::Error>;
+}
+
+/// Removes all tries which are not reachable from any of `roots_to_keep` or from the roots
+/// recorded in `committed_roots`, then stops recording committed roots.
+///
+/// The reachable tries are marked in the store, then the unmarked ones are removed, then the marks
+/// are removed, each in batches of [`PRUNE_BATCH_SIZE`] tries or keys.  Each batch of tries is
+/// removed while holding `committed_roots`, after marking the tries of the roots committed in the
+/// meantime.  Implementations of [`StateProvider::commit`] must hold `committed_roots` for the
+/// duration of the commit and record the new root in it, so that no trie referred to by newly
+/// committed state is ever removed.
+pub(crate) fn prune<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    committed_roots: &Mutex<Option<Vec<Blake2bHash>>>,
+    correlation_id: CorrelationId,
+    roots_to_keep: Vec<Blake2bHash>,
+) -> Result<usize, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    R::ReadWriteTransaction: Deletable + Enumerable,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+    E: for<'b> From<PoisonError<MutexGuard<'b, Option<Vec<Blake2bHash>>>>>,
+{
+    // Marks left behind by an interrupted pruning would protect unreachable tries.
+    let result = remove_prune_marks::<R, S, E>(environment, store, correlation_id).and_then(|_| {
+        prune_unreachable_tries::<R, S, E>(
+            environment,
+            store,
+            committed_roots,
+            correlation_id,
+            roots_to_keep,
+        )
+    });
+    *committed_roots.lock()? = None;
+    let removed = result?;
+    remove_prune_marks::<R, S, E>(environment, store, correlation_id)?;
+    Ok(removed)
+}
+
+fn prune_unreachable_tries<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    committed_roots: &Mutex<Option<Vec<Blake2bHash>>>,
+    correlation_id: CorrelationId,
+    mut trie_keys_to_visit: Vec<Blake2bHash>,
+) -> Result<usize, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    R::ReadWriteTransaction: Deletable + Enumerable,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+    E: for<'b> From<PoisonError<MutexGuard<'b, Option<Vec<Blake2bHash>>>>>,
+{
+    while !trie_keys_to_visit.is_empty() {
+        let mut txn = environment.create_read_write_txn()?;
+        mark_reachable_tries::<Key, StoredValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &mut trie_keys_to_visit,
+            PRUNE_BATCH_SIZE,
+        )?;
+        txn.commit()?;
+    }
+
+    let mut removed = 0;
+    let mut maybe_next_key = Some(Bytes::new());
+    while let Some(next_key) = maybe_next_key {
+        let mut committed_roots = committed_roots.lock()?;
+        let mut txn = environment.create_read_write_txn()?;
+        // State committed since the tries were marked may refer to tries which were unmarked at
+        // that point.  Only the tries written since are visited, so all of them are marked at once.
+        let mut new_roots = committed_roots.as_mut().map(mem::take).unwrap_or_default();
+        mark_reachable_tries::<Key, StoredValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &mut new_roots,
+            usize::MAX,
+        )?;
+        let sweep_result = sweep_tries::<Key, StoredValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            next_key.as_slice(),
+            PRUNE_BATCH_SIZE,
+            false,
+        )?;
+        txn.commit()?;
+        removed += sweep_result.removed;
+        maybe_next_key = sweep_result.next_key;
+    }
+    Ok(removed)
+}
+
+/// Removes all marks of reachable tries from the store, in batches of [`PRUNE_BATCH_SIZE`] keys.
+fn remove_prune_marks<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    R::ReadWriteTransaction: Deletable + Enumerable,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error>,
+{
+    let mut maybe_next_key = Some(Bytes::new());
+    while let Some(next_key) = maybe_next_key {
+        let mut txn = environment.create_read_write_txn()?;
+        maybe_next_key = sweep_tries::<Key, StoredValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            next_key.as_slice(),
+            PRUNE_BATCH_SIZE,
+            true,
+        )?
+        .next_key;
+        txn.commit()?;
+    }
+    Ok(())
+}
+
+/// Starts recording committed roots in `committed_roots`, returning `false` if already recording.
+pub(crate) fn begin_prune<E>(committed_roots: &Mutex<Option<Vec<Blake2bHash>>>) -> Result<bool, E>
+where
+    E: for<'b> From<PoisonError<MutexGuard<'b, Option<Vec<Blake2bHash>>>>>,
+{
+    let mut committed_roots = committed_roots.lock()?;
+    if committed_roots.is_some() {
+        return Ok(false);
+    }
+    *committed_roots = Some(Vec::new());
+    Ok(true)
 }
 
 pub fn commit<'a, R, S, H, E>(
@@ -156,6 +343,16 @@ where
     };
 
     for (key, transform) in effects.into_iter() {
+        if let Transform::Prune = transform {
+            match delete::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key)? {
+                DeleteResult::Deleted(root_hash) => state_root = root_hash,
+                // The value may have been written and pruned within the same set of effects.
+                DeleteResult::DoesNotExist => (),
+                DeleteResult::RootNotFound => return Ok(CommitResult::RootNotFound),
+            }
+            continue;
+        }
+
         let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
 
         let value = match (read_result, transform) {
@@ -10,6 +10,14 @@ use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
 const DEFAULT_ADDRESS: [u8; 32] = [0; 32];
 pub const DEFAULT_WASMLESS_TRANSFER_COST: u32 = 10_000;
 
+/// Prefix of serialized `ProtocolData`, marking the encoding which includes the costs of host
+/// functions and auction entry points added after the initial release.
+///
+/// Protocol data stored by earlier versions is not prefixed, and starts with the `max_memory` of
+/// its `WasmConfig` instead, which is never `u32::MAX`.  Such data is decoded using the legacy
+/// encoding.
+const PROTOCOL_DATA_VERSION_TAG: u32 = u32::MAX;
+
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
@@ -151,6 +159,7 @@ impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
 
+        ret.append(&mut PROTOCOL_DATA_VERSION_TAG.to_bytes()?);
         ret.append(&mut self.wasm_config.to_bytes()?);
         ret.append(&mut self.system_config.to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
@@ -162,7 +171,8 @@ impl ToBytes for ProtocolData {
     }
 
     fn serialized_length(&self) -> usize {
-        self.wasm_config.serialized_length()
+        PROTOCOL_DATA_VERSION_TAG.serialized_length()
+            + self.wasm_config.serialized_length()
             + self.system_config.serialized_length()
             + self.mint.serialized_length()
             + self.handle_payment.serialized_length()
@@ -173,8 +183,16 @@ impl ToBytes for ProtocolData {
 
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_config, rem) = WasmConfig::from_bytes(bytes)?;
-        let (system_config, rem) = FromBytes::from_bytes(rem)?;
+        let (tag, rem) = u32::from_bytes(bytes)?;
+        let (wasm_config, system_config, rem) = if tag == PROTOCOL_DATA_VERSION_TAG {
+            let (wasm_config, rem) = WasmConfig::from_bytes(rem)?;
+            let (system_config, rem) = SystemConfig::from_bytes(rem)?;
+            (wasm_config, system_config, rem)
+        } else {
+            let (wasm_config, rem) = WasmConfig::legacy_from_bytes(bytes)?;
+            let (system_config, rem) = SystemConfig::legacy_from_bytes(rem)?;
+            (wasm_config, system_config, rem)
+        };
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (handle_payment, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
@@ -231,7 +249,10 @@ mod tests {
     use proptest::proptest;
 
     use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
-    use casper_types::{bytesrepr, ContractHash};
+    use casper_types::{
+        bytesrepr::{self, ToBytes},
+        ContractHash,
+    };
 
     use super::{gens, ProtocolData};
 
@@ -309,4 +330,46 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&protocol_data);
         }
     }
+
+    #[test]
+    fn should_deserialize_protocol_data_stored_by_earlier_versions() {
+        let protocol_data = ProtocolData::new(
+            WasmConfig::default(),
+            SystemConfig::default(),
+            [1u8; 32].into(),
+            [2u8; 32].into(),
+            [3u8; 32].into(),
+            [4u8; 32].into(),
+        );
+
+        // Earlier versions stored the protocol data without the version tag, and without the costs
+        // of the host functions added since, which are the last fields of the `WasmConfig`.
+        let host_function_costs = protocol_data.wasm_config().take_host_function_costs();
+        let added_costs_length = host_function_costs.emit_event.serialized_length()
+            + host_function_costs.verify_signature.serialized_length()
+            + host_function_costs.dictionary_remove.serialized_length()
+            + host_function_costs.dictionary_keys.serialized_length();
+        let wasm_config_bytes = protocol_data.wasm_config().to_bytes().unwrap();
+        let mut stored_bytes =
+            wasm_config_bytes[..wasm_config_bytes.len() - added_costs_length].to_vec();
+        // They likewise lacked the cost of the `redelegate` auction entry point, which is the last
+        // field of the `AuctionCosts`.
+        let system_config = protocol_data.system_config();
+        let auction_costs_bytes = system_config.auction_costs().to_bytes().unwrap();
+        let redelegate_cost_length = system_config.auction_costs().redelegate.serialized_length();
+        stored_bytes.extend(system_config.wasmless_transfer_cost().to_bytes().unwrap());
+        stored_bytes
+            .extend(&auction_costs_bytes[..auction_costs_bytes.len() - redelegate_cost_length]);
+        stored_bytes.extend(system_config.mint_costs().to_bytes().unwrap());
+        stored_bytes.extend(system_config.handle_payment_costs().to_bytes().unwrap());
+        stored_bytes.extend(system_config.standard_payment_costs().to_bytes().unwrap());
+        stored_bytes.extend(protocol_data.mint().to_bytes().unwrap());
+        stored_bytes.extend(protocol_data.handle_payment().to_bytes().unwrap());
+        stored_bytes.extend(protocol_data.standard_payment().to_bytes().unwrap());
+        stored_bytes.extend(protocol_data.auction().to_bytes().unwrap());
+
+        let decoded: ProtocolData = bytesrepr::deserialize(stored_bytes).unwrap();
+        assert_eq!(decoded, protocol_data);
+        bytesrepr::test_serialization_roundtrip(&decoded);
+    }
 }
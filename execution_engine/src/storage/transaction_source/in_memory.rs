@@ -7,7 +7,9 @@ use casper_types::bytesrepr::Bytes;
 
 use crate::storage::{
     error::in_memory::Error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{
+        Deletable, Enumerable, Readable, Transaction, TransactionSource, Writable,
+    },
 };
 
 /// A marker for use in a mutex which represents the capability to perform a
@@ -109,6 +111,38 @@ impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
     }
 }
 
+impl<'a> Enumerable for InMemoryReadWriteTransaction<'a> {
+    fn keys_from(
+        &self,
+        handle: Self::Handle,
+        start: &[u8],
+        max_count: usize,
+    ) -> Result<Vec<Bytes>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        let mut keys: Vec<Bytes> = sub_view
+            .keys()
+            .filter(|key| key.as_slice() >= start)
+            .cloned()
+            .collect();
+        keys.sort();
+        keys.truncate(max_count);
+        Ok(keys)
+    }
+}
+
+impl<'a> Deletable for InMemoryReadWriteTransaction<'a> {
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<bool, Self::Error> {
+        let sub_view = match self.view.get_mut(&handle) {
+            Some(view) => view,
+            None => return Ok(false),
+        };
+        Ok(sub_view.remove(&Bytes::from(key)).is_some())
+    }
+}
+
 /// An environment for the in-memory trie store.
 pub struct InMemoryEnvironment {
     data: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
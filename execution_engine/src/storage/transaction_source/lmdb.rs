@@ -2,12 +2,14 @@ use std::path::Path;
 
 use casper_types::bytesrepr::Bytes;
 use lmdb::{
-    self, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+    self, Cursor, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
 };
 
 use crate::storage::{
     error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{
+        Deletable, Enumerable, Readable, Transaction, TransactionSource, Writable,
+    },
     MAX_DBS,
 };
 
@@ -61,6 +63,37 @@ impl<'a> Writable for RwTransaction<'a> {
     }
 }
 
+impl<'a> Enumerable for RwTransaction<'a> {
+    fn keys_from(
+        &self,
+        handle: Self::Handle,
+        start: &[u8],
+        max_count: usize,
+    ) -> Result<Vec<Bytes>, Self::Error> {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(self, handle)?;
+        // LMDB doesn't allow seeking to an empty key.
+        let iter = if start.is_empty() {
+            cursor.iter_start()
+        } else {
+            cursor.iter_from(start)
+        };
+        Ok(iter
+            .take(max_count)
+            .map(|(key, _value)| Bytes::from(key))
+            .collect())
+    }
+}
+
+impl<'a> Deletable for RwTransaction<'a> {
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<bool, Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) => Ok(true),
+            Err(lmdb::Error::NotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
 /// The environment for an LMDB-backed trie store.
 ///
 /// Wraps [`lmdb::Environment`].
@@ -35,6 +35,27 @@ pub trait Writable: Transaction {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
 }
 
+/// A transaction with the capability to iterate over the keys of a given
+/// [`Handle`](Transaction::Handle).
+pub trait Enumerable: Transaction {
+    /// Returns up to `max_count` keys from a given [`Transaction::Handle`] which are not less than
+    /// `start`, in ascending order.
+    fn keys_from(
+        &self,
+        handle: Self::Handle,
+        start: &[u8],
+        max_count: usize,
+    ) -> Result<Vec<Bytes>, Self::Error>;
+}
+
+/// A transaction with the capability to delete from a given [`Handle`](Transaction::Handle).
+pub trait Deletable: Transaction {
+    /// Removes the key-value pair with the given key from a given [`Transaction::Handle`].
+    ///
+    /// Returns `false` if there was no such key.
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<bool, Self::Error>;
+}
+
 /// A source of transactions e.g. values that implement [`Readable`]
 /// and/or [`Writable`].
 pub trait TransactionSource<'a> {
@@ -285,9 +285,13 @@ mod gens {
 mod tests {
     use proptest::prelude::*;
 
-    use casper_types::bytesrepr;
+    use casper_types::{
+        bytesrepr::{self, ToBytes},
+        light_client, Key,
+    };
 
     use super::gens;
+    use crate::shared::stored_value::StoredValue;
 
     proptest! {
         #[test]
@@ -303,5 +307,17 @@ mod tests {
         ) {
             bytesrepr::test_serialization_roundtrip(&proof)
         }
+
+        #[test]
+        fn trie_merkle_proof_is_compatible_with_light_client_proof(
+            proof in gens::trie_merkle_proof_arb()
+        ) {
+            let light_client_proof: light_client::TrieMerkleProof<Key, StoredValue> =
+                bytesrepr::deserialize(proof.to_bytes().unwrap()).unwrap();
+            prop_assert_eq!(
+                light_client_proof.compute_state_hash().unwrap(),
+                proof.compute_state_hash().unwrap().value()
+            );
+        }
     }
 }
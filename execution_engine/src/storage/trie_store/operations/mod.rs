@@ -4,18 +4,18 @@ mod tests;
 use std::{
     cmp,
     collections::{HashSet, VecDeque},
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     mem,
 };
 
 use tracing::warn;
 
-use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+use casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
 
 use crate::{
     shared::newtypes::{Blake2bHash, CorrelationId},
     storage::{
-        transaction_source::{Readable, Writable},
+        transaction_source::{Deletable, Enumerable, Readable, Writable},
         trie::{
             merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
             Parents, Pointer, PointerBlock, Trie, RADIX, USIZE_EXCEEDS_U8,
@@ -295,6 +295,138 @@ where
     Ok(missing_descendants)
 }
 
+/// The first byte of the keys under which [`mark_reachable_tries`] records reachable tries in the
+/// trie store.  These keys are one byte longer than trie keys, so the two can't be confused.
+const PRUNE_MARK_PREFIX: u8 = 0;
+
+fn prune_mark_key(trie_key: &Blake2bHash) -> Vec<u8> {
+    let mut mark_key = Vec::with_capacity(Blake2bHash::LENGTH + 1);
+    mark_key.push(PRUNE_MARK_PREFIX);
+    mark_key.extend_from_slice(trie_key.as_ref());
+    mark_key
+}
+
+fn is_prune_mark_key(key: &[u8]) -> bool {
+    key.len() == Blake2bHash::LENGTH + 1 && key[0] == PRUNE_MARK_PREFIX
+}
+
+/// Marks the tries reachable from `trie_keys_to_visit` in the store, including the given keys
+/// themselves, removing them from `trie_keys_to_visit` as they are visited.
+///
+/// At most `max_marked` tries are marked, so the marking can be spread across several
+/// transactions by calling this again until `trie_keys_to_visit` is empty.  The descendants of
+/// tries which are already marked are assumed to be marked too, or to still be in
+/// `trie_keys_to_visit`, and are not visited again.  Extending the marks with the roots of newly
+/// committed state therefore only visits the tries written since.  Tries which are not present in
+/// the store are skipped.
+pub fn mark_reachable_tries<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    trie_keys_to_visit: &mut Vec<Blake2bHash>,
+    max_marked: usize,
+) -> Result<(), E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + std::fmt::Debug,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let mut marked = 0;
+    while marked < max_marked {
+        let trie_key = match trie_keys_to_visit.pop() {
+            Some(trie_key) => trie_key,
+            None => break,
+        };
+        let mark_key = prune_mark_key(&trie_key);
+        if txn
+            .read(store.handle(), &mark_key)
+            .map_err(S::Error::from)?
+            .is_some()
+        {
+            continue;
+        }
+        let maybe_retrieved_trie: Option<Trie<K, V>> = store.get(txn, &trie_key)?;
+        match maybe_retrieved_trie {
+            // Nothing to keep if the trie is already gone
+            None => continue,
+            Some(Trie::Leaf { .. }) => (),
+            Some(Trie::Node { pointer_block }) => trie_keys_to_visit.extend(
+                pointer_block
+                    .to_indexed_pointers()
+                    .map(|(_, pointer)| pointer.into_hash()),
+            ),
+            Some(Trie::Extension { pointer, .. }) => trie_keys_to_visit.push(pointer.into_hash()),
+        }
+        txn.write(store.handle(), &mark_key, &[])
+            .map_err(S::Error::from)?;
+        marked += 1;
+    }
+    Ok(())
+}
+
+/// The outcome of a call to [`sweep_tries`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct SweepResult {
+    /// The number of removed tries, or marks when removing marks.
+    pub removed: usize,
+    /// The key from which to continue sweeping, or `None` if all keys have been visited.
+    pub next_key: Option<Bytes>,
+}
+
+/// Visits up to `max_visited` keys in the store which are not less than `start`.
+///
+/// If `remove_marks` is `false`, the visited tries which have not been marked by
+/// [`mark_reachable_tries`] are removed.  Otherwise the visited marks are removed, which must be
+/// done for all keys once pruning has finished, or has been interrupted, so that they can't be
+/// mistaken for marks of a later pruning.  Anything which is neither a trie nor a mark is left
+/// alone.
+pub fn sweep_tries<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    start: &[u8],
+    max_visited: usize,
+    remove_marks: bool,
+) -> Result<SweepResult, E>
+where
+    T: Readable<Handle = S::Handle> + Deletable<Handle = S::Handle> + Enumerable,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error>,
+{
+    let mut keys = txn
+        .keys_from(store.handle(), start, max_visited.saturating_add(1))
+        .map_err(S::Error::from)?;
+    let next_key = if keys.len() > max_visited {
+        keys.pop()
+    } else {
+        None
+    };
+
+    let mut removed = 0;
+    for key in &keys {
+        let key = key.as_slice();
+        let should_remove = if remove_marks {
+            is_prune_mark_key(key)
+        } else {
+            match Blake2bHash::try_from(key) {
+                Ok(trie_key) => txn
+                    .read(store.handle(), &prune_mark_key(&trie_key))
+                    .map_err(S::Error::from)?
+                    .is_none(),
+                Err(_) => false,
+            }
+        };
+        if should_remove && txn.delete(store.handle(), key).map_err(S::Error::from)? {
+            removed += 1;
+        }
+    }
+    Ok(SweepResult { removed, next_key })
+}
+
 #[cfg(test)]
 pub fn check_integrity<K, V, T, S, E>(
     _correlation_id: CorrelationId,
@@ -485,8 +617,8 @@ pub enum DeleteResult {
     RootNotFound,
 }
 
-#[allow(unused)]
-fn delete<K, V, T, S, E>(
+/// Removes the leaf under `key_to_delete` from the trie rooted at `root`, returning the new root.
+pub fn delete<K, V, T, S, E>(
     correlation_id: CorrelationId,
     txn: &mut T,
     store: &S,
@@ -1,6 +1,7 @@
 mod delete;
 mod keys;
 mod proptests;
+mod prune;
 mod read;
 mod scan;
 mod synchronize;
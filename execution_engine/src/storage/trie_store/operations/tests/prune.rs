@@ -0,0 +1,173 @@
+use casper_types::bytesrepr::Bytes;
+
+use super::*;
+use crate::storage::transaction_source::{Deletable, Enumerable};
+
+fn last_written_root(initial_root: &Blake2bHash, write_results: &[WriteResult]) -> Blake2bHash {
+    write_results
+        .iter()
+        .fold(*initial_root, |root, write_result| match write_result {
+            WriteResult::Written(new_root) => *new_root,
+            _ => root,
+        })
+}
+
+/// Prunes the store in batches of a single trie or key, so that every step is resumed at least
+/// once.
+fn prune<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    mut roots_to_keep: Vec<Blake2bHash>,
+) -> Result<usize, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    R::ReadWriteTransaction: Deletable + Enumerable,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    while !roots_to_keep.is_empty() {
+        let mut txn: R::ReadWriteTransaction = environment.create_read_write_txn()?;
+        operations::mark_reachable_tries::<TestKey, TestValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &mut roots_to_keep,
+            1,
+        )?;
+        txn.commit()?;
+    }
+
+    let mut removed = 0;
+    for &remove_marks in &[false, true] {
+        let mut maybe_next_key = Some(Bytes::new());
+        while let Some(next_key) = maybe_next_key {
+            let mut txn: R::ReadWriteTransaction = environment.create_read_write_txn()?;
+            let sweep_result = operations::sweep_tries::<TestKey, TestValue, _, _, E>(
+                correlation_id,
+                &mut txn,
+                store,
+                next_key.as_slice(),
+                1,
+                remove_marks,
+            )?;
+            txn.commit()?;
+            if !remove_marks {
+                removed += sweep_result.removed;
+            }
+            maybe_next_key = sweep_result.next_key;
+        }
+    }
+    Ok(removed)
+}
+
+fn prune_removes_unreachable_tries<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    R::ReadWriteTransaction: Deletable + Enumerable,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let write_results =
+        write_leaves::<_, _, _, _, E>(correlation_id, environment, store, root, &TEST_LEAVES)?;
+    let old_root = last_written_root(root, &write_results);
+
+    let write_results = write_leaves::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &old_root,
+        &TEST_LEAVES_UPDATED,
+    )?;
+    let new_root = last_written_root(&old_root, &write_results);
+    assert_ne!(old_root, new_root);
+
+    let removed = prune::<_, _, E>(correlation_id, environment, store, vec![new_root])?;
+    assert!(removed > 0);
+
+    // No marks are left behind.
+    {
+        let txn: R::ReadWriteTransaction = environment.create_read_write_txn()?;
+        let keys = txn.keys_from(store.handle(), &[], usize::MAX)?;
+        assert!(keys
+            .iter()
+            .all(|key| key.as_slice().len() == Blake2bHash::LENGTH));
+        txn.commit()?;
+    }
+
+    {
+        let txn: R::ReadTransaction = environment.create_read_txn()?;
+        for leaf in TEST_LEAVES.iter() {
+            if let Trie::Leaf { key, .. } = leaf {
+                let read_result = operations::read::<TestKey, TestValue, _, _, E>(
+                    correlation_id,
+                    &txn,
+                    store,
+                    &old_root,
+                    key,
+                )?;
+                assert_eq!(read_result, ReadResult::RootNotFound);
+            }
+        }
+        let missing = operations::missing_trie_keys::<TestKey, TestValue, _, _, E>(
+            correlation_id,
+            &txn,
+            store,
+            vec![new_root],
+        )?;
+        assert!(missing.is_empty());
+        txn.commit()?;
+    }
+
+    check_leaves::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &new_root,
+        &TEST_LEAVES_UPDATED,
+        &[],
+    )?;
+
+    // Pruning again with the same roots is a no-op.
+    let removed = prune::<_, _, E>(correlation_id, environment, store, vec![new_root])?;
+    assert_eq!(removed, 0);
+
+    Ok(())
+}
+
+#[test]
+fn lmdb_prune_removes_unreachable_tries() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    prune_removes_unreachable_tries::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_prune_removes_unreachable_tries() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    prune_removes_unreachable_tries::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
@@ -141,6 +141,7 @@ where
             &account,
             correlation_id,
             &preprocessor,
+            &Default::default(),
             &protocol_version,
             &protocol_data,
             phase,
@@ -156,6 +157,7 @@ where
     let mut runtime = Runtime::new(
         config,
         Default::default(),
+        Default::default(),
         memory,
         module,
         context,
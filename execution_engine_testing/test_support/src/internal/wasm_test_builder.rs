@@ -59,9 +59,9 @@ use casper_types::{
         },
         mint::TOTAL_SUPPLY_KEY,
     },
-    CLTyped, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash, ContractWasm,
-    DeployHash, DeployInfo, EraId, Key, KeyTag, PublicKey, RuntimeArgs, Transfer, TransferAddr,
-    URef, U512,
+    CLTyped, CLValue, CallTrace, Contract, ContractHash, ContractPackage, ContractPackageHash,
+    ContractWasm, DeployHash, DeployInfo, EraId, Key, KeyTag, PublicKey, RuntimeArgs, Transfer,
+    TransferAddr, URef, U512,
 };
 
 use crate::internal::{
@@ -119,8 +119,15 @@ impl<S> WasmTestBuilder<S> {
 
 impl Default for InMemoryWasmTestBuilder {
     fn default() -> Self {
+        Self::new_with_config(EngineConfig::default())
+    }
+}
+
+impl InMemoryWasmTestBuilder {
+    /// Creates a builder over an empty in-memory global state, using the given engine
+    /// configuration.
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
         Self::initialize_logging();
-        let engine_config = EngineConfig::default();
 
         let global_state = InMemoryGlobalState::empty().expect("should create global state");
         let engine_state = EngineState::new(global_state, engine_config);
@@ -890,6 +897,15 @@ where
         exec_result.cost()
     }
 
+    /// Returns the contract calls traced while executing the deploy at `index`.
+    ///
+    /// The trace is only recorded if call tracing is enabled in the builder's [`EngineConfig`].
+    pub fn get_call_trace(&self, index: usize) -> Option<&CallTrace> {
+        let exec_results = self.get_exec_result(index)?;
+        let exec_result = exec_results.get(0)?;
+        Some(&exec_result.effect().call_trace)
+    }
+
     pub fn exec_error_message(&self, index: usize) -> Option<String> {
         let response = self.get_exec_result(index)?;
         Some(utils::get_error_message(response))
@@ -0,0 +1,135 @@
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::engine_state::{
+    engine_config::DEFAULT_MAX_QUERY_DEPTH, wasm_module_cache::DEFAULT_WASM_MODULE_CACHE_SIZE,
+    EngineConfig,
+};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs};
+
+const CONTRACT_CALL_TRACING: &str = "call_tracing.wasm";
+const HASH_KEY_NAME: &str = "call_tracing_hash";
+const OUTER_ENTRY_POINT: &str = "outer";
+const INNER_ENTRY_POINT: &str = "inner";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_REVERT: &str = "revert";
+
+fn setup(engine_config: EngineConfig) -> (InMemoryWasmTestBuilder, ContractHash) {
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CALL_TRACING,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).commit().expect_success();
+
+    let contract_hash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(HASH_KEY_NAME)
+        .expect("should have contract hash")
+        .into_hash()
+        .map(ContractHash::new)
+        .expect("should be hash");
+
+    (builder, contract_hash)
+}
+
+fn call_outer(
+    builder: &mut InMemoryWasmTestBuilder,
+    contract_hash: ContractHash,
+    revert: bool,
+) -> usize {
+    let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        OUTER_ENTRY_POINT,
+        runtime_args! {
+            ARG_CONTRACT_HASH => contract_hash,
+            ARG_REVERT => revert,
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+    builder.get_exec_results_count() - 1
+}
+
+fn tracing_config() -> EngineConfig {
+    EngineConfig::new(
+        DEFAULT_MAX_QUERY_DEPTH,
+        true,
+        DEFAULT_WASM_MODULE_CACHE_SIZE,
+    )
+}
+
+#[ignore]
+#[test]
+fn should_trace_nested_contract_calls() {
+    let (mut builder, contract_hash) = setup(tracing_config());
+
+    let index = call_outer(&mut builder, contract_hash, false);
+    builder.expect_success();
+
+    let call_trace = builder
+        .get_call_trace(index)
+        .expect("should have call trace");
+    assert!(call_trace.payment.is_none(), "standard payment is not Wasm");
+    assert!(call_trace.failed_frame().is_none());
+
+    let outer = call_trace.session.as_ref().expect("should trace session");
+    assert_eq!(outer.contract_hash, Some(contract_hash));
+    assert_eq!(outer.entry_point, OUTER_ENTRY_POINT);
+    assert!(outer.error.is_none());
+    assert!(outer
+        .host_function_calls
+        .iter()
+        .any(|host_function_call| host_function_call.name == "host_function_call_contract"));
+
+    assert_eq!(outer.calls.len(), 1);
+    let inner = &outer.calls[0];
+    assert_eq!(inner.contract_hash, Some(contract_hash));
+    assert_eq!(inner.entry_point, INNER_ENTRY_POINT);
+    assert_eq!(inner.args, runtime_args! { ARG_REVERT => false });
+    assert!(inner.calls.is_empty());
+    assert!(!inner.gas_used.is_zero());
+    assert!(outer.gas_used > inner.gas_used);
+}
+
+#[ignore]
+#[test]
+fn should_trace_frame_where_revert_happened() {
+    let (mut builder, contract_hash) = setup(tracing_config());
+
+    let index = call_outer(&mut builder, contract_hash, true);
+    builder.expect_failure();
+
+    let call_trace = builder
+        .get_call_trace(index)
+        .expect("should have call trace");
+    let outer = call_trace.session.as_ref().expect("should trace session");
+    assert!(outer.error.is_some());
+
+    let failed_frame = call_trace.failed_frame().expect("should have failed frame");
+    assert_eq!(failed_frame.entry_point, INNER_ENTRY_POINT);
+    assert_eq!(failed_frame, &outer.calls[0]);
+    assert_eq!(failed_frame.error.as_deref(), Some("User error: 7"));
+}
+
+#[ignore]
+#[test]
+fn should_not_trace_calls_by_default() {
+    let (mut builder, contract_hash) = setup(EngineConfig::default());
+
+    let index = call_outer(&mut builder, contract_hash, false);
+    builder.expect_success();
+
+    let call_trace = builder
+        .get_call_trace(index)
+        .expect("should have call trace");
+    assert!(call_trace.is_empty());
+}
@@ -17,6 +17,8 @@ use dictionary_call::{NEW_DICTIONARY_ITEM_KEY, NEW_DICTIONARY_VALUE};
 const DICTIONARY_WASM: &str = "dictionary.wasm";
 const DICTIONARY_CALL_WASM: &str = "dictionary_call.wasm";
 const DICTIONARY_ITEM_KEY_CHECK: &str = "dictionary-item-key-check.wasm";
+const DICTIONARY_ITERATION_WASM: &str = "dictionary_iteration.wasm";
+const ARG_INDEXED: &str = "indexed";
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
 
 fn setup() -> (InMemoryWasmTestBuilder, ContractHash) {
@@ -592,3 +594,76 @@ fn should_query_dictionary_items_with_test_context() {
         assert_eq!(value, dictionary::DEFAULT_DICTIONARY_VALUE);
     }
 }
+
+#[ignore]
+#[test]
+fn should_remove_and_iterate_dictionary_items() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        DICTIONARY_ITERATION_WASM,
+        runtime_args! { ARG_INDEXED => true },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let dictionary_seed_uref = account
+        .named_keys()
+        .get("dictionary")
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have dictionary");
+
+    let removed_item_key = Key::dictionary(dictionary_seed_uref, b"c");
+    assert!(builder.query(None, removed_item_key, &[]).is_err());
+
+    let remaining_item_key = Key::dictionary(dictionary_seed_uref, b"d");
+    let stored_value = builder
+        .query(None, remaining_item_key, &[])
+        .expect("should have value");
+    let value: u64 = stored_value
+        .as_cl_value()
+        .cloned()
+        .expect("should have cl value")
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(value, 3);
+}
+
+#[ignore]
+#[test]
+fn should_not_iterate_items_of_dictionary_which_is_not_indexed() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        DICTIONARY_ITERATION_WASM,
+        runtime_args! { ARG_INDEXED => false },
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+    let exec_results = builder
+        .get_exec_results()
+        .last()
+        .expect("should have results");
+    assert_eq!(exec_results.len(), 1);
+    let error = exec_results[0].as_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            EngineError::Exec(Error::Revert(ApiError::DictionaryNotIndexed))
+        ),
+        "Received error {:?}",
+        error
+    );
+}
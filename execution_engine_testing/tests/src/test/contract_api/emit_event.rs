@@ -0,0 +1,96 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::{engine_state::Error, execution};
+use casper_types::{runtime_args, CLValue, ContractHash, RuntimeArgs};
+
+const CONTRACT_EMIT_EVENT: &str = "emit_event.wasm";
+const HASH_KEY_NAME: &str = "emit_event_hash";
+const EMIT_ENTRY_POINT: &str = "emit";
+const EMIT_FROM_SESSION_ENTRY_POINT: &str = "emit_from_session";
+const EVENT_NAME: &str = "message";
+const ARG_MESSAGE: &str = "message";
+const MESSAGE: &str = "hello, world";
+
+fn setup() -> (InMemoryWasmTestBuilder, ContractHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENT,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).commit().expect_success();
+
+    let contract_hash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(HASH_KEY_NAME)
+        .expect("should have contract hash")
+        .into_hash()
+        .map(ContractHash::new)
+        .expect("should be hash");
+
+    (builder, contract_hash)
+}
+
+#[ignore]
+#[test]
+fn should_record_event_emitted_by_contract() {
+    let (mut builder, contract_hash) = setup();
+
+    let exec_request = ExecuteRequestBuilder::contract_call_by_name(
+        *DEFAULT_ACCOUNT_ADDR,
+        HASH_KEY_NAME,
+        EMIT_ENTRY_POINT,
+        runtime_args! { ARG_MESSAGE => MESSAGE.to_string() },
+    )
+    .build();
+    builder.exec(exec_request).commit().expect_success();
+
+    let response = builder
+        .get_exec_results()
+        .last()
+        .expect("should have last response");
+    let exec_response = response.last().expect("should have response");
+    let events = &exec_response.effect().events;
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.contract_hash, contract_hash);
+    assert_eq!(event.name, EVENT_NAME);
+    assert_eq!(
+        event.value,
+        CLValue::from_t(MESSAGE.to_string()).expect("should create CLValue")
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_emit_event_from_session() {
+    let (mut builder, _contract_hash) = setup();
+
+    let exec_request = ExecuteRequestBuilder::contract_call_by_name(
+        *DEFAULT_ACCOUNT_ADDR,
+        HASH_KEY_NAME,
+        EMIT_FROM_SESSION_ENTRY_POINT,
+        runtime_args! { ARG_MESSAGE => MESSAGE.to_string() },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_results()
+        .last()
+        .expect("should have last response");
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::InvalidContext));
+    assert!(exec_response.effect().events.is_empty());
+}
@@ -1,7 +1,9 @@
 mod account;
 mod blake2b;
+mod call_tracing;
 mod create_purse;
 mod dictionary;
+mod emit_event;
 mod get_arg;
 mod get_blocktime;
 mod get_call_stack;
@@ -15,3 +17,4 @@ mod subcall;
 mod transfer;
 mod transfer_purse_to_account;
 mod transfer_purse_to_purse;
+mod verify_signature;
@@ -0,0 +1,105 @@
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_types::{
+    bytesrepr::ToBytes, runtime_args, AsymmetricType, PublicKey, RuntimeArgs, Signature,
+};
+
+const VERIFY_SIGNATURE_WASM: &str = "verify_signature.wasm";
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const VERIFY_RESULT: &str = "verify_result";
+
+const MESSAGE: &[u8] = b"Casper verify_signature test message";
+const ED25519_PUBLIC_KEY: &str = "79b5562e8fe654f94078b112e8a98ba7901f853ae695bed7e0e3910bad049664";
+const ED25519_SIGNATURE: &str = "2077b8222f8c517033641be62f28780814f0e6a6b7a9ef5a132403041ab798d0\
+                                 e4437ae45d4661a967c5200f007fac48d3e531c40b75e965824ebadc5d6ed30b";
+const SECP256K1_PUBLIC_KEY: &str =
+    "0284bf7562262bbd6940085748f3be6afa52ae317155181ece31b66351ccffa4b0";
+const SECP256K1_SIGNATURE: &str = "ebee8ef0c9f05b80d68eaf48514dcaea2404acf61682f5cf615a38bbe712b22d\
+                                   1ae2d051fa0a99ab667ba5051e6aeef86b0b502fe6451750a72104102ed4c4a2";
+
+fn ed25519_key_and_signature() -> (PublicKey, Signature) {
+    let public_key = PublicKey::ed25519_from_bytes(hex::decode(ED25519_PUBLIC_KEY).unwrap())
+        .expect("should create public key");
+    let signature = Signature::ed25519_from_bytes(hex::decode(ED25519_SIGNATURE).unwrap())
+        .expect("should create signature");
+    (public_key, signature)
+}
+
+fn secp256k1_key_and_signature() -> (PublicKey, Signature) {
+    let public_key = PublicKey::secp256k1_from_bytes(hex::decode(SECP256K1_PUBLIC_KEY).unwrap())
+        .expect("should create public key");
+    let signature = Signature::secp256k1_from_bytes(hex::decode(SECP256K1_SIGNATURE).unwrap())
+        .expect("should create signature");
+    (public_key, signature)
+}
+
+fn verify(message: &[u8], signature: &Signature, public_key: &PublicKey) -> bool {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        VERIFY_SIGNATURE_WASM,
+        runtime_args! {
+            ARG_MESSAGE => message.to_vec(),
+            ARG_SIGNATURE => signature.to_bytes().unwrap(),
+            ARG_PUBLIC_KEY => public_key.clone(),
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit().expect_success();
+
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let uref = account
+        .named_keys()
+        .get(VERIFY_RESULT)
+        .expect("should have value");
+
+    builder
+        .query(None, *uref, &[])
+        .expect("should query")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should convert")
+}
+
+#[ignore]
+#[test]
+fn should_verify_ed25519_signature() {
+    let (public_key, signature) = ed25519_key_and_signature();
+    assert!(verify(MESSAGE, &signature, &public_key));
+}
+
+#[ignore]
+#[test]
+fn should_verify_secp256k1_signature() {
+    let (public_key, signature) = secp256k1_key_and_signature();
+    assert!(verify(MESSAGE, &signature, &public_key));
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_signature_of_different_message() {
+    let (public_key, signature) = ed25519_key_and_signature();
+    assert!(!verify(b"some other message", &signature, &public_key));
+
+    let (public_key, signature) = secp256k1_key_and_signature();
+    assert!(!verify(b"some other message", &signature, &public_key));
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_signature_with_mismatched_key() {
+    let (ed25519_public_key, ed25519_signature) = ed25519_key_and_signature();
+    let (secp256k1_public_key, secp256k1_signature) = secp256k1_key_and_signature();
+    assert!(!verify(MESSAGE, &ed25519_signature, &secp256k1_public_key));
+    assert!(!verify(MESSAGE, &secp256k1_signature, &ed25519_public_key));
+}
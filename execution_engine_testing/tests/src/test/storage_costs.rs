@@ -123,6 +123,10 @@ static NEW_HOST_FUNCTION_COSTS: Lazy<HostFunctionCosts> = Lazy::new(|| HostFunct
     remove_contract_user_group_urefs: HostFunction::fixed(0),
     print: HostFunction::fixed(0),
     blake2b: HostFunction::fixed(0),
+    emit_event: HostFunction::fixed(0),
+    verify_signature: HostFunction::fixed(0),
+    dictionary_remove: HostFunction::fixed(0),
+    dictionary_keys: HostFunction::fixed(0),
 });
 static STORAGE_COSTS_ONLY: Lazy<WasmConfig> = Lazy::new(|| {
     WasmConfig::new(
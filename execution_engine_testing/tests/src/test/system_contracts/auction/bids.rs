@@ -7,9 +7,11 @@ use once_cell::sync::Lazy;
 use casper_engine_test_support::{
     internal::{
         utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
-        DEFAULT_AUCTION_DELAY, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
-        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_UNBONDING_DELAY,
-        SYSTEM_ADDR, TIMESTAMP_MILLIS_INCREMENT,
+        DEFAULT_AUCTION_DELAY, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG, SYSTEM_ADDR,
+        TIMESTAMP_MILLIS_INCREMENT,
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, MINIMUM_ACCOUNT_CREATION_BALANCE,
 };
@@ -17,7 +19,8 @@ use casper_execution_engine::{
     core::{
         engine_state::{
             self,
-            genesis::{GenesisAccount, GenesisValidator},
+            genesis::{ExecConfig, GenesisAccount, GenesisValidator},
+            run_genesis_request::RunGenesisRequest,
         },
         execution,
     },
@@ -31,9 +34,9 @@ use casper_types::{
     system::{
         self,
         auction::{
-            self, Bids, DelegationRate, EraValidators, UnbondingPurses, ValidatorWeights,
-            ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_PUBLIC_KEY, ARG_VALIDATOR,
-            ERA_ID_KEY, INITIAL_ERA_ID,
+            self, Bids, DelegationRate, EraValidators, UnbondingPurse, UnbondingPurses,
+            ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_NEW_VALIDATOR,
+            ARG_PUBLIC_KEY, ARG_VALIDATOR, ERA_ID_KEY, INITIAL_ERA_ID, REDELEGATIONS_KEY,
         },
     },
     EraId, PublicKey, RuntimeArgs, SecretKey, U512,
@@ -47,6 +50,7 @@ const CONTRACT_ADD_BID: &str = "add_bid.wasm";
 const CONTRACT_WITHDRAW_BID: &str = "withdraw_bid.wasm";
 const CONTRACT_DELEGATE: &str = "delegate.wasm";
 const CONTRACT_UNDELEGATE: &str = "undelegate.wasm";
+const CONTRACT_REDELEGATE: &str = "redelegate.wasm";
 
 const TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE + 1000;
 
@@ -1392,6 +1396,183 @@ fn fully_undelegated_funds_should_be_released() {
     )
 }
 
+#[ignore]
+#[test]
+fn redelegated_funds_should_be_delegated_to_new_validator() {
+    let system_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *SYSTEM_ADDR,
+            ARG_AMOUNT => U512::from(SYSTEM_TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_2_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_2_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let delegator_1_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+
+    let validator_2_add_bid_request = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_2_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_2_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_2),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_2,
+        },
+    )
+    .build();
+
+    let delegator_1_validator_1_delegate_request = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT_1),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+        },
+    )
+    .build();
+
+    let post_genesis_requests = vec![
+        system_fund_request,
+        delegator_1_fund_request,
+        validator_1_fund_request,
+        validator_2_fund_request,
+        validator_1_add_bid_request,
+        validator_2_add_bid_request,
+        delegator_1_validator_1_delegate_request,
+    ];
+
+    let mut timestamp_millis =
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for request in post_genesis_requests {
+        builder.exec(request).commit().expect_success();
+    }
+
+    let auction_hash = builder.get_auction_contract_hash();
+
+    for _ in 0..5 {
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+
+    let delegator_1_main_purse = builder
+        .get_account(*BID_ACCOUNT_1_ADDR)
+        .expect("should have delegator account")
+        .main_purse();
+
+    let delegator_1_redelegate_request = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_REDELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT_1),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+            ARG_NEW_VALIDATOR => NON_FOUNDER_VALIDATOR_2_PK.clone(),
+        },
+    )
+    .build();
+
+    builder
+        .exec(delegator_1_redelegate_request)
+        .commit()
+        .expect_success();
+
+    // The unbonding purse itself is unchanged, with the redelegation tracked alongside it.
+    let unbonding_purses: UnbondingPurses = builder.get_withdraws();
+    let unbonding_purse = unbonding_purses[&*NON_FOUNDER_VALIDATOR_1_ADDR][0].clone();
+    let redelegations: Vec<(UnbondingPurse, PublicKey)> =
+        builder.get_value(auction_hash, REDELEGATIONS_KEY);
+    assert_eq!(
+        redelegations,
+        vec![(unbonding_purse, NON_FOUNDER_VALIDATOR_2_PK.clone())]
+    );
+
+    let delegator_1_purse_balance_before = builder.get_purse_balance(delegator_1_main_purse);
+
+    for _ in 0..=DEFAULT_UNBONDING_DELAY {
+        let bids: Bids = builder.get_bids();
+        assert!(!bids[&NON_FOUNDER_VALIDATOR_2_PK]
+            .delegators()
+            .contains_key(&BID_ACCOUNT_1_PK));
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+
+    // Redelegated funds are not returned to the delegator.
+    assert_eq!(
+        builder.get_purse_balance(delegator_1_main_purse),
+        delegator_1_purse_balance_before
+    );
+
+    let bids: Bids = builder.get_bids();
+    assert!(!bids[&NON_FOUNDER_VALIDATOR_1_PK]
+        .delegators()
+        .contains_key(&BID_ACCOUNT_1_PK));
+    let delegator = bids[&NON_FOUNDER_VALIDATOR_2_PK]
+        .delegators()
+        .get(&BID_ACCOUNT_1_PK)
+        .expect("should have redelegated to validator 2");
+    assert_eq!(*delegator.staked_amount(), U512::from(DELEGATE_AMOUNT_1));
+    assert_eq!(
+        builder.get_purse_balance(*delegator.bonding_purse()),
+        U512::from(DELEGATE_AMOUNT_1)
+    );
+
+    let unbonding_purses: UnbondingPurses = builder.get_withdraws();
+    assert!(unbonding_purses
+        .values()
+        .all(|unbonding_list| unbonding_list.is_empty()));
+
+    let redelegations: Vec<(UnbondingPurse, PublicKey)> =
+        builder.get_value(auction_hash, REDELEGATIONS_KEY);
+    assert!(redelegations.is_empty());
+}
+
 #[ignore]
 #[test]
 fn should_undelegate_delegators_when_validator_unbonds() {
@@ -3016,3 +3197,159 @@ fn should_validate_genesis_delegators_bond_amount() {
 
     builder.run_genesis(&run_genesis_request);
 }
+
+#[ignore]
+#[test]
+fn should_enforce_delegation_limits() {
+    const MINIMUM_DELEGATION_AMOUNT: u64 = DELEGATE_AMOUNT_1;
+    const MAX_DELEGATORS_PER_VALIDATOR: u32 = 1;
+
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let delegator_1 = GenesisAccount::account(
+            DELEGATOR_1.clone(),
+            Motes::new(DELEGATOR_1_BALANCE.into()),
+            None,
+        );
+        let delegator_2 = GenesisAccount::account(
+            DELEGATOR_2.clone(),
+            Motes::new(DELEGATOR_2_BALANCE.into()),
+            None,
+        );
+        tmp.push(delegator_1);
+        tmp.push(delegator_2);
+        tmp
+    };
+
+    let exec_config = ExecConfig::new(
+        accounts,
+        *DEFAULT_WASM_CONFIG,
+        *DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_VALIDATOR_SLOTS,
+        DEFAULT_AUCTION_DELAY,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+        MINIMUM_DELEGATION_AMOUNT,
+        MAX_DELEGATORS_PER_VALIDATOR,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    let system_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *SYSTEM_ADDR,
+            ARG_AMOUNT => U512::from(SYSTEM_TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+
+    let post_genesis_requests = vec![
+        system_fund_request,
+        validator_1_fund_request,
+        validator_1_add_bid_request,
+    ];
+
+    for request in post_genesis_requests {
+        builder.exec(request).commit().expect_success();
+    }
+
+    let delegate_request = |delegator: &PublicKey, amount: u64| {
+        ExecuteRequestBuilder::standard(
+            AccountHash::from(delegator),
+            CONTRACT_DELEGATE,
+            runtime_args! {
+                ARG_AMOUNT => U512::from(amount),
+                ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_DELEGATOR => delegator.clone(),
+            },
+        )
+        .build()
+    };
+
+    // A new delegation below the minimum amount is rejected.
+    builder
+        .exec(delegate_request(&DELEGATOR_1, DELEGATE_AMOUNT_2))
+        .commit();
+    let error = {
+        let response = builder
+            .get_exec_results()
+            .last()
+            .expect("should have last exec result");
+        let exec_response = response.last().expect("should have response");
+        exec_response.as_error().expect("should have error")
+    };
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if *auction_error == system::auction::Error::DelegationAmountTooSmall as u8
+    ));
+
+    builder
+        .exec(delegate_request(&DELEGATOR_1, DELEGATE_AMOUNT_1))
+        .commit()
+        .expect_success();
+
+    // Topping off an existing delegation isn't subject to the minimum amount.
+    builder
+        .exec(delegate_request(&DELEGATOR_1, DELEGATE_AMOUNT_2))
+        .commit()
+        .expect_success();
+
+    // The validator already has the maximum number of delegators.
+    builder
+        .exec(delegate_request(&DELEGATOR_2, DELEGATE_AMOUNT_1))
+        .commit();
+    let error = {
+        let response = builder
+            .get_exec_results()
+            .last()
+            .expect("should have last exec result");
+        let exec_response = response.last().expect("should have response");
+        exec_response.as_error().expect("should have error")
+    };
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if *auction_error == system::auction::Error::ExceededDelegatorSizeLimit as u8
+    ));
+
+    let bids: Bids = builder.get_bids();
+    let delegators = bids[&NON_FOUNDER_VALIDATOR_1_PK].delegators();
+    assert_eq!(delegators.len(), 1);
+    assert_eq!(
+        *delegators[&DELEGATOR_1].staked_amount(),
+        U512::from(DELEGATE_AMOUNT_1 + DELEGATE_AMOUNT_2)
+    );
+}
@@ -4,7 +4,8 @@ use once_cell::sync::Lazy;
 use casper_engine_test_support::{
     internal::{
         InMemoryWasmTestBuilder, DEFAULT_AUCTION_DELAY, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
-        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_MAX_DELEGATORS_PER_VALIDATOR,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG,
         DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG,
     },
     AccountHash,
@@ -75,6 +76,8 @@ fn should_run_genesis() {
     let round_seigniorage_rate = DEFAULT_ROUND_SEIGNIORAGE_RATE;
     let unbonding_delay = DEFAULT_UNBONDING_DELAY;
     let genesis_timestamp = DEFAULT_GENESIS_TIMESTAMP_MILLIS;
+    let minimum_delegation_amount = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    let max_delegators_per_validator = DEFAULT_MAX_DELEGATORS_PER_VALIDATOR;
 
     let exec_config = ExecConfig::new(
         GENESIS_CUSTOM_ACCOUNTS.clone(),
@@ -86,6 +89,8 @@ fn should_run_genesis() {
         round_seigniorage_rate,
         unbonding_delay,
         genesis_timestamp,
+        minimum_delegation_amount,
+        max_delegators_per_validator,
     );
     let run_genesis_request =
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config);
@@ -146,6 +151,8 @@ fn should_track_total_token_supply_in_mint() {
     let round_seigniorage_rate = DEFAULT_ROUND_SEIGNIORAGE_RATE;
     let unbonding_delay = DEFAULT_UNBONDING_DELAY;
     let genesis_timestamp = DEFAULT_GENESIS_TIMESTAMP_MILLIS;
+    let minimum_delegation_amount = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    let max_delegators_per_validator = DEFAULT_MAX_DELEGATORS_PER_VALIDATOR;
     let ee_config = ExecConfig::new(
         accounts.clone(),
         wasm_config,
@@ -156,6 +163,8 @@ fn should_track_total_token_supply_in_mint() {
         round_seigniorage_rate,
         unbonding_delay,
         genesis_timestamp,
+        minimum_delegation_amount,
+        max_delegators_per_validator,
     );
     let run_genesis_request =
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, ee_config);
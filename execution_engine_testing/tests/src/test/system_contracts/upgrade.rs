@@ -21,7 +21,8 @@ use casper_execution_engine::shared::{
 use casper_types::{
     system::{
         auction::{
-            AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+            AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY, MAX_DELEGATORS_PER_VALIDATOR_KEY,
+            MINIMUM_DELEGATION_AMOUNT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         mint::ROUND_SEIGNIORAGE_RATE_KEY,
     },
@@ -568,6 +569,72 @@ fn should_upgrade_only_unbonding_delay() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_upgrade_delegation_limits() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let new_minimum_delegation_amount = 500_000_000_000u64;
+    let new_max_delegators_per_validator = 1200u32;
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_minimum_delegation_amount(new_minimum_delegation_amount)
+            .with_new_max_delegators_per_validator(new_max_delegators_per_validator)
+            .build()
+    };
+
+    builder
+        .upgrade_with_upgrade_request(&mut upgrade_request)
+        .expect_upgrade_success();
+
+    let auction_named_keys = builder
+        .get_contract(builder.get_auction_contract_hash())
+        .expect("auction should exist")
+        .named_keys()
+        .clone();
+
+    let after_minimum_delegation_amount: u64 = builder
+        .query(None, auction_named_keys[MINIMUM_DELEGATION_AMOUNT_KEY], &[])
+        .expect("should have minimum delegation amount")
+        .as_cl_value()
+        .expect("should be a CLValue")
+        .clone()
+        .into_t()
+        .expect("should be u64");
+
+    let after_max_delegators_per_validator: u32 = builder
+        .query(
+            None,
+            auction_named_keys[MAX_DELEGATORS_PER_VALIDATOR_KEY],
+            &[],
+        )
+        .expect("should have max delegators per validator")
+        .as_cl_value()
+        .expect("should be a CLValue")
+        .clone()
+        .into_t()
+        .expect("should be u32");
+
+    assert_eq!(
+        new_minimum_delegation_amount, after_minimum_delegation_amount,
+        "Should have upgraded minimum delegation amount"
+    );
+    assert_eq!(
+        new_max_delegators_per_validator, after_max_delegators_per_validator,
+        "Should have upgraded max delegators per validator"
+    );
+}
+
 #[ignore]
 #[test]
 fn should_apply_global_state_upgrade() {
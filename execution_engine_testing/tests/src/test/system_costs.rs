@@ -20,8 +20,8 @@ use casper_execution_engine::{
         system_config::{
             auction_costs::{
                 AuctionCosts, DEFAULT_ADD_BID_COST, DEFAULT_DELEGATE_COST, DEFAULT_DISTRIBUTE_COST,
-                DEFAULT_RUN_AUCTION_COST, DEFAULT_SLASH_COST, DEFAULT_UNDELEGATE_COST,
-                DEFAULT_WITHDRAW_BID_COST,
+                DEFAULT_REDELEGATE_COST, DEFAULT_RUN_AUCTION_COST, DEFAULT_SLASH_COST,
+                DEFAULT_UNDELEGATE_COST, DEFAULT_WITHDRAW_BID_COST,
             },
             handle_payment_costs::{
                 HandlePaymentCosts, DEFAULT_FINALIZE_PAYMENT_COST, DEFAULT_SET_REFUND_PURSE_COST,
@@ -643,6 +643,11 @@ fn should_charge_for_erroneous_system_contract_calls() {
             auction::METHOD_UNDELEGATE,
             DEFAULT_UNDELEGATE_COST,
         ),
+        (
+            auction_hash,
+            auction::METHOD_REDELEGATE,
+            DEFAULT_REDELEGATE_COST,
+        ),
         (
             auction_hash,
             auction::METHOD_RUN_AUCTION,
@@ -829,6 +834,10 @@ fn should_verify_wasm_add_bid_wasm_cost_is_not_recursive() {
         remove_contract_user_group_urefs: HostFunction::fixed(0),
         print: HostFunction::fixed(0),
         blake2b: HostFunction::fixed(0),
+        emit_event: HostFunction::fixed(0),
+        verify_signature: HostFunction::fixed(0),
+        dictionary_remove: HostFunction::fixed(0),
+        dictionary_keys: HostFunction::fixed(0),
     };
 
     let new_wasm_config = WasmConfig::new(
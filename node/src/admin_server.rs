@@ -0,0 +1,264 @@
+//! Admin server.
+//!
+//! The admin server is a small HTTP server which only listens on a loopback address and allows
+//! the operator of a node to interact with it at runtime, without having to restart it:
+//!
+//! * `PUT /log-filter` replaces the logging filter with the directives given in the request body,
+//!   using the same syntax as the `RUST_LOG` environment variable.
+//! * `GET /diagnostics` returns the sizes of the event queues and the estimated heap memory usage
+//!   of every component of the currently running reactor.
+//! * `POST /shutdown` shuts the node down in an orderly fashion, logging the reason given in the
+//!   request body.
+//!
+//! Unlike the components, the admin server is not owned by a reactor, as it keeps running while
+//! the node moves from one reactor to the next. It exchanges data with the running reactor via
+//! globals checked by the [`Runner`](crate::reactor::Runner), much like the UNIX signal hooks.
+
+mod config;
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    mem,
+    net::SocketAddr,
+    sync::{atomic::Ordering, Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use datasize::MemUsageNode;
+use futures::{future, TryFutureExt};
+use http::Response;
+use hyper::{
+    server::{conn::AddrIncoming, Builder},
+    Body,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use signal_hook::consts::signal::SIGTERM;
+use thiserror::Error;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+use warp::{
+    filters::BoxedFilter,
+    http::StatusCode,
+    reject::Rejection,
+    reply::{self, Reply},
+    Filter,
+};
+
+use crate::{
+    logging,
+    reactor::QueueKind,
+    utils::{self, ListeningError, ResolveAddressError},
+    TERMINATION_REQUESTED,
+};
+pub use config::Config;
+
+/// The log filter URL path.
+pub const LOG_FILTER_API_PATH: &str = "log-filter";
+
+/// The diagnostics URL path.
+pub const DIAGNOSTICS_API_PATH: &str = "diagnostics";
+
+/// The shutdown URL path.
+pub const SHUTDOWN_API_PATH: &str = "shutdown";
+
+/// Maximum size of a request body in bytes.
+const MAX_BODY_SIZE: u64 = 4096;
+
+/// How long to wait for the running reactor to collect diagnostics.
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pending diagnostics requests, answered by the running reactor.
+static DIAGNOSTICS_REQUESTS: Lazy<Mutex<Vec<oneshot::Sender<Arc<Diagnostics>>>>> =
+    Lazy::new(Default::default);
+
+/// An error starting the admin server.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Failed to resolve address.
+    #[error("failed to resolve admin server address: {0}")]
+    ResolveAddress(#[from] ResolveAddressError),
+
+    /// The address is not a loopback address.
+    #[error("admin server address {0} is not a loopback address")]
+    NotLoopback(SocketAddr),
+
+    /// Failed to listen.
+    #[error(transparent)]
+    Listening(#[from] ListeningError),
+}
+
+/// Diagnostics of the running reactor.
+#[derive(Serialize)]
+pub(crate) struct Diagnostics {
+    /// The type name of the reactor.
+    pub(crate) reactor: &'static str,
+    /// The number of events processed by the reactor so far.
+    pub(crate) event_count: usize,
+    /// The number of events in each of the reactor's event queues.
+    pub(crate) queue_sizes: BTreeMap<QueueKind, usize>,
+    /// The estimated heap memory usage of the reactor, broken down by component.
+    pub(crate) memory_usage: Option<MemUsageNode>,
+}
+
+/// Removes and returns all pending diagnostics requests.
+pub(crate) fn take_diagnostics_requests() -> Vec<oneshot::Sender<Arc<Diagnostics>>> {
+    mem::take(
+        &mut *DIAGNOSTICS_REQUESTS
+            .lock()
+            .expect("diagnostics requests lock poisoned"),
+    )
+}
+
+/// Asks the running reactor for its diagnostics.
+///
+/// Returns `None` if the reactor did not answer in time.
+async fn request_diagnostics() -> Option<Arc<Diagnostics>> {
+    let (sender, receiver) = oneshot::channel();
+    DIAGNOSTICS_REQUESTS
+        .lock()
+        .expect("diagnostics requests lock poisoned")
+        .push(sender);
+    tokio::time::timeout(DIAGNOSTICS_TIMEOUT, receiver)
+        .await
+        .ok()?
+        .ok()
+}
+
+/// Requests the running reactor to exit, as if the node had received a `SIGTERM`.
+fn request_shutdown(reason: &str) {
+    warn!(%reason, "shutdown requested via admin server");
+    TERMINATION_REQUESTED.store(SIGTERM as usize, Ordering::SeqCst);
+}
+
+/// Parses the request body as trimmed UTF-8 text.
+fn body_text(body: &[u8]) -> String {
+    String::from_utf8_lossy(body).trim().to_string()
+}
+
+/// Resolves `address`, ensuring it only accepts connections from the local machine.
+fn resolve_loopback_address(address: &str) -> Result<SocketAddr, Error> {
+    let address = utils::resolve_address(address)?;
+    if !address.ip().is_loopback() {
+        return Err(Error::NotLoopback(address));
+    }
+    Ok(address)
+}
+
+fn create_log_filter_filter() -> BoxedFilter<(Response<Body>,)> {
+    warp::put()
+        .and(warp::path(LOG_FILTER_API_PATH))
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::bytes())
+        .map(|body: Bytes| {
+            let directives = body_text(&body);
+            match logging::reload_filter(&directives) {
+                Ok(()) => {
+                    info!(%directives, "log filter replaced via admin server");
+                    reply::with_status("log filter replaced", StatusCode::OK).into_response()
+                }
+                Err(error) => {
+                    reply::with_status(error.to_string(), StatusCode::BAD_REQUEST).into_response()
+                }
+            }
+        })
+        .boxed()
+}
+
+fn create_diagnostics_filter() -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(DIAGNOSTICS_API_PATH))
+        .and(warp::path::end())
+        .and_then(|| async {
+            let response = match request_diagnostics().await {
+                Some(diagnostics) => reply::json(&*diagnostics).into_response(),
+                None => {
+                    warn!("diagnostics not available");
+                    reply::with_status("diagnostics not available", StatusCode::SERVICE_UNAVAILABLE)
+                        .into_response()
+                }
+            };
+            Ok::<_, Rejection>(response)
+        })
+        .boxed()
+}
+
+fn create_shutdown_filter() -> BoxedFilter<(Response<Body>,)> {
+    warp::post()
+        .and(warp::path(SHUTDOWN_API_PATH))
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::bytes())
+        .map(|body: Bytes| {
+            let reason = body_text(&body);
+            if reason.is_empty() {
+                request_shutdown("no reason given");
+            } else {
+                request_shutdown(&reason);
+            }
+            reply::with_status("shutting down", StatusCode::OK).into_response()
+        })
+        .boxed()
+}
+
+/// Run the admin HTTP server.
+async fn run(builder: Builder<AddrIncoming>) {
+    let service = warp::service(
+        create_log_filter_filter()
+            .or(create_diagnostics_filter())
+            .or(create_shutdown_filter()),
+    );
+
+    let make_svc =
+        hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
+
+    let server = builder.serve(make_svc);
+    info!(address = %server.local_addr(), "started admin server");
+
+    // The server is not shut down explicitly, it runs for as long as the node process does.
+    let _ = server
+        .map_err(|error| {
+            warn!(%error, "error running admin server");
+        })
+        .await;
+}
+
+/// Starts the admin server in the background if it is enabled in `config`.
+///
+/// Must be called from within a tokio runtime.
+pub fn start(config: &Config) -> Result<(), Error> {
+    if !config.enable {
+        return Ok(());
+    }
+
+    resolve_loopback_address(&config.address)?;
+    let builder = utils::start_listening(&config.address)?;
+    tokio::spawn(run(builder));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_accept_loopback_addresses() {
+        assert!(resolve_loopback_address("127.0.0.1:8889").is_ok());
+        assert!(resolve_loopback_address("[::1]:8889").is_ok());
+    }
+
+    #[test]
+    fn should_reject_non_loopback_addresses() {
+        assert!(matches!(
+            resolve_loopback_address("0.0.0.0:8889"),
+            Err(Error::NotLoopback(_))
+        ));
+        assert!(matches!(
+            resolve_loopback_address("192.168.1.1:8889"),
+            Err(Error::NotLoopback(_))
+        ));
+    }
+}
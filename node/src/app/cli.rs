@@ -17,6 +17,7 @@ use toml::{value::Table, Value};
 use tracing::{error, info, warn};
 
 use casper_node::{
+    crypto::hash::Digest,
     logging,
     reactor::{initializer, joiner, participating, ReactorExit, Runner},
     setup_signal_hooks,
@@ -75,6 +76,39 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Export the global state under the given state root hash to a snapshot file.
+    ExportState {
+        /// Path to configuration file of this node.
+        #[structopt(long)]
+        config: PathBuf,
+        /// Hex-encoded state root hash of the global state to export.
+        #[structopt(long)]
+        state_root_hash: String,
+        /// Path of the snapshot file to write.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Import the global state held in a snapshot file previously written by `export-state`.
+    ImportState {
+        /// Path to configuration file of this node.
+        #[structopt(long)]
+        config: PathBuf,
+        /// Path of the snapshot file to read.
+        #[structopt(long)]
+        input: PathBuf,
+    },
+    /// Check the given chainspec, reporting every invalid value.
+    CheckChainspec {
+        /// Path to the chainspec file, or to the directory containing it.
+        path: PathBuf,
+    },
+    /// Report the upgrade config which upgrading from the old to the new chainspec results in.
+    DiffChainspec {
+        /// Path to the chainspec file of the previous version, or to the directory containing it.
+        old: PathBuf,
+        /// Path to the chainspec file of the new version, or to the directory containing it.
+        new: PathBuf,
+    },
 }
 
 #[derive(Debug)]
@@ -149,6 +183,9 @@ impl Cli {
                 let validator_config = Self::init(&config, config_ext)?;
                 info!(version = %casper_node::VERSION_STRING.as_str(), "node starting up");
 
+                // The admin server is started before any reactor, as it outlives all of them.
+                casper_node::admin_server::start(&validator_config.value().admin_server)?;
+
                 let pidfile_outcome = {
                     // Determine storage directory to store pidfile in.
                     let storage_config = validator_config.map_ref(|cfg| cfg.storage.clone());
@@ -281,6 +318,53 @@ impl Cli {
                 casper_node::migrate_data(WithDir::new(old_root, old_config), new_config)?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::ExportState {
+                config,
+                state_root_hash,
+                output,
+            } => {
+                let config = Self::init(&config, vec![])?;
+                let state_root_hash = Digest::from_hex(&state_root_hash)
+                    .context("could not parse state root hash")?;
+
+                info!(%state_root_hash, output = %output.display(), "exporting global state");
+                casper_node::export_state(config, state_root_hash, &output)?;
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ImportState { config, input } => {
+                let config = Self::init(&config, vec![])?;
+
+                info!(input = %input.display(), "importing global state");
+                let state_root_hash = casper_node::import_state(config, &input)?;
+                info!(%state_root_hash, "verified imported global state");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::CheckChainspec { path } => {
+                let check = casper_node::check_chainspec(&path)?;
+                for warning in &check.warnings {
+                    println!("warning: {}", warning);
+                }
+                for error in &check.errors {
+                    println!("error: {}", error);
+                }
+                if check.is_valid() {
+                    println!("{} is valid", path.display());
+                    Ok(ExitCode::Success as i32)
+                } else {
+                    println!(
+                        "{} has {} invalid values",
+                        path.display(),
+                        check.errors.len()
+                    );
+                    Ok(ExitCode::Abort as i32)
+                }
+            }
+            Cli::DiffChainspec { old, new } => {
+                for change in casper_node::diff_chainspec(&old, &new)? {
+                    println!("{}", change);
+                }
+                Ok(ExitCode::Success as i32)
+            }
         }
     }
 
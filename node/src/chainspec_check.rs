@@ -0,0 +1,296 @@
+//! Validation of chainspecs, and reporting of the changes an upgrade to a new chainspec makes.
+//!
+//! Both are intended to be run ahead of an upgrade, so that inconsistencies in a new chainspec are
+//! found before a node refuses to start with it.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{
+    crypto::hash::Digest,
+    types::{chainspec, Chainspec},
+    utils::Loadable,
+};
+
+/// Error returned as a result of checking or diffing chainspecs.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error loading a chainspec.
+    #[error("could not load chainspec from {}: {error}", path.display())]
+    LoadChainspec {
+        /// The path of the chainspec.
+        path: PathBuf,
+        /// The underlying error.
+        error: chainspec::Error,
+    },
+
+    /// The chainspec has invalid values.
+    #[error("chainspec at {} is invalid: {}", path.display(), errors.join("; "))]
+    InvalidChainspec {
+        /// The path of the chainspec.
+        path: PathBuf,
+        /// Descriptions of every invalid value.
+        errors: Vec<String>,
+    },
+
+    /// The new chainspec does not describe an upgrade from the old one.
+    #[error("not an upgrade: {0}")]
+    NotAnUpgrade(String),
+
+    /// Error encoding a value as JSON in order to compare it.
+    #[error("failed to encode as JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The outcome of checking a chainspec.
+#[derive(Debug, Default)]
+pub struct ChainspecCheck {
+    /// Descriptions of every value which doesn't make sense.
+    pub errors: Vec<String>,
+    /// Descriptions of every value which is allowed, but likely to be a mistake.
+    pub warnings: Vec<String>,
+}
+
+impl ChainspecCheck {
+    /// Returns `true` if no errors were found.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Loads and validates the chainspec at `path`, reporting every problem found.
+///
+/// `path` can either be the chainspec file itself or the directory containing it.
+pub fn check_chainspec(path: &Path) -> Result<ChainspecCheck, Error> {
+    let chainspec = load(path)?;
+    Ok(ChainspecCheck {
+        errors: chainspec.validation_errors(),
+        warnings: chainspec.validation_warnings(),
+    })
+}
+
+/// Returns a description of every value in the upgrade config which the node would produce when
+/// upgrading from the chainspec at `old_path` to the one at `new_path`, and which differs from the
+/// old chainspec.
+pub fn diff_chainspec(old_path: &Path, new_path: &Path) -> Result<Vec<String>, Error> {
+    let old = load(old_path)?;
+    let new = load(new_path)?;
+    let errors = new.validation_errors();
+    if !errors.is_empty() {
+        return Err(Error::InvalidChainspec {
+            path: new_path.to_path_buf(),
+            errors,
+        });
+    }
+    if new.protocol_version() <= old.protocol_version() {
+        return Err(Error::NotAnUpgrade(format!(
+            "new protocol version {} is not greater than {}",
+            new.protocol_version(),
+            old.protocol_version()
+        )));
+    }
+    if new.is_genesis() {
+        return Err(Error::NotAnUpgrade(format!(
+            "new chainspec has a genesis {}",
+            new.protocol_config.activation_point
+        )));
+    }
+
+    // The pre-state hash is only known once the upgrade is applied to a node's global state.
+    let upgrade_config = new.upgrade_config(Digest::default(), old.protocol_version());
+    let mut changes = vec![format!(
+        "protocol.version: {} -> {}",
+        upgrade_config.current_protocol_version(),
+        upgrade_config.new_protocol_version()
+    )];
+    push_change(
+        &mut changes,
+        "protocol.activation_point",
+        old.protocol_config.activation_point.era_id(),
+        upgrade_config.activation_point(),
+    );
+    if let Some(wasm_config) = upgrade_config.wasm_config() {
+        push_json_changes(&mut changes, "wasm", &old.wasm_config, wasm_config)?;
+    }
+    if let Some(system_config) = upgrade_config.system_config() {
+        push_json_changes(
+            &mut changes,
+            "system_costs",
+            &old.system_costs_config,
+            system_config,
+        )?;
+    }
+    let core_config = &old.core_config;
+    push_change(
+        &mut changes,
+        "core.validator_slots",
+        core_config.validator_slots,
+        upgrade_config.new_validator_slots(),
+    );
+    push_change(
+        &mut changes,
+        "core.auction_delay",
+        core_config.auction_delay,
+        upgrade_config.new_auction_delay(),
+    );
+    push_change(
+        &mut changes,
+        "core.locked_funds_period (ms)",
+        core_config.locked_funds_period.millis(),
+        upgrade_config.new_locked_funds_period_millis(),
+    );
+    push_change(
+        &mut changes,
+        "core.round_seigniorage_rate",
+        core_config.round_seigniorage_rate,
+        upgrade_config.new_round_seigniorage_rate(),
+    );
+    push_change(
+        &mut changes,
+        "core.unbonding_delay",
+        core_config.unbonding_delay,
+        upgrade_config.new_unbonding_delay(),
+    );
+    push_change(
+        &mut changes,
+        "core.minimum_delegation_amount",
+        core_config.minimum_delegation_amount,
+        upgrade_config.new_minimum_delegation_amount(),
+    );
+    push_change(
+        &mut changes,
+        "core.max_delegators_per_validator",
+        core_config.max_delegators_per_validator,
+        upgrade_config.new_max_delegators_per_validator(),
+    );
+    for (key, stored_value) in upgrade_config.global_state_update() {
+        changes.push(format!(
+            "global_state_update: {} = {}",
+            key.to_formatted_string(),
+            serde_json::to_string(stored_value)?
+        ));
+    }
+    Ok(changes)
+}
+
+/// Loads the chainspec from the given file, or from the chainspec file in the given directory.
+fn load(path: &Path) -> Result<Chainspec, Error> {
+    let result = if path.is_dir() {
+        Chainspec::from_path(path)
+    } else {
+        Chainspec::from_file(path)
+    };
+    result.map_err(|error| Error::LoadChainspec {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Describes the change from `old` to `new`, if there is one.
+fn push_change<T: PartialEq + Display>(
+    changes: &mut Vec<String>,
+    name: &str,
+    old: T,
+    new: Option<T>,
+) {
+    match new {
+        Some(new) if new != old => changes.push(format!("{}: {} -> {}", name, old, new)),
+        Some(_) | None => (),
+    }
+}
+
+/// Describes every leaf value which differs between the JSON encodings of `old` and `new`.
+fn push_json_changes<T: Serialize>(
+    changes: &mut Vec<String>,
+    name: &str,
+    old: &T,
+    new: &T,
+) -> Result<(), Error> {
+    let old = serde_json::to_value(old)?;
+    let new = serde_json::to_value(new)?;
+    diff_json(changes, name, &old, &new);
+    Ok(())
+}
+
+fn diff_json(changes: &mut Vec<String>, path: &str, old: &Value, new: &Value) {
+    match (old, new) {
+        (Value::Object(old_fields), Value::Object(new_fields)) => {
+            for (key, new_value) in new_fields {
+                let field_path = format!("{}.{}", path, key);
+                match old_fields.get(key) {
+                    Some(old_value) => diff_json(changes, &field_path, old_value, new_value),
+                    None => changes.push(format!("{}: (none) -> {}", field_path, new_value)),
+                }
+            }
+            for (key, old_value) in old_fields {
+                if !new_fields.contains_key(key) {
+                    changes.push(format!("{}.{}: {} -> (none)", path, key, old_value));
+                }
+            }
+        }
+        _ if old != new => changes.push(format!("{}: {} -> {}", path, old, new)),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_report_changed_leaf_values() {
+        let old = json!({ "a": { "b": 1, "c": 2, "d": 3 }, "e": "x" });
+        let new = json!({ "a": { "b": 1, "c": 5, "f": 4 }, "e": "x" });
+
+        let mut changes = vec![];
+        diff_json(&mut changes, "wasm", &old, &new);
+        changes.sort();
+        assert_eq!(
+            changes,
+            vec![
+                "wasm.a.c: 2 -> 5".to_string(),
+                "wasm.a.d: 3 -> (none)".to_string(),
+                "wasm.a.f: (none) -> 4".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_check_valid_chainspec() {
+        let path = crate::utils::RESOURCES_PATH.join("test/valid/0_9_0");
+        let check = check_chainspec(&path).expect("should load chainspec");
+        assert!(check.is_valid(), "{:?}", check.errors);
+
+        let file_check = check_chainspec(&path.join(chainspec::CHAINSPEC_NAME))
+            .expect("should load chainspec file");
+        assert!(file_check.is_valid(), "{:?}", file_check.errors);
+    }
+
+    #[test]
+    fn should_report_invalid_chainspec() {
+        // An emergency restart whose `last_emergency_restart` doesn't match its activation point.
+        let path = crate::utils::RESOURCES_PATH.join("test/valid/1_0_0");
+        let check = check_chainspec(&path).expect("should load chainspec");
+        assert_eq!(check.errors.len(), 1, "{:?}", check.errors);
+        assert!(check.errors[0].contains("last_emergency_restart"));
+
+        let old_path = crate::utils::RESOURCES_PATH.join("test/valid/0_9_0");
+        let error = diff_chainspec(&old_path, &path).unwrap_err();
+        assert!(matches!(error, Error::InvalidChainspec { .. }));
+    }
+
+    #[test]
+    fn should_not_diff_chainspec_against_itself() {
+        let path = crate::utils::RESOURCES_PATH.join("test/valid/0_9_0");
+        let error = diff_chainspec(&path, &path).unwrap_err();
+        assert!(matches!(error, Error::NotAnUpgrade(_)));
+    }
+}
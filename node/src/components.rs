@@ -24,6 +24,7 @@ pub(crate) mod metrics;
 pub(crate) mod network;
 pub(crate) mod networking_metrics;
 pub(crate) mod small_network;
+pub(crate) mod state_sync;
 pub(crate) mod storage;
 
 use crate::{
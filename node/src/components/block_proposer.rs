@@ -17,13 +17,13 @@ use std::{
     time::Duration,
 };
 
-pub use config::Config;
+pub use config::{Config, DeployOrdering};
 use datasize::DataSize;
 use itertools::Itertools;
 use prometheus::{self, Registry};
 use tracing::{debug, error, info, trace, warn};
 
-use casper_types::PublicKey;
+use casper_types::{account::AccountHash, PublicKey};
 
 use crate::{
     components::{
@@ -31,17 +31,19 @@ use crate::{
         Component,
     },
     effect::{
+        announcements::BlockProposerAnnouncement,
         requests::{BlockPayloadRequest, BlockProposerRequest, StateStoreRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
     types::{
         appendable_block::{AddError, AppendableBlock},
         chainspec::DeployConfig,
-        BlockPayload, Chainspec, DeployHash, DeployHeader, DeployOrTransferHash, Timestamp,
+        BlockPayload, Chainspec, DeployHash, DeployHeader, DeployOrTransferHash, PendingDeploy,
+        PendingDeployStatus, Timestamp,
     },
     NodeRng,
 };
-use deploy_sets::BlockProposerDeploySets;
+use deploy_sets::{ordered_pending_deploys, BlockProposerDeploySets};
 pub(crate) use event::{DeployInfo, Event};
 use metrics::BlockProposerMetrics;
 
@@ -103,7 +105,12 @@ impl BlockProposer {
         local_config: Config,
     ) -> Result<(Self, Effects<Event>), prometheus::Error>
     where
-        REv: From<Event> + From<StorageRequest> + From<StateStoreRequest> + Send + 'static,
+        REv: From<Event>
+            + From<StorageRequest>
+            + From<StateStoreRequest>
+            + From<BlockProposerAnnouncement>
+            + Send
+            + 'static,
     {
         debug!(%next_finalized_block, "creating block proposer");
         let effects = effect_builder
@@ -128,7 +135,12 @@ impl BlockProposer {
 
 impl<REv> Component<REv> for BlockProposer
 where
-    REv: From<Event> + From<StorageRequest> + From<StateStoreRequest> + Send + 'static,
+    REv: From<Event>
+        + From<StorageRequest>
+        + From<StateStoreRequest>
+        + From<BlockProposerAnnouncement>
+        + Send
+        + 'static,
 {
     type Event = Event;
     type ConstructionError = Infallible;
@@ -192,6 +204,12 @@ where
             }
 
             (BlockProposerState::Ready(ref mut ready_state), event) => {
+                // Collecting the gas prices means visiting every pending deploy, so they are only
+                // updated periodically when pruning, and when proposing a block.
+                let update_gas_prices = matches!(
+                    event,
+                    Event::Prune | Event::Request(BlockProposerRequest::RequestBlockPayload(_))
+                );
                 effects.extend(ready_state.handle_event(effect_builder, event));
 
                 // Update metrics after the effects have been applied.
@@ -199,6 +217,10 @@ where
                     ready_state.sets.pending_deploys.len() as i64
                         + ready_state.sets.pending_transfers.len() as i64,
                 );
+                if update_gas_prices {
+                    self.metrics
+                        .set_pending_gas_prices(ready_state.sets.pending_gas_prices());
+                }
             }
         };
 
@@ -231,7 +253,8 @@ impl BlockProposerReady {
         event: Event,
     ) -> Effects<Event>
     where
-        REv: Send + From<StorageRequest> + From<StateStoreRequest>,
+        REv:
+            Send + From<StorageRequest> + From<StateStoreRequest> + From<BlockProposerAnnouncement>,
     {
         match event {
             Event::Request(BlockProposerRequest::RequestBlockPayload(request)) => {
@@ -258,18 +281,32 @@ impl BlockProposerReady {
                         .ignore()
                 }
             }
+            Event::Request(BlockProposerRequest::GetPendingDeploys {
+                account_hash,
+                responder,
+            }) => responder
+                .respond(self.pending_deploys(Timestamp::now(), account_hash))
+                .ignore(),
             Event::BufferDeploy { hash, deploy_info } => {
                 self.add_deploy(Timestamp::now(), hash, *deploy_info);
                 Effects::new()
             }
             Event::Prune => {
-                let pruned = self.prune(Timestamp::now());
-                debug!(%pruned, "pruned deploys from buffer");
+                let (pruned, expired) = self.prune(Timestamp::now());
+                debug!(%pruned, expired = %expired.len(), "pruned deploys from buffer");
+
+                let mut effects = Effects::new();
+                if !expired.is_empty() {
+                    effects.extend(effect_builder.announce_deploys_expired(expired).ignore());
+                }
 
                 // Re-trigger timer after `PRUNE_INTERVAL`.
-                effect_builder
-                    .set_timeout(PRUNE_INTERVAL)
-                    .event(|_| Event::Prune)
+                effects.extend(
+                    effect_builder
+                        .set_timeout(PRUNE_INTERVAL)
+                        .event(|_| Event::Prune),
+                );
+                effects
             }
             Event::Loaded { .. } => {
                 // This should never happen, but we can just ignore the event and carry on.
@@ -429,19 +466,29 @@ impl BlockProposerReady {
             .collect();
         let block_timestamp = context.timestamp();
         let mut appendable_block = AppendableBlock::new(deploy_config, block_timestamp);
+        let ordering = self.local_config.deploy_ordering;
+        let mut deploys_per_account: HashMap<&PublicKey, u32> = HashMap::new();
 
         // We prioritize transfers over deploys, so we try to include them first.
-        for (hash, (deploy_info, received_time)) in &self.sets.pending_transfers {
+        for (hash, deploy_info, received_time) in
+            ordered_pending_deploys(&self.sets.pending_transfers, ordering)
+        {
             if !self.deps_resolved(&deploy_info.header, &past_deploys)
                 || past_deploys.contains(hash)
                 || self.contains_finalized(hash)
-                || block_timestamp.saturating_diff(*received_time) < self.local_config.deploy_delay
+                || block_timestamp.saturating_diff(received_time) < self.local_config.deploy_delay
+                || self.account_limit_reached(&deploys_per_account, &deploy_info.header)
             {
                 continue;
             }
 
-            if let Err(err) = appendable_block.add_transfer(*hash, deploy_info) {
-                match err {
+            match appendable_block.add_transfer(*hash, deploy_info) {
+                Ok(()) => {
+                    *deploys_per_account
+                        .entry(deploy_info.header.account())
+                        .or_default() += 1;
+                }
+                Err(err) => match err {
                     // We added the maximum number of transfers.
                     AddError::TransferCount | AddError::GasLimit | AddError::BlockSize => break,
                     // The deploy is not valid in this block, but might be valid in another.
@@ -450,22 +497,30 @@ impl BlockProposerReady {
                     AddError::InvalidGasAmount | AddError::DeployCount | AddError::Duplicate => {
                         error!(?err, "unexpected error when adding transfer")
                     }
-                }
+                },
             }
         }
 
         // Now we try to add other deploys to the block.
-        for (hash, (deploy_info, received_time)) in &self.sets.pending_deploys {
+        for (hash, deploy_info, received_time) in
+            ordered_pending_deploys(&self.sets.pending_deploys, ordering)
+        {
             if !self.deps_resolved(&deploy_info.header, &past_deploys)
                 || past_deploys.contains(hash)
                 || self.contains_finalized(hash)
-                || block_timestamp.saturating_diff(*received_time) < self.local_config.deploy_delay
+                || block_timestamp.saturating_diff(received_time) < self.local_config.deploy_delay
+                || self.account_limit_reached(&deploys_per_account, &deploy_info.header)
             {
                 continue;
             }
 
-            if let Err(err) = appendable_block.add_deploy(*hash, deploy_info) {
-                match err {
+            match appendable_block.add_deploy(*hash, deploy_info) {
+                Ok(()) => {
+                    *deploys_per_account
+                        .entry(deploy_info.header.account())
+                        .or_default() += 1;
+                }
+                Err(err) => match err {
                     // We added the maximum number of deploys.
                     AddError::DeployCount => break,
                     AddError::BlockSize => {
@@ -485,15 +540,100 @@ impl BlockProposerReady {
                     AddError::InvalidGasAmount => {
                         error!("payment_amount couldn't be converted from motes to gas")
                     }
-                }
+                },
             }
         }
 
         Arc::new(appendable_block.into_block_payload(accusations, random_bit))
     }
 
-    /// Prunes expired deploy information from the BlockProposer, returns the total deploys pruned.
-    fn prune(&mut self, current_instant: Timestamp) -> usize {
+    /// Checks if the deploy's account already has the maximum number of deploys in the block.
+    fn account_limit_reached(
+        &self,
+        deploys_per_account: &HashMap<&PublicKey, u32>,
+        header: &DeployHeader,
+    ) -> bool {
+        let max_deploys_per_account = self.local_config.max_deploys_per_account;
+        max_deploys_per_account != 0
+            && deploys_per_account
+                .get(header.account())
+                .map_or(false, |count| *count >= max_deploys_per_account)
+    }
+
+    /// Returns the pending transfers and deploys, in the order in which they would be considered
+    /// for inclusion in a block, along with the reason each can't currently be proposed, if any.
+    ///
+    /// If `account_hash` is given, only those sent by that account are returned.
+    fn pending_deploys(
+        &self,
+        current_instant: Timestamp,
+        account_hash: Option<AccountHash>,
+    ) -> Vec<PendingDeploy> {
+        let ordering = self.local_config.deploy_ordering;
+        let transfers = ordered_pending_deploys(&self.sets.pending_transfers, ordering)
+            .into_iter()
+            .map(|entry| (true, entry));
+        let deploys = ordered_pending_deploys(&self.sets.pending_deploys, ordering)
+            .into_iter()
+            .map(|entry| (false, entry));
+        transfers
+            .chain(deploys)
+            .filter(|(_, (_, deploy_info, _))| {
+                account_hash.map_or(true, |account_hash| {
+                    deploy_info.header.account().to_account_hash() == account_hash
+                })
+            })
+            .map(
+                |(is_transfer, (hash, deploy_info, received_time))| PendingDeploy {
+                    deploy_hash: *hash,
+                    account: deploy_info.header.account().clone(),
+                    is_transfer,
+                    gas_price: deploy_info.header.gas_price(),
+                    received: received_time,
+                    expires: deploy_info.header.expires(),
+                    status: self.pending_deploy_status(
+                        current_instant,
+                        &deploy_info.header,
+                        received_time,
+                    ),
+                },
+            )
+            .collect()
+    }
+
+    /// Returns whether a pending deploy could be proposed at `current_instant`, and if not, why
+    /// not.
+    ///
+    /// Dependencies are only considered satisfied once they are finalized, since deploys in
+    /// not-yet-finalized ancestors of a proposed block are not known here.
+    fn pending_deploy_status(
+        &self,
+        current_instant: Timestamp,
+        header: &DeployHeader,
+        received_time: Timestamp,
+    ) -> PendingDeployStatus {
+        let dependencies: Vec<DeployHash> = header
+            .dependencies()
+            .iter()
+            .filter(|dep| !self.contains_finalized(dep))
+            .copied()
+            .collect();
+        if !dependencies.is_empty() {
+            return PendingDeployStatus::MissingDependencies { dependencies };
+        }
+        if header.timestamp() > current_instant {
+            return PendingDeployStatus::TimestampInFuture;
+        }
+        let eligible_from = received_time + self.local_config.deploy_delay;
+        if eligible_from > current_instant {
+            return PendingDeployStatus::AwaitingDeployDelay { eligible_from };
+        }
+        PendingDeployStatus::Ready
+    }
+
+    /// Prunes expired deploy information from the BlockProposer, returns the total deploys pruned
+    /// and the hashes of those which expired while still pending.
+    fn prune(&mut self, current_instant: Timestamp) -> (usize, Vec<DeployHash>) {
         self.sets.prune(current_instant)
     }
 
@@ -3,6 +3,25 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::TimeDiff;
 
+/// The order in which pending deploys and transfers are considered for inclusion in a new block.
+///
+/// Ties are broken by the time the deploys were received and then by their hashes, so the order is
+/// always deterministic.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployOrdering {
+    /// Deploys received earlier are proposed first.
+    ArrivalTime,
+    /// Deploys with a higher gas price are proposed first.
+    GasPrice,
+}
+
+impl Default for DeployOrdering {
+    fn default() -> Self {
+        DeployOrdering::ArrivalTime
+    }
+}
+
 /// Block proposer configuration.
 #[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -13,12 +32,21 @@ pub struct Config {
     /// other nodes, and don't have to be requested from the proposer afterwards.
     #[serde(default = "default_deploy_delay")]
     pub deploy_delay: TimeDiff,
+    /// The order in which pending deploys are considered for inclusion in a new block.
+    #[serde(default)]
+    pub deploy_ordering: DeployOrdering,
+    /// The maximum number of deploys and transfers from a single account in a new block, so that
+    /// one account can't crowd out all others.  `0` means no limit.
+    #[serde(default)]
+    pub max_deploys_per_account: u32,
 }
 
 impl Default for Config {
     fn default() -> Config {
         Config {
             deploy_delay: default_deploy_delay(),
+            deploy_ordering: DeployOrdering::default(),
+            max_deploys_per_account: 0,
         }
     }
 }
@@ -1,11 +1,12 @@
 use std::{
+    cmp::Reverse,
     collections::HashMap,
     fmt::{self, Display, Formatter},
 };
 
 use datasize::DataSize;
 
-use super::{event::DeployInfo, BlockHeight, FinalizationQueue};
+use super::{event::DeployInfo, BlockHeight, DeployOrdering, FinalizationQueue};
 use crate::types::{DeployHash, DeployHeader, Timestamp};
 
 /// Stores the internal state of the BlockProposer.
@@ -55,13 +56,26 @@ impl Display for BlockProposerDeploySets {
 }
 
 impl BlockProposerDeploySets {
+    /// Returns the gas prices of all pending deploys and transfers.
+    pub(super) fn pending_gas_prices(&self) -> Vec<u64> {
+        self.pending_deploys
+            .values()
+            .chain(self.pending_transfers.values())
+            .map(|(deploy_info, _)| deploy_info.header.gas_price())
+            .collect()
+    }
+
     /// Prunes expired deploy information from the BlockProposerState, returns the total deploys
-    /// pruned
-    pub(crate) fn prune(&mut self, current_instant: Timestamp) -> usize {
-        let pending_deploys = prune_pending_deploys(&mut self.pending_deploys, current_instant);
-        let pending_transfers = prune_pending_deploys(&mut self.pending_transfers, current_instant);
+    /// pruned along with the hashes of the pruned deploys which were still pending, i.e. which
+    /// expired without being included in a finalized block.
+    pub(crate) fn prune(&mut self, current_instant: Timestamp) -> (usize, Vec<DeployHash>) {
+        let mut expired = prune_pending_deploys(&mut self.pending_deploys, current_instant);
+        expired.extend(prune_pending_deploys(
+            &mut self.pending_transfers,
+            current_instant,
+        ));
         let finalized = prune_deploys(&mut self.finalized_deploys, current_instant);
-        pending_deploys + pending_transfers + finalized
+        (expired.len() + finalized, expired)
     }
 }
 
@@ -77,12 +91,45 @@ pub(super) fn prune_deploys(
 }
 
 /// Prunes expired deploy information from an individual pending deploy collection, returns the
-/// total deploys pruned
+/// hashes of the deploys pruned
 pub(super) fn prune_pending_deploys(
     deploys: &mut HashMap<DeployHash, (DeployInfo, Timestamp)>,
     current_instant: Timestamp,
-) -> usize {
-    let initial_len = deploys.len();
-    deploys.retain(|_hash, (deploy_info, _)| !deploy_info.header.expired(current_instant));
-    initial_len - deploys.len()
+) -> Vec<DeployHash> {
+    let mut pruned = vec![];
+    deploys.retain(|hash, (deploy_info, _)| {
+        let expired = deploy_info.header.expired(current_instant);
+        if expired {
+            pruned.push(*hash);
+        }
+        !expired
+    });
+    pruned
+}
+
+/// Returns the pending deploys from an individual pending deploy collection, in the order in which
+/// they should be considered for inclusion in a block.
+pub(super) fn ordered_pending_deploys(
+    deploys: &HashMap<DeployHash, (DeployInfo, Timestamp)>,
+    ordering: DeployOrdering,
+) -> Vec<(&DeployHash, &DeployInfo, Timestamp)> {
+    let mut ordered: Vec<_> = deploys
+        .iter()
+        .map(|(hash, (deploy_info, received_time))| (hash, deploy_info, *received_time))
+        .collect();
+    match ordering {
+        DeployOrdering::ArrivalTime => {
+            ordered.sort_unstable_by_key(|(hash, _, received_time)| (*received_time, **hash))
+        }
+        DeployOrdering::GasPrice => {
+            ordered.sort_unstable_by_key(|(hash, deploy_info, received_time)| {
+                (
+                    Reverse(deploy_info.header.gas_price()),
+                    *received_time,
+                    **hash,
+                )
+            })
+        }
+    }
+    ordered
 }
@@ -9,6 +9,15 @@ pub(super) struct BlockProposerMetrics {
     /// Amount of pending deploys
     #[data_size(skip)]
     pub(super) pending_deploys: IntGauge,
+    /// Lowest gas price of the pending deploys.
+    #[data_size(skip)]
+    pending_min_gas_price: IntGauge,
+    /// Median gas price of the pending deploys.
+    #[data_size(skip)]
+    pending_median_gas_price: IntGauge,
+    /// Highest gas price of the pending deploys.
+    #[data_size(skip)]
+    pending_max_gas_price: IntGauge,
     /// Registry stored to allow deregistration later.
     #[data_size(skip)]
     registry: Registry,
@@ -18,16 +27,56 @@ impl BlockProposerMetrics {
     /// Creates a new instance of the block proposer metrics.
     pub fn new(registry: Registry) -> Result<Self, prometheus::Error> {
         let pending_deploys = IntGauge::new("pending_deploy", "amount of pending deploys")?;
+        let pending_min_gas_price = IntGauge::new(
+            "pending_deploy_min_gas_price",
+            "lowest gas price of the pending deploys",
+        )?;
+        let pending_median_gas_price = IntGauge::new(
+            "pending_deploy_median_gas_price",
+            "median gas price of the pending deploys",
+        )?;
+        let pending_max_gas_price = IntGauge::new(
+            "pending_deploy_max_gas_price",
+            "highest gas price of the pending deploys",
+        )?;
         registry.register(Box::new(pending_deploys.clone()))?;
+        registry.register(Box::new(pending_min_gas_price.clone()))?;
+        registry.register(Box::new(pending_median_gas_price.clone()))?;
+        registry.register(Box::new(pending_max_gas_price.clone()))?;
         Ok(BlockProposerMetrics {
             pending_deploys,
+            pending_min_gas_price,
+            pending_median_gas_price,
+            pending_max_gas_price,
             registry,
         })
     }
+
+    /// Updates the gas price distribution of the pending deploys.  All values are `0` if there are
+    /// no pending deploys.
+    pub(super) fn set_pending_gas_prices(&self, mut gas_prices: Vec<u64>) {
+        if gas_prices.is_empty() {
+            self.pending_min_gas_price.set(0);
+            self.pending_median_gas_price.set(0);
+            self.pending_max_gas_price.set(0);
+            return;
+        }
+        let median_index = gas_prices.len() / 2;
+        let (lower, median, upper) = gas_prices.select_nth_unstable(median_index);
+        let median = *median;
+        let min = lower.iter().copied().min().unwrap_or(median);
+        let max = upper.iter().copied().max().unwrap_or(median);
+        self.pending_min_gas_price.set(min as i64);
+        self.pending_median_gas_price.set(median as i64);
+        self.pending_max_gas_price.set(max as i64);
+    }
 }
 
 impl Drop for BlockProposerMetrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.pending_deploys);
+        unregister_metric!(self.registry, self.pending_min_gas_price);
+        unregister_metric!(self.registry, self.pending_median_gas_price);
+        unregister_metric!(self.registry, self.pending_max_gas_price);
     }
 }
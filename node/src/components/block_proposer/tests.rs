@@ -1,3 +1,5 @@
+use std::iter;
+
 use casper_execution_engine::{
     core::engine_state::executable_deploy_item::ExecutableDeployItem, shared::gas::Gas,
 };
@@ -63,6 +65,24 @@ fn generate_deploy(
     gas_price: u64,
 ) -> Deploy {
     let secret_key = SecretKey::random(rng);
+    generate_deploy_with_key(
+        &secret_key,
+        timestamp,
+        ttl,
+        dependencies,
+        payment_amount,
+        gas_price,
+    )
+}
+
+fn generate_deploy_with_key(
+    secret_key: &SecretKey,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    dependencies: Vec<DeployHash>,
+    payment_amount: Gas,
+    gas_price: u64,
+) -> Deploy {
     let chain_name = "chain".to_string();
     let args = runtime_args! {
         ARG_AMOUNT => payment_amount.value()
@@ -84,13 +104,20 @@ fn generate_deploy(
         chain_name,
         payment,
         session,
-        &secret_key,
+        secret_key,
     )
 }
 
 fn create_test_proposer(deploy_delay: TimeDiff) -> BlockProposerReady {
+    create_test_proposer_with_config(Config {
+        deploy_delay,
+        ..Default::default()
+    })
+}
+
+fn create_test_proposer_with_config(local_config: Config) -> BlockProposerReady {
     BlockProposerReady {
-        local_config: Config { deploy_delay },
+        local_config,
         ..Default::default()
     }
 }
@@ -328,17 +355,23 @@ fn should_successfully_prune() {
     assert!(proposer.sets.finalized_deploys.contains_key(deploy1.id()));
 
     // test for retained values
-    let pruned = proposer.prune(test_time);
+    let (pruned, expired) = proposer.prune(test_time);
     assert_eq!(pruned, 0);
+    assert!(expired.is_empty());
 
     assert_eq!(proposer.sets.pending_deploys.len(), 3);
     assert_eq!(proposer.sets.finalized_deploys.len(), 1);
     assert!(proposer.sets.finalized_deploys.contains_key(deploy1.id()));
 
     // now move the clock to make some things expire
-    let pruned = proposer.prune(expired_time);
+    let (pruned, expired) = proposer.prune(expired_time);
     assert_eq!(pruned, 3);
 
+    // only the pending deploys are reported as expired, not the finalized one
+    assert_eq!(expired.len(), 2);
+    assert!(expired.contains(deploy2.id()));
+    assert!(expired.contains(deploy3.id()));
+
     assert_eq!(proposer.sets.pending_deploys.len(), 1); // deploy4 is still valid
     assert_eq!(proposer.sets.finalized_deploys.len(), 0);
 }
@@ -754,3 +787,259 @@ fn should_respect_deploy_delay() {
     );
     assert_eq!(&vec![*deploy.id()], block.deploy_hashes());
 }
+
+#[test]
+fn should_propose_deploys_in_order_of_arrival() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(0);
+    let ttl = TimeDiff::from(10000);
+    let mut deploy_config = DeployConfig::default();
+    deploy_config.block_max_deploy_count = 2;
+    let mut proposer = create_test_proposer_with_config(Config {
+        deploy_delay: 0.into(),
+        deploy_ordering: DeployOrdering::ArrivalTime,
+        max_deploys_per_account: 0,
+    });
+
+    // The gas price is ignored: the two earliest deploys are proposed.
+    let deploys = (1..=3)
+        .map(|gas_price| {
+            generate_deploy(
+                &mut rng,
+                creation_time,
+                ttl,
+                vec![],
+                default_gas_payment(),
+                gas_price,
+            )
+        })
+        .collect_vec();
+    for (received_time, deploy) in deploys.iter().enumerate() {
+        proposer.add_deploy(
+            Timestamp::from(received_time as u64),
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        deploy_config,
+        BlockContext::new(100.into(), vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(
+        &vec![*deploys[0].id(), *deploys[1].id()],
+        block.deploy_hashes()
+    );
+}
+
+#[test]
+fn should_propose_deploys_in_order_of_gas_price() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(0);
+    let ttl = TimeDiff::from(10000);
+    let mut deploy_config = DeployConfig::default();
+    deploy_config.block_max_deploy_count = 3;
+    let mut proposer = create_test_proposer_with_config(Config {
+        deploy_delay: 0.into(),
+        deploy_ordering: DeployOrdering::GasPrice,
+        max_deploys_per_account: 0,
+    });
+
+    let cheap = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    let expensive = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        5,
+    );
+    let medium_late = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        3,
+    );
+    let medium_early = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        3,
+    );
+    for (received_time, deploy) in [&cheap, &expensive, &medium_late].iter().enumerate() {
+        proposer.add_deploy(
+            Timestamp::from(10 + received_time as u64),
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+    proposer.add_deploy(
+        Timestamp::from(0),
+        medium_early.deploy_or_transfer_hash(),
+        medium_early.deploy_info().unwrap(),
+    );
+
+    // The cheapest deploy doesn't fit; deploys with equal gas price are ordered by arrival.
+    let block = proposer.propose_block_payload(
+        deploy_config,
+        BlockContext::new(100.into(), vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(
+        &vec![*expensive.id(), *medium_early.id(), *medium_late.id()],
+        block.deploy_hashes()
+    );
+}
+
+#[test]
+fn should_respect_max_deploys_per_account() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(0);
+    let ttl = TimeDiff::from(10000);
+    let deploy_config = DeployConfig::default();
+    let mut proposer = create_test_proposer_with_config(Config {
+        deploy_delay: 0.into(),
+        deploy_ordering: DeployOrdering::GasPrice,
+        max_deploys_per_account: 2,
+    });
+
+    // One account sends three deploys with a high gas price, another one a cheap deploy.
+    let busy_account = SecretKey::random(&mut rng);
+    let busy_deploys = (0..3)
+        .map(|_| {
+            generate_deploy_with_key(
+                &busy_account,
+                creation_time,
+                ttl,
+                vec![],
+                default_gas_payment(),
+                10,
+            )
+        })
+        .collect_vec();
+    let other_deploy = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    for deploy in busy_deploys.iter().chain(iter::once(&other_deploy)) {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        deploy_config,
+        BlockContext::new(100.into(), vec![]),
+        vec![],
+        true,
+    );
+    let busy_count = busy_deploys
+        .iter()
+        .filter(|deploy| block.deploy_hashes().contains(deploy.id()))
+        .count();
+    assert_eq!(busy_count, 2);
+    assert!(block.deploy_hashes().contains(other_deploy.id()));
+    assert_eq!(block.deploy_hashes().len(), 3);
+}
+
+#[test]
+fn should_report_pending_deploy_status() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(10000);
+    let mut proposer = create_test_proposer(10.into()); // Deploy delay: 10 milliseconds
+
+    let secret_key = SecretKey::random(&mut rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let deploy1 = generate_deploy_with_key(
+        &secret_key,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    // let deploy2 depend on deploy1
+    let deploy2 = generate_deploy_with_key(
+        &secret_key,
+        creation_time,
+        ttl,
+        vec![*deploy1.id()],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    // deploy3 is sent by another account, with a timestamp in the future
+    let deploy3 = generate_deploy(
+        &mut rng,
+        Timestamp::from(500),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    for deploy in &[&deploy1, &deploy2, &deploy3] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let status_of = |pending: &[PendingDeploy], deploy: &Deploy| {
+        pending
+            .iter()
+            .find(|pending_deploy| pending_deploy.deploy_hash == *deploy.id())
+            .map(|pending_deploy| pending_deploy.status.clone())
+            .expect("should be pending")
+    };
+
+    let pending = proposer.pending_deploys(105.into(), None);
+    assert_eq!(pending.len(), 3);
+    assert_eq!(
+        status_of(&pending, &deploy1),
+        PendingDeployStatus::AwaitingDeployDelay {
+            eligible_from: 110.into()
+        }
+    );
+    assert_eq!(
+        status_of(&pending, &deploy2),
+        PendingDeployStatus::MissingDependencies {
+            dependencies: vec![*deploy1.id()]
+        }
+    );
+    assert_eq!(
+        status_of(&pending, &deploy3),
+        PendingDeployStatus::TimestampInFuture
+    );
+
+    // only the deploys of the given account are returned when filtering
+    let pending = proposer.pending_deploys(110.into(), Some(account_hash));
+    assert_eq!(pending.len(), 2);
+    assert_eq!(status_of(&pending, &deploy1), PendingDeployStatus::Ready);
+
+    // once deploy1 is finalized, deploy2 is ready as well
+    proposer.finalized_deploys(iter::once(deploy1.deploy_or_transfer_hash()));
+    let pending = proposer.pending_deploys(110.into(), Some(account_hash));
+    assert_eq!(pending.len(), 1);
+    assert_eq!(status_of(&pending, &deploy2), PendingDeployStatus::Ready);
+}
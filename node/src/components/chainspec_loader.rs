@@ -26,15 +26,12 @@ use serde::{Deserialize, Serialize};
 use tokio::task;
 use tracing::{debug, error, info, trace, warn};
 
-use casper_execution_engine::{
-    core::engine_state::{
-        self,
-        genesis::GenesisResult,
-        upgrade::{UpgradeConfig, UpgradeResult},
-    },
-    shared::stored_value::StoredValue,
+use casper_execution_engine::core::engine_state::{
+    self,
+    genesis::GenesisResult,
+    upgrade::{UpgradeConfig, UpgradeResult},
 };
-use casper_types::{bytesrepr::FromBytes, EraId, ProtocolVersion};
+use casper_types::{EraId, ProtocolVersion};
 
 #[cfg(test)]
 use crate::utils::RESOURCES_PATH;
@@ -482,44 +479,10 @@ impl ChainspecLoader {
         block: &Block,
         previous_version: ProtocolVersion,
     ) -> Box<UpgradeConfig> {
-        let new_version = self.chainspec.protocol_config.version;
-        let global_state_update = self
-            .chainspec
-            .protocol_config
-            .global_state_update
-            .as_ref()
-            .map(|state_update| {
-                state_update
-                    .0
-                    .iter()
-                    .map(|(key, stored_value_bytes)| {
-                        let stored_value = StoredValue::from_bytes(stored_value_bytes)
-                            .unwrap_or_else(|error| {
-                                panic!(
-                                "failed to parse global state value as StoredValue for upgrade: {}",
-                                error
-                            )
-                            })
-                            .0;
-                        (*key, stored_value)
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
-        Box::new(UpgradeConfig::new(
-            (*block.state_root_hash()).into(),
-            previous_version,
-            new_version,
-            Some(self.chainspec.wasm_config),
-            Some(self.chainspec.system_costs_config),
-            Some(self.chainspec.protocol_config.activation_point.era_id()),
-            Some(self.chainspec.core_config.validator_slots),
-            Some(self.chainspec.core_config.auction_delay),
-            Some(self.chainspec.core_config.locked_funds_period.millis()),
-            Some(self.chainspec.core_config.round_seigniorage_rate),
-            Some(self.chainspec.core_config.unbonding_delay),
-            global_state_update,
-        ))
+        Box::new(
+            self.chainspec
+                .upgrade_config(*block.state_root_hash(), previous_version),
+        )
     }
 
     fn handle_commit_genesis_result(
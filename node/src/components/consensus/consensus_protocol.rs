@@ -226,6 +226,13 @@ pub(crate) trait ConsensusProtocol<I, C: Context>: Send {
     /// Turns this instance into a passive observer, that does not create any new vertices.
     fn deactivate_validator(&mut self);
 
+    /// Restores the protocol state from the write-ahead log in `wal_file`, if it exists, and
+    /// appends everything that is added to the protocol state from now on to it.
+    fn open_wal(&mut self, wal_file: PathBuf, now: Timestamp) -> ProtocolOutcomes<I, C>;
+
+    /// Stops writing to the write-ahead log and deletes it.
+    fn remove_wal(&mut self);
+
     /// Clears this instance and keeps only the information necessary to validate evidence.
     fn set_evidence_only(&mut self);
 
@@ -32,17 +32,20 @@ use tracing::{debug, error, info, trace, warn};
 use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, U512};
 
 use crate::{
-    components::consensus::{
-        cl_context::{ClContext, Keypair},
-        config::ProtocolConfig,
-        consensus_protocol::{
-            ConsensusProtocol, EraReport, FinalizedBlock as CpFinalizedBlock, ProposedBlock,
-            ProtocolOutcome, ProtocolOutcomes,
+    components::{
+        consensus::{
+            cl_context::{ClContext, Keypair},
+            config::ProtocolConfig,
+            consensus_protocol::{
+                ConsensusProtocol, EraReport, FinalizedBlock as CpFinalizedBlock, ProposedBlock,
+                ProtocolOutcome, ProtocolOutcomes,
+            },
+            metrics::ConsensusMetrics,
+            traits::NodeIdT,
+            ActionId, Config, ConsensusMessage, Event, NewBlockPayload, ReactorEventT,
+            ResolveValidity, TimerId,
         },
-        metrics::ConsensusMetrics,
-        traits::NodeIdT,
-        ActionId, Config, ConsensusMessage, Event, NewBlockPayload, ReactorEventT, ResolveValidity,
-        TimerId,
+        small_network::Offense,
     },
     crypto::hash::Digest,
     effect::{
@@ -107,7 +110,7 @@ pub struct EraSupervisor<I> {
     next_executed_height: u64,
     #[data_size(skip)]
     metrics: ConsensusMetrics,
-    /// The path to the folder where unit hash files will be stored.
+    /// The path to the folder where unit hash files and write-ahead logs will be stored.
     unit_hashes_folder: PathBuf,
     /// The next upgrade activation point. When the era immediately before the activation point is
     /// deactivated, the era supervisor indicates that the node should stop running to allow an
@@ -347,6 +350,10 @@ where
             now,
         );
 
+        // Restore the protocol state we had before a restart, if any, before activating the
+        // validator, so that it continues from its last unit instead of waiting to see it again.
+        outcomes.extend(consensus.open_wal(self.wal_file(&instance_id), now));
+
         if should_activate {
             let secret = Keypair::new(self.secret_signing_key.clone(), our_id.clone());
             outcomes.extend(consensus.activate_validator(
@@ -397,8 +404,9 @@ where
         // units that refer to evidence from any era that was bonded when it was the current one.
         let oldest_evidence_era_id = oldest_bonded_era(&self.protocol_config, oldest_bonded_era_id);
         if let Some(obsolete_era_id) = oldest_evidence_era_id.checked_sub(1) {
-            if let Some(era) = self.active_eras.remove(&obsolete_era_id) {
+            if let Some(mut era) = self.active_eras.remove(&obsolete_era_id) {
                 trace!(era = obsolete_era_id.value(), "removing obsolete era");
+                era.consensus.remove_wal();
                 match fs::remove_file(self.unit_hash_file(era.consensus.instance_id())) {
                     Ok(_) => {}
                     Err(err) => match err.kind() {
@@ -562,6 +570,12 @@ where
         self.bonded_eras().min(self.protocol_config.auction_delay)
     }
 
+    /// Returns the path to the era's write-ahead log of protocol state vertices.
+    fn wal_file(&self, instance_id: &Digest) -> PathBuf {
+        self.unit_hashes_folder
+            .join(format!("highway_wal_{:?}.dat", instance_id))
+    }
+
     /// Returns the path to the era's unit hash file.
     fn unit_hash_file(&self, instance_id: &Digest) -> PathBuf {
         self.unit_hashes_folder.join(format!(
@@ -833,6 +847,8 @@ where
         if faulty_num == old_faulty_num {
             info!(era = era_id.value(), "stop voting in era");
             era.consensus.deactivate_validator();
+            // The era is over, so there's no need to restore its protocol state after a restart.
+            era.consensus.remove_wal();
             if self.should_upgrade_after(&era_id) {
                 // If the next era is at or after the upgrade activation point, stop the node.
                 info!(era = era_id.value(), "shutting down for upgrade");
@@ -1258,7 +1274,7 @@ where
 
     fn disconnect(&self, sender: I) -> Effects<Event<I>> {
         self.effect_builder
-            .announce_disconnect_from_peer(sender)
+            .announce_offense(sender, Offense::InvalidConsensusMessage)
             .ignore()
     }
 
@@ -3,6 +3,7 @@ mod participation;
 mod round_success_meter;
 #[cfg(test)]
 mod tests;
+mod wal;
 
 use std::{
     any::Any,
@@ -45,7 +46,7 @@ use crate::{
 };
 
 pub use self::config::Config as HighwayConfig;
-use self::round_success_meter::RoundSuccessMeter;
+use self::{round_success_meter::RoundSuccessMeter, wal::Wal};
 
 /// Never allow more than this many units in a piece of evidence for conflicting endorsements,
 /// even if eras are longer than this.
@@ -97,6 +98,10 @@ where
     log_participation_interval: TimeDiff,
     /// Whether to log the size of every incoming and outgoing serialized unit.
     log_unit_sizes: bool,
+    /// The write-ahead log to which every vertex other than pings is appended when it is added to
+    /// the protocol state.
+    #[data_size(skip)]
+    wal: Option<Wal>,
 }
 
 impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
@@ -215,6 +220,7 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
             shutdown_on_standstill: config.highway.shutdown_on_standstill,
             log_participation_interval: config.highway.log_participation_interval,
             log_unit_sizes: config.highway.log_unit_sizes,
+            wal: None,
         });
 
         (hw_proto, outcomes)
@@ -255,6 +261,8 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
     fn process_av_effect(&mut self, effect: AvEffect<C>, now: Timestamp) -> ProtocolOutcomes<I, C> {
         match effect {
             AvEffect::NewVertex(vv) => {
+                // Our own vertices have already been added to the protocol state.
+                self.write_to_wal(vv.inner());
                 self.log_unit_size(vv.inner(), "sending new unit");
                 self.calculate_round_exponent(&vv, now);
                 self.process_new_vertex(vv)
@@ -411,6 +419,7 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
         if self.highway.has_vertex(vv.inner()) {
             return vec![];
         }
+        self.write_to_wal(vv.inner());
         self.log_unit_size(vv.inner(), "adding new unit to the protocol state");
         self.log_proposal(vv.inner(), "adding valid proposal to the protocol state");
         let vertex_id = vv.inner().id();
@@ -425,6 +434,71 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
         self.process_av_effects(av_effects, now)
     }
 
+    /// Appends the vertex to the write-ahead log, if there is one. Pings are not persisted, since
+    /// they are not needed to restore the protocol state.
+    fn write_to_wal(&mut self, vertex: &Vertex<C>) {
+        if matches!(vertex, Vertex::Ping(_)) {
+            return;
+        }
+        if let Some(wal) = &mut self.wal {
+            if let Err(error) = wal.append(vertex) {
+                error!(%error, "failed to write to write-ahead log; no longer persisting vertices");
+                self.wal = None;
+            }
+        }
+    }
+
+    /// Adds the vertices read from the write-ahead log to the protocol state.
+    ///
+    /// They have been validated before they were written, so only the checks performed by
+    /// `Highway` are repeated, and consensus values are not validated again. Since the vertices
+    /// were logged in the order in which they were added, their dependencies are always satisfied;
+    /// if they aren't, or a vertex is invalid, the rest of the log is skipped.
+    fn replay_vertices(
+        &mut self,
+        vertices: Vec<Vertex<C>>,
+        now: Timestamp,
+    ) -> ProtocolOutcomes<I, C> {
+        let mut outcomes = vec![];
+        let mut replayed = 0;
+        for vertex in vertices {
+            if self.highway.has_vertex(&vertex) {
+                continue;
+            }
+            let pvv = match self.highway.pre_validate_vertex(vertex) {
+                Ok(pvv) => pvv,
+                Err((vertex, err)) => {
+                    warn!(?vertex, ?err, "invalid vertex in write-ahead log");
+                    break;
+                }
+            };
+            if let Some(dependency) = self.highway.missing_dependency(&pvv) {
+                warn!(
+                    ?pvv,
+                    ?dependency,
+                    "missing dependency of vertex in write-ahead log"
+                );
+                break;
+            }
+            let vv = match self.highway.validate_vertex(pvv) {
+                Ok(vv) => vv,
+                Err((pvv, err)) => {
+                    warn!(?pvv, ?err, "invalid vertex in write-ahead log");
+                    break;
+                }
+            };
+            outcomes.extend(self.add_valid_vertex(vv, now));
+            replayed += 1;
+        }
+        info!(
+            %replayed,
+            instance_id = ?self.highway.instance_id(),
+            "replayed vertices from write-ahead log"
+        );
+        outcomes.extend(self.detect_finality());
+        outcomes
+    }
+
     /// Returns an instance of `RoundSuccessMeter` for the new era: resetting the counters where
     /// appropriate.
     fn next_era_round_succ_meter(&self, timestamp: Timestamp) -> RoundSuccessMeter<C> {
@@ -888,9 +962,38 @@ where
         self.highway.deactivate_validator()
     }
 
+    fn open_wal(&mut self, wal_file: PathBuf, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        if self.wal.is_some() {
+            error!("open_wal called twice");
+            return vec![];
+        }
+        let (wal, vertices) = match Wal::open(wal_file) {
+            Ok(result) => result,
+            Err(error) => {
+                error!(%error, "failed to open write-ahead log; not persisting vertices");
+                return vec![];
+            }
+        };
+        // Only start writing once the logged vertices are back in the protocol state, so that they
+        // don't get logged twice.
+        let outcomes = self.replay_vertices(vertices, now);
+        self.wal = Some(wal);
+        outcomes
+    }
+
+    fn remove_wal(&mut self) {
+        if let Some(wal) = self.wal.take() {
+            if let Err(error) = wal.remove() {
+                warn!(%error, "could not delete write-ahead log");
+            }
+        }
+    }
+
     fn set_evidence_only(&mut self) {
         // TODO: We could also drop the finality detector and round success meter here. Maybe make
         // HighwayProtocol an enum with an EvidenceOnly variant?
+        // The protocol state is dropped, so the log can't be used to restore it anymore.
+        self.remove_wal();
         self.pending_values.clear();
         self.synchronizer.retain_evidence_only();
         self.highway.retain_evidence_only();
@@ -12,7 +12,8 @@ use super::round_success_meter::config::Config as RSMConfig;
 /// NOTE: This is *NOT* protocol configuration that has to be the same on all nodes.
 #[derive(DataSize, Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// Path to the folder where unit hash files will be stored.
+    /// Path to the folder where unit hash files and the write-ahead logs of the eras' protocol
+    /// states will be stored.
     pub unit_hashes_folder: PathBuf,
     /// The duration for which incoming vertices with missing dependencies are kept in a queue.
     pub pending_vertex_timeout: TimeDiff,
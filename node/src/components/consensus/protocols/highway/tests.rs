@@ -296,3 +296,59 @@ fn detect_doppelganger() {
     }
     panic!("failed to return DoppelgangerDetected effect");
 }
+
+#[test]
+fn restore_protocol_state_from_wal() {
+    let creator: ValidatorIndex = ValidatorIndex(0);
+    let validators = vec![(ALICE_PUBLIC_KEY.clone(), 100)];
+    let state: State<ClContext> = new_test_state(validators.iter().map(|(_pk, w)| *w), 0);
+    let panorama: Panorama<ClContext> = Panorama::from(vec![N]);
+    let seq_number = panorama.next_seq_num(&state, creator);
+    let now = Timestamp::zero();
+    let wunit: WireUnit<ClContext> = WireUnit {
+        panorama,
+        creator,
+        instance_id: ClContext::hash(INSTANCE_ID_DATA),
+        value: Some(Arc::new(BlockPayload::new(vec![], vec![], vec![], false))),
+        seq_number,
+        timestamp: now,
+        round_exp: 14,
+        endorsed: BTreeSet::new(),
+    };
+    let alice_keypair: Keypair = Keypair::from(Arc::clone(&*ALICE_SECRET_KEY));
+    let vertex = Vertex::Unit(SignedWireUnit::new(wunit.into_hashed(), &alice_keypair));
+    let msg = bincode::serialize(&HighwayMessage::NewVertex(vertex.clone())).unwrap();
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let wal_file = tmp_dir.path().join("highway_wal.dat");
+    let has_vertex = |highway_protocol: &dyn ConsensusProtocol<NodeId, ClContext>| {
+        highway_protocol
+            .as_any()
+            .downcast_ref::<HighwayProtocol<NodeId, ClContext>>()
+            .unwrap()
+            .highway
+            .has_vertex(&vertex)
+    };
+
+    // Add the unit to a protocol state which persists its vertices.
+    let mut highway_protocol = new_test_highway_protocol(validators.clone(), vec![]);
+    assert!(highway_protocol.open_wal(wal_file.clone(), now).is_empty());
+    let mut outcomes = highway_protocol.handle_message(NodeId(123), msg, now);
+    while let Some(outcome) = outcomes.pop() {
+        if let ProtocolOutcome::QueueAction(ACTION_ID_VERTEX) = outcome {
+            outcomes.extend(highway_protocol.handle_action(ACTION_ID_VERTEX, now))
+        }
+    }
+    assert!(has_vertex(&*highway_protocol));
+    drop(highway_protocol);
+
+    // After a restart, the unit is restored from the log without receiving it again.
+    let mut highway_protocol = new_test_highway_protocol(validators, vec![]);
+    assert!(!has_vertex(&*highway_protocol));
+    let _ = highway_protocol.open_wal(wal_file.clone(), now);
+    assert!(has_vertex(&*highway_protocol));
+
+    // Once the log is removed, the state can't be restored anymore.
+    highway_protocol.remove_wal();
+    assert!(!wal_file.exists());
+}
@@ -0,0 +1,147 @@
+//! A write-ahead log of the vertices added to a Highway instance's protocol state.
+//!
+//! Each entry is a vertex serialized with `bincode`, prefixed with its length as a little-endian
+//! `u32`. Since the vertices are appended in the order in which they were added to the protocol
+//! state, every vertex's dependencies precede it in the log, and replaying the log in order
+//! restores the protocol state.
+
+use std::{
+    convert::TryFrom,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::warn;
+
+/// The size of the length prefix of each entry.
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// An append-only file of serialized vertices.
+#[derive(Debug)]
+pub(crate) struct Wal {
+    file: File,
+    path: PathBuf,
+}
+
+impl Wal {
+    /// Opens the log at `path`, creating it if it doesn't exist yet, and returns it together with
+    /// the entries it already contains.
+    ///
+    /// An incomplete or undecodable entry at the end of the file, e.g. due to a crash while it was
+    /// written, is discarded together with everything after it.
+    #[allow(clippy::integer_arithmetic)] // All offsets are bounded by the file's length.
+    pub(crate) fn open<T: DeserializeOwned>(path: PathBuf) -> io::Result<(Self, Vec<T>)> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        let mut entries = Vec::new();
+        let mut valid_len = 0;
+        let mut remainder = bytes.as_slice();
+        while remainder.len() >= LENGTH_PREFIX_SIZE {
+            let (len_bytes, rest) = remainder.split_at(LENGTH_PREFIX_SIZE);
+            let len = u32::from_le_bytes(
+                <[u8; LENGTH_PREFIX_SIZE]>::try_from(len_bytes).expect("should be 4 bytes"),
+            ) as usize;
+            if rest.len() < len {
+                break;
+            }
+            let (entry_bytes, rest) = rest.split_at(len);
+            match bincode::deserialize(entry_bytes) {
+                Ok(entry) => entries.push(entry),
+                Err(error) => {
+                    warn!(%error, path = %path.display(), "invalid entry in write-ahead log");
+                    break;
+                }
+            }
+            valid_len += LENGTH_PREFIX_SIZE + len;
+            remainder = rest;
+        }
+        if valid_len < bytes.len() {
+            warn!(
+                path = %path.display(),
+                discarded_bytes = bytes.len() - valid_len,
+                "truncating write-ahead log"
+            );
+            file.set_len(valid_len as u64)?;
+        }
+        // New entries are written after the last valid one.
+        let file = OpenOptions::new().append(true).open(&path)?;
+        Ok((Wal { file, path }, entries))
+    }
+
+    /// Appends an entry to the log, returning once it has been flushed to disk.
+    ///
+    /// The entry must be durable before the vertex is gossiped: otherwise a validator restarting
+    /// after a crash could create a unit conflicting with one it has already sent, i.e. equivocate.
+    pub(crate) fn append<T: Serialize>(&mut self, entry: &T) -> io::Result<()> {
+        let entry_bytes = bincode::serialize(entry)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let len = u32::try_from(entry_bytes.len())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let mut bytes = len.to_le_bytes().to_vec();
+        bytes.extend(entry_bytes);
+        self.file.write_all(&bytes)?;
+        self.file.sync_data()
+    }
+
+    /// Closes and deletes the log.
+    pub(crate) fn remove(self) -> io::Result<()> {
+        let Wal { file, path } = self;
+        drop(file);
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_replay_entries() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("wal.dat");
+
+        let (mut wal, entries) = Wal::open::<String>(path.clone()).unwrap();
+        assert!(entries.is_empty());
+        wal.append(&"first".to_string()).unwrap();
+        wal.append(&"second".to_string()).unwrap();
+        drop(wal);
+
+        let (mut wal, entries) = Wal::open::<String>(path.clone()).unwrap();
+        assert_eq!(entries, vec!["first".to_string(), "second".to_string()]);
+        wal.append(&"third".to_string()).unwrap();
+        drop(wal);
+
+        let (wal, entries) = Wal::open::<String>(path.clone()).unwrap();
+        assert_eq!(entries.len(), 3);
+        wal.remove().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn should_discard_incomplete_entry() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("wal.dat");
+
+        let (mut wal, _) = Wal::open::<String>(path.clone()).unwrap();
+        wal.append(&"complete".to_string()).unwrap();
+        // Simulate a crash in the middle of writing an entry.
+        wal.file.write_all(&[100, 0, 0, 0, 1, 2]).unwrap();
+        drop(wal);
+
+        let (mut wal, entries) = Wal::open::<String>(path.clone()).unwrap();
+        assert_eq!(entries, vec!["complete".to_string()]);
+        wal.append(&"next".to_string()).unwrap();
+        drop(wal);
+
+        let (_, entries) = Wal::open::<String>(path).unwrap();
+        assert_eq!(entries, vec!["complete".to_string(), "next".to_string()]);
+    }
+}
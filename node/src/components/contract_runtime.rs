@@ -1,6 +1,8 @@
 //! Contract Runtime component.
 mod config;
 mod operations;
+#[cfg(test)]
+mod tests;
 mod types;
 
 use std::{
@@ -10,17 +12,17 @@ use std::{
     time::Instant,
 };
 
-pub use config::Config;
+pub use config::{Config, PruningMode};
 use smallvec::SmallVec;
 
-pub use types::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest};
+pub use types::{EraValidatorsRequest, SpeculativeExecutionState, ValidatorWeightsByEraIdRequest};
 
 use datasize::DataSize;
 use derive_more::From;
 use lmdb::DatabaseFlags;
-use prometheus::{self, Histogram, HistogramOpts, IntGauge, Registry};
+use prometheus::{self, Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
 use thiserror::Error;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use casper_execution_engine::{
     core::engine_state::{
@@ -36,7 +38,8 @@ use casper_execution_engine::{
     },
 };
 use casper_types::{
-    system::auction::ValidatorWeights, ExecutionResult, ProtocolVersion, PublicKey, U512,
+    system::auction::ValidatorWeights, CallTrace, ContractEvent, ExecutionResult, ProtocolVersion,
+    PublicKey, U512,
 };
 
 use crate::{
@@ -143,6 +146,15 @@ pub struct ContractRuntime {
 
     protocol_version: ProtocolVersion,
 
+    /// How much historical global state to retain.
+    pruning_mode: PruningMode,
+
+    /// Whether to execute the deploys of a block concurrently.
+    enable_parallel_execution: bool,
+
+    /// The maximum number of deploys to execute at once when executing concurrently.
+    max_parallel_executions: usize,
+
     /// A mapping from block height to executed block's ID and post-state hash, to allow
     /// identification of a parent block's details once a finalized block has been executed.
     ///
@@ -176,6 +188,11 @@ pub struct ContractRuntimeMetrics {
     missing_trie_keys: Histogram,
     put_trie: Histogram,
     read_trie: Histogram,
+    speculative_execute: Histogram,
+    prune_global_state: Histogram,
+    wasm_module_cache_hits: IntCounter,
+    wasm_module_cache_misses: IntCounter,
+    reexecuted_deploys: IntCounter,
     /// The current chain height.
     pub chain_height: IntGauge,
 }
@@ -221,6 +238,20 @@ const PUT_TRIE_NAME: &str = "contract_runtime_put_trie";
 const PUT_TRIE_HELP: &str = "tracking run of engine_state.put_trie in seconds.";
 const MISSING_TRIE_KEYS_NAME: &str = "contract_runtime_missing_trie_keys";
 const MISSING_TRIE_KEYS_HELP: &str = "tracking run of engine_state.missing_trie_keys in seconds.";
+const SPECULATIVE_EXECUTE_NAME: &str = "contract_runtime_speculative_execute";
+const SPECULATIVE_EXECUTE_HELP: &str =
+    "tracking run of engine_state.run_execute for speculative deploy execution in seconds.";
+const PRUNE_GLOBAL_STATE_NAME: &str = "contract_runtime_prune_global_state";
+const PRUNE_GLOBAL_STATE_HELP: &str = "tracking run of engine_state.prune in seconds.";
+const WASM_MODULE_CACHE_HITS_NAME: &str = "contract_runtime_wasm_module_cache_hits";
+const WASM_MODULE_CACHE_HITS_HELP: &str =
+    "number of stored contract modules found in the wasm module cache.";
+const WASM_MODULE_CACHE_MISSES_NAME: &str = "contract_runtime_wasm_module_cache_misses";
+const WASM_MODULE_CACHE_MISSES_HELP: &str =
+    "number of stored contract modules not found in the wasm module cache.";
+const REEXECUTED_DEPLOYS_NAME: &str = "contract_runtime_reexecuted_deploys";
+const REEXECUTED_DEPLOYS_HELP: &str =
+    "number of deploys executed in parallel which were re-executed due to conflicts.";
 
 /// Create prometheus Histogram and register.
 fn register_histogram_metric(
@@ -244,8 +275,19 @@ impl ContractRuntimeMetrics {
     fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let chain_height = IntGauge::new("chain_height", "current chain height")?;
         registry.register(Box::new(chain_height.clone()))?;
+        let wasm_module_cache_hits =
+            IntCounter::new(WASM_MODULE_CACHE_HITS_NAME, WASM_MODULE_CACHE_HITS_HELP)?;
+        registry.register(Box::new(wasm_module_cache_hits.clone()))?;
+        let wasm_module_cache_misses =
+            IntCounter::new(WASM_MODULE_CACHE_MISSES_NAME, WASM_MODULE_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(wasm_module_cache_misses.clone()))?;
+        let reexecuted_deploys = IntCounter::new(REEXECUTED_DEPLOYS_NAME, REEXECUTED_DEPLOYS_HELP)?;
+        registry.register(Box::new(reexecuted_deploys.clone()))?;
         Ok(ContractRuntimeMetrics {
             chain_height,
+            wasm_module_cache_hits,
+            wasm_module_cache_misses,
+            reexecuted_deploys,
             run_execute: register_histogram_metric(registry, RUN_EXECUTE_NAME, RUN_EXECUTE_HELP)?,
             apply_effect: register_histogram_metric(
                 registry,
@@ -283,6 +325,16 @@ impl ContractRuntimeMetrics {
                 MISSING_TRIE_KEYS_NAME,
                 MISSING_TRIE_KEYS_HELP,
             )?,
+            speculative_execute: register_histogram_metric(
+                registry,
+                SPECULATIVE_EXECUTE_NAME,
+                SPECULATIVE_EXECUTE_HELP,
+            )?,
+            prune_global_state: register_histogram_metric(
+                registry,
+                PRUNE_GLOBAL_STATE_NAME,
+                PRUNE_GLOBAL_STATE_HELP,
+            )?,
         })
     }
 }
@@ -566,6 +618,47 @@ where
                         }
                         .ignore()
                     }
+                    ContractRuntimeRequest::SpeculativeDeployExecution {
+                        execution_prestate,
+                        deploy,
+                        responder,
+                    } => {
+                        trace!(?execution_prestate, "speculative execution request");
+                        let engine_state = Arc::clone(&self.engine_state);
+                        let metrics = Arc::clone(&self.metrics);
+                        async move {
+                            let execute_request = ExecuteRequest::new(
+                                execution_prestate.state_root_hash.into(),
+                                execution_prestate.block_time.millis(),
+                                vec![DeployItem::from(*deploy)],
+                                execution_prestate.protocol_version,
+                                execution_prestate.proposer,
+                            );
+                            // Executing the deploy may take a while, so keep it off the async
+                            // worker threads.
+                            let result = tokio::task::spawn_blocking(move || {
+                                let correlation_id = CorrelationId::new();
+                                let start = Instant::now();
+                                let result = engine_state
+                                    .run_execute(correlation_id, execute_request)
+                                    .map(|execution_results| {
+                                        execution_results.front().map(ExecutionResult::from)
+                                    });
+                                metrics
+                                    .speculative_execute
+                                    .observe(start.elapsed().as_secs_f64());
+                                result
+                            })
+                            .await
+                            .unwrap_or_else(|error| {
+                                warn!(%error, "failed to join tokio task");
+                                Ok(None)
+                            });
+                            trace!(?result, "speculative execution result");
+                            responder.respond(result).await
+                        }
+                        .ignore()
+                    }
                 }
             }
             Event::BlockAlreadyExists(block) => effect_builder
@@ -618,6 +711,7 @@ where
                             let mut effects = effect_builder
                                 .announce_step_success(era_id, execution_effect)
                                 .ignore();
+                            effects.extend(self.prune_global_state(effect_builder, &state));
                             effects.extend(self.finalize_block_execution(
                                 effect_builder,
                                 state,
@@ -651,6 +745,40 @@ pub enum ConfigError {
     Prometheus(#[from] prometheus::Error),
 }
 
+/// Opens the global state store located in the configured storage directory.
+pub(crate) fn open_engine_state(
+    storage_config: &WithDir<StorageConfig>,
+    contract_runtime_config: &Config,
+) -> Result<EngineState<LmdbGlobalState>, ConfigError> {
+    let path = storage_config.with_dir(storage_config.value().path.clone());
+    let environment = Arc::new(LmdbEnvironment::new(
+        path.as_path(),
+        contract_runtime_config.max_global_state_size(),
+        contract_runtime_config.max_readers(),
+    )?);
+
+    let trie_store = Arc::new(LmdbTrieStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+
+    let protocol_data_store = Arc::new(LmdbProtocolDataStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+
+    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)?;
+    let engine_config = EngineConfig::new(
+        contract_runtime_config.max_query_depth(),
+        contract_runtime_config.enable_call_tracing(),
+        contract_runtime_config.wasm_module_cache_size(),
+    );
+
+    Ok(EngineState::new(global_state, engine_config))
+}
+
 impl ContractRuntime {
     pub(crate) fn new(
         initial_state_root_hash: Digest,
@@ -661,34 +789,15 @@ impl ContractRuntime {
         registry: &Registry,
     ) -> Result<Self, ConfigError> {
         let initial_state = InitialState::new(initial_state_root_hash, initial_block_header);
-        let path = storage_config.with_dir(storage_config.value().path.clone());
-        let environment = Arc::new(LmdbEnvironment::new(
-            path.as_path(),
-            contract_runtime_config.max_global_state_size(),
-            contract_runtime_config.max_readers(),
-        )?);
-
-        let trie_store = Arc::new(LmdbTrieStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let protocol_data_store = Arc::new(LmdbProtocolDataStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)?;
-        let engine_config = EngineConfig::new(contract_runtime_config.max_query_depth());
-
-        let engine_state = Arc::new(EngineState::new(global_state, engine_config));
+        let engine_state = Arc::new(open_engine_state(&storage_config, contract_runtime_config)?);
 
         let metrics = Arc::new(ContractRuntimeMetrics::new(registry)?);
         Ok(ContractRuntime {
             initial_state,
             protocol_version,
+            pruning_mode: contract_runtime_config.pruning_mode(),
+            enable_parallel_execution: contract_runtime_config.enable_parallel_execution(),
+            max_parallel_executions: contract_runtime_config.max_parallel_executions(),
             parent_map: HashMap::new(),
             exec_queue: HashMap::new(),
             engine_state,
@@ -825,9 +934,19 @@ impl ContractRuntime {
             next_era_validator_weights,
         );
 
-        let mut effects = effect_builder
-            .announce_linear_chain_block(block, state.execution_results)
-            .ignore();
+        let mut effects = Effects::new();
+        if !state.call_traces.is_empty() {
+            effects.extend(
+                effect_builder
+                    .put_call_traces_to_storage(*block.hash(), state.call_traces)
+                    .ignore(),
+            );
+        }
+        effects.extend(
+            effect_builder
+                .announce_linear_chain_block(block, state.execution_results, state.contract_events)
+                .ignore(),
+        );
         // If the child is already finalized, start execution.
         if let Some((finalized_block, deploys)) = self.exec_queue.remove(&next_height) {
             effects.extend(self.handle_get_deploys_result(
@@ -839,6 +958,53 @@ impl ContractRuntime {
         effects
     }
 
+    /// Prunes global state in the background according to the configured pruning mode, retaining
+    /// the state of the switch block being finalized.
+    ///
+    /// Blocks executed while pruning is in progress are unaffected: the global state records the
+    /// roots committed from here on and retains them.  If a previous pruning is still in progress,
+    /// this one is skipped.
+    fn prune_global_state<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        state: &RequestState,
+    ) -> Effects<Event> {
+        if self.pruning_mode == PruningMode::Archival {
+            return Effects::new();
+        }
+        match self.engine_state.begin_prune() {
+            Ok(true) => (),
+            Ok(false) => {
+                info!("previous pruning of global state still in progress, skipping");
+                return Effects::new();
+            }
+            Err(error) => {
+                error!(?error, "failed to begin pruning global state");
+                return Effects::new();
+            }
+        }
+
+        let engine_state = Arc::clone(&self.engine_state);
+        let metrics = Arc::clone(&self.metrics);
+        let pruning_mode = self.pruning_mode;
+        let era_id = state.finalized_block.era_id();
+        let height = state.finalized_block.height();
+        let mut state_root_hashes = vec![state.state_root_hash, self.initial_state.state_root_hash];
+        async move {
+            state_root_hashes.extend(
+                operations::retained_state_root_hashes(
+                    effect_builder,
+                    pruning_mode,
+                    era_id,
+                    height,
+                )
+                .await,
+            );
+            operations::prune(engine_state, metrics, state_root_hashes).await;
+        }
+        .ignore()
+    }
+
     fn execute_all_deploys_or_finalize_block_or_step<REv: ReactorEventT>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -902,52 +1068,69 @@ impl ContractRuntime {
         let protocol_version = self.protocol_version;
         let block_time = state.finalized_block.timestamp().millis();
         let proposer = state.finalized_block.proposer();
+        let enable_parallel_execution = self.enable_parallel_execution;
+        let max_parallel_executions = self.max_parallel_executions;
         async move {
-            for deploy in state.remaining_deploys.drain(..) {
-                let deploy_hash = *deploy.id();
-                let deploy_header = deploy.header().clone();
-                let deploy_item = DeployItem::from(deploy);
+            let pre_state_hash = state.state_root_hash;
+            let (deploy_headers, deploys): (Vec<_>, Vec<_>) = state
+                .remaining_deploys
+                .drain(..)
+                .map(|deploy| {
+                    let deploy_hash = *deploy.id();
+                    (
+                        (deploy_hash, deploy.header().clone()),
+                        (deploy_hash, DeployItem::from(deploy)),
+                    )
+                })
+                .unzip();
 
-                let execute_request = ExecuteRequest::new(
-                    state.state_root_hash.into(),
+            // When executing in parallel, every deploy is first executed against the pre-state of
+            // the block.
+            let parallel_results = if enable_parallel_execution {
+                operations::execute_concurrently(
+                    engine_state.clone(),
+                    metrics.clone(),
+                    pre_state_hash,
                     block_time,
-                    vec![deploy_item],
+                    deploys
+                        .iter()
+                        .map(|(_, deploy_item)| deploy_item.clone())
+                        .collect(),
                     protocol_version,
                     proposer.clone(),
-                );
-
-                // TODO: this is currently working coincidentally because we are passing only one
-                // deploy_item per exec. The execution results coming back from the ee lacks the
-                // mapping between deploy_hash and execution result, and this outer logic is
-                // enriching it with the deploy hash. If we were passing multiple deploys per exec
-                // the relation between the deploy and the execution results would be lost.
-                let result =
-                    operations::execute(engine_state.clone(), metrics.clone(), execute_request)
-                        .await;
-
-                trace!(%deploy_hash, ?result, "deploy execution result");
-                // As for now a given state is expected to exist.
-                let execution_results = result.unwrap();
-                match operations::commit_execution_effects(
-                    engine_state.clone(),
-                    metrics.clone(),
-                    state.state_root_hash,
-                    deploy_hash,
-                    execution_results,
+                    max_parallel_executions,
                 )
                 .await
-                {
-                    Ok((state_hash, execution_result)) => {
-                        state
-                            .execution_results
-                            .insert(deploy_hash, (deploy_header, execution_result));
-                        state.state_root_hash = state_hash;
-                    }
-                    // When commit fails we panic as we'll not be able to execute the next
-                    // block.
-                    Err(_err) => panic!("unable to commit"),
+            } else {
+                Vec::new()
+            };
+
+            let (state_root_hash, results) = operations::execute_deploys(
+                engine_state,
+                metrics,
+                pre_state_hash,
+                block_time,
+                deploys,
+                parallel_results,
+                protocol_version,
+                proposer,
+            )
+            .await;
+
+            for ((deploy_hash, deploy_header), (execution_result, call_trace, contract_events)) in
+                deploy_headers.into_iter().zip(results)
+            {
+                state
+                    .execution_results
+                    .insert(deploy_hash, (deploy_header, execution_result));
+                if !call_trace.is_empty() {
+                    state.call_traces.insert(deploy_hash, call_trace);
+                }
+                if !contract_events.is_empty() {
+                    state.contract_events.insert(deploy_hash, contract_events);
                 }
             }
+            state.state_root_hash = state_root_hash;
             state
         }
         .event(|state| Event::Result(Box::new(ContractRuntimeResult::ExecutedAndCommitted(state))))
@@ -964,6 +1147,8 @@ impl ContractRuntime {
                 finalized_block,
                 remaining_deploys: deploys,
                 execution_results: HashMap::new(),
+                call_traces: HashMap::new(),
+                contract_events: HashMap::new(),
                 state_root_hash,
             });
             self.execute_all_deploys_or_finalize_block_or_step(effect_builder, state)
@@ -1006,6 +1191,8 @@ impl ContractRuntime {
                         finalized_block,
                         remaining_deploys: deploys,
                         execution_results: HashMap::new(),
+                        call_traces: HashMap::new(),
+                        contract_events: HashMap::new(),
                         state_root_hash,
                     });
                     self.execute_all_deploys_or_finalize_block_or_step(effect_builder, state)
@@ -1102,6 +1289,10 @@ pub struct RequestState {
     pub remaining_deploys: VecDeque<Deploy>,
     /// A collection of results of executing the deploys.
     pub execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+    /// The call traces recorded while executing the deploys, if call tracing is enabled.
+    pub call_traces: HashMap<DeployHash, CallTrace>,
+    /// The events emitted by contracts while executing the deploys.  These are not persisted.
+    pub contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
     /// Current state root hash of global storage.  Is initialized with the parent block's
     /// state hash, and is updated after each commit.
     pub state_root_hash: Digest,
@@ -1,12 +1,36 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::shared::utils;
+use casper_execution_engine::{
+    core::engine_state::wasm_module_cache::DEFAULT_WASM_MODULE_CACHE_SIZE, shared::utils,
+};
 
 const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 
+/// How much historical global state is retained by the contract runtime.
+///
+/// Pruning happens at the end of every era.  Queries against a pruned state root fail with a
+/// "root not found" error.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningMode {
+    /// The global state of every block is retained.
+    Archival,
+    /// Only the global state of the blocks in the given number of most recent eras is retained,
+    /// along with that of the switch block preceding them.
+    KeepLastEras(u64),
+    /// Only the global state of switch blocks and of the latest block is retained.
+    SwitchBlocks,
+}
+
+impl Default for PruningMode {
+    fn default() -> Self {
+        PruningMode::Archival
+    }
+}
+
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -26,6 +50,29 @@ pub struct Config {
     ///
     /// Defaults to 5.
     max_query_depth: Option<u64>,
+    /// How much historical global state to retain.
+    ///
+    /// Defaults to `archival`.
+    pruning_mode: Option<PruningMode>,
+    /// Whether to record the contract calls made by executed deploys.
+    ///
+    /// Defaults to `false`.
+    enable_call_tracing: Option<bool>,
+    /// The maximum number of stored contract modules to keep in memory, saving them from being
+    /// deserialized on every call.  0 disables the cache.
+    ///
+    /// Defaults to 256.
+    wasm_module_cache_size: Option<usize>,
+    /// Whether to execute the deploys of a block concurrently, re-executing those which turn out
+    /// to conflict with the deploys preceding them in the block.  The resulting global state is
+    /// the same as if the deploys were executed one after another.
+    ///
+    /// Defaults to `false`.
+    enable_parallel_execution: Option<bool>,
+    /// The maximum number of deploys to execute at once when executing in parallel.
+    ///
+    /// Defaults to the number of CPUs.
+    max_parallel_executions: Option<usize>,
 }
 
 impl Config {
@@ -44,6 +91,36 @@ impl Config {
     pub(crate) fn max_query_depth(&self) -> u64 {
         self.max_query_depth.unwrap_or(DEFAULT_MAX_QUERY_DEPTH)
     }
+
+    pub(crate) fn pruning_mode(&self) -> PruningMode {
+        self.pruning_mode.unwrap_or_default()
+    }
+
+    pub(crate) fn enable_call_tracing(&self) -> bool {
+        self.enable_call_tracing.unwrap_or_default()
+    }
+
+    pub(crate) fn wasm_module_cache_size(&self) -> usize {
+        self.wasm_module_cache_size
+            .unwrap_or(DEFAULT_WASM_MODULE_CACHE_SIZE)
+    }
+
+    pub(crate) fn enable_parallel_execution(&self) -> bool {
+        self.enable_parallel_execution.unwrap_or_default()
+    }
+
+    pub(crate) fn max_parallel_executions(&self) -> usize {
+        self.max_parallel_executions
+            .unwrap_or_else(num_cpus::get)
+            .max(1)
+    }
+
+    /// Returns a copy of this config with the given pruning mode.
+    #[cfg(test)]
+    pub(crate) fn with_pruning_mode(mut self, pruning_mode: PruningMode) -> Self {
+        self.pruning_mode = Some(pruning_mode);
+        self
+    }
 }
 
 impl Default for Config {
@@ -52,6 +129,11 @@ impl Default for Config {
             max_global_state_size: Some(DEFAULT_MAX_GLOBAL_STATE_SIZE),
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
+            pruning_mode: Some(PruningMode::default()),
+            enable_call_tracing: Some(false),
+            wasm_module_cache_size: Some(DEFAULT_WASM_MODULE_CACHE_SIZE),
+            enable_parallel_execution: Some(false),
+            max_parallel_executions: None,
         }
     }
 }
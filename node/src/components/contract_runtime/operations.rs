@@ -1,27 +1,37 @@
-use std::{collections::VecDeque, sync::Arc, time::Instant};
+use std::{collections::VecDeque, iter, sync::Arc, time::Instant};
 
-use super::ContractRuntimeMetrics;
-use crate::{crypto::hash::Digest, types::DeployHash};
+use futures::{stream, StreamExt};
+
+use super::{ContractRuntimeMetrics, PruningMode};
+use crate::{
+    crypto::hash::Digest,
+    effect::{requests::StorageRequest, EffectBuilder},
+    types::DeployHash,
+};
 use casper_execution_engine::{
     core::engine_state::{
-        self, EngineState, ExecutionResult as EngineExecutionResult, ExecutionResults,
+        self, DeployItem, EngineState, ExecutionResult as EngineExecutionResult, ExecutionResults,
+        ReadSet,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::global_state::{lmdb::LmdbGlobalState, CommitResult},
 };
-use casper_types::{ExecutionResult, Key};
+use casper_types::{
+    CallTrace, ContractEvent, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey,
+};
 use engine_state::ExecuteRequest;
 use itertools::Itertools;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace, warn};
 
-/// Commits the execution effects.
+/// Commits the execution effects, returning the new state root hash, the execution result, the
+/// call trace recorded while executing the deploy and the events emitted by contracts.
 pub(super) async fn commit_execution_effects(
     engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
     state_root_hash: Digest,
     deploy_hash: DeployHash,
     execution_results: ExecutionResults,
-) -> Result<(Digest, ExecutionResult), ()> {
+) -> Result<(Digest, ExecutionResult, CallTrace, Vec<ContractEvent>), ()> {
     let ee_execution_result = execution_results
         .into_iter()
         .exactly_one()
@@ -55,11 +65,18 @@ pub(super) async fn commit_execution_effects(
         execution_effect.transforms,
     )
     .await;
+    let call_trace = execution_effect.call_trace;
+    let contract_events = execution_effect.events;
     trace!(?commit_result, "commit result");
     match commit_result {
         Ok(CommitResult::Success { state_root }) => {
             debug!(?state_root, "commit succeeded");
-            Ok((state_root.into(), execution_result))
+            Ok((
+                state_root.into(),
+                execution_result,
+                call_trace,
+                contract_events,
+            ))
         }
         _ => {
             error!(
@@ -96,6 +113,317 @@ pub(super) async fn execute(
     let start = Instant::now();
     let result = engine_state.run_execute(correlation_id, execute_request);
     metrics.run_execute.observe(start.elapsed().as_secs_f64());
+    let wasm_module_cache = engine_state.wasm_module_cache();
+    metrics
+        .wasm_module_cache_hits
+        .inc_by(wasm_module_cache.take_hits());
+    metrics
+        .wasm_module_cache_misses
+        .inc_by(wasm_module_cache.take_misses());
     trace!(?result, "execute result");
     result
 }
+
+/// Executes each of `deploy_items` concurrently against the global state under `state_root_hash`,
+/// with at most `max_parallel_executions` executing at once, returning the result of each
+/// execution together with the parts of global state it depended on.
+///
+/// The result is `None` for a deploy whose execution failed, which should then be executed again.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn execute_concurrently(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    state_root_hash: Digest,
+    block_time: u64,
+    deploy_items: Vec<DeployItem>,
+    protocol_version: ProtocolVersion,
+    proposer: PublicKey,
+    max_parallel_executions: usize,
+) -> Vec<Option<(EngineExecutionResult, ReadSet)>> {
+    let execute = move |deploy_item: DeployItem| {
+        let deploy_hash = deploy_item.deploy_hash;
+        let execute_request = ExecuteRequest::new(
+            state_root_hash.into(),
+            block_time,
+            vec![deploy_item],
+            protocol_version,
+            proposer.clone(),
+        );
+        trace!(?execute_request, "execute recording reads");
+        let correlation_id = CorrelationId::new();
+        let start = Instant::now();
+        let result = engine_state.run_execute_recording_reads(correlation_id, execute_request);
+        metrics.run_execute.observe(start.elapsed().as_secs_f64());
+        trace!(?result, "execute recording reads result");
+        match result {
+            Ok(results) => Some(
+                results
+                    .into_iter()
+                    .exactly_one()
+                    .expect("should only be one exec result"),
+            ),
+            Err(error) => {
+                debug!(?deploy_hash, ?error, "parallel deploy execution failed");
+                None
+            }
+        }
+    };
+    run_concurrently(deploy_items, max_parallel_executions, execute).await
+}
+
+/// Runs `execute` on a blocking thread for each of `deploy_items`, with at most
+/// `max_parallel_executions` running at once, returning the results in the order of the deploys.
+///
+/// The result is `None` for a deploy whose execution panicked.
+pub(super) async fn run_concurrently<F>(
+    deploy_items: Vec<DeployItem>,
+    max_parallel_executions: usize,
+    execute: F,
+) -> Vec<Option<(EngineExecutionResult, ReadSet)>>
+where
+    F: Fn(DeployItem) -> Option<(EngineExecutionResult, ReadSet)> + Send + Sync + 'static,
+{
+    let execute = Arc::new(execute);
+    let executions = deploy_items.into_iter().map(|deploy_item| {
+        let execute = Arc::clone(&execute);
+        // The blocking task is only spawned once the future is polled, so no more than
+        // `max_parallel_executions` run at once.
+        async move {
+            tokio::task::spawn_blocking(move || execute(deploy_item))
+                .await
+                .unwrap_or_else(|error| {
+                    // Treat the deploy as conflicting, so that it's executed again.
+                    warn!(%error, "failed to join tokio task");
+                    None
+                })
+        }
+    });
+    stream::iter(executions)
+        .buffered(max_parallel_executions)
+        .collect()
+        .await
+}
+
+/// Returns `true` if an execution against the global state under `pre_state_hash` which depended
+/// on `read_set` conflicts with `transforms`, which took the global state from `pre_state_hash` to
+/// `state_root_hash`.
+///
+/// Runs on a blocking thread, as checking for conflicts reads from global state.
+pub(super) async fn has_read_conflict(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    read_set: ReadSet,
+    pre_state_hash: Digest,
+    state_root_hash: Digest,
+    transforms: Arc<AdditiveMap<Key, Transform>>,
+) -> bool {
+    tokio::task::spawn_blocking(move || {
+        let correlation_id = CorrelationId::new();
+        engine_state
+            .has_read_conflict(
+                correlation_id,
+                &read_set,
+                pre_state_hash.into(),
+                state_root_hash.into(),
+                &transforms,
+            )
+            .unwrap_or_else(|error| {
+                // Be conservative and re-execute the deploy.
+                error!(?error, "failed to check for read conflicts");
+                true
+            })
+    })
+    .await
+    .unwrap_or_else(|error| {
+        // Be conservative and re-execute the deploy.
+        warn!(%error, "failed to join tokio task");
+        true
+    })
+}
+
+/// Executes `deploys` one after another on top of the global state under `pre_state_hash`,
+/// committing the effects of each, and returns the resulting state root hash together with the
+/// execution result, call trace and contract events of each deploy.
+///
+/// `parallel_results` holds the results of executing the deploys concurrently against the
+/// pre-state, as returned by `execute_concurrently`, and is empty if they weren't.  Such a result
+/// is only used if the deploys preceding it didn't change anything it depended on, otherwise the
+/// deploy is executed again.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn execute_deploys(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    pre_state_hash: Digest,
+    block_time: u64,
+    deploys: Vec<(DeployHash, DeployItem)>,
+    parallel_results: Vec<Option<(EngineExecutionResult, ReadSet)>>,
+    protocol_version: ProtocolVersion,
+    proposer: PublicKey,
+) -> (
+    Digest,
+    Vec<(ExecutionResult, CallTrace, Vec<ContractEvent>)>,
+) {
+    let track_transforms = !parallel_results.is_empty();
+    let mut parallel_results = parallel_results.into_iter();
+    let mut state_root_hash = pre_state_hash;
+    // The transforms committed on top of the pre-state so far.
+    let mut block_transforms = Arc::new(AdditiveMap::new());
+    let mut results = Vec::with_capacity(deploys.len());
+
+    for (deploy_hash, deploy_item) in deploys {
+        let parallel_result = match parallel_results.next().flatten() {
+            Some((execution_result, read_set)) => {
+                if has_read_conflict(
+                    engine_state.clone(),
+                    read_set,
+                    pre_state_hash,
+                    state_root_hash,
+                    Arc::clone(&block_transforms),
+                )
+                .await
+                {
+                    metrics.reexecuted_deploys.inc();
+                    None
+                } else {
+                    Some(execution_result)
+                }
+            }
+            None => None,
+        };
+
+        let execution_results = match parallel_result {
+            Some(execution_result) => iter::once(execution_result).collect(),
+            None => {
+                let execute_request = ExecuteRequest::new(
+                    state_root_hash.into(),
+                    block_time,
+                    vec![deploy_item],
+                    protocol_version,
+                    proposer.clone(),
+                );
+
+                // TODO: this is currently working coincidentally because we are passing only one
+                // deploy_item per exec. The execution results coming back from the ee lacks the
+                // mapping between deploy_hash and execution result, and this outer logic is
+                // enriching it with the deploy hash. If we were passing multiple deploys per exec
+                // the relation between the deploy and the execution results would be lost.
+                let result = execute(engine_state.clone(), metrics.clone(), execute_request).await;
+
+                trace!(%deploy_hash, ?result, "deploy execution result");
+                // As for now a given state is expected to exist.
+                result.unwrap()
+            }
+        };
+
+        if track_transforms {
+            // The conflict check holding the other reference has completed, so this doesn't
+            // clone the map.
+            let block_transforms = Arc::make_mut(&mut block_transforms);
+            for execution_result in &execution_results {
+                for (key, transform) in execution_result.effect().transforms.iter() {
+                    block_transforms.insert_add(*key, transform.clone());
+                }
+            }
+        }
+
+        match commit_execution_effects(
+            engine_state.clone(),
+            metrics.clone(),
+            state_root_hash,
+            deploy_hash,
+            execution_results,
+        )
+        .await
+        {
+            Ok((state_hash, execution_result, call_trace, contract_events)) => {
+                results.push((execution_result, call_trace, contract_events));
+                state_root_hash = state_hash;
+            }
+            // When commit fails we panic as we'll not be able to execute the next block.
+            Err(_err) => panic!("unable to commit"),
+        }
+    }
+    (state_root_hash, results)
+}
+
+/// Returns the state root hashes of the stored blocks whose global state is retained by the given
+/// pruning mode once the switch block of `era_id` at `height` has been executed.
+///
+/// The state root hash of the switch block itself is not included, as it is not stored yet.
+pub(super) async fn retained_state_root_hashes<REv>(
+    effect_builder: EffectBuilder<REv>,
+    pruning_mode: PruningMode,
+    era_id: EraId,
+    height: u64,
+) -> Vec<Digest>
+where
+    REv: From<StorageRequest>,
+{
+    let mut state_root_hashes = Vec::new();
+    match pruning_mode {
+        PruningMode::Archival => (),
+        PruningMode::KeepLastEras(num_eras) => {
+            // The oldest retained block is the switch block preceding the retained eras.
+            let lowest_height = if num_eras == 0 {
+                height
+            } else {
+                match era_id.checked_sub(num_eras) {
+                    Some(era_id) => effect_builder
+                        .get_switch_block_header_at_era_id_from_storage(era_id)
+                        .await
+                        .map_or(0, |header| header.height()),
+                    None => 0,
+                }
+            };
+            // Walk backwards so that gaps in the stored chain don't cause us to miss recent blocks.
+            for block_height in (lowest_height..height).rev() {
+                match effect_builder
+                    .get_block_header_at_height_from_storage(block_height)
+                    .await
+                {
+                    Some(header) => state_root_hashes.push(*header.state_root_hash()),
+                    None => break,
+                }
+            }
+        }
+        PruningMode::SwitchBlocks => {
+            for era_id in EraId::new(0).iter(era_id.value()) {
+                if let Some(header) = effect_builder
+                    .get_switch_block_header_at_era_id_from_storage(era_id)
+                    .await
+                {
+                    state_root_hashes.push(*header.state_root_hash());
+                }
+            }
+        }
+    }
+    state_root_hashes
+}
+
+/// Removes all global state which isn't reachable from any of the given state root hashes, or
+/// from any state root committed since pruning began.
+///
+/// Runs on a blocking thread, as marking and removing the tries may take a long time.
+pub(super) async fn prune(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    state_root_hashes: Vec<Digest>,
+) {
+    trace!(?state_root_hashes, "prune");
+    tokio::task::spawn_blocking(move || {
+        let correlation_id = CorrelationId::new();
+        let start = Instant::now();
+        let result = engine_state.prune(
+            correlation_id,
+            state_root_hashes.into_iter().map(Into::into).collect(),
+        );
+        metrics
+            .prune_global_state
+            .observe(start.elapsed().as_secs_f64());
+        match result {
+            Ok(removed) => info!(%removed, "pruned global state"),
+            Err(error) => error!(?error, "failed to prune global state"),
+        }
+    })
+    .await
+    .unwrap_or_else(|error| warn!(%error, "failed to join tokio task"));
+}
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use prometheus::Registry;
+use tempfile::TempDir;
+
+use casper_execution_engine::{
+    core::engine_state::{
+        executable_deploy_item::ExecutableDeployItem, ExecutionResult as EngineExecutionResult,
+        ReadSet,
+    },
+    shared::{motes::Motes, newtypes::CorrelationId},
+};
+use casper_types::{
+    bytesrepr::Bytes,
+    runtime_args,
+    system::{mint, standard_payment},
+    ExecutionResult, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+use super::*;
+use crate::{
+    crypto::AsymmetricKeyExt,
+    testing::TestRng,
+    types::{
+        chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
+        TimeDiff, Timestamp,
+    },
+};
+
+/// The number of funded accounts created at genesis.
+const ACCOUNT_COUNT: usize = 4;
+const ACCOUNT_BALANCE: u64 = 1_000_000_000_000_000;
+const TRANSFER_AMOUNT: u64 = 2_500_000_000;
+const BLOCK_TIME: u64 = 1_000;
+
+struct Fixture {
+    contract_runtime: ContractRuntime,
+    /// The secret keys of the funded accounts, the first of which is also the only validator.
+    secret_keys: Vec<SecretKey>,
+    post_genesis_state_hash: Digest,
+    _storage_dir: TempDir,
+}
+
+impl Fixture {
+    fn new(rng: &mut TestRng) -> Self {
+        let secret_keys: Vec<SecretKey> =
+            (0..ACCOUNT_COUNT).map(|_| SecretKey::random(rng)).collect();
+        let accounts = secret_keys
+            .iter()
+            .enumerate()
+            .map(|(index, secret_key)| {
+                let validator_config = (index == 0)
+                    .then(|| ValidatorConfig::new(Motes::new(U512::from(ACCOUNT_BALANCE)), 0));
+                AccountConfig::new(
+                    PublicKey::from(secret_key),
+                    Motes::new(U512::from(ACCOUNT_BALANCE)),
+                    validator_config,
+                )
+            })
+            .collect();
+        let mut chainspec = Chainspec::from_resources("local");
+        chainspec.network_config.accounts_config = AccountsConfig::new(accounts, vec![]);
+        let chainspec = Arc::new(chainspec);
+
+        let (storage_config, storage_dir) = StorageConfig::default_for_tests();
+        let contract_runtime = ContractRuntime::new(
+            Digest::default(),
+            None,
+            chainspec.protocol_config.version,
+            WithDir::new(storage_dir.path(), storage_config),
+            &Config::default(),
+            &Registry::new(),
+        )
+        .expect("should create contract runtime");
+        let post_genesis_state_hash = match contract_runtime
+            .commit_genesis(chainspec)
+            .expect("should commit genesis")
+        {
+            GenesisResult::Success {
+                post_state_hash, ..
+            } => post_state_hash.into(),
+            genesis_result => panic!("genesis should succeed: {}", genesis_result),
+        };
+
+        Fixture {
+            contract_runtime,
+            secret_keys,
+            post_genesis_state_hash,
+            _storage_dir: storage_dir,
+        }
+    }
+
+    fn proposer(&self) -> PublicKey {
+        PublicKey::from(&self.secret_keys[0])
+    }
+
+    /// Creates a transfer between the accounts with the given indices.
+    fn transfer(&self, source: usize, target: usize) -> (DeployHash, DeployItem) {
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! { standard_payment::ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+        };
+        let session = ExecutableDeployItem::Transfer {
+            args: runtime_args! {
+                mint::ARG_TARGET => PublicKey::from(&self.secret_keys[target]).to_account_hash(),
+                mint::ARG_AMOUNT => U512::from(TRANSFER_AMOUNT),
+                mint::ARG_ID => <Option<u64>>::None
+            },
+        };
+        let deploy = Deploy::new(
+            Timestamp::now(),
+            TimeDiff::from(60_000),
+            1,
+            vec![],
+            "casper-example".to_string(),
+            payment,
+            session,
+            &self.secret_keys[source],
+        );
+        (*deploy.id(), DeployItem::from(deploy))
+    }
+
+    /// Executes `deploys` concurrently against the post-genesis state.
+    async fn execute_concurrently(
+        &self,
+        deploys: &[(DeployHash, DeployItem)],
+    ) -> Vec<Option<(EngineExecutionResult, ReadSet)>> {
+        operations::execute_concurrently(
+            Arc::clone(&self.contract_runtime.engine_state),
+            Arc::clone(&self.contract_runtime.metrics),
+            self.post_genesis_state_hash,
+            BLOCK_TIME,
+            deploys
+                .iter()
+                .map(|(_, deploy_item)| deploy_item.clone())
+                .collect(),
+            self.contract_runtime.protocol_version,
+            self.proposer(),
+            2,
+        )
+        .await
+    }
+
+    /// Executes and commits `deploys` on top of the post-genesis state, using `parallel_results`
+    /// where they don't conflict, and returns the resulting state root hash and execution results.
+    async fn execute_deploys(
+        &self,
+        deploys: Vec<(DeployHash, DeployItem)>,
+        parallel_results: Vec<Option<(EngineExecutionResult, ReadSet)>>,
+    ) -> (Digest, Vec<ExecutionResult>) {
+        let (state_root_hash, results) = operations::execute_deploys(
+            Arc::clone(&self.contract_runtime.engine_state),
+            Arc::clone(&self.contract_runtime.metrics),
+            self.post_genesis_state_hash,
+            BLOCK_TIME,
+            deploys,
+            parallel_results,
+            self.contract_runtime.protocol_version,
+            self.proposer(),
+        )
+        .await;
+        let execution_results = results
+            .into_iter()
+            .map(|(execution_result, _, _)| execution_result)
+            .collect();
+        (state_root_hash, execution_results)
+    }
+
+    fn reexecuted_deploys(&self) -> u64 {
+        self.contract_runtime.metrics.reexecuted_deploys.get()
+    }
+}
+
+fn assert_all_succeeded(execution_results: &[ExecutionResult]) {
+    for execution_result in execution_results {
+        assert!(
+            matches!(execution_result, ExecutionResult::Success { .. }),
+            "deploy should succeed: {:?}",
+            execution_result
+        );
+    }
+}
+
+#[tokio::test]
+async fn should_not_reexecute_transfers_from_different_accounts() {
+    let mut rng = crate::new_rng();
+    let fixture = Fixture::new(&mut rng);
+    let deploys = vec![
+        fixture.transfer(1, 0),
+        fixture.transfer(2, 0),
+        fixture.transfer(3, 0),
+    ];
+
+    let (serial_state_hash, serial_results) =
+        fixture.execute_deploys(deploys.clone(), Vec::new()).await;
+    let parallel_results = fixture.execute_concurrently(&deploys).await;
+    assert!(parallel_results.iter().all(Option::is_some));
+    let (state_hash, results) = fixture.execute_deploys(deploys, parallel_results).await;
+
+    assert_eq!(fixture.reexecuted_deploys(), 0);
+    assert_all_succeeded(&results);
+    assert_eq!(state_hash, serial_state_hash);
+    assert_eq!(results, serial_results);
+}
+
+#[tokio::test]
+async fn should_reexecute_transfer_reading_balance_written_by_preceding_transfer() {
+    let mut rng = crate::new_rng();
+    let fixture = Fixture::new(&mut rng);
+    // Both transfers read and write the balance of the same source purse.
+    let deploys = vec![fixture.transfer(1, 2), fixture.transfer(1, 3)];
+
+    let (serial_state_hash, serial_results) =
+        fixture.execute_deploys(deploys.clone(), Vec::new()).await;
+    let parallel_results = fixture.execute_concurrently(&deploys).await;
+    assert!(parallel_results.iter().all(Option::is_some));
+    let (state_hash, results) = fixture.execute_deploys(deploys, parallel_results).await;
+
+    assert_eq!(fixture.reexecuted_deploys(), 1);
+    assert_all_succeeded(&results);
+    assert_eq!(state_hash, serial_state_hash);
+    assert_eq!(results, serial_results);
+}
+
+#[tokio::test]
+async fn should_reexecute_deploy_whose_parallel_execution_panicked() {
+    let mut rng = crate::new_rng();
+    let fixture = Fixture::new(&mut rng);
+    let deploys = vec![fixture.transfer(1, 0), fixture.transfer(2, 0)];
+    let panicking_deploy_hash = deploys[1].1.deploy_hash;
+
+    let (serial_state_hash, serial_results) =
+        fixture.execute_deploys(deploys.clone(), Vec::new()).await;
+
+    let engine_state = Arc::clone(&fixture.contract_runtime.engine_state);
+    let pre_state_hash = fixture.post_genesis_state_hash;
+    let protocol_version = fixture.contract_runtime.protocol_version;
+    let proposer = fixture.proposer();
+    let execute = move |deploy_item: DeployItem| {
+        if deploy_item.deploy_hash == panicking_deploy_hash {
+            panic!("execution panicked");
+        }
+        let execute_request = ExecuteRequest::new(
+            pre_state_hash.into(),
+            BLOCK_TIME,
+            vec![deploy_item],
+            protocol_version,
+            proposer.clone(),
+        );
+        engine_state
+            .run_execute_recording_reads(CorrelationId::new(), execute_request)
+            .expect("should execute")
+            .pop()
+    };
+    let parallel_results = operations::run_concurrently(
+        deploys
+            .iter()
+            .map(|(_, deploy_item)| deploy_item.clone())
+            .collect(),
+        2,
+        execute,
+    )
+    .await;
+    assert!(parallel_results[0].is_some());
+    assert!(parallel_results[1].is_none());
+    let (state_hash, results) = fixture.execute_deploys(deploys, parallel_results).await;
+
+    assert_eq!(fixture.reexecuted_deploys(), 0);
+    assert_all_succeeded(&results);
+    assert_eq!(state_hash, serial_state_hash);
+    assert_eq!(results, serial_results);
+}
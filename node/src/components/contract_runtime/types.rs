@@ -1,7 +1,12 @@
 use casper_execution_engine::{
     core::engine_state::GetEraValidatorsRequest, shared::newtypes::Blake2bHash,
 };
-use casper_types::{EraId, ProtocolVersion};
+use casper_types::{EraId, ProtocolVersion, PublicKey};
+
+use crate::{
+    crypto::hash::Digest,
+    types::{Block, Timestamp},
+};
 
 /// Request for validator weights for a specific era.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -75,3 +80,30 @@ impl From<EraValidatorsRequest> for GetEraValidatorsRequest {
         GetEraValidatorsRequest::new(input.state_hash, input.protocol_version)
     }
 }
+
+/// The state against which a deploy is speculatively executed.
+///
+/// Usually derived from a block header: the deploy is executed on top of the block's post-state
+/// hash, as if it were included in a child of that block proposed by the same validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeculativeExecutionState {
+    /// The state root hash to execute against.
+    pub state_root_hash: Digest,
+    /// The block time to use during execution.
+    pub block_time: Timestamp,
+    /// The protocol version to use during execution.
+    pub protocol_version: ProtocolVersion,
+    /// The proposer to credit with the execution fees.
+    pub proposer: PublicKey,
+}
+
+impl From<&Block> for SpeculativeExecutionState {
+    fn from(block: &Block) -> Self {
+        SpeculativeExecutionState {
+            state_root_hash: *block.state_root_hash(),
+            block_time: block.timestamp(),
+            protocol_version: block.protocol_version(),
+            proposer: block.body().proposer().clone(),
+        }
+    }
+}
@@ -180,20 +180,34 @@ where
                 block: Box::new(JsonBlock::new(*block, None)),
             }),
             Event::DeployAccepted(deploy) => self.broadcast(SseData::DeployAccepted { deploy }),
+            Event::DeployExpired(deploy_hash) => {
+                self.broadcast(SseData::DeployExpired { deploy_hash })
+            }
             Event::DeployProcessed {
                 deploy_hash,
                 deploy_header,
                 block_hash,
                 execution_result,
-            } => self.broadcast(SseData::DeployProcessed {
-                deploy_hash: Box::new(deploy_hash),
-                account: Box::new(deploy_header.account().clone()),
-                timestamp: deploy_header.timestamp(),
-                ttl: deploy_header.ttl(),
-                dependencies: deploy_header.dependencies().clone(),
-                block_hash: Box::new(block_hash),
-                execution_result,
-            }),
+                contract_events,
+            } => {
+                let mut effects = self.broadcast(SseData::DeployProcessed {
+                    deploy_hash: Box::new(deploy_hash),
+                    account: Box::new(deploy_header.account().clone()),
+                    timestamp: deploy_header.timestamp(),
+                    ttl: deploy_header.ttl(),
+                    dependencies: deploy_header.dependencies().clone(),
+                    block_hash: Box::new(block_hash),
+                    execution_result,
+                });
+                for contract_event in contract_events {
+                    effects.extend(self.broadcast(SseData::ContractEvent {
+                        deploy_hash: Box::new(deploy_hash),
+                        block_hash: Box::new(block_hash),
+                        contract_event: Box::new(contract_event),
+                    }));
+                }
+                effects
+            }
             Event::Fault {
                 era_id,
                 public_key,
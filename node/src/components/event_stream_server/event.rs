@@ -1,6 +1,6 @@
 use std::fmt::{self, Display, Formatter};
 
-use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey};
+use casper_types::{ContractEvent, EraId, ExecutionEffect, ExecutionResult, PublicKey};
 
 use crate::types::{Block, BlockHash, DeployHash, DeployHeader, FinalitySignature, Timestamp};
 
@@ -8,11 +8,13 @@ use crate::types::{Block, BlockHash, DeployHash, DeployHeader, FinalitySignature
 pub enum Event {
     BlockAdded(Box<Block>),
     DeployAccepted(DeployHash),
+    DeployExpired(DeployHash),
     DeployProcessed {
         deploy_hash: DeployHash,
         deploy_header: Box<DeployHeader>,
         block_hash: BlockHash,
         execution_result: Box<ExecutionResult>,
+        contract_events: Vec<ContractEvent>,
     },
     Fault {
         era_id: EraId,
@@ -33,6 +35,9 @@ impl Display for Event {
             Event::DeployAccepted(deploy_hash) => {
                 write!(formatter, "deploy accepted {}", deploy_hash)
             }
+            Event::DeployExpired(deploy_hash) => {
+                write!(formatter, "deploy expired {}", deploy_hash)
+            }
             Event::DeployProcessed { deploy_hash, .. } => {
                 write!(formatter, "deploy processed {}", deploy_hash)
             }
@@ -34,7 +34,10 @@ use warp::{
     Filter, Reply,
 };
 
-use casper_types::{EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_types::{
+    ContractEvent, ContractHash, EraId, ExecutionEffect, ExecutionResult, ProtocolVersion,
+    PublicKey,
+};
 
 use super::DeployGetter;
 use crate::types::{
@@ -45,15 +48,20 @@ use crate::{crypto::AsymmetricKeyExt, testing::TestRng, types::Block};
 
 /// The URL root path.
 pub const SSE_API_ROOT_PATH: &str = "events";
-/// The URL path part to subscribe to all events other than `DeployAccepted`s and
+/// The URL path part to subscribe to all events other than `DeployAccepted`s, `DeployExpired`s and
 /// `FinalitySignature`s.
 pub const SSE_API_MAIN_PATH: &str = "main";
-/// The URL path part to subscribe to only `DeployAccepted` events.
+/// The URL path part to subscribe to only `DeployAccepted` and `DeployExpired` events.
 pub const SSE_API_DEPLOYS_PATH: &str = "deploys";
 /// The URL path part to subscribe to only `FinalitySignature` events.
 pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
+/// The URL path part to subscribe to only `ContractEvent` events.
+pub const SSE_API_CONTRACT_EVENTS_PATH: &str = "contract-events";
 /// The URL query string field name.
 pub const QUERY_FIELD: &str = "start_from";
+/// The URL query string field name used to restrict `ContractEvent`s to those emitted by a single
+/// contract.
+pub const CONTRACT_HASH_QUERY_FIELD: &str = "contract_hash";
 
 /// The filter associated with `/events/main` path.
 const MAIN_FILTER: [EventFilter; 4] = [
@@ -63,9 +71,11 @@ const MAIN_FILTER: [EventFilter; 4] = [
     EventFilter::Step,
 ];
 /// The filter associated with `/events/deploys` path.
-const DEPLOYS_FILTER: [EventFilter; 1] = [EventFilter::DeployAccepted];
+const DEPLOYS_FILTER: [EventFilter; 2] = [EventFilter::DeployAccepted, EventFilter::DeployExpired];
 /// The filter associated with `/events/sigs` path.
 const SIGNATURES_FILTER: [EventFilter; 1] = [EventFilter::FinalitySignature];
+/// The filter associated with `/events/contract-events` path.
+const CONTRACT_EVENTS_FILTER: [EventFilter; 1] = [EventFilter::ContractEvent];
 
 /// The max time to wait for getting a deploy before trying a second and final time.
 const GET_DEPLOY_TIMEOUT: Duration = Duration::from_secs(1);
@@ -90,6 +100,9 @@ pub enum SseData {
         #[schemars(with = "Deploy", description = "a deploy")]
         deploy: DeployHash,
     },
+    /// The given deploy expired before being included in a block and has been dropped by this
+    /// node's block proposer.
+    DeployExpired { deploy_hash: DeployHash },
     /// The given deploy has been executed, committed and forms part of the given block.
     DeployProcessed {
         deploy_hash: Box<DeployHash>,
@@ -114,6 +127,13 @@ pub enum SseData {
         #[data_size(skip)]
         execution_effect: ExecutionEffect,
     },
+    /// The given event was emitted by a contract while executing the given deploy.
+    ContractEvent {
+        deploy_hash: Box<DeployHash>,
+        block_hash: Box<BlockHash>,
+        #[data_size(skip)]
+        contract_event: Box<ContractEvent>,
+    },
 }
 
 impl SseData {
@@ -122,10 +142,23 @@ impl SseData {
             SseData::ApiVersion(_) => true,
             SseData::BlockAdded { .. } => filter.contains(&EventFilter::BlockAdded),
             SseData::DeployAccepted { .. } => filter.contains(&EventFilter::DeployAccepted),
+            SseData::DeployExpired { .. } => filter.contains(&EventFilter::DeployExpired),
             SseData::DeployProcessed { .. } => filter.contains(&EventFilter::DeployProcessed),
             SseData::Fault { .. } => filter.contains(&EventFilter::Fault),
             SseData::FinalitySignature(_) => filter.contains(&EventFilter::FinalitySignature),
             SseData::Step { .. } => filter.contains(&EventFilter::Step),
+            SseData::ContractEvent { .. } => filter.contains(&EventFilter::ContractEvent),
+        }
+    }
+
+    /// Returns `false` if this is a `ContractEvent` which wasn't emitted by the given contract.
+    /// All other data, and all data when no contract hash is given, is considered a match.
+    pub(super) fn matches_contract_hash(&self, maybe_contract_hash: Option<&ContractHash>) -> bool {
+        match (self, maybe_contract_hash) {
+            (SseData::ContractEvent { contract_event, .. }, Some(contract_hash)) => {
+                contract_event.contract_hash == *contract_hash
+            }
+            _ => true,
         }
     }
 }
@@ -160,6 +193,13 @@ impl SseData {
         (event, deploy)
     }
 
+    /// Returns a random `SseData::DeployExpired`.
+    pub(super) fn random_deploy_expired(rng: &mut TestRng) -> Self {
+        SseData::DeployExpired {
+            deploy_hash: DeployHash::random(rng),
+        }
+    }
+
     /// Returns a random `SseData::DeployProcessed`.
     pub(super) fn random_deploy_processed(rng: &mut TestRng) -> Self {
         let deploy = Deploy::random(rng);
@@ -203,6 +243,15 @@ impl SseData {
             execution_effect,
         }
     }
+
+    /// Returns a random `SseData::ContractEvent`.
+    pub(super) fn random_contract_event(rng: &mut TestRng) -> Self {
+        SseData::ContractEvent {
+            deploy_hash: Box::new(DeployHash::random(rng)),
+            block_hash: Box::new(BlockHash::random(rng)),
+            contract_event: Box::new(rng.gen()),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -256,10 +305,12 @@ pub(super) struct NewSubscriberInfo {
 pub(super) enum EventFilter {
     BlockAdded,
     DeployAccepted,
+    DeployExpired,
     DeployProcessed,
     Fault,
     FinalitySignature,
     Step,
+    ContractEvent,
 }
 
 /// Filters the `event`, mapping it to a warp event, or `None` if it should be filtered out.
@@ -298,10 +349,12 @@ async fn filter_map_server_sent_event(
             }))),
 
         &SseData::BlockAdded { .. }
+        | &SseData::DeployExpired { .. }
         | &SseData::DeployProcessed { .. }
         | &SseData::Fault { .. }
         | &SseData::Step { .. }
-        | &SseData::FinalitySignature(_) => Some(Ok(WarpServerSentEvent::default()
+        | &SseData::FinalitySignature(_)
+        | &SseData::ContractEvent { .. } => Some(Ok(WarpServerSentEvent::default()
             .json_data(&event.data)
             .unwrap_or_else(|error| {
                 warn!(%error, ?event, "failed to jsonify sse event");
@@ -342,15 +395,36 @@ pub(super) fn get_filter(path_param: &str) -> Option<&'static [EventFilter]> {
         SSE_API_MAIN_PATH => Some(&MAIN_FILTER[..]),
         SSE_API_DEPLOYS_PATH => Some(&DEPLOYS_FILTER[..]),
         SSE_API_SIGNATURES_PATH => Some(&SIGNATURES_FILTER[..]),
+        SSE_API_CONTRACT_EVENTS_PATH => Some(&CONTRACT_EVENTS_FILTER[..]),
         _ => None,
     }
 }
 
+/// Extracts the starting event ID and the optional contract hash by which to filter
+/// `ContractEvent`s from the provided query.
+///
+/// The "contract_hash" entry, if present, must be mapped to a formatted contract hash, otherwise a
+/// 422 response is returned.  Apart from that, returns a 422 response if `query` isn't empty and
+/// doesn't have exactly one entry, "starts_from" mapped to a value representing an event ID.
+fn parse_query(
+    mut query: HashMap<String, String>,
+) -> Result<(Option<Id>, Option<ContractHash>), Response> {
+    let maybe_contract_hash = match query.remove(CONTRACT_HASH_QUERY_FIELD) {
+        Some(contract_hash_str) => match ContractHash::from_formatted_str(&contract_hash_str) {
+            Ok(contract_hash) => Some(contract_hash),
+            Err(_) => return Err(create_422_contract_hash()),
+        },
+        None => None,
+    };
+
+    parse_start_from(query).map(|maybe_id| (maybe_id, maybe_contract_hash))
+}
+
 /// Extracts the starting event ID from the provided query, or `None` if `query` is empty.
 ///
 /// If `query` is not empty, returns a 422 response if `query` doesn't have exactly one entry,
 /// "starts_from" mapped to a value representing an event ID.
-fn parse_query(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
+fn parse_start_from(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
     if query.is_empty() {
         return Ok(None);
     }
@@ -371,11 +445,13 @@ fn parse_query(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
 /// Creates a 404 response with a useful error message in the body.
 fn create_404() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}' or '/{root}/{sigs}'\n",
+        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}', '/{root}/{sigs}' or \
+        '/{root}/{contract_events}'\n",
         root = SSE_API_ROOT_PATH,
         main = SSE_API_MAIN_PATH,
         deploys = SSE_API_DEPLOYS_PATH,
-        sigs = SSE_API_SIGNATURES_PATH
+        sigs = SSE_API_SIGNATURES_PATH,
+        contract_events = SSE_API_CONTRACT_EVENTS_PATH
     )));
     *response.status_mut() = StatusCode::NOT_FOUND;
     response
@@ -392,6 +468,17 @@ fn create_422() -> Response {
     response
 }
 
+/// Creates a 422 response with a useful error message in the body for use in case of a bad
+/// contract hash in the query string.
+fn create_422_contract_hash() -> Response {
+    let mut response = Response::new(Body::from(format!(
+        "invalid query: expected '{}=contract-<HEX STRING>'\n",
+        CONTRACT_HASH_QUERY_FIELD
+    )));
+    *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
+    response
+}
+
 /// Creates a 503 response (Service Unavailable) to be returned if the server has too many
 /// subscribers.
 fn create_503() -> Response {
@@ -443,8 +530,8 @@ impl ChannelsAndFilter {
                     None => return create_404(),
                 };
 
-                let start_from = match parse_query(query) {
-                    Ok(maybe_id) => maybe_id,
+                let (start_from, maybe_contract_hash) = match parse_query(query) {
+                    Ok(parsed_query) => parsed_query,
                     Err(error_response) => return error_response,
                 };
 
@@ -473,6 +560,7 @@ impl ChannelsAndFilter {
                     initial_events_receiver,
                     ongoing_events_receiver,
                     event_filter,
+                    maybe_contract_hash,
                     deploy_getter.clone(),
                 )))
                 .into_response()
@@ -501,11 +589,13 @@ impl ChannelsAndFilter {
 /// subscribed to the server's event stream.
 ///
 /// It also takes an `EventFilter` which causes events to which the client didn't subscribe to be
-/// skipped.
+/// skipped, and an optional contract hash which causes `ContractEvent`s emitted by any other
+/// contract to be skipped.
 fn stream_to_client(
     initial_events: mpsc::UnboundedReceiver<ServerSentEvent>,
     ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
     event_filter: &'static [EventFilter],
+    maybe_contract_hash: Option<ContractHash>,
     deploy_getter: DeployGetter,
 ) -> impl Stream<Item = Result<WarpServerSentEvent, RecvError>> + 'static {
     // Keep a record of the IDs of the events delivered via the `initial_events` receiver.
@@ -556,6 +646,12 @@ fn stream_to_client(
             async move {
                 match result {
                     Ok(event) => {
+                        if !event
+                            .data
+                            .matches_contract_hash(maybe_contract_hash.as_ref())
+                        {
+                            return None;
+                        }
                         filter_map_server_sent_event(&event, event_filter, cloned_deploy_getter)
                             .await
                     }
@@ -625,6 +721,10 @@ mod tests {
         let mut deploys = HashMap::new();
         let _ = deploys.insert(*deploy.id(), deploy);
         let getter = DeployGetter::with_deploys(deploys);
+        let deploy_expired = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_expired(&mut rng),
+        };
         let deploy_processed = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_deploy_processed(&mut rng),
@@ -641,6 +741,10 @@ mod tests {
             id: Some(rng.gen()),
             data: SseData::random_step(&mut rng),
         };
+        let contract_event = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_contract_event(&mut rng),
+        };
 
         // `EventFilter::Main` should only filter out `DeployAccepted`s and `FinalitySignature`s.
         should_not_filter_out(&api_version, &MAIN_FILTER[..], getter.clone()).await;
@@ -650,18 +754,22 @@ mod tests {
         should_not_filter_out(&step, &MAIN_FILTER[..], getter.clone()).await;
 
         should_filter_out(&deploy_accepted, &MAIN_FILTER[..], getter.clone()).await;
+        should_filter_out(&deploy_expired, &MAIN_FILTER[..], getter.clone()).await;
         should_filter_out(&finality_signature, &MAIN_FILTER[..], getter.clone()).await;
+        should_filter_out(&contract_event, &MAIN_FILTER[..], getter.clone()).await;
 
-        // `EventFilter::DeployAccepted` should filter out everything except `ApiVersion`s and
-        // `DeployAccepted`s.
+        // `EventFilter::DeployAccepted` should filter out everything except `ApiVersion`s,
+        // `DeployAccepted`s and `DeployExpired`s.
         should_not_filter_out(&api_version, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_not_filter_out(&deploy_accepted, &DEPLOYS_FILTER[..], getter.clone()).await;
+        should_not_filter_out(&deploy_expired, &DEPLOYS_FILTER[..], getter.clone()).await;
 
         should_filter_out(&block_added, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_filter_out(&deploy_processed, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_filter_out(&fault, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_filter_out(&finality_signature, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_filter_out(&step, &DEPLOYS_FILTER[..], getter.clone()).await;
+        should_filter_out(&contract_event, &DEPLOYS_FILTER[..], getter.clone()).await;
 
         // `EventFilter::Signatures` should filter out everything except `ApiVersion`s and
         // `FinalitySignature`s.
@@ -670,9 +778,60 @@ mod tests {
 
         should_filter_out(&block_added, &SIGNATURES_FILTER[..], getter.clone()).await;
         should_filter_out(&deploy_accepted, &SIGNATURES_FILTER[..], getter.clone()).await;
+        should_filter_out(&deploy_expired, &SIGNATURES_FILTER[..], getter.clone()).await;
         should_filter_out(&deploy_processed, &SIGNATURES_FILTER[..], getter.clone()).await;
         should_filter_out(&fault, &SIGNATURES_FILTER[..], getter.clone()).await;
-        should_filter_out(&step, &SIGNATURES_FILTER[..], getter).await;
+        should_filter_out(&step, &SIGNATURES_FILTER[..], getter.clone()).await;
+        should_filter_out(&contract_event, &SIGNATURES_FILTER[..], getter.clone()).await;
+
+        // `EventFilter::ContractEvents` should filter out everything except `ApiVersion`s and
+        // `ContractEvent`s.
+        should_not_filter_out(&api_version, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_not_filter_out(&contract_event, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+
+        should_filter_out(&block_added, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_filter_out(
+            &deploy_accepted,
+            &CONTRACT_EVENTS_FILTER[..],
+            getter.clone(),
+        )
+        .await;
+        should_filter_out(&deploy_expired, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_filter_out(
+            &deploy_processed,
+            &CONTRACT_EVENTS_FILTER[..],
+            getter.clone(),
+        )
+        .await;
+        should_filter_out(&fault, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_filter_out(
+            &finality_signature,
+            &CONTRACT_EVENTS_FILTER[..],
+            getter.clone(),
+        )
+        .await;
+        should_filter_out(&step, &CONTRACT_EVENTS_FILTER[..], getter).await;
+    }
+
+    /// This test checks that only `ContractEvent`s emitted by a contract other than the requested
+    /// one are rejected by the contract hash filter.
+    #[test]
+    fn should_match_contract_hash() {
+        let mut rng = crate::new_rng();
+
+        let contract_event = SseData::random_contract_event(&mut rng);
+        let emitter = match &contract_event {
+            SseData::ContractEvent { contract_event, .. } => contract_event.contract_hash,
+            _ => unreachable!(),
+        };
+        let other_contract = ContractHash::new(rng.gen());
+
+        assert!(contract_event.matches_contract_hash(None));
+        assert!(contract_event.matches_contract_hash(Some(&emitter)));
+        assert!(!contract_event.matches_contract_hash(Some(&other_contract)));
+
+        let step = SseData::random_step(&mut rng);
+        assert!(step.matches_contract_hash(Some(&other_contract)));
     }
 
     /// This test checks that events with incorrect IDs (i.e. no types have an ID except for
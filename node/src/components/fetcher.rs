@@ -17,8 +17,9 @@ use casper_execution_engine::{
 use casper_types::Key;
 
 use crate::{
-    components::{fetcher::event::FetchResponder, Component},
+    components::{fetcher::event::FetchResponder, small_network::Offense, Component},
     effect::{
+        announcements::BlocklistAnnouncement,
         requests::{ContractRuntimeRequest, LinearChainRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
@@ -40,6 +41,7 @@ pub trait ReactorEventT<T>:
     + From<ContractRuntimeRequest>
     // Won't be needed when we implement "get block by height" feature in storage.
     + From<LinearChainRequest<NodeId>>
+    + From<BlocklistAnnouncement<NodeId>>
     + Send
     + 'static
 where
@@ -57,6 +59,7 @@ where
         + From<StorageRequest>
         + From<ContractRuntimeRequest>
         + From<LinearChainRequest<NodeId>>
+        + From<BlocklistAnnouncement<NodeId>>
         + Send
         + 'static,
 {
@@ -358,9 +361,21 @@ where
                 self.signal(id, None, peer)
             }
             Event::TimeoutPeer { id, peer } => {
+                // The timeout fires regardless of whether the peer responded in the meantime.
+                let timed_out = self
+                    .responders()
+                    .get(&id)
+                    .map_or(false, |responders| responders.contains_key(&peer));
+                if !timed_out {
+                    return Effects::new();
+                }
                 info!(%id, %peer, "request timed out");
                 self.metrics.timeouts.inc();
-                self.signal(id, None, peer)
+                let mut effects = effect_builder
+                    .announce_offense(peer, Offense::FetchTimeout)
+                    .ignore();
+                effects.extend(self.signal(id, None, peer));
+                effects
             }
         }
     }
@@ -107,6 +107,8 @@ reactor!(Reactor {
         // from a client.
         RpcServerAnnouncement -> [deploy_acceptor];
         ChainspecLoaderAnnouncement -> [!];
+        // Offenses such as fetch timeouts are not tracked in this test.
+        BlocklistAnnouncement<NodeId> -> [#];
     }
 });
 
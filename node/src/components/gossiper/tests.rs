@@ -37,6 +37,7 @@ use crate::{
     protocol::Message as NodeMessage,
     reactor::{self, EventQueueHandle, Runner},
     testing::{
+        fault_model::{FaultModel, Latency, LinkFaults},
         network::{Network, NetworkedReactor},
         ConditionCheckReactor, TestRng,
     },
@@ -322,8 +323,9 @@ impl reactor::Reactor for Reactor {
             Event::NetworkAnnouncement(NetworkAnnouncement::GossipOurAddress(_)) => {
                 unreachable!("should not receive announcements of type GossipOurAddress");
             }
-            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(_)) => {
-                // We do not care about new peers in the gossiper test.
+            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(_))
+            | Event::NetworkAnnouncement(NetworkAnnouncement::PeerDisconnected(_)) => {
+                // We do not care about new or lost peers in the gossiper test.
                 Effects::new()
             }
             Event::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived {
@@ -391,11 +393,18 @@ fn announce_deploy_received(
     }
 }
 
-async fn run_gossip(rng: &mut TestRng, network_size: usize, deploy_count: usize) {
+async fn run_gossip(
+    rng: &mut TestRng,
+    network_size: usize,
+    deploy_count: usize,
+    link_faults: LinkFaults,
+) {
     const TIMEOUT: Duration = Duration::from_secs(20);
     const QUIET_FOR: Duration = Duration::from_millis(50);
 
-    NetworkController::<NodeMessage>::create_active();
+    let mut fault_model = FaultModel::new(rng);
+    fault_model.set_default_link_faults(link_faults);
+    NetworkController::<NodeMessage>::create_active_with_faults(fault_model.into_shared());
     let mut network = Network::<Reactor>::new();
 
     // Add `network_size` nodes.
@@ -441,11 +450,35 @@ async fn should_gossip() {
 
     for network_size in &NETWORK_SIZES {
         for deploy_count in &DEPLOY_COUNTS {
-            run_gossip(&mut rng, *network_size, *deploy_count).await
+            run_gossip(
+                &mut rng,
+                *network_size,
+                *deploy_count,
+                LinkFaults::default(),
+            )
+            .await
         }
     }
 }
 
+#[tokio::test]
+async fn should_gossip_over_slow_links() {
+    const NETWORK_SIZE: usize = 5;
+    const DEPLOY_COUNT: usize = 10;
+
+    let mut rng = crate::new_rng();
+
+    // Random latencies also reorder the messages sent over each link.
+    let link_faults = LinkFaults {
+        latency: Latency::Uniform {
+            min: Duration::from_millis(0),
+            max: Duration::from_millis(20),
+        },
+        drop_probability: 0.0,
+    };
+    run_gossip(&mut rng, NETWORK_SIZE, DEPLOY_COUNT, link_faults).await
+}
+
 #[tokio::test]
 async fn should_get_from_alternate_source() {
     const NETWORK_SIZE: usize = 3;
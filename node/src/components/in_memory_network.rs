@@ -1,7 +1,8 @@
 //! Very fast networking component used for testing and simulations.
 //!
 //! The `InMemoryNetwork` represents a full virtual network with flawless connectivity and delivery
-//! by default.
+//! by default. Latency, message loss, partitions and paused nodes can be injected by creating the
+//! network with a `FaultModel` via `NetworkController::create_active_with_faults`.
 //!
 //! # Setup
 //!
@@ -286,11 +287,15 @@ use std::{
     convert::Infallible,
     fmt::{self, Display, Formatter},
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use rand::seq::IteratorRandom;
 use serde::Serialize;
-use tokio::sync::mpsc::{self, error::SendError};
+use tokio::{
+    sync::mpsc::{self, error::SendError},
+    time,
+};
 use tracing::{debug, error, info, warn};
 
 use crate::{
@@ -301,7 +306,10 @@ use crate::{
     },
     logging,
     reactor::{EventQueueHandle, QueueKind},
-    testing::TestRng,
+    testing::{
+        fault_model::{Delivery, SharedFaultModel},
+        TestRng,
+    },
     types::NodeId,
     NodeRng,
 };
@@ -338,6 +346,9 @@ thread_local! {
 pub struct NetworkController<P> {
     /// Channels for network communication.
     nodes: Network<P>,
+
+    /// Faults injected into the network, if any.
+    fault_model: Option<SharedFaultModel<NodeId>>,
 }
 
 impl<P> NetworkController<P>
@@ -349,6 +360,7 @@ where
         let _ = logging::init();
         NetworkController {
             nodes: Default::default(),
+            fault_model: None,
         }
     }
 
@@ -363,6 +375,20 @@ where
             .with(|active_network| active_network.borrow_mut().replace(Box::new(Self::new())));
     }
 
+    /// Creates a new, empty network controller injecting the faults of `fault_model`, and sets it
+    /// as active.
+    ///
+    /// The fault model can still be changed through `fault_model` while the network is active.
+    pub fn create_active_with_faults(fault_model: SharedFaultModel<NodeId>) {
+        let _ = logging::init();
+        let network = NetworkController {
+            fault_model: Some(fault_model),
+            ..Self::new()
+        };
+        ACTIVE_NETWORK
+            .with(|active_network| active_network.borrow_mut().replace(Box::new(network)));
+    }
+
     /// Removes the active network.
     ///
     /// # Panics
@@ -438,7 +464,12 @@ where
     where
         REv: From<NetworkAnnouncement<NodeId, P>> + Send,
     {
-        InMemoryNetwork::new_with_data(event_queue, NodeId::random(rng), self.nodes.clone())
+        InMemoryNetwork::new_with_data(
+            event_queue,
+            NodeId::random(rng),
+            self.nodes.clone(),
+            self.fault_model.clone(),
+        )
     }
 }
 
@@ -450,6 +481,9 @@ pub struct InMemoryNetwork<P> {
 
     /// The nodes map, contains the incoming channel for each virtual node.
     nodes: Network<P>,
+
+    /// Faults injected into the network, if any.
+    fault_model: Option<SharedFaultModel<NodeId>>,
 }
 
 impl<P> InMemoryNetwork<P>
@@ -471,6 +505,7 @@ where
         event_queue: EventQueueHandle<REv>,
         node_id: NodeId,
         nodes: Network<P>,
+        fault_model: Option<SharedFaultModel<NodeId>>,
     ) -> Self
     where
         REv: From<NetworkAnnouncement<NodeId, P>> + Send,
@@ -486,7 +521,11 @@ where
 
         tokio::spawn(receiver_task(event_queue, receiver));
 
-        InMemoryNetwork { node_id, nodes }
+        InMemoryNetwork {
+            node_id,
+            nodes,
+            fault_model,
+        }
     }
 
     /// Returns this node's ID.
@@ -498,9 +537,11 @@ where
 
 impl<P> InMemoryNetwork<P>
 where
-    P: Display,
+    P: Display + Send + 'static,
 {
     /// Internal helper, sends a payload to a node, ignoring but logging all errors.
+    ///
+    /// The payload is dropped or delayed if the fault model says so.
     fn send(
         &self,
         nodes: &HashMap<NodeId, mpsc::UnboundedSender<(NodeId, P)>>,
@@ -511,14 +552,41 @@ where
             panic!("can't send message to self");
         }
 
+        let latency = match &self.fault_model {
+            Some(fault_model) => {
+                match fault_model
+                    .lock()
+                    .expect("fault model lock poisoned")
+                    .delivery(&self.node_id, &dest)
+                {
+                    Delivery::Drop => {
+                        debug!(%dest, %payload, "dropping message due to injected fault");
+                        return;
+                    }
+                    Delivery::After(latency) => latency,
+                }
+            }
+            None => Duration::from_secs(0),
+        };
+
         match nodes.get(&dest) {
-            Some(sender) => {
+            Some(sender) if latency == Duration::from_secs(0) => {
                 if let Err(SendError((_, msg))) = sender.send((self.node_id, payload)) {
                     warn!(%dest, %msg, "could not send message (send error)");
 
                     // We do nothing else, the message is just dropped.
                 }
             }
+            Some(sender) => {
+                let sender = sender.clone();
+                let node_id = self.node_id;
+                tokio::spawn(async move {
+                    time::sleep(latency).await;
+                    if let Err(SendError((_, msg))) = sender.send((node_id, payload)) {
+                        warn!(%dest, %msg, "could not send delayed message (send error)");
+                    }
+                });
+            }
             None => info!(%dest, %payload, "dropping message to non-existent recipient"),
         }
     }
@@ -526,7 +594,7 @@ where
 
 impl<P, REv> Component<REv> for InMemoryNetwork<P>
 where
-    P: Display + Clone,
+    P: Display + Clone + Send + 'static,
 {
     type Event = Event<P>;
     type ConstructionError = Infallible;
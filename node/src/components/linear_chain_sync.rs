@@ -14,6 +14,11 @@
 //! 8. Execute that block.
 //! 9. Repeat steps 6-8 as long as there's a child in the linear chain.
 //!
+//! If synchronizing global state is enabled, steps 2-4 are replaced by downloading the global state
+//! of the trusted block from peers, after which the downloaded blocks are stored without being
+//! executed.  Only the deploys of blocks recent enough for them not to have expired are downloaded
+//! in that case, as the block proposer needs them to avoid proposing the same deploys again.
+//!
 //! The order of "download block – download deploys – execute" block steps differ,
 //! in order to increase the chances of catching up with the linear chain quicker.
 //! When synchronizing linear chain up to the trusted hash we cannot execute later blocks without
@@ -41,6 +46,7 @@ use casper_types::{EraId, ProtocolVersion};
 
 use super::{
     fetcher::FetchResult,
+    state_sync,
     storage::{self, Storage},
     Component,
 };
@@ -82,6 +88,11 @@ pub(crate) struct LinearChainSync<I> {
     started_syncing: bool,
     /// The protocol version the node is currently running with.
     protocol_version: ProtocolVersion,
+    /// Whether to download the global state of the trusted block instead of executing its
+    /// ancestors.
+    sync_global_state: bool,
+    /// The maximum time to live of a deploy.
+    deploy_max_ttl: TimeDiff,
 }
 
 impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
@@ -97,6 +108,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         after_upgrade: bool,
         next_upgrade_activation_point: Option<ActivationPoint>,
         config: Config,
+        sync_global_state: bool,
     ) -> Result<(Self, Effects<Event<I>>), Err>
     where
         REv: From<Event<I>> + Send,
@@ -113,6 +125,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
                 state,
                 next_upgrade_activation_point,
                 protocol_version,
+                sync_global_state,
             )?;
             Ok((linear_chain_sync, timeout_event))
         } else {
@@ -155,6 +168,8 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
                 min_round_length: chainspec.highway_config.min_round_length(),
                 started_syncing: false,
                 protocol_version,
+                sync_global_state,
+                deploy_max_ttl: chainspec.deploy_config.max_ttl,
             };
             Ok((linear_chain_sync, timeout_event))
         }
@@ -167,6 +182,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         state: State,
         next_upgrade_activation_point: Option<ActivationPoint>,
         protocol_version: ProtocolVersion,
+        sync_global_state: bool,
     ) -> Result<Self, prometheus::Error> {
         let state_key = create_state_key(chainspec);
         info!(?state, "reusing previous state");
@@ -186,6 +202,8 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
             min_round_length: chainspec.highway_config.min_round_length(),
             started_syncing: false,
             protocol_version,
+            sync_global_state,
+            deploy_max_ttl: chainspec.deploy_config.max_ttl,
         })
     }
 
@@ -236,7 +254,12 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
                     .map(|hdr| hdr.hash() == *block.header().parent_hash())
                     .unwrap_or(false)
                     || block.header().is_genesis_child();
-                if should_start_downloading_deploys {
+                if should_start_downloading_deploys && self.sync_global_state {
+                    info!("linear chain downloaded. Start synchronizing global state.");
+                    effect_builder
+                        .immediately()
+                        .event(move |_| Event::StartSyncingGlobalState)
+                } else if should_start_downloading_deploys {
                     info!("linear chain downloaded. Start downloading deploys.");
                     effect_builder
                         .immediately()
@@ -483,6 +506,115 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         }
     }
 
+    /// Returns effects for downloading the global state of the trusted block.
+    ///
+    /// Falls back to executing the downloaded blocks if one of them would make us shut down for
+    /// an upgrade, as the global state of the trusted block is then of a later protocol version.
+    fn start_syncing_global_state<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+    ) -> Effects<Event<I>>
+    where
+        I: Send + 'static,
+        REv: ReactorEventT<I>,
+    {
+        let trusted_block = match &self.state {
+            // The trusted block is the first one downloaded.
+            State::SyncingTrustedHash { linear_chain, .. } => linear_chain.first().cloned(),
+            State::None | State::Done(_) | State::SyncingDescendants { .. } => None,
+        };
+        let trusted_block = match trusted_block {
+            Some(block) => block,
+            None => {
+                error!(state=?self.state, "tried synchronizing global state when in wrong state");
+                return fatal!(
+                    effect_builder,
+                    "tried synchronizing global state when in wrong state"
+                )
+                .ignore();
+            }
+        };
+        let upgrade_ahead = match &self.state {
+            State::SyncingTrustedHash { linear_chain, .. } => linear_chain.iter().any(|block| {
+                block.header().is_switch_block() && self.should_upgrade(block.header().era_id())
+            }),
+            State::None | State::Done(_) | State::SyncingDescendants { .. } => false,
+        };
+        if upgrade_ahead {
+            info!("upgrade point before the trusted block. Start downloading deploys instead.");
+            self.peers.reset(rng);
+            return self.fetch_next_block_deploys(effect_builder);
+        }
+        let state_root_hash = *trusted_block.header().state_root_hash();
+        info!(%state_root_hash, "synchronizing global state of the trusted block");
+        effect_builder
+            .sync_global_state(state_root_hash)
+            .event(move |result| Event::GlobalStateSynced(Box::new(trusted_block), result))
+    }
+
+    /// Handles the result of synchronizing the global state of the trusted block, storing the
+    /// downloaded blocks without executing them.
+    ///
+    /// The deploys of the blocks whose deploys may not have expired yet are downloaded first, as
+    /// the block proposer reads them from storage.
+    fn global_state_synced<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+        trusted_block: Box<Block>,
+        result: Result<(), state_sync::Error>,
+    ) -> Effects<Event<I>>
+    where
+        I: Send + 'static,
+        REv: ReactorEventT<I>,
+    {
+        if let Err(error) = result {
+            error!(%error, "failed to synchronize global state of the trusted block");
+            return fatal!(effect_builder, "failed to synchronize global state").ignore();
+        }
+        let linear_chain = match &mut self.state {
+            State::SyncingTrustedHash { linear_chain, .. } => mem::take(linear_chain),
+            State::None | State::Done(_) | State::SyncingDescendants { .. } => {
+                error!(state=?self.state, "synchronized global state when in wrong state");
+                return fatal!(
+                    effect_builder,
+                    "synchronized global state when in wrong state"
+                )
+                .ignore();
+            }
+        };
+        // Blocks were downloaded from the trusted one down, so store them in reverse.
+        for block in linear_chain.iter().rev() {
+            if block.header().is_switch_block() {
+                self.state.set_last_switch_block_height(block.height());
+            }
+        }
+        info!(
+            count = linear_chain.len(),
+            "global state synchronized. Storing linear chain blocks without executing them."
+        );
+        let deploy_max_ttl = self.deploy_max_ttl;
+        let peers = self.peers.shuffled(rng);
+        async move {
+            for block in linear_chain.into_iter().rev() {
+                if block.timestamp().elapsed() < deploy_max_ttl
+                    && !fetch_block_deploys_from_any(effect_builder, &peers, &block).await
+                {
+                    error!(
+                        block_hash = %block.hash(),
+                        "could not download deploys of recent linear chain block."
+                    );
+                    fatal!(effect_builder, "failed to download linear chain deploys").await;
+                    return None;
+                }
+                effect_builder.put_block_to_storage(Box::new(block)).await;
+            }
+            Some(())
+        }
+        .map_some(move |()| Event::LinearChainStored(trusted_block))
+    }
+
     fn handle_upgrade_shutdown<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -789,6 +921,16 @@ where
                 self.peers.reset(rng);
                 self.fetch_next_block_deploys(effect_builder)
             }
+            Event::StartSyncingGlobalState => self.start_syncing_global_state(effect_builder, rng),
+            Event::GlobalStateSynced(trusted_block, result) => {
+                self.global_state_synced(effect_builder, rng, trusted_block, result)
+            }
+            Event::LinearChainStored(trusted_block) => {
+                // The trusted block wasn't executed, but its global state is in place: continue
+                // as if it had been.
+                self.set_last_block_if_syncing_trusted_hash(&trusted_block);
+                self.block_handled(rng, effect_builder, *trusted_block)
+            }
             Event::NewPeerConnected(peer_id) => {
                 trace!(%peer_id, "new peer connected");
                 // Add to the set of peers we can request things from.
@@ -858,6 +1000,29 @@ where
         })
 }
 
+/// Downloads the deploys of `block` into storage, asking each of `peers` in turn.
+///
+/// Returns `false` if none of the peers had all of them.
+async fn fetch_block_deploys_from_any<I: Clone + Send + 'static, REv>(
+    effect_builder: EffectBuilder<REv>,
+    peers: &[I],
+    block: &Block,
+) -> bool
+where
+    REv: ReactorEventT<I>,
+{
+    for peer in peers {
+        if effect_builder
+            .validate_block(peer.clone(), block.clone())
+            .await
+        {
+            return true;
+        }
+        trace!(block_hash = %block.hash(), "deploys for linear chain block not found on peer");
+    }
+    false
+}
+
 fn fetch_block_by_hash<I: Clone + Send + 'static, REv>(
     effect_builder: EffectBuilder<REv>,
     peer: I,
@@ -1,4 +1,7 @@
-use crate::types::{ActivationPoint, Block, BlockHash};
+use crate::{
+    components::state_sync,
+    types::{ActivationPoint, Block, BlockHash},
+};
 
 use std::fmt::{Debug, Display};
 
@@ -9,6 +12,12 @@ pub enum Event<I> {
     GetBlockHeightResult(u64, BlockByHeightResult<I>),
     GetDeploysResult(DeploysResult<I>),
     StartDownloadingDeploys,
+    /// An event instructing us to download the global state of the trusted block.
+    StartSyncingGlobalState,
+    /// The result of synchronizing the global state of the trusted block.
+    GlobalStateSynced(Box<Block>, Result<(), state_sync::Error>),
+    /// The downloaded linear chain, up to the trusted block, has been stored.
+    LinearChainStored(Box<Block>),
     NewPeerConnected(I),
     BlockHandled(Box<Block>),
     GotUpgradeActivationPoint(ActivationPoint),
@@ -52,6 +61,19 @@ where
                 write!(f, "Get deploys for block result {:?}", result)
             }
             Event::StartDownloadingDeploys => write!(f, "Start downloading deploys event."),
+            Event::StartSyncingGlobalState => write!(f, "Start synchronizing global state event."),
+            Event::GlobalStateSynced(block, result) => match result {
+                Ok(()) => write!(f, "Synchronized global state of block {}", block.hash()),
+                Err(error) => write!(
+                    f,
+                    "Failed to synchronize global state of block {}: {}",
+                    block.hash(),
+                    error
+                ),
+            },
+            Event::LinearChainStored(block) => {
+                write!(f, "Linear chain stored up to block {}", block.hash())
+            }
             Event::NewPeerConnected(peer_id) => write!(f, "A new peer connected: {}", peer_id),
             Event::BlockHandled(block) => {
                 let hash = block.hash();
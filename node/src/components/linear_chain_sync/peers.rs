@@ -57,6 +57,13 @@ impl<I: Clone + PartialEq + 'static> PeersState<I> {
         self.succ_peers.retain(|p| p != peer);
     }
 
+    /// Returns all peers we can request data from, in random order.
+    pub(crate) fn shuffled<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<I> {
+        let mut peers = self.peers.clone();
+        peers.as_mut_slice().shuffle(rng);
+        peers
+    }
+
     /// Returns whether known peer set is empty.
     pub(crate) fn is_empty(&self) -> bool {
         self.peers.is_empty()
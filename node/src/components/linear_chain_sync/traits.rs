@@ -3,7 +3,7 @@ use crate::{
         announcements::ControlAnnouncement,
         requests::{
             BlockValidationRequest, ContractRuntimeRequest, FetcherRequest, StateStoreRequest,
-            StorageRequest,
+            StateSyncRequest, StorageRequest,
         },
     },
     types::{Block, BlockByHeight},
@@ -15,6 +15,7 @@ pub trait ReactorEventT<I>:
     + From<BlockValidationRequest<I>>
     + From<ContractRuntimeRequest>
     + From<StateStoreRequest>
+    + From<StateSyncRequest>
     + From<ControlAnnouncement>
     + Send
 {
@@ -27,6 +28,7 @@ impl<I, REv> ReactorEventT<I> for REv where
         + From<BlockValidationRequest<I>>
         + From<ContractRuntimeRequest>
         + From<StateStoreRequest>
+        + From<StateSyncRequest>
         + From<ControlAnnouncement>
         + Send
 {
@@ -12,7 +12,7 @@ mod tests;
 mod tests_bulk_gossip;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env,
     fmt::{self, Debug, Display, Formatter},
     marker::PhantomData,
@@ -898,15 +898,17 @@ impl<REv: ReactorEventT<P>, P: PayloadT> Component<REv> for Network<REv, P> {
                 num_established,
                 cause,
             } => {
-                if num_established == 0 {
-                    let _ = self.peers.remove(&peer_id);
-                }
+                let disconnected = num_established == 0 && self.peers.remove(&peer_id).is_some();
                 debug!(%peer_id, ?endpoint, %num_established, ?cause, "{}: connection closed", self.our_id);
 
                 // Note: We count multiple connections to the same peer as a single connection.
                 self.net_metrics.peers.set(self.peers.len() as i64);
 
-                Effects::new()
+                if disconnected {
+                    effect_builder.announce_peer_disconnected(*peer_id).ignore()
+                } else {
+                    Effects::new()
+                }
             }
             Event::UnreachableAddress {
                 peer_id,
@@ -1018,6 +1020,14 @@ impl<REv: ReactorEventT<P>, P: PayloadT> Component<REv> for Network<REv, P> {
                         .collect();
                     responder.respond(peers).ignore()
                 }
+                NetworkInfoRequest::GetPeerScores { responder } => {
+                    // Peer reputation is not tracked by this component.
+                    responder.respond(BTreeMap::new()).ignore()
+                }
+                NetworkInfoRequest::GetPeerServesTries { responder, .. } => {
+                    // Capabilities are not exchanged by this component.
+                    responder.respond(false).ignore()
+                }
             },
         }
     }
@@ -130,8 +130,9 @@ impl Reactor for TestReactor {
             )) => {
                 unreachable!();
             }
-            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(_)) => {
-                // We do not care about the announcement of new peers in this test.
+            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(_))
+            | Event::NetworkAnnouncement(NetworkAnnouncement::PeerDisconnected(_)) => {
+                // We do not care about the announcement of new or lost peers in this test.
                 Effects::new()
             }
         }
@@ -1,9 +1,9 @@
-use std::sync::Weak;
+use std::{sync::Weak, time::Instant};
 
-use prometheus::{IntCounter, IntGauge, Registry};
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
 use tracing::debug;
 
-use super::small_network::MessageKind;
+use super::small_network::{MessageKind, TrafficClass};
 use crate::unregister_metric;
 
 /// Network-type agnostic networking metrics.
@@ -19,6 +19,8 @@ pub(super) struct NetworkingMetrics {
     pub(super) queued_messages: IntGauge,
     /// Number of connected peers.
     pub(super) peers: IntGauge,
+    /// Number of banned peers.
+    pub(super) banned_peers: IntGauge,
 
     /// Count of outgoing messages that are protocol overhead.
     pub(super) out_count_protocol: IntCounter,
@@ -32,6 +34,8 @@ pub(super) struct NetworkingMetrics {
     pub(super) out_count_deploy_transfer: IntCounter,
     /// Count of outgoing messages with block request/response payload.
     pub(super) out_count_block_transfer: IntCounter,
+    /// Count of outgoing messages with trie request/response payload.
+    pub(super) out_count_trie_transfer: IntCounter,
     /// Count of outgoing messages with other payload.
     pub(super) out_count_other: IntCounter,
 
@@ -47,9 +51,29 @@ pub(super) struct NetworkingMetrics {
     pub(super) out_bytes_deploy_transfer: IntCounter,
     /// Volume in bytes of outgoing messages with block request/response payload.
     pub(super) out_bytes_block_transfer: IntCounter,
+    /// Volume in bytes of outgoing messages with trie request/response payload.
+    pub(super) out_bytes_trie_transfer: IntCounter,
     /// Volume in bytes of outgoing messages with other payload.
     pub(super) out_bytes_other: IntCounter,
 
+    /// Number of outgoing consensus, finality signature and protocol messages waiting to be sent.
+    pub(super) out_queue_depth_consensus: IntGauge,
+    /// Number of outgoing gossip messages waiting to be sent.
+    pub(super) out_queue_depth_gossip: IntGauge,
+    /// Number of outgoing request/response messages waiting to be sent.
+    pub(super) out_queue_depth_transfer: IntGauge,
+    /// Number of outgoing other messages waiting to be sent.
+    pub(super) out_queue_depth_other: IntGauge,
+    /// Time outgoing consensus, finality signature and protocol messages spent waiting to be sent,
+    /// in seconds.
+    pub(super) out_queue_latency_consensus: Histogram,
+    /// Time outgoing gossip messages spent waiting to be sent, in seconds.
+    pub(super) out_queue_latency_gossip: Histogram,
+    /// Time outgoing request/response messages spent waiting to be sent, in seconds.
+    pub(super) out_queue_latency_transfer: Histogram,
+    /// Time outgoing other messages spent waiting to be sent, in seconds.
+    pub(super) out_queue_latency_other: Histogram,
+
     // Potentially temporary metrics, not supported by all networking components:
     /// Number of do-nothing futures that have not finished executing for read requests.
     pub(super) read_futures_in_flight: prometheus::Gauge,
@@ -80,6 +104,7 @@ impl NetworkingMetrics {
             "number of messages waiting to be sent out",
         )?;
         let peers = IntGauge::new("peers", "number of connected peers")?;
+        let banned_peers = IntGauge::new("banned_peers", "number of banned peers")?;
 
         let out_count_protocol = IntCounter::new(
             "net_out_count_protocol",
@@ -105,6 +130,10 @@ impl NetworkingMetrics {
             "net_out_count_block_transfer",
             "count of outgoing messages with block request/response payload",
         )?;
+        let out_count_trie_transfer = IntCounter::new(
+            "net_out_count_trie_transfer",
+            "count of outgoing messages with trie request/response payload",
+        )?;
         let out_count_other = IntCounter::new(
             "net_out_count_other",
             "count of outgoing messages with other payload",
@@ -134,11 +163,61 @@ impl NetworkingMetrics {
             "net_out_bytes_block_transfer",
             "volume in bytes of outgoing messages with block request/response payload",
         )?;
+        let out_bytes_trie_transfer = IntCounter::new(
+            "net_out_bytes_trie_transfer",
+            "volume in bytes of outgoing messages with trie request/response payload",
+        )?;
         let out_bytes_other = IntCounter::new(
             "net_out_bytes_other",
             "volume in bytes of outgoing messages with other payload",
         )?;
 
+        let out_queue_depth_consensus = IntGauge::new(
+            "net_out_queue_depth_consensus",
+            "number of outgoing consensus messages waiting to be sent",
+        )?;
+        let out_queue_depth_gossip = IntGauge::new(
+            "net_out_queue_depth_gossip",
+            "number of outgoing gossip messages waiting to be sent",
+        )?;
+        let out_queue_depth_transfer = IntGauge::new(
+            "net_out_queue_depth_transfer",
+            "number of outgoing request/response messages waiting to be sent",
+        )?;
+        let out_queue_depth_other = IntGauge::new(
+            "net_out_queue_depth_other",
+            "number of outgoing other messages waiting to be sent",
+        )?;
+
+        let out_queue_latency_consensus = Histogram::with_opts(
+            HistogramOpts::new(
+                "net_out_queue_latency_consensus",
+                "time in seconds outgoing consensus messages spent waiting to be sent",
+            )
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 17)?),
+        )?;
+        let out_queue_latency_gossip = Histogram::with_opts(
+            HistogramOpts::new(
+                "net_out_queue_latency_gossip",
+                "time in seconds outgoing gossip messages spent waiting to be sent",
+            )
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 17)?),
+        )?;
+        let out_queue_latency_transfer = Histogram::with_opts(
+            HistogramOpts::new(
+                "net_out_queue_latency_transfer",
+                "time in seconds outgoing request/response messages spent waiting to be sent",
+            )
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 17)?),
+        )?;
+        let out_queue_latency_other = Histogram::with_opts(
+            HistogramOpts::new(
+                "net_out_queue_latency_other",
+                "time in seconds outgoing other messages spent waiting to be sent",
+            )
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 17)?),
+        )?;
+
         let read_futures_in_flight = prometheus::Gauge::new(
             "owm_read_futures_in_flight",
             "number of do-nothing futures in flight created by `Codec::read_response`",
@@ -161,6 +240,7 @@ impl NetworkingMetrics {
         registry.register(Box::new(open_connections.clone()))?;
         registry.register(Box::new(queued_messages.clone()))?;
         registry.register(Box::new(peers.clone()))?;
+        registry.register(Box::new(banned_peers.clone()))?;
 
         registry.register(Box::new(out_count_protocol.clone()))?;
         registry.register(Box::new(out_count_consensus.clone()))?;
@@ -168,6 +248,7 @@ impl NetworkingMetrics {
         registry.register(Box::new(out_count_address_gossip.clone()))?;
         registry.register(Box::new(out_count_deploy_transfer.clone()))?;
         registry.register(Box::new(out_count_block_transfer.clone()))?;
+        registry.register(Box::new(out_count_trie_transfer.clone()))?;
         registry.register(Box::new(out_count_other.clone()))?;
 
         registry.register(Box::new(out_bytes_protocol.clone()))?;
@@ -176,8 +257,18 @@ impl NetworkingMetrics {
         registry.register(Box::new(out_bytes_address_gossip.clone()))?;
         registry.register(Box::new(out_bytes_deploy_transfer.clone()))?;
         registry.register(Box::new(out_bytes_block_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_trie_transfer.clone()))?;
         registry.register(Box::new(out_bytes_other.clone()))?;
 
+        registry.register(Box::new(out_queue_depth_consensus.clone()))?;
+        registry.register(Box::new(out_queue_depth_gossip.clone()))?;
+        registry.register(Box::new(out_queue_depth_transfer.clone()))?;
+        registry.register(Box::new(out_queue_depth_other.clone()))?;
+        registry.register(Box::new(out_queue_latency_consensus.clone()))?;
+        registry.register(Box::new(out_queue_latency_gossip.clone()))?;
+        registry.register(Box::new(out_queue_latency_transfer.clone()))?;
+        registry.register(Box::new(out_queue_latency_other.clone()))?;
+
         registry.register(Box::new(read_futures_in_flight.clone()))?;
         registry.register(Box::new(read_futures_total.clone()))?;
         registry.register(Box::new(write_futures_in_flight.clone()))?;
@@ -189,12 +280,14 @@ impl NetworkingMetrics {
             open_connections,
             queued_messages,
             peers,
+            banned_peers,
             out_count_protocol,
             out_count_consensus,
             out_count_deploy_gossip,
             out_count_address_gossip,
             out_count_deploy_transfer,
             out_count_block_transfer,
+            out_count_trie_transfer,
             out_count_other,
             out_bytes_protocol,
             out_bytes_consensus,
@@ -202,7 +295,16 @@ impl NetworkingMetrics {
             out_bytes_address_gossip,
             out_bytes_deploy_transfer,
             out_bytes_block_transfer,
+            out_bytes_trie_transfer,
             out_bytes_other,
+            out_queue_depth_consensus,
+            out_queue_depth_gossip,
+            out_queue_depth_transfer,
+            out_queue_depth_other,
+            out_queue_latency_consensus,
+            out_queue_latency_gossip,
+            out_queue_latency_transfer,
+            out_queue_latency_other,
             read_futures_in_flight,
             read_futures_total,
             write_futures_in_flight,
@@ -239,6 +341,10 @@ impl NetworkingMetrics {
                     metrics.out_bytes_block_transfer.inc_by(size);
                     metrics.out_count_block_transfer.inc();
                 }
+                MessageKind::TrieTransfer => {
+                    metrics.out_bytes_trie_transfer.inc_by(size);
+                    metrics.out_count_trie_transfer.inc();
+                }
                 MessageKind::Other => {
                     metrics.out_bytes_other.inc_by(size);
                     metrics.out_count_other.inc();
@@ -248,6 +354,36 @@ impl NetworkingMetrics {
             debug!("not recording metrics, component already shut down");
         }
     }
+
+    /// Records a change in the number of outgoing messages of the given class waiting to be sent.
+    pub(super) fn record_queue_depth_change(this: &Weak<Self>, class: TrafficClass, delta: i64) {
+        if let Some(metrics) = this.upgrade() {
+            let gauge = match class {
+                TrafficClass::Consensus => &metrics.out_queue_depth_consensus,
+                TrafficClass::Gossip => &metrics.out_queue_depth_gossip,
+                TrafficClass::Transfer => &metrics.out_queue_depth_transfer,
+                TrafficClass::Other => &metrics.out_queue_depth_other,
+            };
+            gauge.add(delta);
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records the time an outgoing message of the given class spent waiting to be sent.
+    pub(super) fn record_queue_latency(this: &Weak<Self>, class: TrafficClass, queued_at: Instant) {
+        if let Some(metrics) = this.upgrade() {
+            let histogram = match class {
+                TrafficClass::Consensus => &metrics.out_queue_latency_consensus,
+                TrafficClass::Gossip => &metrics.out_queue_latency_gossip,
+                TrafficClass::Transfer => &metrics.out_queue_latency_transfer,
+                TrafficClass::Other => &metrics.out_queue_latency_other,
+            };
+            histogram.observe(queued_at.elapsed().as_secs_f64());
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
 }
 
 impl Drop for NetworkingMetrics {
@@ -257,6 +393,7 @@ impl Drop for NetworkingMetrics {
         unregister_metric!(self.registry, self.open_connections);
         unregister_metric!(self.registry, self.queued_messages);
         unregister_metric!(self.registry, self.peers);
+        unregister_metric!(self.registry, self.banned_peers);
 
         unregister_metric!(self.registry, self.out_count_protocol);
         unregister_metric!(self.registry, self.out_count_consensus);
@@ -264,6 +401,7 @@ impl Drop for NetworkingMetrics {
         unregister_metric!(self.registry, self.out_count_address_gossip);
         unregister_metric!(self.registry, self.out_count_deploy_transfer);
         unregister_metric!(self.registry, self.out_count_block_transfer);
+        unregister_metric!(self.registry, self.out_count_trie_transfer);
         unregister_metric!(self.registry, self.out_count_other);
         unregister_metric!(self.registry, self.out_bytes_protocol);
         unregister_metric!(self.registry, self.out_bytes_consensus);
@@ -271,8 +409,18 @@ impl Drop for NetworkingMetrics {
         unregister_metric!(self.registry, self.out_bytes_address_gossip);
         unregister_metric!(self.registry, self.out_bytes_deploy_transfer);
         unregister_metric!(self.registry, self.out_bytes_block_transfer);
+        unregister_metric!(self.registry, self.out_bytes_trie_transfer);
         unregister_metric!(self.registry, self.out_bytes_other);
 
+        unregister_metric!(self.registry, self.out_queue_depth_consensus);
+        unregister_metric!(self.registry, self.out_queue_depth_gossip);
+        unregister_metric!(self.registry, self.out_queue_depth_transfer);
+        unregister_metric!(self.registry, self.out_queue_depth_other);
+        unregister_metric!(self.registry, self.out_queue_latency_consensus);
+        unregister_metric!(self.registry, self.out_queue_latency_gossip);
+        unregister_metric!(self.registry, self.out_queue_latency_transfer);
+        unregister_metric!(self.registry, self.out_queue_latency_other);
+
         unregister_metric!(self.registry, self.read_futures_in_flight);
         unregister_metric!(self.registry, self.read_futures_total);
         unregister_metric!(self.registry, self.write_futures_in_flight);
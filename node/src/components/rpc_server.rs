@@ -15,14 +15,17 @@
 //! <https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs>
 
 mod config;
+mod dispatcher;
 mod event;
 mod http_server;
 pub mod rpcs;
 
-use std::{convert::Infallible, fmt::Debug};
+use std::{convert::Infallible, fmt::Debug, sync::Arc};
 
 use datasize::DataSize;
 use futures::join;
+use thiserror::Error;
+use tokio::sync::Semaphore;
 
 use casper_execution_engine::{
     core::engine_state::{
@@ -31,23 +34,23 @@ use casper_execution_engine::{
     },
     storage::protocol_data::ProtocolData,
 };
-use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
+use casper_types::{system::auction::EraValidators, ExecutionResult, Key, ProtocolVersion, URef};
 
 use self::rpcs::chain::BlockIdentifier;
 
 use super::Component;
 use crate::{
-    components::contract_runtime::EraValidatorsRequest,
+    components::contract_runtime::{EraValidatorsRequest, SpeculativeExecutionState},
     crypto::hash::Digest,
     effect::{
         announcements::RpcServerAnnouncement,
         requests::{
-            ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, LinearChainRequest,
-            MetricsRequest, NetworkInfoRequest, RpcRequest, StorageRequest,
+            BlockProposerRequest, ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest,
+            LinearChainRequest, MetricsRequest, NetworkInfoRequest, RpcRequest, StorageRequest,
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{NodeId, StatusFeed},
+    types::{Deploy, NodeId, StatusFeed},
     utils::{self, ListeningError},
     NodeRng,
 };
@@ -60,6 +63,7 @@ pub trait ReactorEventT:
     From<Event>
     + From<RpcRequest<NodeId>>
     + From<RpcServerAnnouncement>
+    + From<BlockProposerRequest>
     + From<ChainspecLoaderRequest>
     + From<ContractRuntimeRequest>
     + From<ConsensusRequest>
@@ -75,6 +79,7 @@ impl<REv> ReactorEventT for REv where
     REv: From<Event>
         + From<RpcRequest<NodeId>>
         + From<RpcServerAnnouncement>
+        + From<BlockProposerRequest>
         + From<ChainspecLoaderRequest>
         + From<ContractRuntimeRequest>
         + From<ConsensusRequest>
@@ -87,8 +92,27 @@ impl<REv> ReactorEventT for REv where
 {
 }
 
+/// An error returned when speculatively executing a deploy.
+#[derive(Debug, Error)]
+pub enum SpeculativeExecError {
+    /// The node is not configured to serve speculative executions.
+    #[error("speculative execution is disabled on this node")]
+    Disabled,
+    /// The maximum number of concurrent speculative executions has been reached.
+    #[error("too many speculative executions in progress, try again later")]
+    Busy,
+    /// The deploy could not be executed.
+    #[error(transparent)]
+    Engine(#[from] engine_state::Error),
+}
+
 #[derive(DataSize, Debug)]
-pub(crate) struct RpcServer {}
+pub(crate) struct RpcServer {
+    /// Permits for speculative executions, one per execution allowed to run concurrently.  `None`
+    /// if speculative execution is disabled.
+    #[data_size(skip)]
+    speculative_exec_permits: Option<Arc<Semaphore>>,
+}
 
 impl RpcServer {
     pub(crate) fn new<REv>(
@@ -105,9 +129,20 @@ impl RpcServer {
             effect_builder,
             api_version,
             config.qps_limit,
+            config.max_batch_size,
         ));
 
-        Ok(RpcServer {})
+        let speculative_exec_permits = if config.enable_speculative_exec {
+            Some(Arc::new(Semaphore::new(
+                config.max_concurrent_speculative_execs,
+            )))
+        } else {
+            None
+        };
+
+        Ok(RpcServer {
+            speculative_exec_permits,
+        })
     }
 }
 
@@ -174,6 +209,38 @@ impl RpcServer {
                 main_responder: responder,
             })
     }
+
+    fn handle_speculative_execute<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        execution_prestate: SpeculativeExecutionState,
+        deploy: Box<Deploy>,
+        responder: Responder<Result<Option<ExecutionResult>, SpeculativeExecError>>,
+    ) -> Effects<Event> {
+        let permit = match &self.speculative_exec_permits {
+            None => {
+                return responder
+                    .respond(Err(SpeculativeExecError::Disabled))
+                    .ignore()
+            }
+            Some(permits) => match Arc::clone(permits).try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => return responder.respond(Err(SpeculativeExecError::Busy)).ignore(),
+            },
+        };
+        async move {
+            let result = effect_builder
+                .speculative_execute_deploy(execution_prestate, deploy)
+                .await;
+            // Only release the permit once the execution has completed.
+            drop(permit);
+            result
+        }
+        .event(move |result| Event::SpeculativeDeployExecuteResult {
+            result,
+            main_responder: responder,
+        })
+    }
 }
 
 impl<REv> Component<REv> for RpcServer
@@ -193,6 +260,16 @@ where
             Event::RpcRequest(RpcRequest::SubmitDeploy { deploy, responder }) => effect_builder
                 .announce_deploy_received(deploy, Some(responder))
                 .ignore(),
+            Event::RpcRequest(RpcRequest::SpeculativeDeployExecute {
+                execution_prestate,
+                deploy,
+                responder,
+            }) => self.handle_speculative_execute(
+                effect_builder,
+                execution_prestate,
+                deploy,
+                responder,
+            ),
             Event::RpcRequest(RpcRequest::GetBlock {
                 maybe_id: Some(BlockIdentifier::Hash(hash)),
                 responder,
@@ -277,12 +354,49 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetCallTraces {
+                deploy_hash,
+                responder,
+            }) => effect_builder
+                .get_call_traces_from_storage(deploy_hash)
+                .event(move |result| Event::GetCallTracesResult {
+                    deploy_hash,
+                    result,
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetAccountHistory {
+                account_hash,
+                offset,
+                limit,
+                responder,
+            }) => effect_builder
+                .get_account_history_from_storage(account_hash, offset, limit)
+                .event(move |result| Event::GetAccountHistoryResult {
+                    account_hash,
+                    result: Box::new(result),
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetPendingDeploys {
+                account_hash,
+                responder,
+            }) => effect_builder
+                .get_pending_deploys(account_hash)
+                .event(move |result| Event::GetPendingDeploysResult {
+                    account_hash,
+                    result,
+                    main_responder: responder,
+                }),
             Event::RpcRequest(RpcRequest::GetPeers { responder }) => effect_builder
                 .network_peers()
                 .event(move |peers| Event::GetPeersResult {
                     peers,
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetPeerScores { responder }) => async move {
+                let scores = effect_builder.network_peer_scores().await;
+                responder.respond(scores).await;
+            }
+            .ignore(),
             Event::RpcRequest(RpcRequest::GetStatus { responder }) => async move {
                 let (last_added_block, peers, chainspec_info, consensus_status) = join!(
                     effect_builder.get_highest_block_from_storage(),
@@ -336,6 +450,21 @@ where
                 result,
                 main_responder,
             } => main_responder.respond(*result).ignore(),
+            Event::GetCallTracesResult {
+                result,
+                main_responder,
+                ..
+            } => main_responder.respond(result).ignore(),
+            Event::GetAccountHistoryResult {
+                result,
+                main_responder,
+                ..
+            } => main_responder.respond(*result).ignore(),
+            Event::GetPendingDeploysResult {
+                result,
+                main_responder,
+                ..
+            } => main_responder.respond(result).ignore(),
             Event::GetPeersResult {
                 peers,
                 main_responder,
@@ -344,6 +473,152 @@ where
                 text,
                 main_responder,
             } => main_responder.respond(text).ignore(),
+            Event::SpeculativeDeployExecuteResult {
+                result,
+                main_responder,
+            } => main_responder
+                .respond(result.map_err(SpeculativeExecError::from))
+                .ignore(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use derive_more::From;
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{
+        effect::announcements::ControlAnnouncement,
+        reactor::ReactorEvent,
+        testing::{ComponentHarness, TestRng},
+        types::Block,
+    };
+
+    /// Reactor event wrapping everything the RPC server may send.
+    #[derive(Debug, From)]
+    enum TestEvent {
+        #[from]
+        RpcServer(Event),
+        #[from]
+        Rpc(RpcRequest<NodeId>),
+        #[from]
+        RpcServerAnnouncement(RpcServerAnnouncement),
+        #[from]
+        BlockProposer(BlockProposerRequest),
+        #[from]
+        ChainspecLoader(ChainspecLoaderRequest),
+        #[from]
+        ContractRuntime(ContractRuntimeRequest),
+        #[from]
+        Consensus(ConsensusRequest),
+        #[from]
+        LinearChain(LinearChainRequest<NodeId>),
+        #[from]
+        Metrics(MetricsRequest),
+        #[from]
+        NetworkInfo(NetworkInfoRequest<NodeId>),
+        #[from]
+        Storage(StorageRequest),
+        #[from]
+        Control(ControlAnnouncement),
+    }
+
+    impl ReactorEvent for TestEvent {
+        fn as_control(&self) -> Option<&ControlAnnouncement> {
+            match self {
+                TestEvent::Control(ctrl_ann) => Some(ctrl_ann),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns a function creating a request to speculatively execute a random deploy.
+    fn speculative_exec_request(
+        rng: &mut TestRng,
+    ) -> impl FnOnce(Responder<Result<Option<ExecutionResult>, SpeculativeExecError>>) -> Event
+    {
+        let execution_prestate = SpeculativeExecutionState::from(&Block::random(rng));
+        let deploy = Box::new(Deploy::random(rng));
+        move |responder| {
+            Event::RpcRequest(RpcRequest::SpeculativeDeployExecute {
+                execution_prestate,
+                deploy,
+                responder,
+            })
+        }
+    }
+
+    #[test]
+    fn should_reject_speculative_exec_when_disabled() {
+        let mut harness = ComponentHarness::<TestEvent>::default();
+        let mut rpc_server = RpcServer {
+            speculative_exec_permits: None,
+        };
+
+        let request = speculative_exec_request(&mut harness.rng);
+        let result = harness.send_request(&mut rpc_server, request);
+        assert!(matches!(result, Err(SpeculativeExecError::Disabled)));
+    }
+
+    #[test]
+    fn should_limit_concurrent_speculative_execs() {
+        let mut harness = ComponentHarness::<TestEvent>::default();
+        let scheduler = harness.scheduler;
+        let mut rpc_server = RpcServer {
+            speculative_exec_permits: Some(Arc::new(Semaphore::new(1))),
+        };
+
+        // The first request is passed on to the contract runtime.
+        let (sender, receiver) = oneshot::channel();
+        let request = speculative_exec_request(&mut harness.rng)(Responder::create(sender));
+        let mut effects = harness.send_event(&mut rpc_server, request);
+        assert_eq!(effects.len(), 1);
+        let execution = harness.runtime.spawn(effects.pop().unwrap());
+        let responder = match harness.runtime.block_on(scheduler.pop()) {
+            (
+                TestEvent::ContractRuntime(ContractRuntimeRequest::SpeculativeDeployExecution {
+                    responder,
+                    ..
+                }),
+                _,
+            ) => responder,
+            (other, _) => panic!("unexpected event: {:?}", other),
+        };
+
+        // A second request is rejected while the first one is still executing.
+        let request = speculative_exec_request(&mut harness.rng);
+        let result = harness.send_request(&mut rpc_server, request);
+        assert!(matches!(result, Err(SpeculativeExecError::Busy)));
+
+        // Complete the first execution.
+        let events = harness.runtime.block_on(async move {
+            responder.respond(Ok(None)).await;
+            execution.await.unwrap()
+        });
+        for event in events {
+            for effect in harness.send_event(&mut rpc_server, event) {
+                harness.runtime.spawn(effect);
+            }
+        }
+        let result = harness.runtime.block_on(receiver).unwrap();
+        assert!(matches!(result, Ok(None)));
+
+        // Further requests are accepted again.
+        let (sender, _receiver) = oneshot::channel();
+        let request = speculative_exec_request(&mut harness.rng)(Responder::create(sender));
+        for effect in harness.send_event(&mut rpc_server, request) {
+            harness.runtime.spawn(effect);
+        }
+        assert!(matches!(
+            harness.runtime.block_on(scheduler.pop()),
+            (
+                TestEvent::ContractRuntime(
+                    ContractRuntimeRequest::SpeculativeDeployExecution { .. }
+                ),
+                _
+            )
+        ));
+    }
+}
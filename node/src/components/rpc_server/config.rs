@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 const DEFAULT_ADDRESS: &str = "0.0.0.0:0";
 /// Default rate limit in qps.
 const DEFAULT_QPS_LIMIT: u64 = 100;
+/// Default maximum number of deploys being speculatively executed at the same time.
+const DEFAULT_MAX_CONCURRENT_SPECULATIVE_EXECS: usize = 1;
+/// Default maximum number of requests in a single batch.
+const DEFAULT_MAX_BATCH_SIZE: usize = 20;
 
 /// JSON-RPC HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -16,8 +20,19 @@ pub struct Config {
     /// Address to bind JSON-RPC HTTP server to.
     pub address: String,
 
-    /// Max rate limit in qps.
+    /// Max rate limit in qps, shared by all transports.  Each request in a batch counts
+    /// separately.
     pub qps_limit: u64,
+
+    /// Whether to serve the `speculative_exec` RPC.
+    pub enable_speculative_exec: bool,
+
+    /// Maximum number of deploys being speculatively executed at the same time.  Further requests
+    /// are rejected until one of them completes.
+    pub max_concurrent_speculative_execs: usize,
+
+    /// Maximum number of requests in a single batch.  Larger batches are rejected as a whole.
+    pub max_batch_size: usize,
 }
 
 impl Config {
@@ -26,6 +41,9 @@ impl Config {
         Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
+            enable_speculative_exec: false,
+            max_concurrent_speculative_execs: DEFAULT_MAX_CONCURRENT_SPECULATIVE_EXECS,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
         }
     }
 }
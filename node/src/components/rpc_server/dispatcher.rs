@@ -0,0 +1,435 @@
+//! Transport-independent handling of JSON-RPC requests.
+//!
+//! The individual RPCs are implemented as warp filters, which expect a single JSON-RPC request in
+//! the body of an HTTP POST to `/rpc`.  The `Dispatcher` feeds such requests to those filters on
+//! behalf of the different transports, splitting batch requests into their individual requests and
+//! collecting the responses.  All requests handled by the dispatcher, including each request in a
+//! batch, count towards the server's `qps_limit`.
+//!
+//! As per the JSON-RPC 2.0 specification, notifications, i.e. requests without an "id", are
+//! handled but not responded to.  A batch consisting only of notifications has no response at all.
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{future, SinkExt, StreamExt};
+use http::{header::CONTENT_TYPE, Request, Response, StatusCode};
+use hyper::{service::Service, Body};
+use serde_json::Value;
+use tokio::time::{self, Instant};
+use tracing::{debug, warn};
+use warp::ws::{Message, WebSocket};
+
+use super::{
+    http_server::new_error_response,
+    rpcs::{ErrorCode, RPC_API_PATH},
+};
+
+/// The period over which the number of handled requests is limited.
+const LIMIT_PERIOD: Duration = Duration::from_secs(1);
+
+/// Limits the rate at which JSON-RPC requests are handled to `qps_limit` per second.
+///
+/// Clones share the same limit.
+#[derive(Clone, Debug)]
+pub(super) struct RequestLimiter {
+    qps_limit: u64,
+    window: Arc<Mutex<LimitWindow>>,
+}
+
+#[derive(Debug)]
+struct LimitWindow {
+    start: Instant,
+    count: u64,
+}
+
+impl RequestLimiter {
+    pub(super) fn new(qps_limit: u64) -> Self {
+        RequestLimiter {
+            qps_limit,
+            window: Arc::new(Mutex::new(LimitWindow {
+                start: Instant::now(),
+                count: 0,
+            })),
+        }
+    }
+
+    /// Waits until a further request can be handled without exceeding the limit.
+    pub(super) async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut window = self.window.lock().expect("request limiter lock poisoned");
+                let now = Instant::now();
+                if now.duration_since(window.start) >= LIMIT_PERIOD {
+                    window.start = now;
+                    window.count = 0;
+                }
+                if window.count < self.qps_limit {
+                    window.count += 1;
+                    return;
+                }
+                window.start + LIMIT_PERIOD - now
+            };
+            time::sleep(wait).await;
+        }
+    }
+}
+
+/// Passes JSON-RPC requests, either single or batched, to the service implementing the RPCs.
+#[derive(Clone, Debug)]
+pub(super) struct Dispatcher<S> {
+    service: S,
+    limiter: RequestLimiter,
+    max_batch_size: usize,
+}
+
+impl<S> Dispatcher<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + Sync + 'static,
+    S::Error: Debug,
+    S::Future: Send,
+{
+    pub(super) fn new(service: S, limiter: RequestLimiter, max_batch_size: usize) -> Self {
+        Dispatcher {
+            service,
+            limiter,
+            max_batch_size,
+        }
+    }
+
+    /// Handles the body of a JSON-RPC request, which may be a batch of requests.
+    ///
+    /// Returns `None` if there is nothing to respond with, as the body only held notifications.
+    pub(super) async fn handle(&self, body: Bytes) -> Option<Response<Body>> {
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(Value::Array(requests)) => self.handle_batch(requests).await,
+            Ok(request) if is_notification(&request) => {
+                let _ = self.handle_single(body).await;
+                None
+            }
+            // Anything else, including malformed JSON, is left to the RPC service to respond to.
+            _ => Some(self.handle_single(body).await),
+        }
+    }
+
+    /// Serves JSON-RPC requests received as messages over the given WebSocket until the client
+    /// disconnects.
+    ///
+    /// Each text or binary message is treated as the body of a JSON-RPC request, and is answered by
+    /// a text message holding the response, unless it only held notifications.  Requests are
+    /// handled in the order they're received.
+    pub(super) async fn serve_websocket(self, mut websocket: WebSocket) {
+        while let Some(result) = websocket.next().await {
+            let message = match result {
+                Ok(message) => message,
+                Err(error) => {
+                    debug!(%error, "error receiving on JSON-RPC websocket");
+                    break;
+                }
+            };
+            if message.is_close() {
+                break;
+            }
+            if !message.is_text() && !message.is_binary() {
+                // Pings and pongs are handled by the websocket implementation.
+                continue;
+            }
+
+            let response = match self
+                .handle(Bytes::copy_from_slice(message.as_bytes()))
+                .await
+            {
+                Some(response) => response,
+                None => continue,
+            };
+            let response_text = match hyper::body::to_bytes(response.into_body()).await {
+                Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Err(error) => {
+                    warn!(%error, "failed to read JSON-RPC response");
+                    continue;
+                }
+            };
+            if let Err(error) = websocket.send(Message::text(response_text)).await {
+                debug!(%error, "error sending on JSON-RPC websocket");
+                break;
+            }
+        }
+        let _ = websocket.close().await;
+    }
+
+    async fn handle_single(&self, body: Bytes) -> Response<Body> {
+        self.limiter.acquire().await;
+        let request = Request::post(format!("/{}", RPC_API_PATH))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("should build request");
+
+        let mut service = self.service.clone();
+        let result = match future::poll_fn(|cx| service.poll_ready(cx)).await {
+            Ok(()) => service.call(request).await,
+            Err(error) => Err(error),
+        };
+        result.unwrap_or_else(|error| {
+            warn!(?error, "failed to handle JSON-RPC request");
+            new_error_response(warp_json_rpc::Error::INTERNAL_ERROR)
+        })
+    }
+
+    /// Handles each request of a batch in turn, responding with the array of the responses to those
+    /// which aren't notifications.
+    ///
+    /// As per the JSON-RPC 2.0 specification, an empty batch is answered with a single "invalid
+    /// request" error.  A batch of more than `max_batch_size` requests is rejected without handling
+    /// any of them.
+    async fn handle_batch(&self, requests: Vec<Value>) -> Option<Response<Body>> {
+        if requests.is_empty() {
+            return Some(new_error_response(warp_json_rpc::Error::INVALID_REQUEST));
+        }
+        if requests.len() > self.max_batch_size {
+            let error = warp_json_rpc::Error::custom(
+                ErrorCode::BatchTooLarge as i64,
+                format!(
+                    "batch of {} requests exceeds the limit of {}",
+                    requests.len(),
+                    self.max_batch_size
+                ),
+            );
+            return Some(new_error_response(error));
+        }
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            let notification = is_notification(&request);
+            let body = Bytes::from(serde_json::to_vec(&request).expect("should serialize JSON"));
+            let response = self.handle_single(body).await;
+            if notification {
+                continue;
+            }
+            let response_bytes = match hyper::body::to_bytes(response.into_body()).await {
+                Ok(bytes) => bytes,
+                Err(error) => {
+                    warn!(%error, "failed to read JSON-RPC response in batch");
+                    continue;
+                }
+            };
+            match serde_json::from_slice::<Value>(&response_bytes) {
+                Ok(response) => responses.push(response),
+                Err(error) => warn!(%error, "invalid JSON-RPC response in batch"),
+            }
+        }
+
+        if responses.is_empty() {
+            return None;
+        }
+        let body = Body::from(serde_json::to_vec(&responses).expect("should serialize JSON"));
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .expect("should build response");
+        Some(response)
+    }
+}
+
+/// Returns `true` if the given request is a notification, i.e. a request object without an "id".
+fn is_notification(request: &Value) -> bool {
+    request
+        .as_object()
+        .map_or(false, |request| !request.contains_key("id"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        task::{Context, Poll},
+    };
+
+    use futures::{future::BoxFuture, FutureExt};
+    use serde_json::json;
+    use warp::Filter;
+
+    use super::*;
+
+    /// A service which responds to every request with the request's "method" as the result.
+    #[derive(Clone)]
+    struct EchoService;
+
+    impl Service<Request<Body>> for EchoService {
+        type Response = Response<Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<'static, Result<Response<Body>, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<Body>) -> Self::Future {
+            async move {
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": request["method"],
+                });
+                Ok(Response::new(Body::from(response.to_string())))
+            }
+            .boxed()
+        }
+    }
+
+    const MAX_BATCH_SIZE: usize = 3;
+
+    fn echo_dispatcher() -> Dispatcher<EchoService> {
+        Dispatcher::new(EchoService, RequestLimiter::new(100), MAX_BATCH_SIZE)
+    }
+
+    async fn response_json(response: Option<Response<Body>>) -> Value {
+        let response = response.expect("should have a response");
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_handle_single_request() {
+        let dispatcher = echo_dispatcher();
+        let request = json!({"jsonrpc": "2.0", "id": 1, "method": "info_get_status"});
+        let response = dispatcher.handle(Bytes::from(request.to_string())).await;
+        assert_eq!(
+            response_json(response).await,
+            json!({"jsonrpc": "2.0", "id": 1, "result": "info_get_status"})
+        );
+    }
+
+    #[tokio::test]
+    async fn should_handle_batch_request() {
+        let dispatcher = echo_dispatcher();
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "chain_get_block"},
+            {"jsonrpc": "2.0", "id": "two", "method": "state_get_item"},
+        ]);
+        let response = dispatcher.handle(Bytes::from(batch.to_string())).await;
+        assert_eq!(
+            response_json(response).await,
+            json!([
+                {"jsonrpc": "2.0", "id": 1, "result": "chain_get_block"},
+                {"jsonrpc": "2.0", "id": "two", "result": "state_get_item"},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_empty_batch() {
+        let dispatcher = echo_dispatcher();
+        let response = dispatcher.handle(Bytes::from("[]")).await;
+        let response = response_json(response).await;
+        // -32600 is the JSON-RPC "invalid request" error code.
+        assert_eq!(response["error"]["code"], json!(-32600));
+    }
+
+    #[tokio::test]
+    async fn should_reject_oversized_batch() {
+        let dispatcher = echo_dispatcher();
+        let batch = Value::Array(
+            (0..=MAX_BATCH_SIZE)
+                .map(|id| json!({"jsonrpc": "2.0", "id": id, "method": "info_get_status"}))
+                .collect(),
+        );
+        let response = dispatcher.handle(Bytes::from(batch.to_string())).await;
+        let response = response_json(response).await;
+        assert_eq!(
+            response["error"]["code"],
+            json!(ErrorCode::BatchTooLarge as i64)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_respond_to_notifications() {
+        let dispatcher = echo_dispatcher();
+        let batch = json!([
+            {"jsonrpc": "2.0", "method": "chain_get_block"},
+            {"jsonrpc": "2.0", "id": 2, "method": "state_get_item"},
+        ]);
+        let response = dispatcher.handle(Bytes::from(batch.to_string())).await;
+        assert_eq!(
+            response_json(response).await,
+            json!([{"jsonrpc": "2.0", "id": 2, "result": "state_get_item"}])
+        );
+
+        let batch = json!([
+            {"jsonrpc": "2.0", "method": "chain_get_block"},
+            {"jsonrpc": "2.0", "method": "state_get_item"},
+        ]);
+        let response = dispatcher.handle(Bytes::from(batch.to_string())).await;
+        assert!(response.is_none());
+
+        let notification = json!({"jsonrpc": "2.0", "method": "info_get_status"});
+        let response = dispatcher
+            .handle(Bytes::from(notification.to_string()))
+            .await;
+        assert!(response.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_serve_websocket() {
+        let dispatcher = echo_dispatcher();
+        let filter = warp::path("rpc-ws")
+            .and(warp::ws())
+            .map(move |ws: warp::ws::Ws| {
+                let dispatcher = dispatcher.clone();
+                ws.on_upgrade(move |websocket| dispatcher.serve_websocket(websocket))
+            });
+        let mut client = warp::test::ws()
+            .path("/rpc-ws")
+            .handshake(filter)
+            .await
+            .expect("should connect");
+
+        // A batch of notifications isn't answered, so the next message is the single response.
+        let notifications = json!([{"jsonrpc": "2.0", "method": "chain_get_block"}]);
+        client.send_text(notifications.to_string()).await;
+        let request = json!({"jsonrpc": "2.0", "id": 1, "method": "info_get_status"});
+        client.send_text(request.to_string()).await;
+        let message = client.recv().await.expect("should receive response");
+        let response: Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+        assert_eq!(
+            response,
+            json!({"jsonrpc": "2.0", "id": 1, "result": "info_get_status"})
+        );
+
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 2, "method": "chain_get_block"},
+            {"jsonrpc": "2.0", "id": 3, "method": "state_get_item"},
+        ]);
+        client.send_text(batch.to_string()).await;
+        let message = client.recv().await.expect("should receive response");
+        let response: Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+        assert_eq!(
+            response,
+            json!([
+                {"jsonrpc": "2.0", "id": 2, "result": "chain_get_block"},
+                {"jsonrpc": "2.0", "id": 3, "result": "state_get_item"},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn should_limit_request_rate() {
+        time::pause();
+        let limiter = RequestLimiter::new(2);
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        // The third request in the same period has to wait for the next one.
+        let third = limiter.acquire();
+        tokio::pin!(third);
+        assert!(futures::poll!(third.as_mut()).is_pending());
+        time::advance(LIMIT_PERIOD).await;
+        third.await;
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Display, Formatter},
 };
 
@@ -9,12 +9,18 @@ use casper_execution_engine::{
     core::engine_state::{self, BalanceResult, GetBidsResult, GetEraValidatorsError, QueryResult},
     storage::protocol_data::ProtocolData,
 };
-use casper_types::{system::auction::EraValidators, Transfer};
+use casper_types::{
+    account::AccountHash, system::auction::EraValidators, CallTrace, ExecutionResult, Transfer,
+};
 
+use super::SpeculativeExecError;
 use crate::{
     effect::{requests::RpcRequest, Responder},
     rpcs::chain::BlockIdentifier,
-    types::{Block, BlockHash, BlockSignatures, Deploy, DeployHash, DeployMetadata, NodeId},
+    types::{
+        AccountHistoryPage, Block, BlockHash, BlockSignatures, Deploy, DeployHash, DeployMetadata,
+        NodeId, PendingDeploy,
+    },
 };
 
 #[derive(Debug, From)]
@@ -52,6 +58,21 @@ pub enum Event {
         result: Box<Option<(Deploy, DeployMetadata)>>,
         main_responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    GetCallTracesResult {
+        deploy_hash: DeployHash,
+        result: HashMap<BlockHash, CallTrace>,
+        main_responder: Responder<HashMap<BlockHash, CallTrace>>,
+    },
+    GetAccountHistoryResult {
+        account_hash: AccountHash,
+        result: Box<Option<AccountHistoryPage>>,
+        main_responder: Responder<Option<AccountHistoryPage>>,
+    },
+    GetPendingDeploysResult {
+        account_hash: Option<AccountHash>,
+        result: Vec<PendingDeploy>,
+        main_responder: Responder<Vec<PendingDeploy>>,
+    },
     GetPeersResult {
         peers: BTreeMap<NodeId, String>,
         main_responder: Responder<BTreeMap<NodeId, String>>,
@@ -64,6 +85,10 @@ pub enum Event {
         result: Result<BalanceResult, engine_state::Error>,
         main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    SpeculativeDeployExecuteResult {
+        result: Result<Option<ExecutionResult>, engine_state::Error>,
+        main_responder: Responder<Result<Option<ExecutionResult>, SpeculativeExecError>>,
+    },
 }
 
 impl Display for Event {
@@ -110,11 +135,47 @@ impl Display for Event {
             Event::GetDeployResult { hash, result, .. } => {
                 write!(formatter, "get deploy result for {}: {:?}", hash, result)
             }
+            Event::GetCallTracesResult {
+                deploy_hash,
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get call traces result for {}: {:?}",
+                deploy_hash, result
+            ),
+            Event::GetAccountHistoryResult {
+                account_hash,
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get account history result for {}: {:?}",
+                account_hash, result
+            ),
+            Event::GetPendingDeploysResult {
+                account_hash: Some(account_hash),
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get pending deploys result for {}: {}",
+                account_hash,
+                result.len()
+            ),
+            Event::GetPendingDeploysResult {
+                account_hash: None,
+                result,
+                ..
+            } => write!(formatter, "get pending deploys result: {}", result.len()),
             Event::GetPeersResult { peers, .. } => write!(formatter, "get peers: {}", peers.len()),
             Event::GetMetricsResult { text, .. } => match text {
                 Some(txt) => write!(formatter, "get metrics ({} bytes)", txt.len()),
                 None => write!(formatter, "get metrics (failed)"),
             },
+            Event::SpeculativeDeployExecuteResult { result, .. } => {
+                write!(formatter, "speculative execution result: {:?}", result)
+            }
         }
     }
 }
@@ -1,5 +1,6 @@
-use std::{convert::Infallible, time::Duration};
+use std::convert::Infallible;
 
+use bytes::Bytes;
 use futures::future;
 use http::{Response, StatusCode};
 use hyper::{
@@ -8,21 +9,24 @@ use hyper::{
 };
 use serde::Serialize;
 use tokio::sync::oneshot;
-use tower::builder::ServiceBuilder;
 use tracing::{info, trace};
-use warp::{Filter, Rejection};
+use warp::{ws::Ws, Filter, Rejection};
 
 use casper_types::ProtocolVersion;
 
 use super::{
-    rpcs::{self, RpcWithOptionalParamsExt, RpcWithParamsExt, RpcWithoutParamsExt, RPC_API_PATH},
+    dispatcher::{Dispatcher, RequestLimiter},
+    rpcs::{
+        self, RpcWithOptionalParamsExt, RpcWithParamsExt, RpcWithoutParamsExt, RPC_API_PATH,
+        RPC_WS_API_PATH,
+    },
     ReactorEventT,
 };
 use crate::effect::EffectBuilder;
 
 // This is a workaround for not being able to create a `warp_json_rpc::Response` without a
 // `warp_json_rpc::Builder`.
-fn new_error_response(error: warp_json_rpc::Error) -> Response<Body> {
+pub(super) fn new_error_response(error: warp_json_rpc::Error) -> Response<Body> {
     #[derive(Serialize)]
     struct JsonRpcErrorResponse {
         jsonrpc: String,
@@ -44,15 +48,30 @@ fn new_error_response(error: warp_json_rpc::Error) -> Response<Body> {
         .unwrap()
 }
 
+/// Returns the response to a request consisting only of notifications, which have no responses.
+fn new_empty_response() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .unwrap()
+}
+
 /// Run the JSON-RPC server.
+///
+/// Requests are accepted either as the body of an HTTP POST to `/rpc`, or as messages over a
+/// WebSocket connected to `/rpc-ws`.  Both transports accept single requests and batches of up to
+/// `max_batch_size` requests, and share the same `qps_limit`.
 pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     qps_limit: u64,
+    max_batch_size: usize,
 ) {
     // RPC filters.
     let rpc_put_deploy = rpcs::account::PutDeploy::create_filter(effect_builder, api_version);
+    let rpc_speculative_exec =
+        rpcs::account::SpeculativeExec::create_filter(effect_builder, api_version);
     let rpc_get_block = rpcs::chain::GetBlock::create_filter(effect_builder, api_version);
     let rpc_get_block_transfers =
         rpcs::chain::GetBlockTransfers::create_filter(effect_builder, api_version);
@@ -63,6 +82,10 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rpc_get_account_info =
         rpcs::state::GetAccountInfo::create_filter(effect_builder, api_version);
     let rpc_get_deploy = rpcs::info::GetDeploy::create_filter(effect_builder, api_version);
+    let rpc_get_account_history =
+        rpcs::info::GetAccountHistory::create_filter(effect_builder, api_version);
+    let rpc_get_pending_deploys =
+        rpcs::info::GetPendingDeploys::create_filter(effect_builder, api_version);
     let rpc_get_peers = rpcs::info::GetPeers::create_filter(effect_builder, api_version);
     let rpc_get_status = rpcs::info::GetStatus::create_filter(effect_builder, api_version);
     let rpc_get_era_info =
@@ -72,6 +95,8 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rpc_get_rpcs = rpcs::docs::ListRpcs::create_filter(effect_builder, api_version);
     let rpc_get_dictionary_item =
         rpcs::state::GetDictionaryItem::create_filter(effect_builder, api_version);
+    let rpc_get_contract_schema =
+        rpcs::state::GetContractSchema::create_filter(effect_builder, api_version);
 
     // Catch requests where the method is not one we handle.
     let unknown_method = warp::path(RPC_API_PATH)
@@ -93,12 +118,15 @@ pub(super) async fn run<REv: ReactorEventT>(
     //        update to or move away from warp_json_rpc.
     let service = warp_json_rpc::service(
         rpc_put_deploy
+            .or(rpc_speculative_exec)
             .or(rpc_get_block)
             .or(rpc_get_block_transfers)
             .or(rpc_get_state_root_hash)
             .or(rpc_get_item)
             .or(rpc_get_balance)
             .or(rpc_get_deploy)
+            .or(rpc_get_account_history)
+            .or(rpc_get_pending_deploys)
             .or(rpc_get_peers)
             .or(rpc_get_status)
             .or(rpc_get_era_info)
@@ -106,18 +134,43 @@ pub(super) async fn run<REv: ReactorEventT>(
             .or(rpc_get_account_info)
             .or(rpc_get_rpcs)
             .or(rpc_get_dictionary_item)
+            .or(rpc_get_contract_schema)
             .or(unknown_method)
             .or(parse_failure),
     );
 
+    let dispatcher = Dispatcher::new(service, RequestLimiter::new(qps_limit), max_batch_size);
+
+    // Single and batch requests over HTTP.
+    let http_dispatcher = dispatcher.clone();
+    let rpc_http =
+        warp::path(RPC_API_PATH)
+            .and(warp::body::bytes())
+            .and_then(move |body: Bytes| {
+                let dispatcher = http_dispatcher.clone();
+                async move {
+                    let response = dispatcher
+                        .handle(body)
+                        .await
+                        .unwrap_or_else(new_empty_response);
+                    Ok::<_, Rejection>(response)
+                }
+            });
+
+    // Single and batch requests over a persistent WebSocket connection.
+    let rpc_ws = warp::path(RPC_WS_API_PATH)
+        .and(warp::ws())
+        .map(move |ws: Ws| {
+            let dispatcher = dispatcher.clone();
+            ws.on_upgrade(move |websocket| dispatcher.serve_websocket(websocket))
+        });
+
+    let service = warp::service(rpc_ws.or(rpc_http));
+
     // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
     let make_svc =
         hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
 
-    let make_svc = ServiceBuilder::new()
-        .rate_limit(qps_limit, Duration::from_secs(1))
-        .service(make_svc);
-
     let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
 
     let server = builder.serve(make_svc);
@@ -31,12 +31,14 @@ use docs::DocExample;
 
 /// The URL path.
 pub const RPC_API_PATH: &str = "rpc";
+/// The URL path for JSON-RPC requests over WebSocket.
+pub const RPC_WS_API_PATH: &str = "rpc-ws";
 
 /// Error code returned if the JSON-RPC response indicates failure.
 ///
 /// See <https://www.jsonrpc.org/specification#error_object> for details.
 #[repr(i64)]
-enum ErrorCode {
+pub(super) enum ErrorCode {
     NoSuchDeploy = -32000,
     NoSuchBlock = -32001,
     ParseQueryKey = -32002,
@@ -48,6 +50,11 @@ enum ErrorCode {
     InvalidDeploy = -32008,
     NoSuchAccount = -32009,
     FailedToGetDictionaryURef = -32010,
+    SpeculativeExecFailed = -32011,
+    NoSuchStateRoot = -32012,
+    NoSuchContract = -32013,
+    SpeculativeExecUnavailable = -32014,
+    BatchTooLarge = -32015,
 }
 
 #[derive(Debug)]
@@ -298,6 +305,12 @@ mod common {
     ) -> Result<(StoredValue, Vec<u8>), (ErrorCode, String)> {
         let (value, proof) = match query_result {
             Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+            Ok(QueryResult::RootNotFound) => {
+                let error_msg =
+                    "state query failed: state root not found (it may have been pruned)"
+                        .to_string();
+                return Err((ErrorCode::NoSuchStateRoot, error_msg));
+            }
             Ok(query_result) => {
                 let error_msg = format!("state query failed: {:?}", query_result);
                 return Err((ErrorCode::QueryFailed, error_msg));
@@ -14,17 +14,21 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 use warp_json_rpc::Builder;
 
-use casper_types::ProtocolVersion;
+use casper_types::{ExecutionResult, ProtocolVersion};
 
 use super::{
+    chain::BlockIdentifier,
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt,
 };
 use crate::{
-    components::rpc_server::rpcs::ErrorCode,
+    components::{
+        contract_runtime::SpeculativeExecutionState,
+        rpc_server::{rpcs::ErrorCode, SpeculativeExecError},
+    },
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{Deploy, DeployHash},
+    types::{Block, BlockHash, Deploy, DeployHash, Item},
 };
 
 static PUT_DEPLOY_PARAMS: Lazy<PutDeployParams> = Lazy::new(|| PutDeployParams {
@@ -34,6 +38,15 @@ static PUT_DEPLOY_RESULT: Lazy<PutDeployResult> = Lazy::new(|| PutDeployResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     deploy_hash: *Deploy::doc_example().id(),
 });
+static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
+    block_identifier: Some(BlockIdentifier::Hash(Block::doc_example().id())),
+    deploy: Deploy::doc_example().clone(),
+});
+static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    block_hash: Block::doc_example().id(),
+    execution_result: ExecutionResult::example().clone(),
+});
 
 /// Params for "account_put_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -123,3 +136,153 @@ impl RpcWithParamsExt for PutDeploy {
         .boxed()
     }
 }
+
+/// Params for "speculative_exec" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpeculativeExecParams {
+    /// Block hash or height on top of which to execute the deploy.  Defaults to the latest block.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// The `Deploy` to execute.
+    pub deploy: Deploy,
+}
+
+impl DocExample for SpeculativeExecParams {
+    fn doc_example() -> &'static Self {
+        &*SPECULATIVE_EXEC_PARAMS
+    }
+}
+
+/// Result for "speculative_exec" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpeculativeExecResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block on top of which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// Result of the execution.
+    pub execution_result: ExecutionResult,
+}
+
+impl DocExample for SpeculativeExecResult {
+    fn doc_example() -> &'static Self {
+        &*SPECULATIVE_EXEC_RESULT
+    }
+}
+
+/// "speculative_exec" RPC
+pub struct SpeculativeExec {}
+
+impl RpcWithParams for SpeculativeExec {
+    const METHOD: &'static str = "speculative_exec";
+    type RequestParams = SpeculativeExecParams;
+    type ResponseResult = SpeculativeExecResult;
+}
+
+impl RpcWithParamsExt for SpeculativeExec {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let SpeculativeExecParams {
+                block_identifier: maybe_id,
+                mut deploy,
+            } = params;
+            let deploy_hash = *deploy.id();
+
+            if let Err(error) = deploy.is_valid() {
+                info!(
+                    %deploy_hash,
+                    %error,
+                    "the deploy submitted for speculative execution was invalid",
+                );
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidDeploy as i64,
+                    error.to_string(),
+                ))?);
+            }
+
+            let block: Block = {
+                let maybe_block = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::GetBlock {
+                            maybe_id,
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+
+                match maybe_block {
+                    None => {
+                        let error_msg = if maybe_id.is_none() {
+                            "speculative-exec failed to get last added block".to_string()
+                        } else {
+                            "speculative-exec failed to get specified block".to_string()
+                        };
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::NoSuchBlock as i64,
+                            error_msg,
+                        ))?);
+                    }
+                    Some((block, _)) => block,
+                }
+            };
+
+            let execution_prestate = SpeculativeExecutionState::from(&block);
+            let execution_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::SpeculativeDeployExecute {
+                        execution_prestate,
+                        deploy: Box::new(deploy),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            match execution_result {
+                Ok(Some(execution_result)) => {
+                    let result = Self::ResponseResult {
+                        api_version,
+                        block_hash: *block.hash(),
+                        execution_result,
+                    };
+                    Ok(response_builder.success(result)?)
+                }
+                Ok(None) => {
+                    let error_msg = "speculative-exec produced no execution result".to_string();
+                    info!(%deploy_hash, "{}", error_msg);
+                    Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::SpeculativeExecFailed as i64,
+                        error_msg,
+                    ))?)
+                }
+                Err(error @ SpeculativeExecError::Disabled)
+                | Err(error @ SpeculativeExecError::Busy) => {
+                    let error_msg = format!("speculative-exec unavailable: {}", error);
+                    info!(%deploy_hash, "{}", error_msg);
+                    Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::SpeculativeExecUnavailable as i64,
+                        error_msg,
+                    ))?)
+                }
+                Err(SpeculativeExecError::Engine(error)) => {
+                    let error_msg = format!("speculative-exec failed to execute: {}", error);
+                    info!(%deploy_hash, "{}", error_msg);
+                    Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::SpeculativeExecFailed as i64,
+                        error_msg,
+                    ))?)
+                }
+            }
+        }
+        .boxed()
+    }
+}
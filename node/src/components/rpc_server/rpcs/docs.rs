@@ -19,9 +19,9 @@ use warp_json_rpc::Builder;
 use casper_types::ProtocolVersion;
 
 use super::{
-    account::PutDeploy,
+    account::{PutDeploy, SpeculativeExec},
     chain::{GetBlock, GetBlockTransfers, GetStateRootHash},
-    info::{GetDeploy, GetPeers, GetStatus},
+    info::{GetAccountHistory, GetDeploy, GetPeers, GetPendingDeploys, GetStatus},
     state::{GetAuctionInfo, GetBalance, GetItem},
     Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
     RpcWithoutParamsExt,
@@ -30,7 +30,7 @@ use crate::{
     effect::EffectBuilder,
     rpcs::{
         chain::GetEraInfoBySwitchBlock,
-        state::{GetAccountInfo, GetDictionaryItem},
+        state::{GetAccountInfo, GetContractSchema, GetDictionaryItem},
     },
 };
 
@@ -53,21 +53,33 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     let info = OpenRpcInfoField {
         version: DOCS_EXAMPLE_PROTOCOL_VERSION.to_string(),
         title: "Client API of Casper Node".to_string(),
-        description: "This describes the JSON-RPC 2.0 API of a node on the Casper network."
+        description: "This describes the JSON-RPC 2.0 API of a node on the Casper network.  \
+            Batch requests are supported, and the API is available over both HTTP and WebSocket."
             .to_string(),
         contact,
         license,
     };
 
-    let server = OpenRpcServerEntry {
+    let http_server = OpenRpcServerEntry {
         name: "any Casper Network node".to_string(),
         url: "http://IP:PORT/rpc/".to_string(),
+        description: "JSON-RPC 2.0 over HTTP.  The body of each POST request holds either a \
+            single request or a batch array of requests."
+            .to_string(),
+    };
+    let websocket_server = OpenRpcServerEntry {
+        name: "any Casper Network node (WebSocket)".to_string(),
+        url: "ws://IP:PORT/rpc-ws/".to_string(),
+        description: "JSON-RPC 2.0 over a persistent WebSocket connection.  Each message holds \
+            either a single request or a batch array of requests, and is answered by a text \
+            message holding the response."
+            .to_string(),
     };
 
     let mut schema = OpenRpcSchema {
         openrpc: "1.0.0-rc1".to_string(),
         info,
-        servers: vec![server],
+        servers: vec![http_server, websocket_server],
         methods: vec![],
         components: Components {
             schemas: Map::new(),
@@ -75,9 +87,22 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     };
 
     schema.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
+    schema.push_with_params::<SpeculativeExec>(
+        "executes a Deploy against the state of a Block without committing the effects",
+    );
     schema.push_with_params::<GetDeploy>("returns a Deploy from the network");
     schema.push_with_params::<GetAccountInfo>("returns an Account from the network");
+    schema.push_with_params::<GetAccountHistory>(
+        "returns the deploys and transfers which touched an Account, excluding transfers made \
+         directly to one of its purses rather than to the Account itself",
+    );
     schema.push_with_params::<GetDictionaryItem>("returns an item from a Dictionary");
+    schema.push_with_params::<GetContractSchema>(
+        "returns the entry points, versions and user groups of a stored contract",
+    );
+    schema.push_with_optional_params::<GetPendingDeploys>(
+        "returns the Deploys awaiting inclusion in a Block, and why any are being held back",
+    );
     schema.push_without_params::<GetPeers>("returns a list of peers connected to the node");
     schema.push_without_params::<GetStatus>("returns the current status of the node");
     schema.push_with_optional_params::<GetBlock>("returns a Block from the network");
@@ -308,6 +333,7 @@ struct OpenRpcLicenseField {
 struct OpenRpcServerEntry {
     name: String,
     url: String,
+    description: String,
 }
 
 /// The struct containing the documentation for the RPCs.
@@ -484,6 +510,7 @@ mod tests {
         let server = OpenRpcServerEntry {
             name: "any Casper Network node".to_string(),
             url: "http://IP:PORT/rpc/".to_string(),
+            description: String::new(),
         };
 
         let schema = OpenRpcSchema {
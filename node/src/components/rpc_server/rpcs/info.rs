@@ -3,9 +3,13 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::str;
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    str,
+};
 
-use futures::{future::BoxFuture, FutureExt};
+use futures::{future::BoxFuture, join, FutureExt};
 use http::Response;
 use hyper::Body;
 use once_cell::sync::Lazy;
@@ -14,17 +18,20 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 use warp_json_rpc::Builder;
 
-use casper_types::{ExecutionResult, ProtocolVersion};
+use casper_types::{account::AccountHash, CallTrace, ExecutionResult, ProtocolVersion, Transfer};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
-    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt, RpcWithoutParams,
-    RpcWithoutParamsExt,
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithOptionalParamsExt,
+    RpcWithParams, RpcWithParamsExt, RpcWithoutParams, RpcWithoutParamsExt,
 };
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{Block, BlockHash, Deploy, DeployHash, GetStatusResult, Item, PeersMap},
+    types::{
+        AccountHistoryEntry, AccountHistoryPage, Block, BlockHash, Deploy, DeployHash,
+        GetStatusResult, Item, NodeId, PeersMap, PendingDeploy, PendingDeployStatus,
+    },
 };
 
 static GET_DEPLOY_PARAMS: Lazy<GetDeployParams> = Lazy::new(|| GetDeployParams {
@@ -36,11 +43,57 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
     execution_results: vec![JsonExecutionResult {
         block_hash: Block::doc_example().id(),
         result: ExecutionResult::example().clone(),
+        call_trace: None,
     }],
 });
-static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
-    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
-    peers: GetStatusResult::doc_example().peers.clone(),
+static GET_ACCOUNT_HISTORY_PARAMS: Lazy<GetAccountHistoryParams> =
+    Lazy::new(|| GetAccountHistoryParams {
+        account_hash: Deploy::doc_example().header().account().to_account_hash(),
+        offset: 0,
+        limit: 10,
+    });
+static GET_ACCOUNT_HISTORY_RESULT: Lazy<GetAccountHistoryResult> =
+    Lazy::new(|| GetAccountHistoryResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        account_history: AccountHistoryPage {
+            total: 1,
+            entries: vec![AccountHistoryEntry {
+                block_hash: Block::doc_example().id(),
+                deploy_hash: *Deploy::doc_example().id(),
+                transfers: vec![Transfer::default()],
+            }],
+        },
+    });
+static GET_PENDING_DEPLOYS_PARAMS: Lazy<GetPendingDeploysParams> =
+    Lazy::new(|| GetPendingDeploysParams {
+        account_hash: Deploy::doc_example().header().account().to_account_hash(),
+    });
+static GET_PENDING_DEPLOYS_RESULT: Lazy<GetPendingDeploysResult> = Lazy::new(|| {
+    let header = Deploy::doc_example().header();
+    GetPendingDeploysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        pending_deploys: vec![PendingDeploy {
+            deploy_hash: *Deploy::doc_example().id(),
+            account: header.account().clone(),
+            is_transfer: false,
+            gas_price: header.gas_price(),
+            received: header.timestamp(),
+            expires: header.expires(),
+            status: PendingDeployStatus::Ready,
+        }],
+    }
+});
+static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| {
+    let node_id = NodeId::doc_example();
+    let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 54321);
+    let mut peers = BTreeMap::new();
+    peers.insert(*node_id, socket_addr.to_string());
+    let mut scores = BTreeMap::new();
+    scores.insert(*node_id, -15);
+    GetPeersResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        peers: PeersMap::with_scores(peers, &scores),
+    }
 });
 
 /// Params for "info_get_deploy" RPC request.
@@ -65,6 +118,10 @@ pub struct JsonExecutionResult {
     pub block_hash: BlockHash,
     /// Execution result.
     pub result: ExecutionResult,
+    /// The contract calls made while executing the deploy.  Only present if the node has call
+    /// tracing enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_trace: Option<CallTrace>,
 }
 
 /// Result for "info_get_deploy" RPC response.
@@ -128,11 +185,25 @@ impl RpcWithParamsExt for GetDeploy {
                 }
             };
 
+            let mut call_traces = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetCallTraces {
+                        deploy_hash: params.deploy_hash,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
             // Return the result.
             let execution_results = metadata
                 .execution_results
                 .into_iter()
-                .map(|(block_hash, result)| JsonExecutionResult { block_hash, result })
+                .map(|(block_hash, result)| JsonExecutionResult {
+                    block_hash,
+                    result,
+                    call_trace: call_traces.remove(&block_hash),
+                })
                 .collect();
 
             let result = Self::ResponseResult {
@@ -146,6 +217,157 @@ impl RpcWithParamsExt for GetDeploy {
     }
 }
 
+/// The maximum number of entries returned by a single "info_get_account_history" request.
+const MAX_ACCOUNT_HISTORY_PAGE_SIZE: u64 = 100;
+
+/// Params for "info_get_account_history" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountHistoryParams {
+    /// The account hash.
+    pub account_hash: AccountHash,
+    /// The number of entries to skip, starting from the oldest.
+    pub offset: u64,
+    /// The maximum number of entries to return.  Capped at 100.
+    pub limit: u64,
+}
+
+impl DocExample for GetAccountHistoryParams {
+    fn doc_example() -> &'static Self {
+        &*GET_ACCOUNT_HISTORY_PARAMS
+    }
+}
+
+/// Result for "info_get_account_history" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountHistoryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The requested page of the account's deploys and transfers.
+    pub account_history: AccountHistoryPage,
+}
+
+impl DocExample for GetAccountHistoryResult {
+    fn doc_example() -> &'static Self {
+        &*GET_ACCOUNT_HISTORY_RESULT
+    }
+}
+
+/// "info_get_account_history" RPC.
+pub struct GetAccountHistory {}
+
+impl RpcWithParams for GetAccountHistory {
+    const METHOD: &'static str = "info_get_account_history";
+    type RequestParams = GetAccountHistoryParams;
+    type ResponseResult = GetAccountHistoryResult;
+}
+
+impl RpcWithParamsExt for GetAccountHistory {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let maybe_account_history = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetAccountHistory {
+                        account_hash: params.account_hash,
+                        offset: params.offset,
+                        limit: params.limit.min(MAX_ACCOUNT_HISTORY_PAGE_SIZE),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            // An account without any recorded activity simply has an empty history.
+            let account_history = maybe_account_history.unwrap_or_else(|| AccountHistoryPage {
+                total: 0,
+                entries: vec![],
+            });
+
+            let result = Self::ResponseResult {
+                api_version,
+                account_history,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "info_get_pending_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetPendingDeploysParams {
+    /// Only deploys sent by this account are returned.
+    pub account_hash: AccountHash,
+}
+
+impl DocExample for GetPendingDeploysParams {
+    fn doc_example() -> &'static Self {
+        &*GET_PENDING_DEPLOYS_PARAMS
+    }
+}
+
+/// Result for "info_get_pending_deploys" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetPendingDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The deploys and transfers held by this node awaiting inclusion in a block.
+    pub pending_deploys: Vec<PendingDeploy>,
+}
+
+impl DocExample for GetPendingDeploysResult {
+    fn doc_example() -> &'static Self {
+        &*GET_PENDING_DEPLOYS_RESULT
+    }
+}
+
+/// "info_get_pending_deploys" RPC.
+pub struct GetPendingDeploys {}
+
+impl RpcWithOptionalParams for GetPendingDeploys {
+    const METHOD: &'static str = "info_get_pending_deploys";
+    type OptionalRequestParams = GetPendingDeploysParams;
+    type ResponseResult = GetPendingDeploysResult;
+}
+
+impl RpcWithOptionalParamsExt for GetPendingDeploys {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        maybe_params: Option<Self::OptionalRequestParams>,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let pending_deploys = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetPendingDeploys {
+                        account_hash: maybe_params.map(|params| params.account_hash),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let result = Self::ResponseResult {
+                api_version,
+                pending_deploys,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
 /// Result for "info_get_peers" RPC response.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -153,7 +375,7 @@ pub struct GetPeersResult {
     /// The RPC API version.
     #[schemars(with = "String")]
     pub api_version: ProtocolVersion,
-    /// The node ID and network address of each connected peer.
+    /// The node ID, network address and reputation score of each connected peer.
     pub peers: PeersMap,
 }
 
@@ -178,16 +400,20 @@ impl RpcWithoutParamsExt for GetPeers {
         api_version: ProtocolVersion,
     ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
         async move {
-            let peers = effect_builder
-                .make_request(
+            let (peers, scores) = join!(
+                effect_builder.make_request(
                     |responder| RpcRequest::GetPeers { responder },
                     QueueKind::Api,
+                ),
+                effect_builder.make_request(
+                    |responder| RpcRequest::GetPeerScores { responder },
+                    QueueKind::Api,
                 )
-                .await;
+            );
 
             let result = Self::ResponseResult {
                 api_version,
-                peers: PeersMap::from(peers),
+                peers: PeersMap::with_scores(peers, &scores),
             };
             Ok(response_builder.success(result)?)
         }
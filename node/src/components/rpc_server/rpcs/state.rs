@@ -19,7 +19,8 @@ use casper_execution_engine::{
     shared::stored_value::StoredValue as DomainStoredValue,
 };
 use casper_types::{
-    bytesrepr::ToBytes, CLValue, Key, ProtocolVersion, PublicKey, SecretKey, URef, U512,
+    bytesrepr::ToBytes, CLValue, ContractHash, ContractPackageHash, ContractVersion, Key,
+    ProtocolVersion, PublicKey, SecretKey, URef, U512,
 };
 
 use super::{
@@ -37,7 +38,7 @@ use crate::{
         RpcWithOptionalParamsExt,
     },
     types::{
-        json_compatibility::{Account as JsonAccount, AuctionState, StoredValue},
+        json_compatibility::{Account as JsonAccount, AuctionState, ContractSchema, StoredValue},
         Block,
     },
 };
@@ -100,6 +101,18 @@ static GET_DICTIONARY_ITEM_RESULT: Lazy<GetDictionaryItemResult> =
         stored_value: StoredValue::CLValue(CLValue::from_t(1u64).unwrap()),
         merkle_proof: MERKLE_PROOF.clone(),
     });
+static GET_CONTRACT_SCHEMA_PARAMS: Lazy<GetContractSchemaParams> =
+    Lazy::new(|| GetContractSchemaParams {
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+        contract_identifier: ContractIdentifier::ContractHash(
+            ContractSchema::doc_example().contract_hash(),
+        ),
+    });
+static GET_CONTRACT_SCHEMA_RESULT: Lazy<GetContractSchemaResult> =
+    Lazy::new(|| GetContractSchemaResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        contract_schema: ContractSchema::doc_example().clone(),
+    });
 
 /// Params for "state_get_item" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -284,6 +297,16 @@ impl RpcWithParamsExt for GetBalance {
 
             let (balance_value, balance_proof) = match balance_result {
                 Ok(BalanceResult::Success { motes, proof }) => (motes, proof),
+                Ok(BalanceResult::RootNotFound) => {
+                    let error_msg =
+                        "get-balance failed: state root not found (it may have been pruned)"
+                            .to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchStateRoot as i64,
+                        error_msg,
+                    ))?);
+                }
                 Ok(balance_result) => {
                     let error_msg = format!("get-balance failed: {:?}", balance_result);
                     info!("{}", error_msg);
@@ -830,3 +853,277 @@ impl RpcWithParamsExt for GetDictionaryItem {
         .boxed()
     }
 }
+
+/// Options for identifying the contract whose schema is requested.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum ContractIdentifier {
+    /// Identify the contract by its hash.
+    ContractHash(ContractHash),
+    /// Identify the contract by the hash of its package.
+    ContractPackageHash {
+        /// The hash of the contract package.
+        contract_package_hash: ContractPackageHash,
+        /// The version of the contract within the package.  If omitted, the latest enabled
+        /// version is used.
+        #[serde(default)]
+        contract_version: Option<ContractVersion>,
+    },
+}
+
+/// Params for "state_get_contract_schema" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractSchemaParams {
+    /// The block identifier.  If omitted, the state as of the latest block is used.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// The contract identifier.
+    pub contract_identifier: ContractIdentifier,
+}
+
+impl DocExample for GetContractSchemaParams {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_SCHEMA_PARAMS
+    }
+}
+
+/// Result for "state_get_contract_schema" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractSchemaResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The interface of the contract.
+    pub contract_schema: ContractSchema,
+}
+
+impl DocExample for GetContractSchemaResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_SCHEMA_RESULT
+    }
+}
+
+/// "state_get_contract_schema" RPC.
+pub struct GetContractSchema {}
+
+impl RpcWithParams for GetContractSchema {
+    const METHOD: &'static str = "state_get_contract_schema";
+    type RequestParams = GetContractSchemaParams;
+    type ResponseResult = GetContractSchemaResult;
+}
+
+impl RpcWithParamsExt for GetContractSchema {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let maybe_id = params.block_identifier;
+            let maybe_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetBlock {
+                        maybe_id,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+            let state_root_hash = match maybe_block {
+                Some((block, _)) => *block.header().state_root_hash(),
+                None => {
+                    let error_msg = if maybe_id.is_none() {
+                        "get-contract-schema failed to get last added block".to_string()
+                    } else {
+                        "get-contract-schema failed to get specified block".to_string()
+                    };
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let schema = match get_contract_schema(
+                effect_builder,
+                state_root_hash,
+                params.contract_identifier,
+            )
+            .await
+            {
+                Ok(schema) => schema,
+                Err((error_code, error_msg)) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder
+                        .error(warp_json_rpc::Error::custom(error_code as i64, error_msg))?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version,
+                contract_schema: schema,
+            };
+
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Reads the contract identified by `contract_identifier` and its package from global state, and
+/// derives the contract's schema from them.
+async fn get_contract_schema<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    contract_identifier: ContractIdentifier,
+) -> Result<ContractSchema, (ErrorCode, String)> {
+    let (contract_hash, contract, contract_package) = match contract_identifier {
+        ContractIdentifier::ContractHash(contract_hash) => {
+            let contract =
+                match query(effect_builder, state_root_hash, contract_hash.into()).await? {
+                    DomainStoredValue::Contract(contract) => contract,
+                    other => {
+                        return Err(unexpected_stored_value(
+                            contract_hash.into(),
+                            "contract",
+                            &other,
+                        ))
+                    }
+                };
+            let contract_package_hash = contract.contract_package_hash();
+            let contract_package = match query(
+                effect_builder,
+                state_root_hash,
+                contract_package_hash.into(),
+            )
+            .await?
+            {
+                DomainStoredValue::ContractPackage(contract_package) => contract_package,
+                other => {
+                    return Err(unexpected_stored_value(
+                        contract_package_hash.into(),
+                        "contract package",
+                        &other,
+                    ))
+                }
+            };
+            (contract_hash, contract, contract_package)
+        }
+        ContractIdentifier::ContractPackageHash {
+            contract_package_hash,
+            contract_version,
+        } => {
+            let contract_package = match query(
+                effect_builder,
+                state_root_hash,
+                contract_package_hash.into(),
+            )
+            .await?
+            {
+                DomainStoredValue::ContractPackage(contract_package) => contract_package,
+                other => {
+                    return Err(unexpected_stored_value(
+                        contract_package_hash.into(),
+                        "contract package",
+                        &other,
+                    ))
+                }
+            };
+            // Versions are ordered by protocol version first, so the last match is the newest.
+            let maybe_contract_hash = contract_package
+                .enabled_versions()
+                .into_iter()
+                .rev()
+                .find(|(version_key, _)| match contract_version {
+                    Some(contract_version) => version_key.contract_version() == contract_version,
+                    None => true,
+                })
+                .map(|(_, contract_hash)| contract_hash);
+            let contract_hash = match maybe_contract_hash {
+                Some(contract_hash) => contract_hash,
+                None => {
+                    let error_msg = format!(
+                        "{} has no enabled contract version matching {:?}",
+                        contract_package_hash.to_formatted_string(),
+                        contract_version
+                    );
+                    return Err((ErrorCode::NoSuchContract, error_msg));
+                }
+            };
+            let contract =
+                match query(effect_builder, state_root_hash, contract_hash.into()).await? {
+                    DomainStoredValue::Contract(contract) => contract,
+                    other => {
+                        return Err(unexpected_stored_value(
+                            contract_hash.into(),
+                            "contract",
+                            &other,
+                        ))
+                    }
+                };
+            (contract_hash, contract, contract_package)
+        }
+    };
+
+    Ok(ContractSchema::new(
+        contract_hash,
+        &contract,
+        &contract_package,
+    ))
+}
+
+/// Reads the value stored under `base_key` from global state.
+async fn query<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    base_key: Key,
+) -> Result<DomainStoredValue, (ErrorCode, String)> {
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key,
+                path: vec![],
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+    match query_result {
+        Ok(QueryResult::Success { value, .. }) => Ok(*value),
+        Ok(QueryResult::RootNotFound) => Err((
+            ErrorCode::NoSuchStateRoot,
+            "state query failed: state root not found (it may have been pruned)".to_string(),
+        )),
+        Ok(QueryResult::ValueNotFound(_)) => Err((
+            ErrorCode::NoSuchContract,
+            format!("nothing stored under {}", base_key.to_formatted_string()),
+        )),
+        Ok(query_result) => Err((
+            ErrorCode::QueryFailed,
+            format!("state query failed: {:?}", query_result),
+        )),
+        Err(error) => Err((
+            ErrorCode::QueryFailedToExecute,
+            format!("state query failed to execute: {:?}", error),
+        )),
+    }
+}
+
+/// Returns the error for a value of an unexpected type stored under `key`.
+fn unexpected_stored_value(
+    key: Key,
+    expected: &str,
+    stored_value: &DomainStoredValue,
+) -> (ErrorCode, String) {
+    let error_msg = format!(
+        "{} is not a {}, found {}",
+        key.to_formatted_string(),
+        expected,
+        stored_value.type_name()
+    );
+    (ErrorCode::NoSuchContract, error_msg)
+}
@@ -28,17 +28,20 @@ mod config;
 mod counting_format;
 mod error;
 mod event;
+mod fair_queue;
 mod gossiped_address;
 mod limiter;
 mod message;
 mod message_pack_format;
 mod outgoing;
+mod reputation;
 mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
 mod tests;
 
 use std::{
+    borrow::Cow,
     collections::{BTreeMap, HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Debug, Display, Formatter},
@@ -61,7 +64,7 @@ use thiserror::Error;
 use tokio::{
     net::TcpStream,
     sync::{
-        mpsc::{self, UnboundedSender},
+        mpsc::{self, error::SendError, UnboundedSender},
         watch,
     },
     task::JoinHandle,
@@ -78,13 +81,16 @@ use self::{
     message::ConsensusKeyPair,
     message_pack_format::MessagePackFormat,
     outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
+    reputation::Reputation,
     symmetry::ConnectionSymmetry,
     tasks::NetworkContext,
 };
 pub(crate) use self::{
     event::Event,
+    fair_queue::TrafficClass,
     gossiped_address::GossipedAddress,
     message::{Message, MessageKind, Payload},
+    reputation::Offense,
 };
 use super::consensus;
 use crate::{
@@ -93,13 +99,13 @@ use crate::{
         announcements::{BlocklistAnnouncement, LinearChainAnnouncement, NetworkAnnouncement},
         requests::{
             ChainspecLoaderRequest, ContractRuntimeRequest, NetworkInfoRequest, NetworkRequest,
-            StorageRequest,
+            StateStoreRequest, StorageRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     reactor::{EventQueueHandle, Finalize, ReactorEvent},
     tls::{self, TlsCert, ValidationError},
-    types::NodeId,
+    types::{NodeId, Timestamp},
     utils::{self, display_error, WithDir},
     NodeRng,
 };
@@ -130,10 +136,17 @@ const OUTGOING_MANAGER_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
 /// Interval for checking for symmetrical connections.
 const SYMMETRY_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
 
+/// Interval for removing expired bans and recovered reputation scores.
+const REPUTATION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The key under which the ban list is persisted in the state store.
+const BAN_LIST_KEY: &[u8] = b"small_network_ban_list";
+
 #[derive(Clone, DataSize, Debug)]
 pub struct OutgoingHandle<P> {
+    /// Sender for messages to be sent, along with the time they were queued.
     #[data_size(skip)] // Unfortunately, there is no way to inspect an `UnboundedSender`.
-    sender: UnboundedSender<Arc<Message<P>>>,
+    sender: UnboundedSender<(Arc<Message<P>>, Instant)>,
     peer_addr: SocketAddr,
 }
 
@@ -158,6 +171,13 @@ where
     outgoing_manager: OutgoingManager<OutgoingHandle<P>, ConnectionError>,
     /// Tracks whether a connection is symmetric or not.
     connection_symmetries: HashMap<NodeId, ConnectionSymmetry>,
+    /// Senders which close an incoming connection from the given peer and address when dropped.
+    #[data_size(skip)]
+    incoming_closers: HashMap<(NodeId, SocketAddr), watch::Sender<()>>,
+    /// Peers which announced in their handshakes that they answer requests for global state tries.
+    trie_serving_peers: HashSet<NodeId>,
+    /// Reputation scores and bans of peers.
+    reputation: Reputation,
 
     /// Channel signaling a shutdown of the small network.
     // Note: This channel is closed when `SmallNetwork` is dropped, signalling the receivers that
@@ -201,6 +221,7 @@ where
         + From<NetworkAnnouncement<NodeId, P>>
         + From<ContractRuntimeRequest>
         + From<StorageRequest>
+        + From<StateStoreRequest>
         + From<ChainspecLoaderRequest>,
 {
     /// Creates a new small network component instance.
@@ -213,6 +234,7 @@ where
         small_network_identity: SmallNetworkIdentity,
         chain_info_source: C,
         initial_era: Option<EraId>,
+        serves_tries: bool,
     ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
         let mut known_addresses = HashSet::new();
         for address in &cfg.known_addresses {
@@ -298,6 +320,7 @@ where
             chain_info: chain_info_source.into(),
             public_addr,
             consensus_keys,
+            serves_tries,
         });
 
         // Run the server task.
@@ -313,11 +336,20 @@ where
             server_shutdown_receiver,
         ));
 
+        let reputation = Reputation::new(
+            cfg.ban_threshold,
+            cfg.ban_duration,
+            cfg.score_recovery_interval,
+        );
+
         let mut component = SmallNetwork {
             cfg,
             context,
             outgoing_manager,
             connection_symmetries: HashMap::new(),
+            incoming_closers: HashMap::new(),
+            trie_serving_peers: HashSet::new(),
+            reputation,
             shutdown_sender: Some(server_shutdown_sender),
             shutdown_receiver,
             server_join_handle: Some(server_join_handle),
@@ -357,6 +389,13 @@ where
                 .event(|_| Event::SweepOutgoing),
         );
 
+        // Restore the bans persisted before the last shutdown.
+        effects.extend(
+            effect_builder
+                .load_state(Cow::Borrowed(BAN_LIST_KEY))
+                .event(Event::BansLoaded),
+        );
+
         Ok((component, effects))
     }
 
@@ -404,7 +443,7 @@ where
     fn send_message(&self, dest: NodeId, msg: Arc<Message<P>>) {
         // Try to send the message.
         if let Some(connection) = self.outgoing_manager.get_route(dest) {
-            if let Err(msg) = connection.sender.send(msg) {
+            if let Err(SendError((msg, _))) = connection.sender.send((msg, Instant::now())) {
                 // We lost the connection, but that fact has not reached us yet.
                 warn!(our_id=%self.context.our_id, %dest, ?msg, "dropped outgoing message, lost connection");
             } else {
@@ -434,16 +473,18 @@ where
             }
             IncomingConnection::Failed {
                 peer_addr: _,
-                peer_id: _,
+                peer_id,
                 ref error,
             } => {
-                // TODO: At this point, we could consider blocking peers by [`PeerID`], but this
-                //       feature is not implemented yet.
                 debug!(
                     err = display_error(error),
                     "incoming connection failed after TLS setup"
                 );
-                Effects::new()
+                if self.is_blockable_offense(error) {
+                    self.penalize(effect_builder, peer_id, Offense::ProtocolViolation)
+                } else {
+                    Effects::new()
+                }
             }
             IncomingConnection::Loopback => {
                 // Loopback connections are closed immediately, but will be marked as such by the
@@ -457,10 +498,20 @@ where
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_serves_tries,
                 stream,
             } => {
+                if self.reputation.is_banned(&peer_id, Timestamp::now()) {
+                    info!("refusing incoming connection from banned peer");
+                    return Effects::new();
+                }
+
                 info!("new incoming connection established");
 
+                if peer_serves_tries {
+                    self.trie_serving_peers.insert(peer_id);
+                }
+
                 // Learn the address the peer gave us.
                 let dial_requests =
                     self.outgoing_manager
@@ -477,7 +528,11 @@ where
                     effects.extend(self.connection_completed(effect_builder, peer_id));
                 }
 
-                // Now we can start the message reader.
+                // Now we can start the message reader, which can be closed individually should the
+                // peer get banned.
+                let (close_sender, close_receiver) = watch::channel(());
+                self.incoming_closers
+                    .insert((peer_id, peer_addr), close_sender);
                 let boxed_span = Box::new(span.clone());
                 effects.extend(
                     tasks::message_reader(
@@ -486,6 +541,7 @@ where
                         self.incoming_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.shutdown_receiver.clone(),
+                        close_receiver,
                         peer_id,
                         span.clone(),
                     )
@@ -505,6 +561,7 @@ where
 
     fn handle_incoming_closed(
         &mut self,
+        effect_builder: EffectBuilder<REv>,
         result: io::Result<()>,
         peer_id: Box<NodeId>,
         peer_addr: SocketAddr,
@@ -521,18 +578,24 @@ where
                 }
             }
 
+            self.incoming_closers.remove(&(*peer_id, peer_addr));
+
             // Update the connection symmetries.
-            self.connection_symmetries
-                .entry(*peer_id)
-                .or_default()
-                .remove_incoming(peer_addr, Instant::now());
+            let symmetry = self.connection_symmetries.entry(*peer_id).or_default();
+            let was_symmetric = symmetry.is_symmetric();
+            symmetry.remove_incoming(peer_addr, Instant::now());
+            let lost = was_symmetric && !symmetry.is_symmetric();
 
-            Effects::new()
+            if lost {
+                self.connection_lost(effect_builder, *peer_id)
+            } else {
+                Effects::new()
+            }
         })
     }
 
-    /// Determines whether an outgoing peer should be blocked based on the connection error.
-    fn is_blockable_offense_for_outgoing(&self, error: &ConnectionError) -> bool {
+    /// Determines whether a peer should be blocked based on the connection error.
+    fn is_blockable_offense(&self, error: &ConnectionError) -> bool {
         match error {
             // Potentially transient failures.
             ConnectionError::TlsInitialization(_)
@@ -563,6 +626,13 @@ where
         span: Span,
     ) -> Effects<Event<P>> {
         let now = Instant::now();
+        // A peer which failed the handshake in a blockable way is penalized by its node ID.
+        let offender = match outgoing {
+            OutgoingConnection::Failed {
+                peer_id, ref error, ..
+            } if self.is_blockable_offense(error) => Some(peer_id),
+            _ => None,
+        };
         span.clone().in_scope(|| match outgoing {
             OutgoingConnection::FailedEarly { peer_addr, error }
             | OutgoingConnection::Failed {
@@ -572,9 +642,14 @@ where
             } => {
                 debug!(err=%display_error(&error), "outgoing connection failed");
                 // We perform blocking first, to not trigger a reconnection before blocking.
+                let mut effects = offender
+                    .map(|peer_id| {
+                        self.penalize(effect_builder, peer_id, Offense::ProtocolViolation)
+                    })
+                    .unwrap_or_default();
                 let mut requests = Vec::new();
 
-                if self.is_blockable_offense_for_outgoing(&error) {
+                if self.is_blockable_offense(&error) {
                     requests.extend(self.outgoing_manager.block_addr(peer_addr, now).into_iter());
                 }
 
@@ -589,7 +664,8 @@ where
                         .into_iter(),
                 );
 
-                self.process_dial_requests(requests)
+                effects.extend(self.process_dial_requests(requests));
+                effects
             }
             OutgoingConnection::Loopback { peer_addr } => {
                 // Loopback connections are marked, but closed.
@@ -603,10 +679,23 @@ where
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_serves_tries,
                 sink,
             } => {
+                if self.reputation.is_banned(&peer_id, Timestamp::now()) {
+                    // Dropping the sink closes the connection, and blocking the address prevents
+                    // an immediate reconnection.
+                    info!("dropping outgoing connection to banned peer");
+                    let requests = self.outgoing_manager.block_addr(peer_addr, now);
+                    return self.process_dial_requests(requests);
+                }
+
                 info!("new outgoing connection established");
 
+                if peer_serves_tries {
+                    self.trie_serving_peers.insert(peer_id);
+                }
+
                 let (sender, receiver) = mpsc::unbounded_channel();
                 let handle = OutgoingHandle { peer_addr, sender };
 
@@ -637,6 +726,7 @@ where
                         self.outgoing_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.net_metrics.queued_messages.clone(),
+                        Arc::downgrade(&self.net_metrics),
                     )
                     .instrument(span)
                     .event(move |_| Event::OutgoingDropped {
@@ -652,6 +742,7 @@ where
 
     fn handle_outgoing_dropped(
         &mut self,
+        effect_builder: EffectBuilder<REv>,
         peer_id: NodeId,
         peer_addr: SocketAddr,
     ) -> Effects<Event<P>> {
@@ -659,12 +750,16 @@ where
             .outgoing_manager
             .handle_connection_drop(peer_addr, Instant::now());
 
-        self.connection_symmetries
-            .entry(peer_id)
-            .or_default()
-            .unmark_outgoing(Instant::now());
+        let symmetry = self.connection_symmetries.entry(peer_id).or_default();
+        let was_symmetric = symmetry.is_symmetric();
+        symmetry.unmark_outgoing(Instant::now());
+        let lost = was_symmetric && !symmetry.is_symmetric();
 
-        self.process_dial_requests(requests)
+        let mut effects = self.process_dial_requests(requests);
+        if lost {
+            effects.extend(self.connection_lost(effect_builder, peer_id));
+        }
+        effects
     }
 
     /// Gossips our public listening address, and schedules the next such gossip round.
@@ -685,6 +780,7 @@ where
             .filter_map(|(peer_id, sym)| {
                 if sym.should_be_reaped(now, MAX_ASYMMETRIC_TIME) {
                     info!(%peer_id, "reaping asymmetric connection");
+                    self.trie_serving_peers.remove(&peer_id);
 
                     // Get the outgoing connection and block it.
                     if let Some(addr) = self.outgoing_manager.get_addr(peer_id) {
@@ -749,9 +845,13 @@ where
         span.in_scope(|| match msg {
             Message::Handshake { .. } => {
                 // We should never receive a handshake message on an established connection. Simply
-                // discard it. This may be too lenient, so we may consider simply dropping the
-                // connection in the future instead.
+                // discard it and count it against the peer's reputation.
                 warn!("received unexpected handshake");
+                self.penalize(effect_builder, peer_id, Offense::ProtocolViolation)
+            }
+            Message::Payload(_) if self.reputation.is_banned(&peer_id, Timestamp::now()) => {
+                // The connection was established before the ban and has not been closed yet.
+                debug!("dropping message from banned peer");
                 Effects::new()
             }
             Message::Payload(payload) => effect_builder
@@ -760,6 +860,55 @@ where
         })
     }
 
+    /// Lowers the reputation of a peer which committed an offense, banning it if its score falls
+    /// below the threshold.
+    fn penalize(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        peer_id: NodeId,
+        offense: Offense,
+    ) -> Effects<Event<P>> {
+        let now = Instant::now();
+        let banned = self
+            .reputation
+            .penalize(peer_id, offense, now, Timestamp::now());
+        self.net_metrics
+            .banned_peers
+            .set(self.reputation.bans().len() as i64);
+
+        if !banned && !offense.is_severe() {
+            debug!(%peer_id, %offense, "peer committed offense");
+            return Effects::new();
+        }
+
+        let mut effects = Effects::new();
+        if banned {
+            warn!(%peer_id, %offense, "banning peer after offense");
+            effects.extend(self.persist_bans(effect_builder));
+        } else {
+            warn!(%peer_id, %offense, "adding peer to blocklist after offense");
+        }
+
+        // Close the incoming connections of the peer, new ones are refused as they are established
+        // while the peer is banned.
+        self.incoming_closers
+            .retain(|(incoming_peer_id, _), _| *incoming_peer_id != peer_id);
+
+        // Block the current outgoing address of the peer, which closes the outgoing connection.
+        if let Some(addr) = self.outgoing_manager.get_addr(peer_id) {
+            let requests = self.outgoing_manager.block_addr(addr, now);
+            effects.extend(self.process_dial_requests(requests));
+        }
+        effects
+    }
+
+    /// Saves the current bans to the state store.
+    fn persist_bans(&self, effect_builder: EffectBuilder<REv>) -> Effects<Event<P>> {
+        effect_builder
+            .save_state(Cow::Borrowed(BAN_LIST_KEY), self.reputation.bans().clone())
+            .ignore()
+    }
+
     /// Handle the change of the active era.
     fn handle_active_era_change(
         &mut self,
@@ -826,6 +975,19 @@ where
         effect_builder.announce_new_peer(peer_id).ignore()
     }
 
+    /// Emits an announcement that a previously completed connection has been lost.
+    fn connection_lost(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        peer_id: NodeId,
+    ) -> Effects<Event<P>> {
+        // The peer announces its capabilities again in the handshake of its next connection.
+        self.trie_serving_peers.remove(&peer_id);
+        trace!(num_peers = self.peers().len(), lost_peer=%peer_id, "connection lost");
+        self.net_metrics.peers.set(self.peers().len() as i64);
+        effect_builder.announce_peer_disconnected(peer_id).ignore()
+    }
+
     /// Returns the set of connected nodes.
     pub(crate) fn peers(&self) -> BTreeMap<NodeId, String> {
         let mut ret = BTreeMap::new();
@@ -890,6 +1052,7 @@ where
         + From<NetworkAnnouncement<NodeId, P>>
         + From<ContractRuntimeRequest>
         + From<StorageRequest>
+        + From<StateStoreRequest>
         + From<ChainspecLoaderRequest>,
     P: Payload,
 {
@@ -914,14 +1077,14 @@ where
                 peer_id,
                 peer_addr,
                 span,
-            } => self.handle_incoming_closed(result, peer_id, peer_addr, *span),
+            } => self.handle_incoming_closed(effect_builder, result, peer_id, peer_addr, *span),
 
             Event::OutgoingConnection { outgoing, span } => {
                 self.handle_outgoing_connection(effect_builder, *outgoing, span)
             }
 
             Event::OutgoingDropped { peer_id, peer_addr } => {
-                self.handle_outgoing_dropped(*peer_id, peer_addr)
+                self.handle_outgoing_dropped(effect_builder, *peer_id, peer_addr)
             }
 
             Event::NetworkRequest { req } => {
@@ -963,6 +1126,12 @@ where
                 NetworkInfoRequest::GetPeers { responder } => {
                     responder.respond(self.peers()).ignore()
                 }
+                NetworkInfoRequest::GetPeerScores { responder } => responder
+                    .respond(self.reputation.scores(Instant::now()))
+                    .ignore(),
+                NetworkInfoRequest::GetPeerServesTries { peer, responder } => responder
+                    .respond(self.trie_serving_peers.contains(&peer))
+                    .ignore(),
             },
             Event::PeerAddressReceived(gossiped_address) => {
                 let requests = self.outgoing_manager.learn_addr(
@@ -972,18 +1141,24 @@ where
                 );
                 self.process_dial_requests(requests)
             }
-            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted(peer_id)) => {
-                // TODO: We do not have a proper by-node-ID blocklist, but rather only block the
-                // current outgoing address of a peer.
-                warn!(%peer_id, "adding peer to blocklist after transgression");
-
-                if let Some(addr) = self.outgoing_manager.get_addr(*peer_id) {
-                    let requests = self.outgoing_manager.block_addr(addr, Instant::now());
-                    self.process_dial_requests(requests)
-                } else {
-                    // Peer got away with it, no longer an outgoing connection.
-                    Effects::new()
+            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted {
+                offender,
+                offense,
+            }) => self.penalize(effect_builder, *offender, offense),
+            Event::BansLoaded(maybe_bans) => {
+                if let Some(bans) = maybe_bans {
+                    info!(count = bans.len(), "restoring persisted bans");
+                    self.reputation.restore_bans(bans);
+                    self.net_metrics
+                        .banned_peers
+                        .set(self.reputation.bans().len() as i64);
                 }
+
+                // Drop the bans which expired while we were offline, and sweep regularly from now
+                // on.
+                effect_builder
+                    .immediately()
+                    .event(|_| Event::SweepReputation)
             }
 
             Event::GossipOurAddress => {
@@ -1009,6 +1184,24 @@ where
 
                 effects
             }
+            Event::SweepReputation => {
+                let bans_expired = self.reputation.sweep(Instant::now(), Timestamp::now());
+                self.net_metrics
+                    .banned_peers
+                    .set(self.reputation.bans().len() as i64);
+
+                let mut effects = if bans_expired {
+                    self.persist_bans(effect_builder)
+                } else {
+                    Effects::new()
+                };
+                effects.extend(
+                    effect_builder
+                        .set_timeout(REPUTATION_SWEEP_INTERVAL)
+                        .event(|_| Event::SweepReputation),
+                );
+                effects
+            }
             Event::SweepOutgoing => {
                 let now = Instant::now();
                 let requests = self.outgoing_manager.perform_housekeeping(now);
@@ -46,6 +46,7 @@ impl ChainInfo {
         public_addr: SocketAddr,
         consensus_keys: Option<&ConsensusKeyPair>,
         connection_id: ConnectionId,
+        serves_tries: bool,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
@@ -53,6 +54,7 @@ impl ChainInfo {
             protocol_version: self.protocol_version,
             consensus_certificate: consensus_keys
                 .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
+            serves_tries,
         }
     }
 }
@@ -20,6 +20,15 @@ const DEFAULT_PUBLIC_ADDRESS: &str = "127.0.0.1:0";
 /// Default interval for gossiping network addresses.
 const DEFAULT_GOSSIP_INTERVAL: Duration = Duration::from_secs(30);
 
+/// Default reputation score at or below which a peer is banned.
+const DEFAULT_BAN_THRESHOLD: i64 = -100;
+
+/// Default duration of a ban.
+const DEFAULT_BAN_DURATION: TimeDiff = TimeDiff::from_seconds(60 * 60);
+
+/// Default time it takes a peer's reputation score to recover by one point.
+const DEFAULT_SCORE_RECOVERY_INTERVAL: TimeDiff = TimeDiff::from_seconds(60);
+
 // Default values for networking configuration:
 impl Default for Config {
     fn default() -> Self {
@@ -33,6 +42,9 @@ impl Default for Config {
             max_addr_pending_time: TimeDiff::from_seconds(60),
             max_outgoing_byte_rate_non_validators: 0,
             max_incoming_message_rate_non_validators: 0,
+            ban_threshold: DEFAULT_BAN_THRESHOLD,
+            ban_duration: DEFAULT_BAN_DURATION,
+            score_recovery_interval: DEFAULT_SCORE_RECOVERY_INTERVAL,
         }
     }
 }
@@ -63,6 +75,27 @@ pub struct Config {
     pub max_outgoing_byte_rate_non_validators: u32,
     /// Maximum of requests answered from non-validating peers. Unlimited if 0.
     pub max_incoming_message_rate_non_validators: u32,
+    /// Reputation score at or below which a peer is banned.
+    #[serde(default = "default_ban_threshold")]
+    pub ban_threshold: i64,
+    /// How long a peer stays banned, including across restarts of the node.
+    #[serde(default = "default_ban_duration")]
+    pub ban_duration: TimeDiff,
+    /// Time it takes a peer's reputation score to recover by one point.
+    #[serde(default = "default_score_recovery_interval")]
+    pub score_recovery_interval: TimeDiff,
+}
+
+fn default_ban_threshold() -> i64 {
+    DEFAULT_BAN_THRESHOLD
+}
+
+fn default_ban_duration() -> TimeDiff {
+    DEFAULT_BAN_DURATION
+}
+
+fn default_score_recovery_interval() -> TimeDiff {
+    DEFAULT_SCORE_RECOVERY_INTERVAL
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     io, mem,
     net::SocketAddr,
@@ -20,6 +20,7 @@ use crate::{
         requests::{NetworkInfoRequest, NetworkRequest},
     },
     protocol::Message as ProtocolMessage,
+    types::Timestamp,
 };
 
 const _SMALL_NETWORK_EVENT_SIZE: usize = mem::size_of::<Event<ProtocolMessage>>();
@@ -86,6 +87,10 @@ pub enum Event<P> {
     SweepSymmetries,
     /// Housekeeping for the outgoing manager.
     SweepOutgoing,
+    /// We are due for removing expired bans and recovered reputation scores.
+    SweepReputation,
+    /// The bans persisted before the last shutdown have been loaded, with the time each expires.
+    BansLoaded(Option<BTreeMap<NodeId, Timestamp>>),
 
     /// Blocklist announcement
     #[from]
@@ -152,6 +157,16 @@ impl<P: Display> Display for Event<P> {
             Event::SweepSymmetries => {
                 write!(f, "sweep connection symmetries")
             }
+            Event::SweepReputation => {
+                write!(f, "sweep peer reputation")
+            }
+            Event::BansLoaded(bans) => {
+                write!(
+                    f,
+                    "loaded {} persisted bans",
+                    bans.as_ref().map_or(0, BTreeMap::len)
+                )
+            }
             Event::LinearChainAnnouncement(ann) => {
                 write!(f, "linear chain announcement: {}", ann)
             }
@@ -201,6 +216,8 @@ pub enum IncomingConnection<P> {
         peer_id: NodeId,
         /// The public key the peer is validating with, if any.
         peer_consensus_public_key: Option<PublicKey>,
+        /// Whether the peer answers requests for global state tries.
+        peer_serves_tries: bool,
         /// Stream of incoming messages. for incoming connections.
         #[serde(skip_serializing)]
         stream: SplitStream<FramedTransport<P>>,
@@ -224,6 +241,7 @@ impl<P> Display for IncomingConnection<P> {
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_serves_tries: _,
                 stream: _,
             } => {
                 write!(
@@ -271,6 +289,8 @@ pub enum OutgoingConnection<P> {
         peer_id: NodeId,
         /// The public key the peer is validating with, if any.
         peer_consensus_public_key: Option<PublicKey>,
+        /// Whether the peer answers requests for global state tries.
+        peer_serves_tries: bool,
         /// Sink for outgoing messages.
         #[serde(skip_serializing)]
         sink: SplitSink<FramedTransport<P>, Arc<Message<P>>>,
@@ -293,6 +313,7 @@ impl<P> Display for OutgoingConnection<P> {
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_serves_tries: _,
                 sink: _,
             } => {
                 write!(f, "connection established to {}/{}", peer_addr, peer_id)?;
@@ -0,0 +1,248 @@
+//! Weighted fair queuing of outgoing messages.
+//!
+//! Every outgoing connection has a single sending task, so a large number of big messages queued
+//! for a peer, e.g. responses to block or deploy fetch requests, would otherwise delay any
+//! consensus message queued after them.  Instead, messages are sorted into per-class queues which
+//! are served using deficit round robin: on every visit, a class may send up to its weight times
+//! `QUANTUM` bytes, carrying any unused allowance over to its next visit while it has messages
+//! waiting.
+
+use std::{collections::VecDeque, time::Instant};
+
+use super::MessageKind;
+
+/// Number of bytes a class of weight 1 may send per round.
+const QUANTUM: u64 = 16 * 1024;
+
+/// The class of an outgoing message, which determines its share of a connection's bandwidth.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TrafficClass {
+    /// Consensus messages and finality signatures, along with protocol messages.
+    Consensus,
+    /// Gossiped deploys and addresses.
+    Gossip,
+    /// Items transferred directly via requests and responses, e.g. deploys, blocks and tries.
+    Transfer,
+    /// Any other kind of message.
+    Other,
+}
+
+impl TrafficClass {
+    /// All traffic classes, in the order they are served in.
+    const ALL: [TrafficClass; 4] = [
+        TrafficClass::Consensus,
+        TrafficClass::Gossip,
+        TrafficClass::Transfer,
+        TrafficClass::Other,
+    ];
+
+    /// The relative share of bandwidth the class receives while other classes are backlogged.
+    fn weight(self) -> u64 {
+        match self {
+            TrafficClass::Consensus => 8,
+            TrafficClass::Gossip => 2,
+            TrafficClass::Transfer => 1,
+            TrafficClass::Other => 1,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            TrafficClass::Consensus => 0,
+            TrafficClass::Gossip => 1,
+            TrafficClass::Transfer => 2,
+            TrafficClass::Other => 3,
+        }
+    }
+}
+
+impl From<MessageKind> for TrafficClass {
+    fn from(kind: MessageKind) -> Self {
+        match kind {
+            MessageKind::Protocol | MessageKind::Consensus => TrafficClass::Consensus,
+            MessageKind::DeployGossip | MessageKind::AddressGossip => TrafficClass::Gossip,
+            MessageKind::DeployTransfer
+            | MessageKind::BlockTransfer
+            | MessageKind::TrieTransfer => TrafficClass::Transfer,
+            MessageKind::Other => TrafficClass::Other,
+        }
+    }
+}
+
+/// A message waiting in a `FairQueue`.
+#[derive(Debug)]
+pub(super) struct Queued<T> {
+    /// The queued message.
+    pub(super) item: T,
+    /// The class of the message.
+    pub(super) class: TrafficClass,
+    /// The estimated size of the message on the wire, in bytes.
+    pub(super) size: u32,
+    /// The time the message was queued.
+    pub(super) queued_at: Instant,
+}
+
+/// A set of per-class queues served in weighted fair order.
+#[derive(Debug)]
+pub(super) struct FairQueue<T> {
+    /// The queue of each traffic class.
+    queues: [VecDeque<Queued<T>>; 4],
+    /// The number of bytes each class may still send before the next class is served.
+    deficits: [u64; 4],
+    /// Index of the class currently being served.
+    current: usize,
+    /// Whether the current class has already been granted its quantum on this visit.
+    granted: bool,
+}
+
+impl<T> FairQueue<T> {
+    /// Creates a new, empty queue.
+    pub(super) fn new() -> Self {
+        FairQueue {
+            queues: Default::default(),
+            deficits: [0; 4],
+            current: 0,
+            granted: false,
+        }
+    }
+
+    /// Appends a message to the queue of its class.
+    pub(super) fn push(&mut self, item: T, class: TrafficClass, size: u32, queued_at: Instant) {
+        self.queues[class.index()].push_back(Queued {
+            item,
+            class,
+            size,
+            queued_at,
+        });
+    }
+
+    /// Removes the next message to be sent, if any.
+    pub(super) fn pop(&mut self) -> Option<Queued<T>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // Terminates, as the deficit of a non-empty queue grows on every visit.
+        loop {
+            let index = self.current;
+            match self.queues[index].front() {
+                Some(front) => {
+                    if !self.granted {
+                        self.deficits[index] += TrafficClass::ALL[index].weight() * QUANTUM;
+                        self.granted = true;
+                    }
+                    let size = u64::from(front.size);
+                    if size <= self.deficits[index] {
+                        self.deficits[index] -= size;
+                        let queued = self.queues[index].pop_front();
+                        if self.queues[index].is_empty() {
+                            // An idle class does not accumulate allowance.
+                            self.deficits[index] = 0;
+                        }
+                        return queued;
+                    }
+                }
+                None => self.deficits[index] = 0,
+            }
+            self.current = (index + 1) % self.queues.len();
+            self.granted = false;
+        }
+    }
+
+    /// Returns `true` if no message is queued.
+    pub(super) fn is_empty(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
+    }
+
+    /// Removes all queued messages.
+    pub(super) fn drain(&mut self) -> impl Iterator<Item = Queued<T>> + '_ {
+        self.queues.iter_mut().flat_map(|queue| queue.drain(..))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::{FairQueue, TrafficClass, QUANTUM};
+
+    #[test]
+    fn should_pop_in_order_within_class() {
+        let mut queue = FairQueue::new();
+        let now = Instant::now();
+        for item in 0..10 {
+            queue.push(item, TrafficClass::Transfer, 100, now);
+        }
+
+        let popped: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|queued| queued.item)
+            .collect();
+        assert_eq!(popped, (0..10).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn should_not_delay_consensus_behind_transfers() {
+        let mut queue = FairQueue::new();
+        let now = Instant::now();
+        let transfer_size = QUANTUM as u32;
+        for _ in 0..100 {
+            queue.push("transfer", TrafficClass::Transfer, transfer_size, now);
+        }
+        assert_eq!(queue.pop().unwrap().item, "transfer");
+
+        // A consensus message queued behind a burst of transfers is sent after at most one more
+        // round of transfers.
+        queue.push("consensus", TrafficClass::Consensus, 1_000, now);
+        let position = std::iter::from_fn(|| queue.pop())
+            .position(|queued| queued.item == "consensus")
+            .unwrap();
+        assert!(
+            position <= 1,
+            "consensus message sent at position {}",
+            position
+        );
+    }
+
+    #[test]
+    fn should_share_bandwidth_by_weight() {
+        let mut queue = FairQueue::new();
+        let now = Instant::now();
+        let size = 1_024;
+        for _ in 0..1_000 {
+            queue.push(TrafficClass::Consensus, TrafficClass::Consensus, size, now);
+            queue.push(TrafficClass::Transfer, TrafficClass::Transfer, size, now);
+        }
+
+        // While both classes are backlogged, consensus receives eight times the bandwidth.  Each
+        // round sends 128 consensus messages and 16 transfers.
+        let sent: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .take(6 * 144)
+            .map(|queued| queued.class)
+            .collect();
+        let consensus = sent
+            .iter()
+            .filter(|class| **class == TrafficClass::Consensus)
+            .count();
+        assert_eq!(consensus, 8 * (sent.len() - consensus));
+
+        // Once consensus is idle, transfers get all of the bandwidth.
+        let remaining: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(remaining.len(), 2_000 - 6 * 144);
+        assert!(remaining[remaining.len() - 800..]
+            .iter()
+            .all(|queued| queued.class == TrafficClass::Transfer));
+    }
+
+    #[test]
+    fn should_send_messages_larger_than_quantum() {
+        let mut queue = FairQueue::new();
+        let now = Instant::now();
+        queue.push(1, TrafficClass::Other, 10 * QUANTUM as u32, now);
+        queue.push(2, TrafficClass::Gossip, 100, now);
+
+        assert_eq!(queue.pop().unwrap().item, 2);
+        assert_eq!(queue.pop().unwrap().item, 1);
+        assert!(queue.pop().is_none());
+    }
+}
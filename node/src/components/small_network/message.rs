@@ -30,6 +30,9 @@ pub enum Message<P> {
         /// A self-signed certificate indicating validator status.
         #[serde(default)]
         consensus_certificate: Option<ConsensusCertificate>,
+        /// Whether the node answers requests for global state tries.
+        #[serde(default)]
+        serves_tries: bool,
     },
     Payload(P),
 }
@@ -113,6 +116,7 @@ impl<P: Display> Display for Message<P> {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                serves_tries,
             } => {
                 write!(
                     f,
@@ -121,10 +125,12 @@ impl<P: Display> Display for Message<P> {
                 )?;
 
                 if let Some(cert) = consensus_certificate {
-                    write!(f, "{}", cert)
+                    write!(f, "{}", cert)?;
                 } else {
-                    f.write_str("-")
+                    f.write_str("-")?;
                 }
+
+                write!(f, ", serves_tries: {}", serves_tries)
             }
             Message::Payload(payload) => write!(f, "payload: {}", payload),
         }
@@ -146,6 +152,8 @@ pub enum MessageKind {
     DeployTransfer,
     /// Blocks for finality signatures being transferred directly (via requests and other means).
     BlockTransfer,
+    /// Global state tries being transferred directly (via requests).
+    TrieTransfer,
     /// Any other kind of payload (or missing classification).
     Other,
 }
@@ -159,6 +167,7 @@ impl Display for MessageKind {
             MessageKind::AddressGossip => f.write_str("address_gossip"),
             MessageKind::DeployTransfer => f.write_str("deploy_transfer"),
             MessageKind::BlockTransfer => f.write_str("block_transfer"),
+            MessageKind::TrieTransfer => f.write_str("trie_transfer"),
             MessageKind::Other => f.write_str("other"),
         }
     }
@@ -297,6 +306,7 @@ mod tests {
             protocol_version: ProtocolVersion::from_parts(5, 6, 7),
             // TODO: Test _with_ handshake instead.
             consensus_certificate: None,
+            serves_tries: true,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -330,11 +340,13 @@ mod tests {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                serves_tries,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
+                assert!(!serves_tries);
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -352,11 +364,13 @@ mod tests {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                serves_tries,
             } => {
                 assert_eq!(network_name, "serialization-test");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
+                assert!(!serves_tries);
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -0,0 +1,313 @@
+//! Peer reputation tracking.
+//!
+//! Every peer starts out with a score of zero.  Offenses reported by other components lower the
+//! score of the offending peer, while it slowly recovers back to zero over time.  Once a peer's
+//! score falls to or below the configured threshold, the peer is banned for a fixed duration.
+//!
+//! Honest peers don't answer requests for items they don't have, so fetch timeouts lower a peer's
+//! score by at most one point per recovery interval, which it recovers from in the same time.
+//! Timeouts alone therefore never get a peer banned.
+//!
+//! Bans are keyed by node ID and expire at a wall-clock time, so that they can be persisted and
+//! remain in force across restarts.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{NodeId, TimeDiff, Timestamp};
+
+/// A misbehavior of a peer which lowers its reputation.
+#[derive(Copy, Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Offense {
+    /// The peer sent us a deploy which failed validation.
+    InvalidDeploy,
+    /// The peer sent consensus a message which failed validation, e.g. an invalid vertex.
+    InvalidConsensusMessage,
+    /// The peer did not respond to a fetch request in time.
+    FetchTimeout,
+    /// The peer violated the networking protocol, e.g. by connecting with the wrong chainspec.
+    ProtocolViolation,
+}
+
+impl Offense {
+    /// The amount by which the offense lowers the offender's score.
+    fn penalty(self) -> i64 {
+        match self {
+            Offense::InvalidDeploy => 10,
+            Offense::InvalidConsensusMessage => 50,
+            Offense::FetchTimeout => 1,
+            Offense::ProtocolViolation => 50,
+        }
+    }
+
+    /// Returns `true` if the offender should also be disconnected from immediately, regardless of
+    /// its score.
+    pub(super) fn is_severe(self) -> bool {
+        match self {
+            Offense::InvalidConsensusMessage | Offense::ProtocolViolation => true,
+            Offense::InvalidDeploy | Offense::FetchTimeout => false,
+        }
+    }
+}
+
+impl Display for Offense {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Offense::InvalidDeploy => write!(formatter, "invalid deploy"),
+            Offense::InvalidConsensusMessage => write!(formatter, "invalid consensus message"),
+            Offense::FetchTimeout => write!(formatter, "fetch timeout"),
+            Offense::ProtocolViolation => write!(formatter, "protocol violation"),
+        }
+    }
+}
+
+/// The score of a single peer.
+#[derive(Copy, Clone, DataSize, Debug)]
+struct PeerScore {
+    /// The score as of `updated`.
+    score: i64,
+    /// The time the score was last changed, from which it recovers.
+    #[data_size(skip)]
+    updated: Instant,
+}
+
+/// The reputation scores and bans of all peers.
+#[derive(DataSize, Debug)]
+pub(super) struct Reputation {
+    /// Scores of the peers which committed an offense and haven't fully recovered yet.
+    scores: HashMap<NodeId, PeerScore>,
+    /// Banned peers, along with the time their ban expires.
+    bans: BTreeMap<NodeId, Timestamp>,
+    /// The time each peer was last penalized for a fetch timeout, if within the recovery interval.
+    #[data_size(skip)]
+    fetch_timeouts: HashMap<NodeId, Instant>,
+    /// A peer is banned once its score falls to or below this value.
+    ban_threshold: i64,
+    /// How long a peer stays banned.
+    ban_duration: TimeDiff,
+    /// The time it takes a score to recover by one point.
+    recovery_interval: Duration,
+}
+
+impl Reputation {
+    /// Creates a new instance without any scores or bans.
+    pub(super) fn new(
+        ban_threshold: i64,
+        ban_duration: TimeDiff,
+        recovery_interval: TimeDiff,
+    ) -> Self {
+        Reputation {
+            scores: HashMap::new(),
+            bans: BTreeMap::new(),
+            fetch_timeouts: HashMap::new(),
+            ban_threshold,
+            ban_duration,
+            // Prevent dividing by zero when recovering.
+            recovery_interval: Duration::from(recovery_interval).max(Duration::from_millis(1)),
+        }
+    }
+
+    /// Returns the current score of the given peer.
+    pub(super) fn score(&self, peer: &NodeId, now: Instant) -> i64 {
+        self.scores
+            .get(peer)
+            .map_or(0, |peer_score| self.recovered(peer_score, now))
+    }
+
+    /// Returns the current scores of all peers which don't have a neutral score.
+    pub(super) fn scores(&self, now: Instant) -> BTreeMap<NodeId, i64> {
+        self.scores
+            .iter()
+            .map(|(peer, peer_score)| (*peer, self.recovered(peer_score, now)))
+            .filter(|(_, score)| *score != 0)
+            .collect()
+    }
+
+    /// Lowers the score of the given peer according to the offense committed.
+    ///
+    /// A fetch timeout is ignored if the peer was already penalized for one within the recovery
+    /// interval.
+    ///
+    /// Returns `true` if the peer was banned as a result.
+    pub(super) fn penalize(
+        &mut self,
+        peer: NodeId,
+        offense: Offense,
+        now: Instant,
+        wall_clock_now: Timestamp,
+    ) -> bool {
+        if offense == Offense::FetchTimeout {
+            let recovery_interval = self.recovery_interval;
+            let recently_penalized = self.fetch_timeouts.get(&peer).map_or(false, |last| {
+                now.saturating_duration_since(*last) < recovery_interval
+            });
+            if recently_penalized {
+                return false;
+            }
+            self.fetch_timeouts.insert(peer, now);
+        }
+        let score = self.score(&peer, now) - offense.penalty();
+        if score > self.ban_threshold {
+            self.scores.insert(
+                peer,
+                PeerScore {
+                    score,
+                    updated: now,
+                },
+            );
+            return false;
+        }
+        // The ban replaces the score, which starts afresh once the ban expires.
+        self.scores.remove(&peer);
+        self.bans.insert(peer, wall_clock_now + self.ban_duration);
+        true
+    }
+
+    /// Returns `true` if the given peer is currently banned.
+    pub(super) fn is_banned(&self, peer: &NodeId, wall_clock_now: Timestamp) -> bool {
+        self.bans
+            .get(peer)
+            .map_or(false, |until| *until > wall_clock_now)
+    }
+
+    /// Returns all bans, to be persisted.
+    pub(super) fn bans(&self) -> &BTreeMap<NodeId, Timestamp> {
+        &self.bans
+    }
+
+    /// Adds previously persisted bans, keeping the later expiry time of any peer banned twice.
+    pub(super) fn restore_bans(&mut self, bans: BTreeMap<NodeId, Timestamp>) {
+        for (peer, until) in bans {
+            let entry = self.bans.entry(peer).or_insert(until);
+            *entry = (*entry).max(until);
+        }
+    }
+
+    /// Removes expired bans and fully recovered scores.
+    ///
+    /// Returns `true` if any ban was removed.
+    pub(super) fn sweep(&mut self, now: Instant, wall_clock_now: Timestamp) -> bool {
+        let recovery_interval = self.recovery_interval;
+        self.scores
+            .retain(|_, peer_score| recovered_score(peer_score, recovery_interval, now) != 0);
+        self.fetch_timeouts
+            .retain(|_, last| now.saturating_duration_since(*last) < recovery_interval);
+        let bans_before = self.bans.len();
+        self.bans.retain(|_, until| *until > wall_clock_now);
+        self.bans.len() != bans_before
+    }
+
+    fn recovered(&self, peer_score: &PeerScore, now: Instant) -> i64 {
+        recovered_score(peer_score, self.recovery_interval, now)
+    }
+}
+
+/// Returns the score after recovering by one point per `recovery_interval` since it was updated.
+fn recovered_score(peer_score: &PeerScore, recovery_interval: Duration, now: Instant) -> i64 {
+    let elapsed = now.saturating_duration_since(peer_score.updated);
+    let recovered = (elapsed.as_millis() / recovery_interval.as_millis()) as i64;
+    (peer_score.score + recovered).min(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn reputation() -> Reputation {
+        Reputation::new(
+            -100,
+            TimeDiff::from_seconds(3_600),
+            TimeDiff::from_seconds(60),
+        )
+    }
+
+    #[test]
+    fn should_recover_over_time() {
+        let mut rng = TestRng::new();
+        let peer = NodeId::random(&mut rng);
+        let mut reputation = reputation();
+        let now = Instant::now();
+
+        assert!(!reputation.penalize(peer, Offense::InvalidDeploy, now, Timestamp::now()));
+        assert_eq!(reputation.score(&peer, now), -10);
+        assert_eq!(reputation.score(&peer, now + 4 * MINUTE), -6);
+        assert_eq!(reputation.score(&peer, now + 20 * MINUTE), 0);
+
+        assert!(!reputation.sweep(now + 4 * MINUTE, Timestamp::now()));
+        assert_eq!(reputation.scores(now + 4 * MINUTE).get(&peer), Some(&-6));
+        reputation.sweep(now + 20 * MINUTE, Timestamp::now());
+        assert!(reputation.scores(now + 20 * MINUTE).is_empty());
+    }
+
+    #[test]
+    fn should_not_ban_for_fetch_timeouts_alone() {
+        let mut rng = TestRng::new();
+        let peer = NodeId::random(&mut rng);
+        let mut reputation = reputation();
+        let now = Instant::now();
+        let wall_clock_now = Timestamp::now();
+
+        // Only the first of many timeouts within a recovery interval lowers the score.
+        for _ in 0..1_000 {
+            assert!(!reputation.penalize(peer, Offense::FetchTimeout, now, wall_clock_now));
+        }
+        assert_eq!(reputation.score(&peer, now), -1);
+
+        // Timeouts at the rate the score recovers never accumulate.
+        for minute in 1..1_000 {
+            let later = now + minute * MINUTE;
+            assert!(!reputation.penalize(peer, Offense::FetchTimeout, later, wall_clock_now));
+            assert_eq!(reputation.score(&peer, later), -1);
+        }
+        assert!(!reputation.is_banned(&peer, wall_clock_now));
+    }
+
+    #[test]
+    fn should_ban_once_below_threshold() {
+        let mut rng = TestRng::new();
+        let peer = NodeId::random(&mut rng);
+        let mut reputation = reputation();
+        let now = Instant::now();
+        let wall_clock_now = Timestamp::now();
+
+        assert!(!reputation.penalize(peer, Offense::ProtocolViolation, now, wall_clock_now));
+        assert!(!reputation.is_banned(&peer, wall_clock_now));
+        assert!(reputation.penalize(peer, Offense::InvalidConsensusMessage, now, wall_clock_now));
+        assert!(reputation.is_banned(&peer, wall_clock_now));
+
+        // The ban expires after the ban duration.
+        let expiry = wall_clock_now + TimeDiff::from_seconds(3_600);
+        assert!(reputation.is_banned(&peer, expiry - TimeDiff::from(1)));
+        assert!(!reputation.is_banned(&peer, expiry));
+        assert!(reputation.sweep(now, expiry));
+        assert!(reputation.bans().is_empty());
+    }
+
+    #[test]
+    fn should_restore_bans() {
+        let mut rng = TestRng::new();
+        let peer = NodeId::random(&mut rng);
+        let mut reputation = reputation();
+        let wall_clock_now = Timestamp::now();
+        let until = wall_clock_now + TimeDiff::from_seconds(60);
+
+        let mut bans = BTreeMap::new();
+        bans.insert(peer, until);
+        reputation.restore_bans(bans.clone());
+        assert!(reputation.is_banned(&peer, wall_clock_now));
+
+        // An earlier expiry does not shorten an existing ban.
+        bans.insert(peer, wall_clock_now);
+        reputation.restore_bans(bans);
+        assert_eq!(reputation.bans().get(&peer), Some(&until));
+    }
+}
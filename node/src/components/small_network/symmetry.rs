@@ -183,6 +183,11 @@ impl ConnectionSymmetry {
         }
     }
 
+    /// Returns whether the connection is fully symmetric.
+    pub(super) fn is_symmetric(&self) -> bool {
+        matches!(self, ConnectionSymmetry::Symmetric { .. })
+    }
+
     /// Returns the set of incoming addresses, if any.
     pub(super) fn incoming_addrs(&self) -> Option<&BTreeSet<SocketAddr>> {
         match self {
@@ -7,7 +7,7 @@ use std::{
     net::SocketAddr,
     pin::Pin,
     sync::{Arc, Weak},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use casper_types::PublicKey;
@@ -38,6 +38,7 @@ use super::{
     counting_format::{ConnectionId, Role},
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
+    fair_queue::{FairQueue, TrafficClass},
     framed,
     limiter::LimiterHandle,
     message::ConsensusKeyPair,
@@ -133,7 +134,7 @@ where
 
     // Negotiate the handshake, concluding the incoming connection process.
     match negotiate_handshake(&context, &mut transport, connection_id).await {
-        Ok((public_addr, peer_consensus_public_key)) => {
+        Ok((public_addr, peer_consensus_public_key, peer_serves_tries)) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
@@ -150,6 +151,7 @@ where
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_serves_tries,
                 sink,
             }
         }
@@ -182,6 +184,8 @@ where
     pub(super) public_addr: SocketAddr,
     /// Optional set of consensus keys, to identify as a validator during handshake.
     pub(super) consensus_keys: Option<ConsensusKeyPair>,
+    /// Whether to advertise during handshake that we answer requests for global state tries.
+    pub(super) serves_tries: bool,
 }
 
 /// Handles an incoming connection.
@@ -228,7 +232,7 @@ where
 
     // Negotiate the handshake, concluding the incoming connection process.
     match negotiate_handshake(&context, &mut transport, connection_id).await {
-        Ok((public_addr, peer_consensus_public_key)) => {
+        Ok((public_addr, peer_consensus_public_key, peer_serves_tries)) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
@@ -241,6 +245,7 @@ where
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_serves_tries,
                 stream,
             }
         }
@@ -318,7 +323,7 @@ async fn negotiate_handshake<P, REv>(
     context: &NetworkContext<REv>,
     transport: &mut FramedTransport<P>,
     connection_id: ConnectionId,
-) -> Result<(SocketAddr, Option<PublicKey>), ConnectionError>
+) -> Result<(SocketAddr, Option<PublicKey>, bool), ConnectionError>
 where
     P: Payload,
 {
@@ -327,6 +332,7 @@ where
         context.public_addr,
         context.consensus_keys.as_ref(),
         connection_id,
+        context.serves_tries,
     );
 
     io_timeout(HANDSHAKE_TIMEOUT, transport.send(Arc::new(handshake)))
@@ -342,9 +348,10 @@ where
         public_addr,
         protocol_version,
         consensus_certificate,
+        serves_tries,
     } = remote_handshake
     {
-        debug!(%protocol_version, "handshake received");
+        debug!(%protocol_version, serves_tries, "handshake received");
 
         // The handshake was valid, we can check the network name.
         if network_name != context.chain_info.network_name {
@@ -358,7 +365,7 @@ where
             })
             .transpose()?;
 
-        Ok((public_addr, peer_consensus_public_key))
+        Ok((public_addr, peer_consensus_public_key, serves_tries))
     } else {
         // Received a non-handshake, this is an error.
         Err(ConnectionError::DidNotSendHandshake)
@@ -440,12 +447,14 @@ pub(super) async fn server<P, REv>(
 
 /// Network message reader.
 ///
-/// Schedules all received messages until the stream is closed or an error occurs.
+/// Schedules all received messages until the stream is closed or an error occurs, or until either
+/// `shutdown_receiver` or `close_receiver` is closed.
 pub(super) async fn message_reader<REv, P>(
     context: Arc<NetworkContext<REv>>,
     mut stream: SplitStream<FramedTransport<P>>,
     limiter: Box<dyn LimiterHandle>,
     mut shutdown_receiver: watch::Receiver<()>,
+    mut close_receiver: watch::Receiver<()>,
     peer_id: NodeId,
     span: Span,
 ) -> io::Result<()>
@@ -489,11 +498,16 @@ where
     };
 
     let shutdown_messages = async move { while shutdown_receiver.changed().await.is_ok() {} };
-
-    // Now we can wait for either the `shutdown` channel's remote end to do be dropped or the
-    // while loop to terminate.
-    match future::select(Box::pin(shutdown_messages), Box::pin(read_messages)).await {
-        Either::Left(_) => info!("shutting down incoming connection message reader"),
+    let close_messages = async move { while close_receiver.changed().await.is_ok() {} };
+
+    // Now we can wait for either the `shutdown` or the `close` channel's remote end to be dropped
+    // or the while loop to terminate.
+    let stop_messages = future::select(Box::pin(shutdown_messages), Box::pin(close_messages));
+    match future::select(stop_messages, Box::pin(read_messages)).await {
+        Either::Left((Either::Left(_), _)) => {
+            info!("shutting down incoming connection message reader")
+        }
+        Either::Left((Either::Right(_), _)) => info!("closing incoming connection"),
         Either::Right(_) => (),
     }
 
@@ -503,27 +517,47 @@ where
 /// Network message sender.
 ///
 /// Reads from a channel and sends all messages, until the stream is closed or an error occurs.
+///
+/// Messages waiting to be sent are sorted by their traffic class and sent in weighted fair order,
+/// so that a backlog of one class, e.g. large fetch responses, does not hold up the others.
 pub(super) async fn message_sender<P>(
-    mut queue: UnboundedReceiver<Arc<Message<P>>>,
+    mut queue: UnboundedReceiver<(Arc<Message<P>>, Instant)>,
     mut sink: SplitSink<FramedTransport<P>, Arc<Message<P>>>,
     limiter: Box<dyn LimiterHandle>,
     counter: IntGauge,
+    net_metrics: Weak<NetworkingMetrics>,
 ) where
     P: Payload,
 {
-    while let Some(message) = queue.recv().await {
-        counter.dec();
+    let mut fair_queue = FairQueue::new();
+
+    loop {
+        // Only wait for new messages if there is nothing left to send.
+        if fair_queue.is_empty() {
+            match queue.recv().await {
+                Some((message, queued_at)) => {
+                    enqueue(&mut fair_queue, message, queued_at, &counter, &net_metrics)
+                }
+                None => break,
+            }
+        }
+        // Messages queued in the meantime compete for the next send.
+        while let Ok((message, queued_at)) = queue.try_recv() {
+            enqueue(&mut fair_queue, message, queued_at, &counter, &net_metrics);
+        }
 
-        // TODO: Refactor message sending to not use `tokio_serde` anymore to avoid duplicate
-        //       serialization.
-        let estimated_wire_size = rmp_serde::to_vec(&message)
-            .as_ref()
-            .map(Vec::len)
-            .unwrap_or(0) as u32;
-        limiter.request_allowance(estimated_wire_size).await;
+        let queued = match fair_queue.pop() {
+            Some(queued) => queued,
+            // Not reachable, as at least one message was queued above.
+            None => continue,
+        };
+        NetworkingMetrics::record_queue_depth_change(&net_metrics, queued.class, -1);
+        NetworkingMetrics::record_queue_latency(&net_metrics, queued.class, queued.queued_at);
+
+        limiter.request_allowance(queued.size).await;
 
         // We simply error-out if the sink fails, it means that our connection broke.
-        if let Err(ref err) = sink.send(message).await {
+        if let Err(ref err) = sink.send(queued.item).await {
             info!(
                 err = display_error(err),
                 "message send failed, closing outgoing connection"
@@ -531,4 +565,32 @@ pub(super) async fn message_sender<P>(
             break;
         };
     }
+
+    // The remaining messages are dropped along with the connection.
+    for queued in fair_queue.drain() {
+        NetworkingMetrics::record_queue_depth_change(&net_metrics, queued.class, -1);
+    }
+}
+
+/// Adds a message received from the component to the queue of its traffic class.
+fn enqueue<P>(
+    fair_queue: &mut FairQueue<Arc<Message<P>>>,
+    message: Arc<Message<P>>,
+    queued_at: Instant,
+    counter: &IntGauge,
+    net_metrics: &Weak<NetworkingMetrics>,
+) where
+    P: Payload,
+{
+    counter.dec();
+
+    // TODO: Refactor message sending to not use `tokio_serde` anymore to avoid duplicate
+    //       serialization.
+    let estimated_wire_size = rmp_serde::to_vec(&message)
+        .as_ref()
+        .map(Vec::len)
+        .unwrap_or(0) as u32;
+    let class = TrafficClass::from(message.classify());
+    NetworkingMetrics::record_queue_depth_change(net_metrics, class, 1);
+    fair_queue.push(message, class, estimated_wire_size, queued_at);
 }
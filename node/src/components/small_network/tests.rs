@@ -29,9 +29,10 @@ use crate::{
     effect::{
         announcements::{ControlAnnouncement, GossiperAnnouncement, NetworkAnnouncement},
         requests::{
-            ChainspecLoaderRequest, ContractRuntimeRequest, NetworkRequest, StorageRequest,
+            ChainspecLoaderRequest, ContractRuntimeRequest, NetworkRequest, StateStoreRequest,
+            StorageRequest,
         },
-        EffectBuilder, Effects,
+        EffectBuilder, EffectExt, Effects,
     },
     protocol,
     reactor::{self, EventQueueHandle, Finalize, Reactor, Runner},
@@ -60,6 +61,8 @@ enum Event {
     NetworkAnnouncement(#[serde(skip_serializing)] NetworkAnnouncement<NodeId, Message>),
     #[from]
     AddressGossiperAnnouncement(#[serde(skip_serializing)] GossiperAnnouncement<GossipedAddress>),
+    #[from]
+    StateStoreRequest(StateStoreRequest),
 }
 
 impl ReactorEvent for Event {
@@ -166,6 +169,7 @@ impl Reactor for TestReactor {
             small_network_identity,
             ChainInfo::create_for_testing(),
             None,
+            false,
         )?;
         let gossiper_config = gossiper::Config::new_with_small_timeouts();
         let address_gossiper =
@@ -222,8 +226,9 @@ impl Reactor for TestReactor {
                 };
                 self.dispatch_event(effect_builder, rng, Event::AddressGossiper(event))
             }
-            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(_)) => {
-                // We do not care about the announcement of new peers in this test.
+            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(_))
+            | Event::NetworkAnnouncement(NetworkAnnouncement::PeerDisconnected(_)) => {
+                // We do not care about the announcement of new or lost peers in this test.
                 Effects::new()
             }
             Event::AddressGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem(
@@ -237,6 +242,14 @@ impl Reactor for TestReactor {
                 // We do not care about the announcement of gossiping finished in this test.
                 Effects::new()
             }
+            // There is no storage in this test, so nothing is ever persisted.
+            Event::StateStoreRequest(StateStoreRequest::Load { responder, .. }) => {
+                responder.respond(None).ignore()
+            }
+            Event::StateStoreRequest(StateStoreRequest::Save { responder, .. })
+            | Event::StateStoreRequest(StateStoreRequest::Delete { responder, .. }) => {
+                responder.respond(()).ignore()
+            }
         }
     }
 
@@ -0,0 +1,499 @@
+//! Global state synchronizer.
+//!
+//! Downloads the global state under a given state root hash from peers, so that a joining node
+//! can start from the state of a recent block instead of executing every block since genesis.
+//!
+//! Steps are:
+//! 1. Load the checkpoint of a previous, interrupted synchronization of the same state root hash.
+//!    Without a checkpoint, ask the contract runtime which tries are missing under the root.
+//! 2. Fetch up to `max_parallel_fetches` tries from the frontier of missing tries at the same time,
+//!    spreading the requests across all connected peers.
+//! 3. Put every fetched trie into the trie store, replacing it in the frontier by those of its
+//!    descendants which are still missing.
+//! 4. Every `checkpoint_interval` stored tries, save the frontier to the state store, so that a
+//!    restarted node can resume from it.
+//! 5. Once the frontier is empty, ask the contract runtime again for missing tries under the root.
+//!    If there are none, the global state is complete and the checkpoint is deleted.
+//!
+//! Tries are only requested from peers which announced in their network handshake that they serve
+//! tries, and peers are dropped again once they disconnect or get banned. If no such peer is
+//! connected for `peer_timeout`, synchronization is given up.
+//!
+//! Tries are identified by the hash of their serialized form, so a trie received from a peer is
+//! known to be the one requested and does not need to be validated any further.
+
+mod config;
+mod event;
+mod frontier;
+mod metrics;
+
+use std::{borrow::Cow, convert::Infallible};
+
+use datasize::DataSize;
+use prometheus::Registry;
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+use casper_execution_engine::{
+    shared::{newtypes::Blake2bHash, stored_value::StoredValue},
+    storage::trie::Trie,
+};
+use casper_types::Key;
+
+use crate::{
+    components::{fetcher::FetchResult, Component},
+    effect::{
+        requests::{
+            ContractRuntimeRequest, FetcherRequest, NetworkInfoRequest, StateStoreRequest,
+            StateSyncRequest,
+        },
+        EffectBuilder, EffectExt, Effects, Responder,
+    },
+    types::{NodeId, TimeDiff, Timestamp},
+    NodeRng,
+};
+pub use config::Config;
+pub use event::Event;
+use frontier::Frontier;
+use metrics::StateSyncMetrics;
+
+/// A helper trait constraining `StateSync` compatible reactor events.
+pub trait ReactorEventT:
+    From<Event>
+    + From<FetcherRequest<NodeId, Trie<Key, StoredValue>>>
+    + From<ContractRuntimeRequest>
+    + From<StateStoreRequest>
+    + From<NetworkInfoRequest<NodeId>>
+    + Send
+    + 'static
+{
+}
+
+impl<REv> ReactorEventT for REv where
+    REv: From<Event>
+        + From<FetcherRequest<NodeId, Trie<Key, StoredValue>>>
+        + From<ContractRuntimeRequest>
+        + From<StateStoreRequest>
+        + From<NetworkInfoRequest<NodeId>>
+        + Send
+        + 'static
+{
+}
+
+/// An error synchronizing global state.
+#[derive(Clone, Debug, Error)]
+pub enum Error {
+    /// Reading from or writing to the trie store failed.
+    #[error("trie store error: {0}")]
+    TrieStore(String),
+
+    /// A trie could not be fetched from any peer.
+    #[error("failed to fetch trie {trie_key} after {attempts} attempts")]
+    TrieUnavailable {
+        /// The key of the trie.
+        trie_key: Blake2bHash,
+        /// The number of failed attempts to fetch it.
+        attempts: u32,
+    },
+
+    /// The global state under a different state root hash is already being synchronized.
+    #[error("already synchronizing global state under {0}")]
+    AlreadySyncing(Blake2bHash),
+
+    /// No peer serving tries was connected for the configured time.
+    #[error("no peer serving tries connected for {0}")]
+    NoPeers(TimeDiff),
+}
+
+/// The synchronization of the global state under a single state root hash.
+#[derive(Debug)]
+struct ActiveSync {
+    state_root_hash: Blake2bHash,
+    /// The tries still missing from the trie store.
+    frontier: Frontier,
+    /// Whether the checkpoint of a previous synchronization has been loaded yet.
+    started: bool,
+    /// Whether we are waiting for the contract runtime to find the tries missing under the root.
+    finding_missing_tries: bool,
+    /// The number of tries stored since the last checkpoint.
+    stored_since_checkpoint: u64,
+    /// The total number of tries stored.
+    stored: u64,
+    /// Since when no peer serving tries has been connected, if none is.
+    without_peers_since: Option<Timestamp>,
+    responders: Vec<Responder<Result<(), Error>>>,
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct StateSync {
+    config: Config,
+    /// The connected peers serving tries, which tries are fetched from.
+    peers: Vec<NodeId>,
+    /// The index in `peers` of the peer which was asked for a trie last.
+    last_peer: usize,
+    #[data_size(skip)]
+    sync: Option<ActiveSync>,
+    #[data_size(skip)]
+    metrics: StateSyncMetrics,
+}
+
+impl StateSync {
+    pub(crate) fn new(config: Config, registry: &Registry) -> Result<Self, prometheus::Error> {
+        Ok(StateSync {
+            config,
+            peers: Vec::new(),
+            last_peer: 0,
+            sync: None,
+            metrics: StateSyncMetrics::new(registry)?,
+        })
+    }
+
+    /// Returns the synchronization of the global state under `state_root_hash`, if it is ongoing.
+    fn active_sync(&mut self, state_root_hash: &Blake2bHash) -> Option<&mut ActiveSync> {
+        self.sync
+            .as_mut()
+            .filter(|sync| sync.state_root_hash == *state_root_hash)
+    }
+
+    fn handle_sync_request<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        state_root_hash: Blake2bHash,
+        responder: Responder<Result<(), Error>>,
+    ) -> Effects<Event> {
+        if let Some(sync) = self.sync.as_mut() {
+            if sync.state_root_hash == state_root_hash {
+                sync.responders.push(responder);
+                return Effects::new();
+            }
+            let error = Error::AlreadySyncing(sync.state_root_hash);
+            return responder.respond(Err(error)).ignore();
+        }
+
+        info!(%state_root_hash, "starting to synchronize global state");
+        self.sync = Some(ActiveSync {
+            state_root_hash,
+            frontier: Frontier::default(),
+            started: false,
+            finding_missing_tries: false,
+            stored_since_checkpoint: 0,
+            stored: 0,
+            without_peers_since: None,
+            responders: vec![responder],
+        });
+        let mut effects = effect_builder
+            .load_state(checkpoint_key(&state_root_hash))
+            .event(move |checkpoint| Event::CheckpointLoaded {
+                state_root_hash,
+                checkpoint,
+            });
+        if self.peers.is_empty() {
+            effects.extend(self.await_peers(effect_builder));
+        }
+        effects
+    }
+
+    /// Starts the timeout for a peer serving tries to connect, unless it is running already.
+    fn await_peers<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event> {
+        let sync = match self.sync.as_mut() {
+            Some(sync) if sync.without_peers_since.is_none() => sync,
+            _ => return Effects::new(),
+        };
+        warn!(
+            state_root_hash = %sync.state_root_hash,
+            "no peer serving tries connected, waiting for one"
+        );
+        sync.without_peers_since = Some(Timestamp::now());
+        let state_root_hash = sync.state_root_hash;
+        effect_builder
+            .set_timeout(self.config.peer_timeout().into())
+            .event(move |_| Event::PeerTimeout { state_root_hash })
+    }
+
+    /// Starts fetching tries from the frontier, up to the configured number of parallel fetches.
+    ///
+    /// If the frontier is empty, asks the contract runtime for the tries still missing instead.
+    fn fetch_tries<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event> {
+        let mut effects = Effects::new();
+        let sync = match self.sync.as_mut() {
+            Some(sync) if sync.started => sync,
+            _ => return effects,
+        };
+
+        if sync.frontier.is_empty() {
+            if !sync.finding_missing_tries {
+                sync.finding_missing_tries = true;
+                let state_root_hash = sync.state_root_hash;
+                effects.extend(
+                    effect_builder
+                        .find_missing_descendant_trie_keys(state_root_hash)
+                        .event(move |result| Event::MissingTrieKeys {
+                            state_root_hash,
+                            result,
+                        }),
+                );
+            }
+        } else if !self.peers.is_empty() {
+            while sync.frontier.in_flight_count() < self.config.max_parallel_fetches() {
+                let trie_key = match sync.frontier.start_fetch() {
+                    Some(trie_key) => trie_key,
+                    None => break,
+                };
+                self.last_peer = (self.last_peer + 1) % self.peers.len();
+                let peer = self.peers[self.last_peer];
+                effects.extend(
+                    effect_builder
+                        .fetch_trie(trie_key, peer)
+                        .event(move |result| Event::TrieFetched {
+                            trie_key,
+                            peer,
+                            result,
+                        }),
+                );
+            }
+        }
+
+        self.update_metrics();
+        effects
+    }
+
+    /// Ends the ongoing synchronization, notifying everyone waiting for it.
+    fn finish<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        result: Result<(), Error>,
+    ) -> Effects<Event> {
+        let sync = match self.sync.take() {
+            Some(sync) => sync,
+            None => return Effects::new(),
+        };
+        let key = checkpoint_key(&sync.state_root_hash);
+        let mut effects = match &result {
+            Ok(()) => {
+                info!(
+                    state_root_hash = %sync.state_root_hash,
+                    stored_tries = sync.stored,
+                    "finished synchronizing global state"
+                );
+                effect_builder.delete_state(key).ignore()
+            }
+            Err(error) => {
+                error!(
+                    state_root_hash = %sync.state_root_hash,
+                    %error,
+                    "failed to synchronize global state"
+                );
+                // Keep the progress made so far for the next attempt.
+                effect_builder
+                    .save_state(key, sync.frontier.checkpoint())
+                    .ignore()
+            }
+        };
+        for responder in sync.responders {
+            effects.extend(responder.respond(result.clone()).ignore());
+        }
+        self.update_metrics();
+        effects
+    }
+
+    fn update_metrics(&self) {
+        let (pending, in_flight) = self.sync.as_ref().map_or((0, 0), |sync| {
+            (
+                sync.frontier.pending_count(),
+                sync.frontier.in_flight_count(),
+            )
+        });
+        self.metrics.pending_tries.set(pending as i64);
+        self.metrics.in_flight_tries.set(in_flight as i64);
+    }
+}
+
+impl<REv> Component<REv> for StateSync
+where
+    REv: ReactorEventT,
+{
+    type Event = Event;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match event {
+            Event::Request(StateSyncRequest::SyncGlobalState {
+                state_root_hash,
+                responder,
+            }) => self.handle_sync_request(effect_builder, state_root_hash.into(), responder),
+            Event::NewPeer(peer) => effect_builder
+                .network_peer_serves_tries(peer)
+                .event(move |serves_tries| Event::PeerServesTries { peer, serves_tries }),
+            Event::PeerServesTries { peer, serves_tries } => {
+                if !serves_tries {
+                    debug!(%peer, "not fetching tries from peer which doesn't serve them");
+                    return Effects::new();
+                }
+                if !self.peers.contains(&peer) {
+                    self.peers.push(peer);
+                }
+                if let Some(sync) = self.sync.as_mut() {
+                    sync.without_peers_since = None;
+                }
+                self.fetch_tries(effect_builder)
+            }
+            Event::PeerDisconnected(peer) => {
+                let index = match self.peers.iter().position(|known_peer| *known_peer == peer) {
+                    Some(index) => index,
+                    None => return Effects::new(),
+                };
+                debug!(%peer, "no longer fetching tries from disconnected peer");
+                self.peers.remove(index);
+                // Continue the round-robin with the peer following the removed one.
+                if index <= self.last_peer && self.last_peer > 0 {
+                    self.last_peer -= 1;
+                }
+                if self.peers.is_empty() {
+                    self.await_peers(effect_builder)
+                } else {
+                    Effects::new()
+                }
+            }
+            Event::PeerTimeout { state_root_hash } => {
+                // A peer may have connected in the meantime, or the timeout was restarted since.
+                let peer_timeout = self.config.peer_timeout();
+                let timed_out = self
+                    .active_sync(&state_root_hash)
+                    .and_then(|sync| sync.without_peers_since)
+                    .map_or(false, |since| since.elapsed() >= peer_timeout);
+                if timed_out {
+                    self.finish(effect_builder, Err(Error::NoPeers(peer_timeout)))
+                } else {
+                    Effects::new()
+                }
+            }
+            Event::CheckpointLoaded {
+                state_root_hash,
+                checkpoint,
+            } => {
+                let sync = match self.active_sync(&state_root_hash) {
+                    Some(sync) => sync,
+                    None => return Effects::new(),
+                };
+                if let Some(trie_keys) = checkpoint {
+                    info!(
+                        %state_root_hash,
+                        tries = trie_keys.len(),
+                        "resuming global state synchronization from checkpoint"
+                    );
+                    sync.frontier.add(trie_keys);
+                }
+                sync.started = true;
+                self.fetch_tries(effect_builder)
+            }
+            Event::MissingTrieKeys {
+                state_root_hash,
+                result,
+            } => {
+                let sync = match self.active_sync(&state_root_hash) {
+                    Some(sync) => sync,
+                    None => return Effects::new(),
+                };
+                sync.finding_missing_tries = false;
+                match result {
+                    Ok(trie_keys) if trie_keys.is_empty() => self.finish(effect_builder, Ok(())),
+                    Ok(trie_keys) => {
+                        debug!(%state_root_hash, tries = trie_keys.len(), "found missing tries");
+                        sync.frontier.add(trie_keys);
+                        self.fetch_tries(effect_builder)
+                    }
+                    Err(error) => {
+                        self.finish(effect_builder, Err(Error::TrieStore(error.to_string())))
+                    }
+                }
+            }
+            Event::TrieFetched {
+                trie_key,
+                peer,
+                result,
+            } => {
+                let sync = match self.sync.as_mut() {
+                    Some(sync) if sync.frontier.is_in_flight(&trie_key) => sync,
+                    _ => return Effects::new(),
+                };
+                let trie = match result {
+                    Some(FetchResult::FromPeer(trie, _)) => {
+                        self.metrics.tries_fetched.inc();
+                        trie
+                    }
+                    Some(FetchResult::FromStorage(trie)) => trie,
+                    None => {
+                        self.metrics.fetch_failures.inc();
+                        let attempts = sync.frontier.failed(trie_key);
+                        if attempts >= self.config.max_fetch_attempts() {
+                            let error = Error::TrieUnavailable { trie_key, attempts };
+                            return self.finish(effect_builder, Err(error));
+                        }
+                        debug!(%trie_key, %peer, attempts, "failed to fetch trie, retrying");
+                        return self.fetch_tries(effect_builder);
+                    }
+                };
+                effect_builder
+                    .put_trie_and_find_missing_descendant_trie_keys(trie)
+                    .event(move |result| Event::TriePut { trie_key, result })
+            }
+            Event::TriePut { trie_key, result } => {
+                let sync = match self.sync.as_mut() {
+                    Some(sync) if sync.frontier.is_in_flight(&trie_key) => sync,
+                    _ => return Effects::new(),
+                };
+                let missing_descendants = match result {
+                    Ok(missing_descendants) => missing_descendants,
+                    Err(error) => {
+                        warn!(%trie_key, %error, "failed to put trie");
+                        return self
+                            .finish(effect_builder, Err(Error::TrieStore(error.to_string())));
+                    }
+                };
+                sync.frontier.stored(&trie_key, missing_descendants);
+                sync.stored += 1;
+                sync.stored_since_checkpoint += 1;
+
+                let mut effects = Effects::new();
+                if sync.stored_since_checkpoint >= self.config.checkpoint_interval() {
+                    sync.stored_since_checkpoint = 0;
+                    info!(
+                        state_root_hash = %sync.state_root_hash,
+                        stored_tries = sync.stored,
+                        pending_tries = sync.frontier.pending_count(),
+                        "global state synchronization progress"
+                    );
+                    effects.extend(
+                        effect_builder
+                            .save_state(
+                                checkpoint_key(&sync.state_root_hash),
+                                sync.frontier.checkpoint(),
+                            )
+                            .ignore(),
+                    );
+                }
+                effects.extend(self.fetch_tries(effect_builder));
+                effects
+            }
+        }
+    }
+}
+
+/// Returns the key in the state store under which the frontier of the synchronization of the
+/// global state under `state_root_hash` is checkpointed.
+fn checkpoint_key(state_root_hash: &Blake2bHash) -> Cow<'static, [u8]> {
+    format!("state_sync:state_root_hash={:x}", state_root_hash)
+        .into_bytes()
+        .into()
+}
@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::types::TimeDiff;
+
+const DEFAULT_MAX_PARALLEL_FETCHES: u32 = 64;
+const DEFAULT_MAX_FETCH_ATTEMPTS: u32 = 20;
+const DEFAULT_CHECKPOINT_INTERVAL: u32 = 1_000;
+const DEFAULT_PEER_TIMEOUT: &str = "1min";
+
+/// Configuration options for synchronizing global state.
+#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Whether to download the global state of the trusted block instead of executing every block
+    /// preceding it.
+    enable: bool,
+    /// The maximum number of tries being fetched from peers at the same time.
+    max_parallel_fetches: u32,
+    /// The number of times fetching a single trie may fail before synchronization is given up.
+    max_fetch_attempts: u32,
+    /// The number of tries stored between two checkpoints of the synchronization progress.
+    checkpoint_interval: u32,
+    /// How long synchronization may go without a connected peer serving tries before it is given
+    /// up.
+    peer_timeout: TimeDiff,
+}
+
+impl Config {
+    pub(crate) fn enable(&self) -> bool {
+        self.enable
+    }
+
+    pub(crate) fn max_parallel_fetches(&self) -> usize {
+        self.max_parallel_fetches.max(1) as usize
+    }
+
+    pub(crate) fn max_fetch_attempts(&self) -> u32 {
+        self.max_fetch_attempts.max(1)
+    }
+
+    pub(crate) fn checkpoint_interval(&self) -> u64 {
+        self.checkpoint_interval.max(1) as u64
+    }
+
+    pub(crate) fn peer_timeout(&self) -> TimeDiff {
+        self.peer_timeout
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_enable(mut self, enable: bool) -> Self {
+        self.enable = enable;
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            enable: false,
+            max_parallel_fetches: DEFAULT_MAX_PARALLEL_FETCHES,
+            max_fetch_attempts: DEFAULT_MAX_FETCH_ATTEMPTS,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            peer_timeout: TimeDiff::from_str(DEFAULT_PEER_TIMEOUT).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::{self, Display, Formatter};
+
+use derive_more::From;
+
+use casper_execution_engine::{
+    core::engine_state,
+    shared::{newtypes::Blake2bHash, stored_value::StoredValue},
+    storage::trie::Trie,
+};
+use casper_types::Key;
+
+use crate::{components::fetcher::FetchResult, effect::requests::StateSyncRequest, types::NodeId};
+
+/// `StateSync` events.
+#[derive(Debug, From)]
+pub enum Event {
+    /// A request to synchronize global state.
+    #[from]
+    Request(StateSyncRequest),
+    /// A new peer connected, which tries may be fetched from.
+    NewPeer(NodeId),
+    /// Whether a new peer announced that it serves tries.
+    PeerServesTries { peer: NodeId, serves_tries: bool },
+    /// The connection to a peer was lost.
+    PeerDisconnected(NodeId),
+    /// The time to wait for a peer serving tries has passed.
+    PeerTimeout { state_root_hash: Blake2bHash },
+    /// The result of loading the checkpoint of a previous synchronization of the same global
+    /// state.
+    CheckpointLoaded {
+        state_root_hash: Blake2bHash,
+        checkpoint: Option<Vec<Blake2bHash>>,
+    },
+    /// The result of looking for the tries missing under the state root hash.
+    MissingTrieKeys {
+        state_root_hash: Blake2bHash,
+        result: Result<Vec<Blake2bHash>, engine_state::Error>,
+    },
+    /// The result of fetching a trie.
+    TrieFetched {
+        trie_key: Blake2bHash,
+        peer: NodeId,
+        result: Option<FetchResult<Trie<Key, StoredValue>, NodeId>>,
+    },
+    /// The result of putting a fetched trie into the trie store.
+    TriePut {
+        trie_key: Blake2bHash,
+        result: Result<Vec<Blake2bHash>, engine_state::Error>,
+    },
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Request(request) => write!(formatter, "{}", request),
+            Event::NewPeer(peer) => write!(formatter, "new peer connected: {}", peer),
+            Event::PeerServesTries { peer, serves_tries } => {
+                if *serves_tries {
+                    write!(formatter, "peer {} serves tries", peer)
+                } else {
+                    write!(formatter, "peer {} doesn't serve tries", peer)
+                }
+            }
+            Event::PeerDisconnected(peer) => write!(formatter, "peer disconnected: {}", peer),
+            Event::PeerTimeout { state_root_hash } => write!(
+                formatter,
+                "timed out waiting for peers to synchronize {}",
+                state_root_hash
+            ),
+            Event::CheckpointLoaded {
+                state_root_hash,
+                checkpoint,
+            } => write!(
+                formatter,
+                "loaded checkpoint for {} with {} tries",
+                state_root_hash,
+                checkpoint.as_ref().map_or(0, Vec::len)
+            ),
+            Event::MissingTrieKeys {
+                state_root_hash,
+                result,
+            } => match result {
+                Ok(trie_keys) => write!(
+                    formatter,
+                    "found {} tries missing under {}",
+                    trie_keys.len(),
+                    state_root_hash
+                ),
+                Err(error) => write!(
+                    formatter,
+                    "failed to find tries missing under {}: {}",
+                    state_root_hash, error
+                ),
+            },
+            Event::TrieFetched {
+                trie_key,
+                peer,
+                result,
+            } => {
+                if result.is_some() {
+                    write!(formatter, "fetched {} from {}", trie_key, peer)
+                } else {
+                    write!(formatter, "failed to fetch {} from {}", trie_key, peer)
+                }
+            }
+            Event::TriePut { trie_key, result } => match result {
+                Ok(missing_descendants) => write!(
+                    formatter,
+                    "put {} with {} missing descendants",
+                    trie_key,
+                    missing_descendants.len()
+                ),
+                Err(error) => write!(formatter, "failed to put {}: {}", trie_key, error),
+            },
+        }
+    }
+}
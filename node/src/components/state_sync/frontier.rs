@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use casper_execution_engine::shared::newtypes::Blake2bHash;
+
+/// The boundary between the part of a global state which is already in the trie store and the
+/// part which is still missing.
+///
+/// Every missing trie of the global state is either in the frontier itself, or a descendant of a
+/// trie in the frontier.  Storing a trie yields all of its missing descendants, however deep, so a
+/// stale copy of the frontier remains a valid starting point to resume synchronizing from.
+#[derive(Debug, Default)]
+pub(super) struct Frontier {
+    /// Tries waiting to be fetched, in the order they were discovered.
+    pending: VecDeque<Blake2bHash>,
+    /// Tries currently being fetched.
+    in_flight: HashSet<Blake2bHash>,
+    /// All tries either pending or in flight, used to avoid fetching shared subtries twice.
+    known: HashSet<Blake2bHash>,
+    /// The number of failed attempts to fetch each trie.
+    failures: HashMap<Blake2bHash, u32>,
+}
+
+impl Frontier {
+    /// Adds the given missing tries to the frontier, ignoring those already in it.
+    pub(super) fn add<I: IntoIterator<Item = Blake2bHash>>(&mut self, trie_keys: I) {
+        for trie_key in trie_keys {
+            if self.known.insert(trie_key) {
+                self.pending.push_back(trie_key);
+            }
+        }
+    }
+
+    /// Takes the next trie to be fetched, marking it as in flight.
+    pub(super) fn start_fetch(&mut self) -> Option<Blake2bHash> {
+        let trie_key = self.pending.pop_front()?;
+        self.in_flight.insert(trie_key);
+        Some(trie_key)
+    }
+
+    /// Returns `true` if the given trie is currently being fetched.
+    pub(super) fn is_in_flight(&self, trie_key: &Blake2bHash) -> bool {
+        self.in_flight.contains(trie_key)
+    }
+
+    /// Removes a trie which has been stored from the frontier, replacing it with its descendants
+    /// which are still missing.
+    pub(super) fn stored(&mut self, trie_key: &Blake2bHash, missing_descendants: Vec<Blake2bHash>) {
+        self.in_flight.remove(trie_key);
+        self.known.remove(trie_key);
+        self.failures.remove(trie_key);
+        self.add(missing_descendants);
+    }
+
+    /// Puts a trie which could not be fetched back at the end of the queue, returning the number of
+    /// times fetching it failed so far.
+    pub(super) fn failed(&mut self, trie_key: Blake2bHash) -> u32 {
+        if self.in_flight.remove(&trie_key) {
+            self.pending.push_back(trie_key);
+        }
+        let failures = self.failures.entry(trie_key).or_default();
+        *failures += 1;
+        *failures
+    }
+
+    /// Returns `true` if there are no tries left to be fetched or waited for.
+    pub(super) fn is_empty(&self) -> bool {
+        self.known.is_empty()
+    }
+
+    /// Returns the number of tries waiting to be fetched.
+    pub(super) fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns the number of tries currently being fetched.
+    pub(super) fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Returns all tries in the frontier, to be persisted and resumed from after a restart.
+    pub(super) fn checkpoint(&self) -> Vec<Blake2bHash> {
+        self.in_flight
+            .iter()
+            .chain(self.pending.iter())
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie_key(byte: u8) -> Blake2bHash {
+        Blake2bHash::new(&[byte])
+    }
+
+    #[test]
+    fn should_not_fetch_shared_tries_twice() {
+        let mut frontier = Frontier::default();
+        frontier.add(vec![trie_key(1), trie_key(2)]);
+
+        let first = frontier.start_fetch().unwrap();
+        assert_eq!(first, trie_key(1));
+        // Another parent sharing the pending and in-flight subtries.
+        frontier.add(vec![trie_key(1), trie_key(2), trie_key(3)]);
+
+        assert_eq!(frontier.pending_count(), 2);
+        assert_eq!(frontier.in_flight_count(), 1);
+    }
+
+    #[test]
+    fn should_replace_stored_trie_with_missing_descendants() {
+        let mut frontier = Frontier::default();
+        frontier.add(vec![trie_key(1)]);
+        let root = frontier.start_fetch().unwrap();
+        assert!(frontier.is_in_flight(&root));
+
+        frontier.stored(&root, vec![trie_key(2), trie_key(3)]);
+        assert!(!frontier.is_in_flight(&root));
+        assert_eq!(frontier.pending_count(), 2);
+
+        while let Some(trie_key) = frontier.start_fetch() {
+            frontier.stored(&trie_key, vec![]);
+        }
+        assert!(frontier.is_empty());
+    }
+
+    #[test]
+    fn should_requeue_failed_tries() {
+        let mut frontier = Frontier::default();
+        frontier.add(vec![trie_key(1), trie_key(2)]);
+        let first = frontier.start_fetch().unwrap();
+
+        assert_eq!(frontier.failed(first), 1);
+        assert_eq!(frontier.start_fetch(), Some(trie_key(2)));
+        assert_eq!(frontier.start_fetch(), Some(first));
+        assert_eq!(frontier.failed(first), 2);
+
+        // A successful fetch resets the failure count.
+        assert_eq!(frontier.start_fetch(), Some(first));
+        frontier.stored(&first, vec![]);
+        frontier.add(vec![first]);
+        assert_eq!(frontier.start_fetch(), Some(first));
+        assert_eq!(frontier.failed(first), 1);
+    }
+
+    #[test]
+    fn should_checkpoint_pending_and_in_flight_tries() {
+        let mut frontier = Frontier::default();
+        frontier.add(vec![trie_key(1), trie_key(2), trie_key(3)]);
+        let in_flight = frontier.start_fetch().unwrap();
+        frontier.stored(&in_flight, vec![trie_key(4)]);
+        frontier.start_fetch().unwrap();
+
+        let mut checkpoint = frontier.checkpoint();
+        checkpoint.sort();
+        let mut expected = vec![trie_key(2), trie_key(3), trie_key(4)];
+        expected.sort();
+        assert_eq!(checkpoint, expected);
+    }
+}
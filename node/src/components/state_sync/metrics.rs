@@ -0,0 +1,59 @@
+use prometheus::{IntCounter, IntGauge, Registry};
+
+use crate::unregister_metric;
+
+#[derive(Debug)]
+pub(super) struct StateSyncMetrics {
+    /// Number of tries fetched from peers.
+    pub(super) tries_fetched: IntCounter,
+    /// Number of failed attempts to fetch a trie from a peer.
+    pub(super) fetch_failures: IntCounter,
+    /// Number of tries known to be missing which are waiting to be fetched.
+    pub(super) pending_tries: IntGauge,
+    /// Number of tries currently being fetched.
+    pub(super) in_flight_tries: IntGauge,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl StateSyncMetrics {
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let tries_fetched = IntCounter::new(
+            "state_sync_tries_fetched",
+            "number of global state tries fetched from peers",
+        )?;
+        let fetch_failures = IntCounter::new(
+            "state_sync_fetch_failures",
+            "number of failed attempts to fetch a global state trie from a peer",
+        )?;
+        let pending_tries = IntGauge::new(
+            "state_sync_pending_tries",
+            "number of missing global state tries waiting to be fetched",
+        )?;
+        let in_flight_tries = IntGauge::new(
+            "state_sync_in_flight_tries",
+            "number of global state tries currently being fetched",
+        )?;
+        registry.register(Box::new(tries_fetched.clone()))?;
+        registry.register(Box::new(fetch_failures.clone()))?;
+        registry.register(Box::new(pending_tries.clone()))?;
+        registry.register(Box::new(in_flight_tries.clone()))?;
+
+        Ok(StateSyncMetrics {
+            tries_fetched,
+            fetch_failures,
+            pending_tries,
+            in_flight_tries,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for StateSyncMetrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.tries_fetched);
+        unregister_metric!(self.registry, self.fetch_failures);
+        unregister_metric!(self.registry, self.pending_tries);
+        unregister_metric!(self.registry, self.in_flight_tries);
+    }
+}
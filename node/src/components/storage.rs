@@ -7,7 +7,9 @@
 //! * storing and loading deploys,
 //! * [temporary until refactored] holding `DeployMetadata` for each deploy,
 //! * holding a read-only copy of the chainspec,
-//! * keeping an index of blocks by height and
+//! * keeping an index of blocks by height,
+//! * keeping a persistent index of deploys and transfers by account,
+//! * holding the call traces of executed deploys, if the contract runtime records them and
 //! * [unimplemented] managing disk usage by pruning blocks and deploys from storage.
 //!
 //! Any I/O performed by the component is done on the event handling thread, this is on purpose as
@@ -28,8 +30,13 @@
 //!
 //! ## Indices
 //!
-//! The current implementation keeps only in-memory indices, which are not persisted, based upon the
-//! estimate that they are reasonably quick to rebuild on start-up and do not take up much memory.
+//! The current implementation keeps mostly in-memory indices, which are not persisted, based upon
+//! the estimate that they are reasonably quick to rebuild on start-up and do not take up much
+//! memory.
+//!
+//! The exception is the account history index, which grows with the number of executed deploys and
+//! is therefore persisted in its own database. It is only rebuilt from the stored blocks and
+//! execution results if it is found to be empty on start-up.
 //!
 //! ## Errors
 //!
@@ -45,7 +52,7 @@ mod tests;
 #[cfg(test)]
 use std::{collections::BTreeSet, convert::TryFrom};
 use std::{
-    collections::{btree_map::Entry, BTreeMap, HashSet},
+    collections::{btree_map::Entry, BTreeMap, HashMap, HashSet},
     fmt::{self, Display, Formatter},
     fs, io, mem,
     path::{Path, PathBuf},
@@ -55,7 +62,8 @@ use std::{
 use datasize::DataSize;
 use derive_more::From;
 use lmdb::{
-    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, Transaction, WriteFlags,
+    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, RwTransaction, Transaction,
+    WriteFlags,
 };
 use serde::{Deserialize, Serialize};
 use static_assertions::const_assert;
@@ -65,12 +73,14 @@ use thiserror::Error;
 use tracing::{debug, error, info};
 
 use casper_execution_engine::shared::newtypes::Blake2bHash;
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, Transfer, Transform};
+use casper_types::{
+    account::{AccountHash, ACCOUNT_HASH_LENGTH},
+    CallTrace, EraId, ExecutionResult, ProtocolVersion, Transfer, Transform,
+};
 
 use super::Component;
-#[cfg(test)]
-use crate::crypto::hash::Digest;
 use crate::{
+    crypto::hash::Digest,
     effect::{
         requests::{StateStoreRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
@@ -78,8 +88,9 @@ use crate::{
     fatal,
     reactor::ReactorEvent,
     types::{
-        Block, BlockBody, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockSignatures, Deploy,
-        DeployHash, DeployHeader, DeployMetadata, Item, SharedObject, TimeDiff,
+        transfer_accounts, AccountHistoryEntry, AccountHistoryPage, Block, BlockBody, BlockHash,
+        BlockHeader, BlockHeaderWithMetadata, BlockSignatures, Deploy, DeployHash, DeployHeader,
+        DeployMetadata, Item, SharedObject, TimeDiff,
     },
     utils::{display_error, WithDir},
     NodeRng,
@@ -105,8 +116,14 @@ const DEFAULT_MAX_DEPLOY_STORE_SIZE: usize = 300 * GIB;
 const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
+/// The number of blocks whose account activity is recorded in a single transaction when rebuilding
+/// the account history databases.
+const ACCOUNT_HISTORY_REBUILD_BATCH_SIZE: usize = 1_000;
+/// The key in the state store under which the height of the next block to record when rebuilding
+/// the account history databases is kept, until the rebuild is complete.
+const ACCOUNT_HISTORY_REBUILD_KEY: &[u8] = b"account_history_rebuild_height";
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 7;
+const MAX_DB_COUNT: u32 = 11;
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -207,6 +224,12 @@ pub struct Storage {
     /// The state storage database.
     #[data_size(skip)]
     state_store_db: Database,
+    /// The account history databases.
+    #[data_size(skip)]
+    account_history_dbs: AccountHistoryDbs,
+    /// The call trace database.
+    #[data_size(skip)]
+    call_trace_db: Database,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
@@ -297,6 +320,12 @@ impl Storage {
         let transfer_db = env.create_db(Some("transfer"), DatabaseFlags::empty())?;
         let state_store_db = env.create_db(Some("state_store"), DatabaseFlags::empty())?;
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
+        let account_history_dbs = AccountHistoryDbs {
+            entry_db: env.create_db(Some("account_history"), DatabaseFlags::empty())?,
+            len_db: env.create_db(Some("account_history_len"), DatabaseFlags::empty())?,
+            index_db: env.create_db(Some("account_history_index"), DatabaseFlags::empty())?,
+        };
+        let call_trace_db = env.create_db(Some("call_trace"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("reindexing block store");
@@ -370,6 +399,20 @@ impl Storage {
             should_check_integrity,
         )?;
         initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_block_hashes)?;
+        initialize_call_trace_db(&env, &call_trace_db, &deleted_block_hashes)?;
+        initialize_account_history_db(
+            &env,
+            account_history_dbs,
+            &AccountHistorySourceDbs {
+                block_header_db,
+                block_body_db,
+                deploy_db,
+                deploy_metadata_db,
+            },
+            state_store_db,
+            &block_height_index,
+            &deleted_block_hashes_raw,
+        )?;
 
         Ok(Storage {
             root,
@@ -381,6 +424,8 @@ impl Storage {
             deploy_metadata_db,
             transfer_db,
             state_store_db,
+            account_history_dbs,
+            call_trace_db,
             block_height_index,
             switch_block_era_id_index,
             deploy_hash_index,
@@ -420,6 +465,10 @@ impl Storage {
                 };
                 Ok(responder.respond(bytes).ignore())
             }
+            StateStoreRequest::Delete { key, responder } => {
+                self.del_state_store(key)?;
+                Ok(responder.respond(()).ignore())
+            }
         }
     }
 
@@ -628,6 +677,15 @@ impl Storage {
                     // inverted; for a given block_hash 0n deploys and each deploy has exactly 1
                     // result (aka deploy_metadata in this context).
 
+                    record_account_activity(
+                        &mut txn,
+                        self.account_history_dbs,
+                        self.deploy_db,
+                        *block_hash,
+                        deploy_hash,
+                        &execution_result,
+                    )?;
+
                     // Update metadata and write back to db.
                     metadata
                         .execution_results
@@ -652,6 +710,32 @@ impl Storage {
                 txn.commit()?;
                 responder.respond(()).ignore()
             }
+            StorageRequest::PutCallTraces {
+                block_hash,
+                call_traces,
+                responder,
+            } => {
+                let mut txn = self.env.begin_rw_txn()?;
+                for (deploy_hash, call_trace) in call_traces {
+                    let mut block_call_traces = self
+                        .get_call_traces(&mut txn, &deploy_hash)?
+                        .unwrap_or_default();
+                    block_call_traces.insert(*block_hash, call_trace);
+                    let _ =
+                        txn.put_value(self.call_trace_db, &deploy_hash, &block_call_traces, true)?;
+                }
+                txn.commit()?;
+                responder.respond(()).ignore()
+            }
+            StorageRequest::GetCallTraces {
+                deploy_hash,
+                responder,
+            } => responder
+                .respond(
+                    self.get_call_traces(&mut self.env.begin_ro_txn()?, &deploy_hash)?
+                        .unwrap_or_default(),
+                )
+                .ignore(),
             StorageRequest::GetDeployAndMetadata {
                 deploy_hash,
                 responder,
@@ -772,6 +856,20 @@ impl Storage {
             StorageRequest::GetFinalizedDeploys { ttl, responder } => {
                 responder.respond(self.get_finalized_deploys(ttl)?).ignore()
             }
+            StorageRequest::GetAccountHistory {
+                account_hash,
+                offset,
+                limit,
+                responder,
+            } => {
+                let account_history = self.get_account_history_page(
+                    &mut self.env.begin_ro_txn()?,
+                    &account_hash,
+                    offset,
+                    limit,
+                )?;
+                responder.respond(account_history).ignore()
+            }
         })
     }
 
@@ -1071,6 +1169,40 @@ impl Storage {
         Ok(tx.get_value(self.transfer_db, block_hash)?)
     }
 
+    /// Retrieves the call traces of a deploy, by the hashes of the blocks it was executed in.
+    fn get_call_traces<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<HashMap<BlockHash, CallTrace>>, Error> {
+        Ok(tx.get_value(self.call_trace_db, deploy_hash)?)
+    }
+
+    /// Retrieves the requested range of entries of an account's history, or `None` if no activity
+    /// has been recorded for the account.
+    fn get_account_history_page<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        account_hash: &AccountHash,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Option<AccountHistoryPage>, Error> {
+        let dbs = self.account_history_dbs;
+        let total: u64 = match tx.get_value(dbs.len_db, account_hash)? {
+            Some(total) => total,
+            None => return Ok(None),
+        };
+        let end = offset.saturating_add(limit).min(total);
+        let mut entries = Vec::new();
+        for seq in offset..end {
+            let entry_key = account_history_entry_key(account_hash.as_ref(), seq);
+            if let Some(entry) = tx.get_value(dbs.entry_db, &entry_key)? {
+                entries.push(entry);
+            }
+        }
+        Ok(Some(AccountHistoryPage { total, entries }))
+    }
+
     /// Retrieves finality signatures for a block with a given block hash
     fn get_finality_signatures<Tx: Transaction>(
         &self,
@@ -1163,6 +1295,96 @@ fn insert_to_deploy_index(
     Ok(())
 }
 
+/// The databases holding the account history index.
+///
+/// Each entry of an account's history is stored individually, keyed by the account hash followed by
+/// the entry's big-endian sequence number, so that recording activity or retrieving a page of the
+/// history only touches the entries involved.
+#[derive(Clone, Copy, Debug)]
+struct AccountHistoryDbs {
+    /// The entries of all accounts' histories.
+    entry_db: Database,
+    /// The number of entries in each account's history, keyed by account hash.
+    len_db: Database,
+    /// The sequence number of each entry, keyed by account hash, block hash and deploy hash.
+    index_db: Database,
+}
+
+/// Returns the key of the entry of an account's history with the given sequence number.
+fn account_history_entry_key(account_hash: &[u8], seq: u64) -> Vec<u8> {
+    [account_hash, &seq.to_be_bytes()].concat()
+}
+
+/// Returns the key under which the sequence number of the entry of an account's history for the
+/// given deploy is stored.
+fn account_history_index_key(
+    account_hash: &[u8],
+    block_hash: &BlockHash,
+    deploy_hash: &DeployHash,
+) -> Vec<u8> {
+    [account_hash, block_hash.as_ref(), deploy_hash.as_ref()].concat()
+}
+
+/// Records the execution of a deploy in the history of every account it touched.
+///
+/// The touched accounts are the account which sent the deploy and the senders and recipients of all
+/// transfers the deploy performed.  Recording the same activity twice is a no-op.
+fn record_account_activity(
+    txn: &mut RwTransaction,
+    dbs: AccountHistoryDbs,
+    deploy_db: Database,
+    block_hash: BlockHash,
+    deploy_hash: DeployHash,
+    execution_result: &ExecutionResult,
+) -> Result<(), LmdbExtError> {
+    let mut activity: BTreeMap<AccountHash, Vec<Transfer>> = BTreeMap::new();
+
+    let maybe_deploy: Option<Deploy> = txn.get_value(deploy_db, &deploy_hash)?;
+    if let Some(deploy) = maybe_deploy {
+        activity.insert(deploy.header().account().to_account_hash(), vec![]);
+    }
+
+    if let ExecutionResult::Success { effect, .. } = execution_result {
+        for transform_entry in &effect.transforms {
+            if let Transform::WriteTransfer(transfer) = &transform_entry.transform {
+                for account_hash in transfer_accounts(transfer) {
+                    activity.entry(account_hash).or_default().push(*transfer);
+                }
+            }
+        }
+    }
+
+    for (account_hash, transfers) in activity {
+        let index_key = account_history_index_key(account_hash.as_ref(), &block_hash, &deploy_hash);
+        let maybe_seq: Option<u64> = txn.get_value(dbs.index_db, &index_key)?;
+        match maybe_seq {
+            // The deploy was recorded before, so we only add any transfers not yet recorded.
+            Some(seq) => {
+                let entry_key = account_history_entry_key(account_hash.as_ref(), seq);
+                let mut entry: AccountHistoryEntry = txn
+                    .get_value(dbs.entry_db, &entry_key)?
+                    .unwrap_or_else(|| AccountHistoryEntry::new(block_hash, deploy_hash));
+                if entry.add_transfers(transfers) {
+                    let _ = txn.put_value(dbs.entry_db, &entry_key, &entry, true)?;
+                }
+            }
+            None => {
+                let seq: u64 = txn
+                    .get_value(dbs.len_db, &account_hash)?
+                    .unwrap_or_default();
+                let mut entry = AccountHistoryEntry::new(block_hash, deploy_hash);
+                let _ = entry.add_transfers(transfers);
+                let entry_key = account_history_entry_key(account_hash.as_ref(), seq);
+                let _ = txn.put_value(dbs.entry_db, &entry_key, &entry, true)?;
+                let _ = txn.put_value(dbs.index_db, &index_key, &seq, true)?;
+                let _ = txn.put_value(dbs.len_db, &account_hash, &(seq + 1), true)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// On-disk storage configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -1334,6 +1556,11 @@ impl Storage {
             .collect()
     }
 
+    /// Returns the height of the highest stored block, if any.
+    pub fn highest_block_height(&self) -> Option<u64> {
+        self.block_height_index.keys().last().copied()
+    }
+
     /// Get the switch block for a specified era number in a read-only LMDB database transaction.
     ///
     /// # Panics
@@ -1474,3 +1701,188 @@ fn initialize_deploy_metadata_db(
     info!("deploy metadata database initialized");
     Ok(())
 }
+
+/// Purges stale entries from the call trace database.
+fn initialize_call_trace_db(
+    env: &Environment,
+    call_trace_db: &Database,
+    deleted_block_hashes: &HashSet<BlockHash>,
+) -> Result<(), LmdbExtError> {
+    info!("initializing call trace database");
+    let mut txn = env.begin_rw_txn()?;
+    let mut cursor = txn.open_rw_cursor(*call_trace_db)?;
+
+    for (raw_key, raw_val) in cursor.iter() {
+        let mut call_traces: HashMap<BlockHash, CallTrace> = lmdb_ext::deserialize(raw_val)?;
+        let len_before = call_traces.len();
+        call_traces.retain(|block_hash, _| !deleted_block_hashes.contains(block_hash));
+
+        if call_traces.is_empty() {
+            cursor.del(WriteFlags::empty())?;
+        } else if len_before != call_traces.len() {
+            let buffer = lmdb_ext::serialize(&call_traces)?;
+            cursor.put(&raw_key, &buffer, WriteFlags::empty())?;
+        }
+    }
+
+    drop(cursor);
+    txn.commit()?;
+
+    info!("call trace database initialized");
+    Ok(())
+}
+
+/// The databases the account history index is derived from.
+struct AccountHistorySourceDbs {
+    block_header_db: Database,
+    block_body_db: Database,
+    deploy_db: Database,
+    deploy_metadata_db: Database,
+}
+
+/// Purges stale entries from the account history databases, or rebuilds them from the stored
+/// blocks and execution results if they are empty.
+///
+/// The rebuild records the blocks in batches of `ACCOUNT_HISTORY_REBUILD_BATCH_SIZE`, each in its
+/// own transaction along with the height of the next block to record, so an interrupted rebuild
+/// resumes where it left off on the next start.
+fn initialize_account_history_db(
+    env: &Environment,
+    dbs: AccountHistoryDbs,
+    source_dbs: &AccountHistorySourceDbs,
+    state_store_db: Database,
+    block_height_index: &BTreeMap<u64, BlockHash>,
+    deleted_block_hashes: &HashSet<&[u8]>,
+) -> Result<(), LmdbExtError> {
+    info!("initializing account history database");
+    let mut txn = env.begin_rw_txn()?;
+
+    if !deleted_block_hashes.is_empty() {
+        remove_account_history_of_blocks(&mut txn, dbs, deleted_block_hashes)?;
+    }
+
+    let mut maybe_next_height: Option<u64> =
+        txn.get_value(state_store_db, &ACCOUNT_HISTORY_REBUILD_KEY)?;
+    let is_empty = txn.open_ro_cursor(dbs.len_db)?.iter().next().is_none();
+    if maybe_next_height.is_none() && is_empty && !block_height_index.is_empty() {
+        maybe_next_height = Some(0);
+    }
+    txn.commit()?;
+
+    while let Some(next_height) = maybe_next_height {
+        info!(%next_height, "rebuilding account history index");
+        let mut txn = env.begin_rw_txn()?;
+        let mut batch = block_height_index
+            .range(next_height..)
+            .take(ACCOUNT_HISTORY_REBUILD_BATCH_SIZE + 1);
+        for (_, block_hash) in batch.by_ref().take(ACCOUNT_HISTORY_REBUILD_BATCH_SIZE) {
+            record_block_account_activity(&mut txn, dbs, source_dbs, block_hash)?;
+        }
+        maybe_next_height = batch.next().map(|(height, _)| *height);
+        match maybe_next_height {
+            Some(height) => {
+                let _ =
+                    txn.put_value(state_store_db, &ACCOUNT_HISTORY_REBUILD_KEY, &height, true)?;
+            }
+            None => {
+                let _ = txn.del_value(state_store_db, &ACCOUNT_HISTORY_REBUILD_KEY)?;
+            }
+        }
+        txn.commit()?;
+    }
+
+    info!("account history database initialized");
+    Ok(())
+}
+
+/// Records the account activity of all deploys executed in the given stored block.
+fn record_block_account_activity(
+    txn: &mut RwTransaction,
+    dbs: AccountHistoryDbs,
+    source_dbs: &AccountHistorySourceDbs,
+    block_hash: &BlockHash,
+) -> Result<(), LmdbExtError> {
+    let block_header: BlockHeader = match txn.get_value(source_dbs.block_header_db, block_hash)? {
+        Some(block_header) => block_header,
+        None => return Ok(()),
+    };
+    let block_body: BlockBody =
+        match txn.get_value(source_dbs.block_body_db, block_header.body_hash())? {
+            Some(block_body) => block_body,
+            None => return Ok(()),
+        };
+    for deploy_hash in block_body
+        .deploy_hashes()
+        .iter()
+        .chain(block_body.transfer_hashes())
+    {
+        let metadata: DeployMetadata =
+            match txn.get_value(source_dbs.deploy_metadata_db, deploy_hash)? {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+        if let Some(execution_result) = metadata.execution_results.get(block_hash) {
+            record_account_activity(
+                txn,
+                dbs,
+                source_dbs.deploy_db,
+                *block_hash,
+                *deploy_hash,
+                execution_result,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the entries referring to any of the given blocks from the account history databases.
+///
+/// Blocks are only ever deleted from the tip of the chain, so the entries referring to them are the
+/// last ones of each account's history, which is truncated to before the first of them.
+fn remove_account_history_of_blocks(
+    txn: &mut RwTransaction,
+    dbs: AccountHistoryDbs,
+    deleted_block_hashes: &HashSet<&[u8]>,
+) -> Result<(), LmdbExtError> {
+    // The new length of the history of each affected account, keyed by account hash.
+    let mut truncated_lens: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+    let mut cursor = txn.open_ro_cursor(dbs.index_db)?;
+    for (raw_key, raw_val) in cursor.iter() {
+        let (raw_account_hash, rest) = raw_key.split_at(ACCOUNT_HASH_LENGTH);
+        if deleted_block_hashes.contains(&rest[..Digest::LENGTH]) {
+            let seq: u64 = lmdb_ext::deserialize(raw_val)?;
+            let len = truncated_lens
+                .entry(raw_account_hash.to_vec())
+                .or_insert(seq);
+            *len = (*len).min(seq);
+        }
+    }
+    drop(cursor);
+
+    for (raw_account_hash, truncated_len) in truncated_lens {
+        let len: u64 = txn
+            .get_value(dbs.len_db, &raw_account_hash)?
+            .unwrap_or_default();
+        for seq in truncated_len..len {
+            let entry_key = account_history_entry_key(&raw_account_hash, seq);
+            let maybe_entry: Option<AccountHistoryEntry> =
+                txn.get_value(dbs.entry_db, &entry_key)?;
+            if let Some(entry) = maybe_entry {
+                let index_key = account_history_index_key(
+                    &raw_account_hash,
+                    &entry.block_hash,
+                    &entry.deploy_hash,
+                );
+                let _ = txn.del_value(dbs.index_db, &index_key)?;
+                let _ = txn.del_value(dbs.entry_db, &entry_key)?;
+            }
+        }
+        if truncated_len == 0 {
+            let _ = txn.del_value(dbs.len_db, &raw_account_hash)?;
+        } else {
+            let _ = txn.put_value(dbs.len_db, &raw_account_hash, &truncated_len, true)?;
+        }
+    }
+
+    Ok(())
+}
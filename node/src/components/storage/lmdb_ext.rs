@@ -114,6 +114,10 @@ pub(super) trait WriteTransactionExt {
         value: &V,
         overwrite: bool,
     ) -> Result<bool, LmdbExtError>;
+    /// Helper function to remove a value from a database.
+    ///
+    /// Returns `true` if the value has actually been removed, `false` if the key did not exist.
+    fn del_value<K: AsRef<[u8]>>(&mut self, db: Database, key: &K) -> Result<bool, LmdbExtError>;
 }
 
 impl<T> TransactionExt for T
@@ -158,6 +162,14 @@ impl WriteTransactionExt for RwTransaction<'_> {
             Err(err) => Err(err.into()),
         }
     }
+
+    fn del_value<K: AsRef<[u8]>>(&mut self, db: Database, key: &K) -> Result<bool, LmdbExtError> {
+        match self.del(db, key, None) {
+            Ok(()) => Ok(true),
+            Err(lmdb::Error::NotFound) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
 /// Deserializes from a buffer.
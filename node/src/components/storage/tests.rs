@@ -7,20 +7,23 @@ use rand::{prelude::SliceRandom, Rng};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use smallvec::smallvec;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, SecretKey};
+use casper_types::{
+    account::AccountHash, runtime_args, CallFrame, CallTrace, EraId, ExecutionResult,
+    HostFunctionCall, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
+};
 
-use super::{Config, Storage};
+use super::{Config, Storage, ACCOUNT_HISTORY_REBUILD_KEY};
 use crate::{
-    components::storage::lmdb_ext::WriteTransactionExt,
-    crypto::AsymmetricKeyExt,
+    components::storage::lmdb_ext::{TransactionExt, WriteTransactionExt},
+    crypto::{hash::Digest, AsymmetricKeyExt},
     effect::{
         requests::{StateStoreRequest, StorageRequest},
         Multiple,
     },
     testing::{ComponentHarness, TestRng, UnitTestEvent},
     types::{
-        Block, BlockHash, BlockHeader, BlockSignatures, Deploy, DeployHash, DeployMetadata,
-        FinalitySignature,
+        AccountHistoryPage, Block, BlockHash, BlockHeader, BlockPayload, BlockSignatures, Deploy,
+        DeployHash, DeployMetadata, FinalitySignature, FinalizedBlock, Timestamp,
     },
     utils::WithDir,
 };
@@ -86,6 +89,27 @@ fn random_block_at_height(rng: &mut TestRng, height: u64) -> Box<Block> {
     block
 }
 
+/// Creates a random block with a specific block height, containing only the given deploy.
+fn random_block_with_deploy(rng: &mut TestRng, height: u64, deploy_hash: DeployHash) -> Box<Block> {
+    let block_payload = BlockPayload::new(vec![deploy_hash], vec![], vec![], false);
+    let finalized_block = FinalizedBlock::new(
+        block_payload,
+        None,
+        Timestamp::now(),
+        EraId::from(0),
+        height,
+        PublicKey::from(&SecretKey::random(rng)),
+    );
+    Box::new(Block::new(
+        BlockHash::random(rng),
+        Digest::random(rng),
+        Digest::random(rng),
+        finalized_block,
+        None,
+        ProtocolVersion::V1_0_0,
+    ))
+}
+
 /// Creates 3 random signatures for the given block.
 fn random_signatures(rng: &mut TestRng, block: &Block) -> BlockSignatures {
     let block_hash = *block.hash();
@@ -198,6 +222,27 @@ fn get_deploy_and_metadata(
     response
 }
 
+/// Loads a page of an account's history from the storage component.
+fn get_account_history(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    account_hash: AccountHash,
+    offset: u64,
+    limit: u64,
+) -> Option<AccountHistoryPage> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetAccountHistory {
+            account_hash,
+            offset,
+            limit,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Requests the highest block from a storage component.
 fn get_highest_block(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -290,6 +335,42 @@ fn put_execution_results(
     response
 }
 
+/// Stores call traces in a storage component.
+fn put_call_traces(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    block_hash: BlockHash,
+    call_traces: HashMap<DeployHash, CallTrace>,
+) {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::PutCallTraces {
+            block_hash: Box::new(block_hash),
+            call_traces,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Loads the call traces of a deploy from the storage component.
+fn get_call_traces(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    deploy_hash: DeployHash,
+) -> HashMap<BlockHash, CallTrace> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetCallTraces {
+            deploy_hash,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Saves state from the storage component.
 fn save_state<T>(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -746,6 +827,172 @@ fn store_execution_results_for_two_blocks() {
     );
 }
 
+#[test]
+fn store_call_traces_for_two_blocks() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy_hash = DeployHash::random(&mut harness.rng);
+    let block_hash_a = BlockHash::random(&mut harness.rng);
+    let block_hash_b = BlockHash::random(&mut harness.rng);
+
+    assert!(get_call_traces(&mut harness, &mut storage, deploy_hash).is_empty());
+
+    let call = CallFrame {
+        contract_hash: Some([1; 32].into()),
+        contract_package_hash: Some([2; 32].into()),
+        entry_point: "transfer".to_string(),
+        args: runtime_args! { "amount" => U512::from(10) },
+        gas_used: U512::from(2_500),
+        host_function_calls: vec![HostFunctionCall {
+            name: "host_function_read_value".to_string(),
+            cost: U512::from(1_000),
+        }],
+        calls: Vec::new(),
+        error: Some("User error: 1".to_string()),
+    };
+    let first_trace = CallTrace {
+        payment: None,
+        session: Some(CallFrame {
+            contract_hash: None,
+            contract_package_hash: None,
+            entry_point: "call".to_string(),
+            args: RuntimeArgs::new(),
+            gas_used: U512::from(5_000),
+            host_function_calls: Vec::new(),
+            calls: vec![call],
+            error: Some("User error: 1".to_string()),
+        }),
+        truncated: false,
+    };
+    let mut first_traces = HashMap::new();
+    first_traces.insert(deploy_hash, first_trace.clone());
+    put_call_traces(&mut harness, &mut storage, block_hash_a, first_traces);
+
+    let second_trace = CallTrace::default();
+    let mut second_traces = HashMap::new();
+    second_traces.insert(deploy_hash, second_trace.clone());
+    put_call_traces(&mut harness, &mut storage, block_hash_b, second_traces);
+
+    let mut expected_call_traces = HashMap::new();
+    expected_call_traces.insert(block_hash_a, first_trace);
+    expected_call_traces.insert(block_hash_b, second_trace);
+    assert_eq!(
+        get_call_traces(&mut harness, &mut storage, deploy_hash),
+        expected_call_traces
+    );
+}
+
+#[test]
+fn store_execution_results_records_account_history() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy = Deploy::random(&mut harness.rng);
+    let account_hash = deploy.header().account().to_account_hash();
+
+    // Nothing has been recorded for the sender yet.
+    assert!(get_account_history(&mut harness, &mut storage, account_hash, 0, 10).is_none());
+
+    put_deploy(&mut harness, &mut storage, Box::new(deploy.clone()));
+
+    let block_hashes = [
+        BlockHash::random(&mut harness.rng),
+        BlockHash::random(&mut harness.rng),
+        BlockHash::random(&mut harness.rng),
+    ];
+    for block_hash in &block_hashes {
+        let mut execution_results = HashMap::new();
+        execution_results.insert(*deploy.id(), harness.rng.gen());
+        put_execution_results(&mut harness, &mut storage, *block_hash, execution_results);
+    }
+
+    // Storing the same results again must not duplicate any entries.
+    let mut execution_results = HashMap::new();
+    execution_results.insert(*deploy.id(), harness.rng.gen());
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        block_hashes[0],
+        execution_results,
+    );
+
+    let full_page = get_account_history(&mut harness, &mut storage, account_hash, 0, 10)
+        .expect("should have account history");
+    assert_eq!(full_page.total, 3);
+    let recorded_block_hashes: Vec<_> = full_page
+        .entries
+        .iter()
+        .map(|entry| entry.block_hash)
+        .collect();
+    assert_eq!(recorded_block_hashes, block_hashes.to_vec());
+    assert!(full_page
+        .entries
+        .iter()
+        .all(|entry| entry.deploy_hash == *deploy.id()));
+
+    // Check pagination.
+    let page = get_account_history(&mut harness, &mut storage, account_hash, 1, 1)
+        .expect("should have account history");
+    assert_eq!(page.total, 3);
+    assert_eq!(page.entries, full_page.entries[1..2].to_vec());
+
+    let page = get_account_history(&mut harness, &mut storage, account_hash, 5, 10)
+        .expect("should have account history");
+    assert_eq!(page.total, 3);
+    assert!(page.entries.is_empty());
+}
+
+#[test]
+fn should_resume_interrupted_account_history_rebuild() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let mut deploys = Vec::new();
+    for height in 0..3 {
+        let deploy = Deploy::random(&mut harness.rng);
+        let block = random_block_with_deploy(&mut harness.rng, height, *deploy.id());
+        let block_hash = *block.hash();
+        put_deploy(&mut harness, &mut storage, Box::new(deploy.clone()));
+        put_block(&mut harness, &mut storage, block);
+        let mut execution_results = HashMap::new();
+        execution_results.insert(*deploy.id(), harness.rng.gen());
+        put_execution_results(&mut harness, &mut storage, block_hash, execution_results);
+        deploys.push(deploy);
+    }
+
+    // Simulate a rebuild interrupted after recording the block at height 0, which was cleared
+    // again so that it's apparent whether the rebuild resumes from height 1.
+    let dbs = storage.account_history_dbs;
+    let mut txn = storage.env.begin_rw_txn().unwrap();
+    txn.clear_db(dbs.entry_db).unwrap();
+    txn.clear_db(dbs.len_db).unwrap();
+    txn.clear_db(dbs.index_db).unwrap();
+    txn.put_value(
+        storage.state_store_db,
+        &ACCOUNT_HISTORY_REBUILD_KEY,
+        &1u64,
+        true,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    drop(storage);
+
+    let mut storage = storage_fixture(&harness);
+    for (height, deploy) in deploys.iter().enumerate() {
+        let account_hash = deploy.header().account().to_account_hash();
+        let page = get_account_history(&mut harness, &mut storage, account_hash, 0, 10);
+        assert_eq!(height > 0, page.is_some());
+    }
+
+    // The rebuild is complete, so it doesn't run again.
+    let mut txn = storage.env.begin_ro_txn().unwrap();
+    let maybe_next_height: Option<u64> = txn
+        .get_value(storage.state_store_db, &ACCOUNT_HISTORY_REBUILD_KEY)
+        .unwrap();
+    assert!(maybe_next_height.is_none());
+}
+
 #[test]
 fn store_random_execution_results() {
     let mut harness = ComponentHarness::default();
@@ -1143,6 +1390,14 @@ fn should_hard_reset() {
                 !metadata.execution_results.is_empty()
             );
         }
+
+        // Check deploys in deleted blocks have been removed from their senders' histories.
+        for (index, deploy) in deploys.iter().enumerate() {
+            let account_hash = deploy.header().account().to_account_hash();
+            let page = get_account_history(&mut harness, &mut storage, account_hash, 0, 10);
+            let should_have_history = index < blocks_per_era * reset_era;
+            assert_eq!(should_have_history, page.is_some());
+        }
     };
 
     // Test with a hard reset to era 2, deleting blocks (and associated data) 6 and 7.
@@ -4,10 +4,7 @@
 #![allow(clippy::field_reassign_with_default)]
 
 use ed25519_dalek::ExpandedSecretKey;
-use k256::ecdsa::{
-    signature::{Signer, Verifier},
-    Signature as Secp256k1Signature, VerifyingKey as Secp256k1PublicKey,
-};
+use k256::ecdsa::{signature::Signer, Signature as Secp256k1Signature};
 #[cfg(test)]
 use k256::elliptic_curve::sec1::ToEncodedPoint;
 
@@ -56,26 +53,7 @@ pub fn verify<T: AsRef<[u8]>>(
     signature: &Signature,
     public_key: &PublicKey,
 ) -> Result<()> {
-    match (signature, public_key) {
-        (Signature::System, _) => Err(Error::AsymmetricKey(String::from(
-            "signatures based on the system key cannot be verified",
-        ))),
-        (Signature::Ed25519(signature), PublicKey::Ed25519(public_key)) => public_key
-            .verify_strict(message.as_ref(), signature)
-            .map_err(|_| Error::AsymmetricKey(String::from("failed to verify Ed25519 signature"))),
-        (Signature::Secp256k1(signature), PublicKey::Secp256k1(public_key)) => {
-            let verifier: &Secp256k1PublicKey = public_key;
-            verifier
-                .verify(message.as_ref(), signature)
-                .map_err(|error| {
-                    Error::AsymmetricKey(format!("failed to verify secp256k1 signature: {}", error))
-                })
-        }
-        _ => Err(Error::AsymmetricKey(format!(
-            "type mismatch between {} and {}",
-            signature, public_key
-        ))),
-    }
+    casper_types::crypto::verify(message, signature, public_key).map_err(Error::from)
 }
 
 #[cfg(test)]
@@ -96,8 +96,8 @@ use casper_execution_engine::{
     storage::{protocol_data::ProtocolData, trie::Trie},
 };
 use casper_types::{
-    system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey,
-    Transfer, U512,
+    account::AccountHash, system::auction::EraValidators, CallTrace, ContractEvent, EraId,
+    ExecutionResult, Key, ProtocolVersion, PublicKey, Transfer, U512,
 };
 
 use crate::{
@@ -105,30 +105,32 @@ use crate::{
         block_validator::ValidatingBlock,
         chainspec_loader::{CurrentRunInfo, NextUpgrade},
         consensus::{BlockContext, ClContext},
-        contract_runtime::EraValidatorsRequest,
+        contract_runtime::{EraValidatorsRequest, SpeculativeExecutionState},
         deploy_acceptor,
         fetcher::FetchResult,
-        small_network::GossipedAddress,
+        small_network::{GossipedAddress, Offense},
+        state_sync,
     },
     crypto::hash::Digest,
     effect::requests::LinearChainRequest,
     reactor::{EventQueueHandle, QueueKind},
     types::{
-        Block, BlockByHeight, BlockHash, BlockHeader, BlockPayload, BlockSignatures, Chainspec,
-        ChainspecInfo, Deploy, DeployHash, DeployHeader, DeployMetadata, FinalitySignature,
-        FinalizedBlock, Item, TimeDiff, Timestamp,
+        AccountHistoryPage, Block, BlockByHeight, BlockHash, BlockHeader, BlockPayload,
+        BlockSignatures, Chainspec, ChainspecInfo, Deploy, DeployHash, DeployHeader,
+        DeployMetadata, FinalitySignature, FinalizedBlock, Item, PendingDeploy, TimeDiff,
+        Timestamp,
     },
     utils::Source,
 };
 use announcements::{
-    ChainspecLoaderAnnouncement, ConsensusAnnouncement, ContractRuntimeAnnouncement,
-    ControlAnnouncement, DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
-    NetworkAnnouncement, RpcServerAnnouncement,
+    BlockProposerAnnouncement, ChainspecLoaderAnnouncement, ConsensusAnnouncement,
+    ContractRuntimeAnnouncement, ControlAnnouncement, DeployAcceptorAnnouncement,
+    GossiperAnnouncement, LinearChainAnnouncement, NetworkAnnouncement, RpcServerAnnouncement,
 };
 use requests::{
     BlockPayloadRequest, BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest,
     ConsensusRequest, ContractRuntimeRequest, FetcherRequest, MetricsRequest, NetworkInfoRequest,
-    NetworkRequest, StateStoreRequest, StorageRequest,
+    NetworkRequest, StateStoreRequest, StateSyncRequest, StorageRequest,
 };
 
 use self::announcements::BlocklistAnnouncement;
@@ -585,6 +587,33 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the reputation scores of network peers which don't have a neutral score.
+    pub async fn network_peer_scores<I>(self) -> BTreeMap<I, i64>
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::GetPeerScores { responder },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Gets whether a connected network peer announced that it answers requests for global state
+    /// tries.
+    pub(crate) async fn network_peer_serves_tries<I>(self, peer: I) -> bool
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::GetPeerServesTries { peer, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Announces that a network message has been received.
     pub(crate) async fn announce_message_received<I, P>(self, sender: I, payload: P)
     where
@@ -624,6 +653,19 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that the connection to a peer has been lost.
+    pub(crate) async fn announce_peer_disconnected<I, P>(self, peer_id: I)
+    where
+        REv: From<NetworkAnnouncement<I, P>>,
+    {
+        self.0
+            .schedule(
+                NetworkAnnouncement::PeerDisconnected(peer_id),
+                QueueKind::NetworkIncoming,
+            )
+            .await;
+    }
+
     /// Announces that a gossiper has received a new item, where the item's ID is the complete item.
     pub(crate) async fn announce_complete_item_received_via_gossip<T: Item>(self, item: T::Id)
     where
@@ -707,12 +749,17 @@ impl<REv> EffectBuilder<REv> {
         self,
         block: Block,
         execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+        contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
     ) where
         REv: From<ContractRuntimeAnnouncement>,
     {
         self.0
             .schedule(
-                ContractRuntimeAnnouncement::linear_chain_block(block, execution_results),
+                ContractRuntimeAnnouncement::linear_chain_block(
+                    block,
+                    execution_results,
+                    contract_events,
+                ),
                 QueueKind::Regular,
             )
             .await
@@ -747,6 +794,20 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announce that the block proposer pruned the given deploys, which expired before being
+    /// included in a block.
+    pub(crate) async fn announce_deploys_expired(self, deploy_hashes: Vec<DeployHash>)
+    where
+        REv: From<BlockProposerAnnouncement>,
+    {
+        self.0
+            .schedule(
+                BlockProposerAnnouncement::DeploysExpired(deploy_hashes),
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Announce upgrade activation point read.
     pub(crate) async fn announce_upgrade_activation_point_read(self, next_upgrade: NextUpgrade)
     where
@@ -973,7 +1034,6 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Puts a trie into the trie store and asynchronously returns any missing descendant trie keys.
-    #[allow(unused)]
     pub(crate) async fn put_trie_and_find_missing_descendant_trie_keys(
         self,
         trie: Box<Trie<Key, StoredValue>>,
@@ -988,6 +1048,24 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Finds the keys of all tries under `trie_key` which are missing from the trie store.
+    pub(crate) async fn find_missing_descendant_trie_keys(
+        self,
+        trie_key: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::MissingTrieKeys {
+                trie_key,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Puts the given deploy into the deploy store.
     pub(crate) async fn put_deploy_to_storage(self, deploy: Box<Deploy>) -> bool
     where
@@ -1038,6 +1116,44 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Stores the given call traces for the deploys in the given block.
+    pub(crate) async fn put_call_traces_to_storage(
+        self,
+        block_hash: BlockHash,
+        call_traces: HashMap<DeployHash, CallTrace>,
+    ) where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutCallTraces {
+                block_hash: Box::new(block_hash),
+                call_traces,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the call traces recorded for the given deploy, by the hashes of the blocks it was
+    /// executed in.
+    pub(crate) async fn get_call_traces_from_storage(
+        self,
+        deploy_hash: DeployHash,
+    ) -> HashMap<BlockHash, CallTrace>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetCallTraces {
+                deploy_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested deploys from the deploy store.
     pub(crate) async fn get_deploy_and_metadata_from_storage(
         self,
@@ -1056,6 +1172,28 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the requested page of an account's history from storage.
+    pub(crate) async fn get_account_history_from_storage(
+        self,
+        account_hash: AccountHash,
+        offset: u64,
+        limit: u64,
+    ) -> Option<AccountHistoryPage>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAccountHistory {
+                account_hash,
+                offset,
+                limit,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested block and its associated metadata.
     pub(crate) async fn get_block_at_height_with_metadata_from_storage(
         self,
@@ -1148,6 +1286,46 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the requested global state trie using the trie fetcher.
+    pub(crate) async fn fetch_trie<I>(
+        self,
+        trie_key: Blake2bHash,
+        peer: I,
+    ) -> Option<FetchResult<Trie<Key, StoredValue>, I>>
+    where
+        REv: From<FetcherRequest<I, Trie<Key, StoredValue>>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| FetcherRequest::Fetch {
+                id: trie_key,
+                peer,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Synchronizes the global state under `state_root_hash` from peers, returning once every
+    /// trie under it is in the trie store.
+    pub(crate) async fn sync_global_state(
+        self,
+        state_root_hash: Digest,
+    ) -> Result<(), state_sync::Error>
+    where
+        REv: From<StateSyncRequest>,
+    {
+        self.make_request(
+            |responder| StateSyncRequest::SyncGlobalState {
+                state_root_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Requests a linear chain block at `block_height`.
     pub(crate) async fn fetch_block_by_height<I>(
         self,
@@ -1195,6 +1373,25 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the deploys and transfers pending inclusion in a block, optionally restricted to
+    /// those sent by the given account.
+    pub(crate) async fn get_pending_deploys(
+        self,
+        account_hash: Option<AccountHash>,
+    ) -> Vec<PendingDeploy>
+    where
+        REv: From<BlockProposerRequest>,
+    {
+        self.make_request(
+            |responder| BlockProposerRequest::GetPendingDeploys {
+                account_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Passes a finalized proto-block to the block executor component to execute it.
     pub(crate) async fn execute_block(self, finalized_block: FinalizedBlock)
     where
@@ -1275,14 +1472,17 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
-    /// Announce the intent to disconnect from a specific peer, which consensus thinks is faulty.
-    pub(crate) async fn announce_disconnect_from_peer<I>(self, peer: I)
+    /// Announces that a specific peer committed an offense, lowering its reputation.
+    pub(crate) async fn announce_offense<I>(self, peer: I, offense: Offense)
     where
         REv: From<BlocklistAnnouncement<I>>,
     {
         self.0
             .schedule(
-                BlocklistAnnouncement::OffenseCommitted(Box::new(peer)),
+                BlocklistAnnouncement::OffenseCommitted {
+                    offender: Box::new(peer),
+                    offense,
+                },
                 QueueKind::Regular,
             )
             .await
@@ -1451,6 +1651,20 @@ impl<REv> EffectBuilder<REv> {
         }
     }
 
+    /// Deletes state previously saved to storage.
+    ///
+    /// Deleting state which does not exist is not an error.
+    pub(crate) async fn delete_state(self, key: Cow<'static, [u8]>)
+    where
+        REv: From<StateStoreRequest>,
+    {
+        self.make_request(
+            move |responder| StateStoreRequest::Delete { key, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Requests a query be executed on the Contract Runtime component.
     pub(crate) async fn query_global_state(
         self,
@@ -1512,6 +1726,28 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the execution of a deploy against the given state without committing its effects.
+    ///
+    /// This operation is read only.
+    pub(crate) async fn speculative_execute_deploy(
+        self,
+        execution_prestate: SpeculativeExecutionState,
+        deploy: Box<Deploy>,
+    ) -> Result<Option<ExecutionResult>, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::SpeculativeDeployExecution {
+                execution_prestate,
+                deploy,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Returns `ProtocolData` by `ProtocolVersion`.
     ///
     /// This operation is read only.
@@ -10,17 +10,19 @@ use std::{
 
 use serde::Serialize;
 
-use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey};
+use casper_types::{ContractEvent, EraId, ExecutionEffect, ExecutionResult, PublicKey};
 
 use crate::{
     components::{
-        chainspec_loader::NextUpgrade, deploy_acceptor::Error, small_network::GossipedAddress,
+        chainspec_loader::NextUpgrade,
+        deploy_acceptor::Error,
+        small_network::{GossipedAddress, Offense},
     },
     effect::Responder,
     types::{
         Block, Deploy, DeployHash, DeployHeader, FinalitySignature, FinalizedBlock, Item, Timestamp,
     },
-    utils::Source,
+    utils::{DisplayIter, Source},
 };
 
 /// Control announcements are special announcements handled directly by the runtime/runner.
@@ -76,6 +78,9 @@ pub enum NetworkAnnouncement<I, P> {
     ///                 not rely on or use this for anything without asking anyone that has written
     ///                 this section of the code first!
     NewPeer(I),
+    /// The connection to a previously announced peer was lost, e.g. because it disconnected or
+    /// was banned.
+    PeerDisconnected(I),
 }
 
 impl<I, P> Display for NetworkAnnouncement<I, P>
@@ -92,6 +97,9 @@ where
             NetworkAnnouncement::NewPeer(id) => {
                 write!(formatter, "new peer connection established to {}", id)
             }
+            NetworkAnnouncement::PeerDisconnected(id) => {
+                write!(formatter, "peer connection to {} lost", id)
+            }
         }
     }
 }
@@ -195,11 +203,37 @@ impl Display for ConsensusAnnouncement {
     }
 }
 
+/// A block proposer announcement.
+#[derive(Debug, Serialize)]
+pub enum BlockProposerAnnouncement {
+    /// The given deploys expired before being included in a block and have been pruned.
+    DeploysExpired(Vec<DeployHash>),
+}
+
+impl Display for BlockProposerAnnouncement {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockProposerAnnouncement::DeploysExpired(deploy_hashes) => {
+                write!(
+                    formatter,
+                    "pruned expired deploys {}",
+                    DisplayIter::new(deploy_hashes)
+                )
+            }
+        }
+    }
+}
+
 /// A block-list related announcement.
 #[derive(Debug, Serialize)]
 pub enum BlocklistAnnouncement<I> {
-    /// A given peer committed a blockable offense.
-    OffenseCommitted(Box<I>),
+    /// A given peer committed an offense, lowering its reputation.
+    OffenseCommitted {
+        /// The peer which committed the offense.
+        offender: Box<I>,
+        /// The offense committed.
+        offense: Offense,
+    },
 }
 
 impl<I> Display for BlocklistAnnouncement<I>
@@ -208,8 +242,8 @@ where
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            BlocklistAnnouncement::OffenseCommitted(peer) => {
-                write!(f, "peer {} committed offense", peer)
+            BlocklistAnnouncement::OffenseCommitted { offender, offense } => {
+                write!(f, "peer {} committed offense: {}", offender, offense)
             }
         }
     }
@@ -236,10 +270,12 @@ impl ContractRuntimeAnnouncement {
     pub fn linear_chain_block(
         block: Block,
         execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+        contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
     ) -> Self {
         Self::LinearChainBlock(Box::new(LinearChainBlock {
             block,
             execution_results,
+            contract_events,
         }))
     }
     /// Create a ContractRuntimeAnnouncement::BlockAlreadyExecuted from a Block.
@@ -263,6 +299,8 @@ pub struct LinearChainBlock {
     pub block: Block,
     /// The results of executing the deploys in this block.
     pub execution_results: HashMap<DeployHash, (DeployHeader, ExecutionResult)>,
+    /// The events emitted by contracts while executing the deploys in this block.
+    pub contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
 }
 
 impl Display for ContractRuntimeAnnouncement {
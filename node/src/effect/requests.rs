@@ -30,8 +30,9 @@ use casper_execution_engine::{
     storage::{protocol_data::ProtocolData, trie::Trie},
 };
 use casper_types::{
+    account::AccountHash,
     system::auction::{EraValidators, ValidatorWeights},
-    EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, Transfer, URef,
+    CallTrace, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, Transfer, URef,
 };
 
 use super::Responder;
@@ -40,16 +41,20 @@ use crate::{
         block_validator::ValidatingBlock,
         chainspec_loader::CurrentRunInfo,
         consensus::{BlockContext, ClContext},
-        contract_runtime::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest},
+        contract_runtime::{
+            EraValidatorsRequest, SpeculativeExecutionState, ValidatorWeightsByEraIdRequest,
+        },
         deploy_acceptor::Error,
         fetcher::FetchResult,
+        rpc_server::SpeculativeExecError,
+        state_sync,
     },
     crypto::hash::Digest,
     rpcs::{chain::BlockIdentifier, docs::OpenRpcSchema},
     types::{
-        Block as LinearBlock, Block, BlockHash, BlockHeader, BlockPayload, BlockSignatures,
-        Chainspec, ChainspecInfo, Deploy, DeployHash, DeployHeader, DeployMetadata, FinalizedBlock,
-        Item, NodeId, StatusFeed, TimeDiff,
+        AccountHistoryPage, Block as LinearBlock, Block, BlockHash, BlockHeader, BlockPayload,
+        BlockSignatures, Chainspec, ChainspecInfo, Deploy, DeployHash, DeployHeader,
+        DeployMetadata, FinalizedBlock, Item, NodeId, PendingDeploy, StatusFeed, TimeDiff,
     },
     utils::DisplayIter,
 };
@@ -184,6 +189,19 @@ pub enum NetworkInfoRequest<I> {
         // TODO - change the `String` field to a `libp2p::Multiaddr` once small_network is removed.
         responder: Responder<BTreeMap<I, String>>,
     },
+    /// Get the reputation scores of peers.
+    GetPeerScores {
+        /// Responder to be called with the scores of all peers which don't have a neutral score.
+        responder: Responder<BTreeMap<I, i64>>,
+    },
+    /// Get whether a connected peer answers requests for global state tries.
+    GetPeerServesTries {
+        /// The peer.
+        peer: I,
+        /// Responder to be called with `true` if the peer announced in its handshake that it
+        /// serves tries, or `false` otherwise or if the peer isn't connected.
+        responder: Responder<bool>,
+    },
 }
 
 impl<I> Display for NetworkInfoRequest<I>
@@ -193,6 +211,12 @@ where
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             NetworkInfoRequest::GetPeers { responder: _ } => write!(formatter, "get peers"),
+            NetworkInfoRequest::GetPeerScores { responder: _ } => {
+                write!(formatter, "get peer scores")
+            }
+            NetworkInfoRequest::GetPeerServesTries { peer, responder: _ } => {
+                write!(formatter, "get whether {} serves tries", peer)
+            }
         }
     }
 }
@@ -309,6 +333,18 @@ pub enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Vec<(DeployHash, DeployHeader)>>,
     },
+    /// Retrieve a page of the deploys and transfers which touched the given account.
+    GetAccountHistory {
+        /// The account whose history should be retrieved.
+        account_hash: AccountHash,
+        /// The number of entries to skip, starting from the oldest.
+        offset: u64,
+        /// The maximum number of entries to return.
+        limit: u64,
+        /// Responder to call with the results.  Returns `None` if no activity of the account is
+        /// known.
+        responder: Responder<Option<AccountHistoryPage>>,
+    },
     /// Store execution results for a set of deploys of a single block.
     ///
     /// Will return a fatal error if there are already execution results known for a specific
@@ -324,6 +360,15 @@ pub enum StorageRequest {
         /// Responder to call when done storing.
         responder: Responder<()>,
     },
+    /// Store call traces for a set of deploys of a single block.
+    PutCallTraces {
+        /// Hash of block.
+        block_hash: Box<BlockHash>,
+        /// Mapping of deploys to the call traces recorded while executing them in the block.
+        call_traces: HashMap<DeployHash, CallTrace>,
+        /// Responder to call when done storing.
+        responder: Responder<()>,
+    },
     /// Retrieve deploy and its metadata.
     GetDeployAndMetadata {
         /// Hash of deploy to be retrieved.
@@ -331,6 +376,14 @@ pub enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    /// Retrieve the call traces of a deploy, by the hashes of the blocks it was executed in.
+    GetCallTraces {
+        /// Hash of deploy whose call traces are to be retrieved.
+        deploy_hash: DeployHash,
+        /// Responder to call with the results.  Returns an empty map if no call traces were
+        /// recorded for the deploy.
+        responder: Responder<HashMap<BlockHash, CallTrace>>,
+    },
     /// Retrieve block and its metadata by its hash.
     GetBlockAndMetadataByHash {
         /// The hash of the block.
@@ -409,9 +462,15 @@ impl Display for StorageRequest {
             StorageRequest::PutExecutionResults { block_hash, .. } => {
                 write!(formatter, "put execution results for {}", block_hash)
             }
+            StorageRequest::PutCallTraces { block_hash, .. } => {
+                write!(formatter, "put call traces for {}", block_hash)
+            }
             StorageRequest::GetDeployAndMetadata { deploy_hash, .. } => {
                 write!(formatter, "get deploy and metadata for {}", deploy_hash)
             }
+            StorageRequest::GetCallTraces { deploy_hash, .. } => {
+                write!(formatter, "get call traces for {}", deploy_hash)
+            }
             StorageRequest::GetBlockAndMetadataByHash { block_hash, .. } => {
                 write!(
                     formatter,
@@ -442,6 +501,16 @@ impl Display for StorageRequest {
             StorageRequest::GetFinalizedDeploys { ttl, .. } => {
                 write!(formatter, "get finalized deploys, ttl: {:?}", ttl)
             }
+            StorageRequest::GetAccountHistory {
+                account_hash,
+                offset,
+                limit,
+                ..
+            } => write!(
+                formatter,
+                "get account history for {}, offset: {}, limit: {}",
+                account_hash, offset, limit
+            ),
         }
     }
 }
@@ -467,6 +536,13 @@ pub enum StateStoreRequest {
         /// Responder for value, if found, returning the previously passed in serialization form.
         responder: Responder<Option<Vec<u8>>>,
     },
+    /// Deletes a piece of state from storage.
+    Delete {
+        /// Key to delete.
+        key: Cow<'static, [u8]>,
+        /// Notification when deleting is complete.
+        responder: Responder<()>,
+    },
 }
 
 impl Display for StateStoreRequest {
@@ -478,6 +554,9 @@ impl Display for StateStoreRequest {
             StateStoreRequest::Load { key, .. } => {
                 write!(f, "load data from key {}", HexFmt(key))
             }
+            StateStoreRequest::Delete { key, .. } => {
+                write!(f, "delete data under key {}", HexFmt(key))
+            }
         }
     }
 }
@@ -506,6 +585,13 @@ pub struct BlockPayloadRequest {
 pub enum BlockProposerRequest {
     /// Request a list of deploys to propose in a new block.
     RequestBlockPayload(BlockPayloadRequest),
+    /// Request the deploys and transfers which are pending inclusion in a block.
+    GetPendingDeploys {
+        /// If given, only deploys sent by this account are returned.
+        account_hash: Option<AccountHash>,
+        /// Responder to call with the result.
+        responder: Responder<Vec<PendingDeploy>>,
+    },
 }
 
 impl Display for BlockProposerRequest {
@@ -524,6 +610,13 @@ impl Display for BlockProposerRequest {
                 context.height(),
                 next_finalized
             ),
+            BlockProposerRequest::GetPendingDeploys {
+                account_hash: Some(account_hash),
+                ..
+            } => write!(formatter, "get pending deploys of {}", account_hash),
+            BlockProposerRequest::GetPendingDeploys {
+                account_hash: None, ..
+            } => write!(formatter, "get pending deploys"),
         }
     }
 }
@@ -542,6 +635,15 @@ pub enum RpcRequest<I> {
         /// Responder to call.
         responder: Responder<Result<(), Error>>,
     },
+    /// Execute a deploy against the given state without committing the resulting effects.
+    SpeculativeDeployExecute {
+        /// The state against which the deploy is executed.
+        execution_prestate: SpeculativeExecutionState,
+        /// The deploy to execute.
+        deploy: Box<Deploy>,
+        /// Responder to call with the result.
+        responder: Responder<Result<Option<ExecutionResult>, SpeculativeExecError>>,
+    },
     /// If `maybe_identifier` is `Some`, return the specified block if it exists, else `None`.  If
     /// `maybe_identifier` is `None`, return the latest block.
     GetBlock {
@@ -607,11 +709,42 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    /// Return the call traces recorded for a deploy, by the hashes of the blocks it was executed
+    /// in.
+    GetCallTraces {
+        /// The hash of the deploy whose call traces are to be retrieved.
+        deploy_hash: DeployHash,
+        /// Responder to call with the result.
+        responder: Responder<HashMap<BlockHash, CallTrace>>,
+    },
+    /// Return a page of the deploys and transfers which touched the given account.
+    GetAccountHistory {
+        /// The account whose history should be retrieved.
+        account_hash: AccountHash,
+        /// The number of entries to skip, starting from the oldest.
+        offset: u64,
+        /// The maximum number of entries to return.
+        limit: u64,
+        /// Responder to call with the result.
+        responder: Responder<Option<AccountHistoryPage>>,
+    },
+    /// Return the deploys and transfers pending inclusion in a block.
+    GetPendingDeploys {
+        /// If given, only deploys sent by this account are returned.
+        account_hash: Option<AccountHash>,
+        /// Responder to call with the result.
+        responder: Responder<Vec<PendingDeploy>>,
+    },
     /// Return the connected peers.
     GetPeers {
         /// Responder to call with the result.
         responder: Responder<BTreeMap<I, String>>,
     },
+    /// Return the reputation scores of peers.
+    GetPeerScores {
+        /// Responder to call with the result.
+        responder: Responder<BTreeMap<I, i64>>,
+    },
     /// Return string formatted status or `None` if an error occurred.
     GetStatus {
         /// Responder to call with the result.
@@ -628,6 +761,15 @@ impl<I> Display for RpcRequest<I> {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             RpcRequest::SubmitDeploy { deploy, .. } => write!(formatter, "submit {}", *deploy),
+            RpcRequest::SpeculativeDeployExecute {
+                execution_prestate,
+                deploy,
+                ..
+            } => write!(
+                formatter,
+                "speculatively execute {} on {}",
+                *deploy, execution_prestate.state_root_hash
+            ),
             RpcRequest::GetBlock {
                 maybe_id: Some(BlockIdentifier::Hash(hash)),
                 ..
@@ -671,7 +813,21 @@ impl<I> Display for RpcRequest<I> {
                 state_root_hash, purse_uref
             ),
             RpcRequest::GetDeploy { hash, .. } => write!(formatter, "get {}", hash),
+            RpcRequest::GetCallTraces { deploy_hash, .. } => {
+                write!(formatter, "get call traces for {}", deploy_hash)
+            }
+            RpcRequest::GetAccountHistory { account_hash, .. } => {
+                write!(formatter, "get account history for {}", account_hash)
+            }
+            RpcRequest::GetPendingDeploys {
+                account_hash: Some(account_hash),
+                ..
+            } => write!(formatter, "get pending deploys of {}", account_hash),
+            RpcRequest::GetPendingDeploys {
+                account_hash: None, ..
+            } => write!(formatter, "get pending deploys"),
             RpcRequest::GetPeers { .. } => write!(formatter, "get peers"),
+            RpcRequest::GetPeerScores { .. } => write!(formatter, "get peer scores"),
             RpcRequest::GetStatus { .. } => write!(formatter, "get status"),
             RpcRequest::GetMetrics { .. } => write!(formatter, "get metrics"),
         }
@@ -824,6 +980,16 @@ pub enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<Vec<Blake2bHash>, engine_state::Error>>,
     },
+    /// Execute a deploy without committing its effects to global state.
+    SpeculativeDeployExecution {
+        /// The state against which the deploy is executed.
+        #[serde(skip_serializing)]
+        execution_prestate: SpeculativeExecutionState,
+        /// The deploy to execute.
+        deploy: Box<Deploy>,
+        /// Responder to call with the result.
+        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+    },
 }
 
 impl Display for ContractRuntimeRequest {
@@ -892,6 +1058,16 @@ impl Display for ContractRuntimeRequest {
                     trie_key
                 )
             }
+            ContractRuntimeRequest::SpeculativeDeployExecution {
+                execution_prestate,
+                deploy,
+                ..
+            } => write!(
+                formatter,
+                "execute {} on {} without committing",
+                deploy.id(),
+                execution_prestate.state_root_hash
+            ),
         }
     }
 }
@@ -970,6 +1146,29 @@ impl<I: Display> Display for LinearChainRequest<I> {
     }
 }
 
+/// A request to synchronize global state from peers.
+#[derive(Debug, Serialize)]
+#[must_use]
+pub enum StateSyncRequest {
+    /// Fetches every trie under the given state root hash which is missing from the trie store.
+    SyncGlobalState {
+        /// The state root hash of the global state to synchronize.
+        state_root_hash: Digest,
+        /// Responder to call once the global state is complete, or synchronizing it failed.
+        responder: Responder<Result<(), state_sync::Error>>,
+    },
+}
+
+impl Display for StateSyncRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateSyncRequest::SyncGlobalState {
+                state_root_hash, ..
+            } => write!(f, "sync global state under {}", state_root_hash),
+        }
+    }
+}
+
 #[derive(DataSize, Debug)]
 #[must_use]
 /// Consensus component requests.
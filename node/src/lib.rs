@@ -24,6 +24,8 @@
 
 extern crate test;
 
+pub mod admin_server;
+mod chainspec_check;
 pub mod components;
 mod config_migration;
 pub mod crypto;
@@ -32,6 +34,7 @@ pub mod effect;
 pub mod logging;
 pub mod protocol;
 pub mod reactor;
+mod state_snapshot;
 #[cfg(test)]
 pub mod testing;
 pub mod tls;
@@ -52,6 +55,10 @@ use signal_hook::{
     flag,
 };
 
+pub use admin_server::Config as AdminServerConfig;
+pub use chainspec_check::{
+    check_chainspec, diff_chainspec, ChainspecCheck, Error as ChainspecCheckError,
+};
 pub use components::{
     block_proposer::Config as BlockProposerConfig,
     consensus::Config as ConsensusConfig,
@@ -64,10 +71,12 @@ pub use components::{
     rest_server::Config as RestServerConfig,
     rpc_server::{rpcs, Config as RpcServerConfig},
     small_network::{Config as SmallNetworkConfig, Error as SmallNetworkError},
+    state_sync::Config as StateSyncConfig,
     storage::{Config as StorageConfig, Error as StorageError},
 };
 pub use config_migration::{migrate_config, Error as ConfigMigrationError};
 pub use data_migration::{migrate_data, Error as DataMigrationError};
+pub use state_snapshot::{export_state, import_state, Error as StateSnapshotError};
 pub use types::NodeRng;
 pub use utils::OS_PAGE_SIZE;
 
@@ -5,6 +5,7 @@ use std::{env, fmt, io};
 use ansi_term::{Color, Style};
 use anyhow::anyhow;
 use datasize::DataSize;
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tracing::{
@@ -18,7 +19,7 @@ use tracing_subscriber::{
         FmtContext, FormatEvent, FormatFields, FormattedFields,
     },
     registry::LookupSpan,
-    EnvFilter,
+    reload, EnvFilter,
 };
 
 const LOG_CONFIGURATION_ENVVAR: &str = "RUST_LOG";
@@ -29,6 +30,11 @@ const LOG_FIELD_MODULE: &str = "log.module_path";
 const LOG_FIELD_FILE: &str = "log.file";
 const LOG_FIELD_LINE: &str = "log.line";
 
+/// Replaces the filter of the installed logger, set once the logging system is initialized.
+type FilterReloader = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+static FILTER_RELOADER: OnceCell<FilterReloader> = OnceCell::new();
+
 /// Logging configuration.
 #[derive(DataSize, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -279,18 +285,46 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
 
     match config.format {
         // Setup a new tracing-subscriber writing to `stdout` for logging.
-        LoggingFormat::Text => tracing_subscriber::fmt()
-            .with_writer(io::stdout)
-            .with_env_filter(filter)
-            .fmt_fields(formatter)
-            .event_format(FmtEvent::new(config.color, config.abbreviate_modules))
-            .try_init(),
+        LoggingFormat::Text => {
+            let builder = tracing_subscriber::fmt()
+                .with_writer(io::stdout)
+                .with_env_filter(filter)
+                .fmt_fields(formatter)
+                .event_format(FmtEvent::new(config.color, config.abbreviate_modules))
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            builder.try_init().map_err(|error| anyhow!(error))?;
+            set_filter_reloader(Box::new(move |filter| handle.reload(filter)));
+        }
         // JSON logging writes to `stdout` as well but uses the JSON format.
-        LoggingFormat::Json => tracing_subscriber::fmt()
-            .with_writer(io::stdout)
-            .with_env_filter(filter)
-            .json()
-            .try_init(),
+        LoggingFormat::Json => {
+            let builder = tracing_subscriber::fmt()
+                .with_writer(io::stdout)
+                .with_env_filter(filter)
+                .json()
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            builder.try_init().map_err(|error| anyhow!(error))?;
+            set_filter_reloader(Box::new(move |filter| handle.reload(filter)));
+        }
     }
-    .map_err(|error| anyhow!(error))
+
+    Ok(())
+}
+
+fn set_filter_reloader(filter_reloader: FilterReloader) {
+    // `try_init` fails if a logger was installed before, so this is only ever reached once.
+    let _ = FILTER_RELOADER.set(filter_reloader);
+}
+
+/// Replaces the filter of the installed logger with one parsed from `directives`.
+///
+/// `directives` use the same syntax as the `RUST_LOG` environment variable, e.g.
+/// `warn,casper_node::components::consensus=debug`.
+pub fn reload_filter(directives: &str) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_new(directives).map_err(|error| anyhow!(error))?;
+    let filter_reloader = FILTER_RELOADER
+        .get()
+        .ok_or_else(|| anyhow!("logging has not been initialized"))?;
+    filter_reloader(filter).map_err(|error| anyhow!(error))
 }
@@ -30,8 +30,6 @@ pub mod joiner;
 pub mod participating;
 mod queue_kind;
 
-#[cfg(test)]
-use std::sync::Arc;
 use std::{
     any,
     collections::HashMap,
@@ -40,10 +38,10 @@ use std::{
     fs::File,
     mem,
     str::FromStr,
-    sync::atomic::Ordering,
+    sync::{atomic::Ordering, Arc},
 };
 
-use datasize::DataSize;
+use datasize::{DataSize, MemUsageNode};
 use futures::{future::BoxFuture, FutureExt};
 use jemalloc_ctl::{epoch as jemalloc_epoch, stats::allocated as jemalloc_allocated};
 use once_cell::sync::Lazy;
@@ -59,6 +57,7 @@ use tracing_futures::Instrument;
 use utils::rlimit::{Limit, OpenFiles, ResourceLimit};
 
 use crate::{
+    admin_server::{self, Diagnostics},
     effect::{announcements::ControlAnnouncement, Effect, EffectBuilder, Effects},
     types::{ExitCode, Timestamp},
     unregister_metric,
@@ -257,6 +256,12 @@ pub trait Reactor: Sized {
 
     /// Instructs the reactor to update performance metrics, if any.
     fn update_metrics(&mut self, _event_queue_handle: EventQueueHandle<Self::Event>) {}
+
+    /// Returns the estimated heap memory usage of the reactor, broken down by component, if
+    /// supported.
+    fn memory_usage(&self) -> Option<MemUsageNode> {
+        None
+    }
 }
 
 /// A reactor event type.
@@ -552,6 +557,16 @@ where
             QUEUE_DUMP_REQUESTED.store(false, Ordering::SeqCst);
         }
 
+        // Answer any diagnostics requests made through the admin server.
+        let diagnostics_responders = admin_server::take_diagnostics_requests();
+        if !diagnostics_responders.is_empty() {
+            debug!("collecting diagnostics as requested");
+            let diagnostics = Arc::new(self.diagnostics());
+            for responder in diagnostics_responders {
+                let _ = responder.send(diagnostics.clone());
+            }
+        }
+
         let (event, q) = self.scheduler.pop().await;
 
         // Create another span for tracing the processing of one event.
@@ -655,6 +670,16 @@ where
         })
     }
 
+    /// Collects the event queue sizes and the memory usage of the reactor.
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            reactor: any::type_name::<R>(),
+            event_count: self.event_count,
+            queue_sizes: self.scheduler.event_queues_counts().into_iter().collect(),
+            memory_usage: self.reactor.memory_usage(),
+        }
+    }
+
     /// Handles dumping queue contents to files in /tmp.
     async fn dump_queues(&mut self) {
         let timestamp = Timestamp::now();
@@ -2,7 +2,7 @@
 
 use std::fmt::{self, Display, Formatter};
 
-use datasize::DataSize;
+use datasize::{DataSize, MemUsageNode};
 use derive_more::From;
 use prometheus::Registry;
 use reactor::ReactorEvent;
@@ -301,6 +301,10 @@ impl reactor::Reactor for Reactor {
         }
     }
 
+    fn memory_usage(&self) -> Option<MemUsageNode> {
+        Some(self.estimate_detailed_heap_size())
+    }
+
     fn maybe_exit(&self) -> Option<ReactorExit> {
         self.chainspec_loader.reactor_exit()
     }
@@ -9,7 +9,7 @@ use std::{
     sync::Arc,
 };
 
-use datasize::DataSize;
+use datasize::{DataSize, MemUsageNode};
 use derive_more::From;
 use memory_metrics::MemoryMetrics;
 use prometheus::Registry;
@@ -17,6 +17,9 @@ use reactor::ReactorEvent;
 use serde::Serialize;
 use tracing::{debug, error, info, warn};
 
+use casper_execution_engine::{shared::stored_value::StoredValue, storage::trie::Trie};
+use casper_types::Key;
+
 #[cfg(test)]
 use crate::testing::network::NetworkedReactor;
 use crate::{
@@ -34,20 +37,22 @@ use crate::{
         metrics::Metrics,
         network::{self, Network, NetworkIdentity, ENABLE_LIBP2P_NET_ENV_VAR},
         rest_server::{self, RestServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, Offense, SmallNetwork, SmallNetworkIdentity},
+        state_sync::{self, StateSync},
         storage::{self, Storage},
         Component,
     },
     effect::{
         announcements::{
-            ChainspecLoaderAnnouncement, ContractRuntimeAnnouncement, ControlAnnouncement,
-            DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
-            LinearChainBlock, NetworkAnnouncement,
+            BlocklistAnnouncement, ChainspecLoaderAnnouncement, ContractRuntimeAnnouncement,
+            ControlAnnouncement, DeployAcceptorAnnouncement, GossiperAnnouncement,
+            LinearChainAnnouncement, LinearChainBlock, NetworkAnnouncement,
         },
         requests::{
             BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest, ConsensusRequest,
             ContractRuntimeRequest, FetcherRequest, LinearChainRequest, MetricsRequest,
-            NetworkInfoRequest, NetworkRequest, RestRequest, StateStoreRequest, StorageRequest,
+            NetworkInfoRequest, NetworkRequest, RestRequest, StateStoreRequest, StateSyncRequest,
+            StorageRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
@@ -61,7 +66,7 @@ use crate::{
     },
     types::{
         Block, BlockByHeight, BlockHeader, BlockHeaderWithMetadata, Deploy, ExitCode, NodeId, Tag,
-        Timestamp,
+        Timestamp, TrieResponse,
     },
     utils::{Source, WithDir},
     NodeRng,
@@ -120,6 +125,14 @@ pub enum Event {
     #[from]
     DeployFetcher(#[serde(skip_serializing)] fetcher::Event<Deploy>),
 
+    /// Global state trie fetcher event.
+    #[from]
+    TrieFetcher(#[serde(skip_serializing)] fetcher::Event<Trie<Key, StoredValue>>),
+
+    /// Global state synchronizer event.
+    #[from]
+    StateSync(#[serde(skip_serializing)] state_sync::Event),
+
     /// Deploy acceptor event.
     #[from]
     DeployAcceptor(#[serde(skip_serializing)] deploy_acceptor::Event),
@@ -157,6 +170,14 @@ pub enum Event {
     #[from]
     DeployFetcherRequest(#[serde(skip_serializing)] FetcherRequest<NodeId, Deploy>),
 
+    /// Global state trie fetcher request.
+    #[from]
+    TrieFetcherRequest(#[serde(skip_serializing)] FetcherRequest<NodeId, Trie<Key, StoredValue>>),
+
+    /// Global state synchronizer request.
+    #[from]
+    StateSyncRequest(#[serde(skip_serializing)] StateSyncRequest),
+
     /// Block validation request.
     #[from]
     BlockValidatorRequest(#[serde(skip_serializing)] BlockValidationRequest<NodeId>),
@@ -198,6 +219,10 @@ pub enum Event {
     #[from]
     ChainspecLoaderAnnouncement(#[serde(skip_serializing)] ChainspecLoaderAnnouncement),
 
+    /// Blocklist announcement.
+    #[from]
+    BlocklistAnnouncement(BlocklistAnnouncement<NodeId>),
+
     /// Consensus request.
     #[from]
     ConsensusRequest(#[serde(skip_serializing)] ConsensusRequest),
@@ -275,6 +300,8 @@ impl Display for Event {
             Event::DeployFetcherRequest(request) => {
                 write!(f, "deploy fetcher request: {}", request)
             }
+            Event::TrieFetcherRequest(request) => write!(f, "trie fetcher request: {}", request),
+            Event::StateSyncRequest(request) => write!(f, "state sync request: {}", request),
             Event::LinearChainSync(event) => write!(f, "linear chain: {}", event),
             Event::BlockFetcher(event) => write!(f, "block fetcher: {}", event),
             Event::BlockByHeightFetcherRequest(request) => {
@@ -282,6 +309,8 @@ impl Display for Event {
             }
             Event::BlockValidator(event) => write!(f, "block validator event: {}", event),
             Event::DeployFetcher(event) => write!(f, "deploy fetcher event: {}", event),
+            Event::TrieFetcher(event) => write!(f, "trie fetcher event: {}", event),
+            Event::StateSync(event) => write!(f, "state sync event: {}", event),
             Event::BlockProposerRequest(req) => write!(f, "block proposer request: {}", req),
             Event::ContractRuntime(event) => write!(f, "contract runtime event: {:?}", event),
             Event::LinearChain(event) => write!(f, "linear chain event: {}", event),
@@ -304,6 +333,7 @@ impl Display for Event {
             Event::ChainspecLoaderAnnouncement(ann) => {
                 write!(f, "chainspec loader announcement: {}", ann)
             }
+            Event::BlocklistAnnouncement(ann) => write!(f, "blocklist announcement: {}", ann),
             Event::StateStoreRequest(req) => write!(f, "state store request: {}", req),
             Event::ConsensusRequest(req) => write!(f, "consensus request: {:?}", req),
         }
@@ -331,6 +361,8 @@ pub struct Reactor {
     block_by_height_fetcher: Fetcher<BlockByHeight>,
     pub(super) block_header_by_hash_fetcher: Fetcher<BlockHeader>,
     pub(super) block_header_with_metadata_fetcher: Fetcher<BlockHeaderWithMetadata>,
+    trie_fetcher: Fetcher<Trie<Key, StoredValue>>,
+    state_sync: StateSync,
     #[data_size(skip)]
     deploy_acceptor: DeployAcceptor,
     #[data_size(skip)]
@@ -394,6 +426,7 @@ impl reactor::Reactor for Reactor {
             small_network_identity,
             chainspec_loader.chainspec().as_ref(),
             None,
+            false,
         )?;
 
         let linear_chain_fetcher = Fetcher::new("linear_chain", config.fetcher, registry)?;
@@ -467,6 +500,10 @@ impl reactor::Reactor for Reactor {
         let block_header_by_hash_fetcher: Fetcher<BlockHeader> =
             Fetcher::new("block_header_by_hash", config.fetcher, registry)?;
 
+        let trie_fetcher = Fetcher::new("trie", config.fetcher, registry)?;
+
+        let state_sync = StateSync::new(config.state_sync, registry)?;
+
         let deploy_acceptor =
             DeployAcceptor::new(config.deploy_acceptor, &*chainspec_loader.chainspec());
 
@@ -495,6 +532,7 @@ impl reactor::Reactor for Reactor {
             chainspec_loader.after_upgrade(),
             maybe_next_activation_point,
             config.linear_chain_sync,
+            config.state_sync.enable(),
         )?;
 
         effects.extend(reactor::wrap_effects(
@@ -526,6 +564,8 @@ impl reactor::Reactor for Reactor {
                 block_header_by_hash_fetcher,
                 block_header_with_metadata_fetcher:
                     block_header_and_finality_signatures_by_height_fetcher,
+                trie_fetcher,
+                state_sync,
                 deploy_acceptor,
                 event_queue_metrics,
                 rest_server,
@@ -554,14 +594,28 @@ impl reactor::Reactor for Reactor {
             Event::ControlAnnouncement(ctrl_ann) => {
                 unreachable!("unhandled control announcement: {}", ctrl_ann)
             }
-            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(id)) => reactor::wrap_effects(
-                Event::LinearChainSync,
-                self.linear_chain_sync.handle_event(
+            Event::NetworkAnnouncement(NetworkAnnouncement::NewPeer(id)) => {
+                let mut effects = reactor::wrap_effects(
+                    Event::LinearChainSync,
+                    self.linear_chain_sync.handle_event(
+                        effect_builder,
+                        rng,
+                        linear_chain_sync::Event::NewPeerConnected(id),
+                    ),
+                );
+                effects.extend(self.dispatch_event(
                     effect_builder,
                     rng,
-                    linear_chain_sync::Event::NewPeerConnected(id),
+                    Event::StateSync(state_sync::Event::NewPeer(id)),
+                ));
+                effects
+            }
+            Event::NetworkAnnouncement(NetworkAnnouncement::PeerDisconnected(id)) => self
+                .dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::StateSync(state_sync::Event::PeerDisconnected(id)),
                 ),
-            ),
             Event::NetworkAnnouncement(NetworkAnnouncement::GossipOurAddress(gossiped_address)) => {
                 let event = gossiper::Event::ItemReceived {
                     item_id: gossiped_address,
@@ -615,6 +669,29 @@ impl reactor::Reactor for Reactor {
                     };
                     self.dispatch_event(effect_builder, rng, Event::BlockByHeightFetcher(event))
                 }
+                Message::GetResponse {
+                    tag: Tag::Trie,
+                    serialized_item,
+                } => {
+                    let trie_response: TrieResponse = match bincode::deserialize(&serialized_item) {
+                        Ok(trie_response) => trie_response,
+                        Err(err) => {
+                            error!("failed to decode trie from {}: {}", sender, err);
+                            return Effects::new();
+                        }
+                    };
+                    let event = match trie_response {
+                        TrieResponse::Absent(trie_key) => fetcher::Event::AbsentRemotely {
+                            id: trie_key,
+                            peer: sender,
+                        },
+                        TrieResponse::Trie(trie) => fetcher::Event::GotRemotely {
+                            item: trie,
+                            source: Source::Peer(sender),
+                        },
+                    };
+                    self.dispatch_event(effect_builder, rng, Event::TrieFetcher(event))
+                }
                 Message::GetResponse {
                     tag: Tag::Deploy,
                     serialized_item,
@@ -674,9 +751,21 @@ impl reactor::Reactor for Reactor {
                 source,
             }) => {
                 let deploy_hash = *deploy.id();
-                let peer = source;
-                warn!(?deploy_hash, ?peer, "Invalid deploy received from a peer.");
-                Effects::new()
+                warn!(
+                    ?deploy_hash,
+                    ?source,
+                    "Invalid deploy received from a peer."
+                );
+                match source {
+                    Source::Peer(peer) => {
+                        let ann = BlocklistAnnouncement::OffenseCommitted {
+                            offender: Box::new(peer),
+                            offense: Offense::InvalidDeploy,
+                        };
+                        self.dispatch_event(effect_builder, rng, Event::BlocklistAnnouncement(ann))
+                    }
+                    Source::Client | Source::Ourself => Effects::new(),
+                }
             }
             Event::Storage(event) => reactor::wrap_effects(
                 Event::Storage,
@@ -720,6 +809,20 @@ impl reactor::Reactor for Reactor {
             Event::DeployFetcherRequest(request) => {
                 self.dispatch_event(effect_builder, rng, Event::DeployFetcher(request.into()))
             }
+            Event::TrieFetcher(event) => reactor::wrap_effects(
+                Event::TrieFetcher,
+                self.trie_fetcher.handle_event(effect_builder, rng, event),
+            ),
+            Event::TrieFetcherRequest(request) => {
+                self.dispatch_event(effect_builder, rng, Event::TrieFetcher(request.into()))
+            }
+            Event::StateSync(event) => reactor::wrap_effects(
+                Event::StateSync,
+                self.state_sync.handle_event(effect_builder, rng, event),
+            ),
+            Event::StateSyncRequest(request) => {
+                self.dispatch_event(effect_builder, rng, Event::StateSync(request.into()))
+            }
             Event::BlockByHeightFetcherRequest(request) => self.dispatch_event(
                 effect_builder,
                 rng,
@@ -736,6 +839,7 @@ impl reactor::Reactor for Reactor {
                 let LinearChainBlock {
                     block,
                     execution_results,
+                    mut contract_events,
                 } = *linear_chain_block;
                 let mut effects = Effects::new();
                 let block_hash = *block.hash();
@@ -758,6 +862,9 @@ impl reactor::Reactor for Reactor {
                             deploy_header: Box::new(deploy_header),
                             block_hash,
                             execution_result: Box::new(execution_result),
+                            contract_events: contract_events
+                                .remove(&deploy_hash)
+                                .unwrap_or_default(),
                         });
                     effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
                 }
@@ -866,6 +973,9 @@ impl reactor::Reactor for Reactor {
                 };
                 self.dispatch_event(effect_builder, rng, event)
             }
+            Event::BlocklistAnnouncement(ann) => {
+                self.dispatch_event(effect_builder, rng, Event::SmallNetwork(ann.into()))
+            }
             Event::ChainspecLoaderAnnouncement(
                 ChainspecLoaderAnnouncement::UpgradeActivationPointRead(next_upgrade),
             ) => {
@@ -904,6 +1014,10 @@ impl reactor::Reactor for Reactor {
         self.event_queue_metrics
             .record_event_queue_counts(&event_queue_handle);
     }
+
+    fn memory_usage(&self) -> Option<MemUsageNode> {
+        Some(self.estimate_detailed_heap_size())
+    }
 }
 
 impl Reactor {
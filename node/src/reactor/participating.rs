@@ -15,13 +15,19 @@ use std::{
     sync::Arc,
 };
 
-use datasize::DataSize;
+use datasize::{DataSize, MemUsageNode};
 use derive_more::From;
 use prometheus::Registry;
 use reactor::ReactorEvent;
 use serde::Serialize;
 use tracing::{debug, error, trace, warn};
 
+use casper_execution_engine::{
+    shared::{newtypes::Blake2bHash, stored_value::StoredValue},
+    storage::trie::Trie,
+};
+use casper_types::Key;
+
 #[cfg(test)]
 use crate::testing::network::NetworkedReactor;
 
@@ -41,16 +47,16 @@ use crate::{
         network::{self, Network, NetworkIdentity, ENABLE_LIBP2P_NET_ENV_VAR},
         rest_server::{self, RestServer},
         rpc_server::{self, RpcServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, Offense, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
         Component,
     },
     effect::{
         announcements::{
-            BlocklistAnnouncement, ChainspecLoaderAnnouncement, ConsensusAnnouncement,
-            ContractRuntimeAnnouncement, ControlAnnouncement, DeployAcceptorAnnouncement,
-            GossiperAnnouncement, LinearChainAnnouncement, LinearChainBlock, NetworkAnnouncement,
-            RpcServerAnnouncement,
+            BlockProposerAnnouncement, BlocklistAnnouncement, ChainspecLoaderAnnouncement,
+            ConsensusAnnouncement, ContractRuntimeAnnouncement, ControlAnnouncement,
+            DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
+            LinearChainBlock, NetworkAnnouncement, RpcServerAnnouncement,
         },
         requests::{
             BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest, ConsensusRequest,
@@ -62,7 +68,7 @@ use crate::{
     },
     protocol::Message,
     reactor::{self, event_queue_metrics::EventQueueMetrics, EventQueueHandle, ReactorExit},
-    types::{BlockHash, BlockHeader, Deploy, ExitCode, NodeId, Tag},
+    types::{BlockHash, BlockHeader, Deploy, ExitCode, NodeId, SharedObject, Tag, TrieResponse},
     utils::{Source, WithDir},
     NodeRng,
 };
@@ -187,6 +193,9 @@ pub enum Event {
     /// Blocklist announcement.
     #[from]
     BlocklistAnnouncement(BlocklistAnnouncement<NodeId>),
+    /// Block proposer announcement.
+    #[from]
+    BlockProposerAnnouncement(#[serde(skip_serializing)] BlockProposerAnnouncement),
 }
 
 impl ReactorEvent for Event {
@@ -300,6 +309,9 @@ impl Display for Event {
             Event::BlocklistAnnouncement(ann) => {
                 write!(f, "blocklist announcement: {}", ann)
             }
+            Event::BlockProposerAnnouncement(ann) => {
+                write!(f, "block proposer announcement: {}", ann)
+            }
         }
     }
 }
@@ -372,6 +384,11 @@ impl Reactor {
     pub(crate) fn storage(&self) -> &Storage {
         &self.storage
     }
+
+    /// Inspect the contract runtime.
+    pub(crate) fn contract_runtime(&self) -> &ContractRuntime {
+        &self.contract_runtime
+    }
 }
 
 impl reactor::Reactor for Reactor {
@@ -461,6 +478,7 @@ impl reactor::Reactor for Reactor {
             small_network_identity,
             chainspec_loader.chainspec().as_ref(),
             Some(initial_era),
+            true,
         )?;
 
         let mut effects = reactor::wrap_effects(Event::BlockProposer, block_proposer_effects);
@@ -817,6 +835,41 @@ impl reactor::Reactor for Reactor {
                                 }
                             }
                         }
+                        Tag::Trie => {
+                            let trie_key: Blake2bHash = match bincode::deserialize(&serialized_id) {
+                                Ok(trie_key) => trie_key,
+                                Err(error) => {
+                                    error!(
+                                        "failed to decode {:?} from {}: {}",
+                                        serialized_id, sender, error
+                                    );
+                                    return Effects::new();
+                                }
+                            };
+                            return async move {
+                                let response = match effect_builder.read_trie(trie_key).await {
+                                    Some(trie) => TrieResponse::Trie(Box::new(trie)),
+                                    None => {
+                                        debug!("failed to get {} for {}", trie_key, sender);
+                                        TrieResponse::Absent(trie_key)
+                                    }
+                                };
+                                match bincode::serialize(&response) {
+                                    Ok(serialized_item) => {
+                                        let message = Message::new_get_response_raw_unchecked::<
+                                            Trie<Key, StoredValue>,
+                                        >(
+                                            SharedObject::owned(serialized_item)
+                                        );
+                                        effect_builder.send_message(sender, message).await
+                                    }
+                                    Err(error) => {
+                                        error!("failed to create get-response: {}", error)
+                                    }
+                                }
+                            }
+                            .ignore();
+                        }
                     },
                     Message::GetResponse {
                         tag,
@@ -872,6 +925,10 @@ impl reactor::Reactor for Reactor {
                             );
                             return Effects::new();
                         }
+                        Tag::Trie => {
+                            error!("cannot handle get response for trie from {}", sender);
+                            return Effects::new();
+                        }
                     },
                     Message::FinalitySignature(fs) => {
                         Event::LinearChain(linear_chain::Event::FinalitySignatureReceived(fs, true))
@@ -890,6 +947,10 @@ impl reactor::Reactor for Reactor {
                 trace!("new peer announcement not handled in the participating reactor");
                 Effects::new()
             }
+            Event::NetworkAnnouncement(NetworkAnnouncement::PeerDisconnected(_peer_id)) => {
+                trace!("peer disconnected announcement not handled in the participating reactor");
+                Effects::new()
+            }
             Event::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived {
                 deploy,
                 responder,
@@ -951,8 +1012,17 @@ impl reactor::Reactor for Reactor {
             }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
                 deploy: _,
-                source: _,
-            }) => Effects::new(),
+                source,
+            }) => match source {
+                Source::Peer(peer) => {
+                    let ann = BlocklistAnnouncement::OffenseCommitted {
+                        offender: Box::new(peer),
+                        offense: Offense::InvalidDeploy,
+                    };
+                    self.dispatch_event(effect_builder, rng, Event::BlocklistAnnouncement(ann))
+                }
+                Source::Client | Source::Ourself => Effects::new(),
+            },
             Event::ConsensusAnnouncement(consensus_announcement) => match consensus_announcement {
                 ConsensusAnnouncement::Finalized(block) => {
                     let reactor_event =
@@ -984,6 +1054,7 @@ impl reactor::Reactor for Reactor {
                 let LinearChainBlock {
                     block,
                     execution_results,
+                    mut contract_events,
                 } = *linear_chain_block;
                 let mut effects = Effects::new();
                 let block_hash = *block.hash();
@@ -1006,6 +1077,9 @@ impl reactor::Reactor for Reactor {
                             deploy_header: Box::new(deploy_header),
                             block_hash,
                             execution_result: Box::new(execution_result),
+                            contract_events: contract_events
+                                .remove(&deploy_hash)
+                                .unwrap_or_default(),
                         });
                     effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
                 }
@@ -1028,6 +1102,18 @@ impl reactor::Reactor for Reactor {
                 });
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
+            Event::BlockProposerAnnouncement(BlockProposerAnnouncement::DeploysExpired(
+                deploy_hashes,
+            )) => {
+                let mut effects = Effects::new();
+                for deploy_hash in deploy_hashes {
+                    let reactor_event = Event::EventStreamServer(
+                        event_stream_server::Event::DeployExpired(deploy_hash),
+                    );
+                    effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
+                }
+                effects
+            }
             Event::DeployGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem(
                 gossiped_deploy_id,
             )) => {
@@ -1101,6 +1187,10 @@ impl reactor::Reactor for Reactor {
             .record_event_queue_counts(&event_queue_handle)
     }
 
+    fn memory_usage(&self) -> Option<MemUsageNode> {
+        Some(self.estimate_detailed_heap_size())
+    }
+
     fn maybe_exit(&self) -> Option<ReactorExit> {
         self.consensus
             .stop_for_upgrade()
@@ -1118,4 +1208,13 @@ impl NetworkedReactor for Reactor {
             self.small_network.node_id()
         }
     }
+
+    fn message_sender(event: &Event) -> Option<NodeId> {
+        match event {
+            Event::NetworkAnnouncement(NetworkAnnouncement::MessageReceived { sender, .. }) => {
+                Some(*sender)
+            }
+            _ => None,
+        }
+    }
 }
@@ -2,10 +2,10 @@ use datasize::DataSize;
 use serde::Deserialize;
 
 use crate::{
-    logging::LoggingConfig, types::NodeConfig, BlockProposerConfig, ConsensusConfig,
-    ContractRuntimeConfig, DeployAcceptorConfig, EventStreamServerConfig, FetcherConfig,
-    GossipConfig, LinearChainSyncConfig, RestServerConfig, RpcServerConfig, SmallNetworkConfig,
-    StorageConfig,
+    logging::LoggingConfig, types::NodeConfig, AdminServerConfig, BlockProposerConfig,
+    ConsensusConfig, ContractRuntimeConfig, DeployAcceptorConfig, EventStreamServerConfig,
+    FetcherConfig, GossipConfig, LinearChainSyncConfig, RestServerConfig, RpcServerConfig,
+    SmallNetworkConfig, StateSyncConfig, StorageConfig,
 };
 
 /// Root configuration.
@@ -42,4 +42,10 @@ pub struct Config {
     /// Block proposer configuration.
     #[serde(default)]
     pub block_proposer: BlockProposerConfig,
+    /// Admin API server configuration.
+    #[serde(default)]
+    pub admin_server: AdminServerConfig,
+    /// Global state synchronization configuration.
+    #[serde(default)]
+    pub state_sync: StateSyncConfig,
 }
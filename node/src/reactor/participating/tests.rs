@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::bail;
 use log::info;
@@ -11,13 +15,17 @@ use casper_execution_engine::shared::motes::Motes;
 use casper_types::{system::auction::DelegationRate, EraId, PublicKey, SecretKey, U512};
 
 use crate::{
-    components::{consensus, gossiper, small_network, storage},
+    components::{
+        consensus,
+        contract_runtime::{self, PruningMode},
+        gossiper, small_network, storage,
+    },
     crypto::AsymmetricKeyExt,
     reactor::{initializer, joiner, participating, ReactorExit, Runner},
-    testing::{self, network::Network, TestRng},
+    testing::{self, fault_model::FaultModel, network::Network, TestRng},
     types::{
         chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
-        ActivationPoint, Chainspec, Timestamp,
+        ActivationPoint, Chainspec, NodeId, Timestamp,
     },
     utils::{External, Loadable, WithDir, RESOURCES_PATH},
     NodeRng,
@@ -28,6 +36,7 @@ struct TestChain {
     keys: Vec<Arc<SecretKey>>,
     storages: Vec<TempDir>,
     chainspec: Arc<Chainspec>,
+    contract_runtime_config: contract_runtime::Config,
 }
 
 type Nodes = crate::testing::network::Nodes<participating::Reactor>;
@@ -52,6 +61,24 @@ impl TestChain {
         Self::new_with_keys(rng, keys, stakes)
     }
 
+    /// Instantiates a new test chain configuration.
+    ///
+    /// Generates secret keys for `size` validators with equal stakes and creates a matching
+    /// chainspec.
+    fn new_with_equal_stakes(rng: &mut TestRng, size: usize) -> Self {
+        let keys: Vec<Arc<SecretKey>> = (0..size)
+            .map(|_| Arc::new(SecretKey::random(rng)))
+            .collect();
+        let stakes = keys
+            .iter()
+            .map(|secret_key| {
+                let stake = U512::from(100) * U512::from(u128::MAX);
+                (PublicKey::from(&**secret_key), stake)
+            })
+            .collect();
+        Self::new_with_keys(rng, keys, stakes)
+    }
+
     /// Instantiates a new test chain configuration.
     ///
     /// Takes a vector of bonded keys with specified bond amounts.
@@ -93,6 +120,7 @@ impl TestChain {
             keys,
             chainspec: Arc::new(chainspec),
             storages: Vec::new(),
+            contract_runtime_config: Default::default(),
         }
     }
 
@@ -106,6 +134,7 @@ impl TestChain {
                 small_network::Config::default_local_net(first_node_port)
             },
             gossip: gossiper::Config::new_with_small_timeouts(),
+            contract_runtime: self.contract_runtime_config,
             ..Default::default()
         };
 
@@ -199,6 +228,147 @@ async fn run_participating_network() {
         .await;
 }
 
+/// Returns a predicate to check if all of the nodes are at least in era `era_id` and have pruned
+/// the global state of the switch block of era `pruned_era_id`.
+fn has_pruned_era(era_id: EraId, pruned_era_id: EraId) -> impl Fn(&Nodes) -> bool {
+    move |nodes: &Nodes| {
+        nodes.values().all(|runner| {
+            let reactor = runner.reactor().inner();
+            if reactor.consensus().current_era() < era_id {
+                return false;
+            }
+            match reactor
+                .storage()
+                .transactional_get_switch_block_by_era_id(pruned_era_id.value())
+            {
+                Some(switch_block) => !reactor
+                    .contract_runtime()
+                    .trie_store_check(vec![(*switch_block.header().state_root_hash()).into()])
+                    .is_empty(),
+                None => false,
+            }
+        })
+    }
+}
+
+#[tokio::test]
+async fn run_participating_network_with_pruning() {
+    testing::init_logging();
+
+    let mut rng = crate::new_rng();
+
+    const NETWORK_SIZE: usize = 3;
+    let mut chain = TestChain::new(&mut rng, NETWORK_SIZE);
+    chain.contract_runtime_config =
+        contract_runtime::Config::default().with_pruning_mode(PruningMode::KeepLastEras(1));
+
+    let mut net = chain
+        .create_initialized_network(&mut rng)
+        .await
+        .expect("network initialization failed");
+
+    // Pruning at the end of era 2 removes the state of the era 0 switch block.
+    net.settle_on(
+        &mut rng,
+        has_pruned_era(EraId::from(3), EraId::from(0)),
+        Duration::from_secs(180),
+    )
+    .await;
+
+    // Blocks were executed while pruning ran in the background, yet the state of the latest
+    // switch block is complete.
+    for runner in net.nodes().values() {
+        let reactor = runner.reactor().inner();
+        let current_era = reactor.consensus().current_era();
+        let switch_block = reactor
+            .storage()
+            .transactional_get_switch_block_by_era_id(current_era.value() - 1)
+            .expect("should have the latest switch block");
+        let missing = reactor
+            .contract_runtime()
+            .trie_store_check(vec![(*switch_block.header().state_root_hash()).into()]);
+        assert!(missing.is_empty(), "missing tries: {:?}", missing);
+    }
+}
+
+/// Returns the height of the highest block stored by any of the nodes.
+fn highest_block_height(nodes: &Nodes) -> Option<u64> {
+    nodes
+        .values()
+        .filter_map(|runner| runner.reactor().inner().storage().highest_block_height())
+        .max()
+}
+
+/// Returns a predicate to check if all of the nodes have stored a block above `height`.
+fn has_block_above(height: Option<u64>) -> impl Fn(&Nodes) -> bool {
+    move |nodes: &Nodes| {
+        nodes
+            .values()
+            .all(|runner| runner.reactor().inner().storage().highest_block_height() > height)
+    }
+}
+
+/// Runs the network for `duration` of real time.
+async fn run_for(net: &mut Network<participating::Reactor>, rng: &mut TestRng, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    net.settle_on(rng, |_| Instant::now() >= deadline, duration * 2)
+        .await;
+}
+
+#[tokio::test]
+async fn run_participating_network_through_healed_partition() {
+    testing::init_logging();
+
+    let mut rng = crate::new_rng();
+
+    // With a finality threshold of 34%, finalizing blocks requires more than 67% of the weight.
+    const NETWORK_SIZE: usize = 5;
+    const MINORITY_SIZE: usize = 2;
+    // Rounds last at least 2^12 ms, so this is long enough for blocks finalized from units sent
+    // before the partition to be stored, and the partition is held for several rounds after that.
+    const SETTLE_DURATION: Duration = Duration::from_secs(15);
+    const PARTITION_DURATION: Duration = Duration::from_secs(30);
+    let mut chain = TestChain::new_with_equal_stakes(&mut rng, NETWORK_SIZE);
+
+    let mut net = chain
+        .create_initialized_network(&mut rng)
+        .await
+        .expect("network initialization failed");
+    let fault_model = FaultModel::new(&mut rng).into_shared();
+    net.set_fault_model(fault_model.clone());
+
+    net.settle_on(&mut rng, is_in_era(EraId::from(1)), Duration::from_secs(90))
+        .await;
+
+    // Split the validators into two groups, neither of which has enough weight to finalize blocks
+    // on its own.
+    let mut node_ids: Vec<NodeId> = net.nodes().keys().cloned().collect();
+    node_ids.sort();
+    let (minority, majority) = node_ids.split_at(MINORITY_SIZE);
+    fault_model.lock().unwrap().partition(vec![
+        minority.iter().cloned().collect(),
+        majority.iter().cloned().collect(),
+    ]);
+
+    run_for(&mut net, &mut rng, SETTLE_DURATION).await;
+    let stalled_height = highest_block_height(net.nodes());
+    run_for(&mut net, &mut rng, PARTITION_DURATION).await;
+    assert_eq!(
+        highest_block_height(net.nodes()),
+        stalled_height,
+        "no block should be finalized while the network is partitioned"
+    );
+
+    // Once the partition has healed, the validators should finalize blocks again.
+    fault_model.lock().unwrap().heal();
+    net.settle_on(
+        &mut rng,
+        has_block_above(stalled_height),
+        Duration::from_secs(120),
+    )
+    .await;
+}
+
 // TODO: fix this test
 #[tokio::test]
 async fn run_equivocator_network() {
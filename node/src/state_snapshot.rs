@@ -0,0 +1,410 @@
+//! Export and import of global state snapshots.
+//!
+//! A snapshot holds every trie reachable from a single state root hash.  It is written as a
+//! header followed by a sequence of chunks, each of which carries a batch of serialized tries
+//! along with a checksum of its payload.  Tries are written parents first, so that an importing
+//! node can track which descendants are still outstanding as it goes.  Every chunk is imported in
+//! a single transaction.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, info};
+
+use casper_execution_engine::{
+    core::engine_state::{self, EngineState},
+    shared::{
+        newtypes::{Blake2bHash, CorrelationId},
+        stored_value::StoredValue,
+    },
+    storage::{global_state::lmdb::LmdbGlobalState, trie::Trie},
+};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key,
+};
+
+use crate::{
+    components::contract_runtime::{self, ConfigError},
+    crypto::hash::{self, Digest},
+    reactor::participating::Config,
+    utils::WithDir,
+};
+
+/// Identifies a file as a global state snapshot.
+const MAGIC: [u8; 8] = *b"CSPRSNAP";
+/// The version of the snapshot format written by this node.
+const FORMAT_VERSION: u32 = 1;
+/// The maximum number of tries written to a single chunk.
+const TRIES_PER_CHUNK: usize = 10_000;
+/// The payload size after which no further tries are added to a chunk.
+const CHUNK_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+/// The maximum serialized size of a chunk accepted on import, which also bounds the allocations
+/// made while decoding a corrupt or malicious snapshot.
+const MAX_CHUNK_SIZE: u64 = 256 * 1024 * 1024;
+/// The maximum serialized size of the snapshot header.
+const MAX_HEADER_SIZE: u64 = 1024;
+
+/// Error returned as a result of exporting or importing a global state snapshot.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error opening the global state store.
+    #[error("error opening global state: {0}")]
+    OpenGlobalState(ConfigError),
+
+    /// Error reading from or writing to the global state store.
+    #[error("global state error: {0}")]
+    GlobalState(engine_state::Error),
+
+    /// The requested state root hash is not in global state.
+    #[error("state root hash {0} not found in global state")]
+    RootNotFound(Digest),
+
+    /// A trie reachable from the state root hash is not in global state.
+    #[error("trie {0} is reachable from the state root but missing from global state")]
+    MissingTrie(Digest),
+
+    /// Error creating, reading or writing the snapshot file.
+    #[error("error accessing snapshot file {path}: {error}")]
+    SnapshotIo {
+        /// The file path.
+        path: String,
+        /// The IO error.
+        error: io::Error,
+    },
+
+    /// Error serializing or deserializing a part of the snapshot.
+    #[error("error (de)serializing snapshot: {0}")]
+    Serialization(bincode::Error),
+
+    /// Error encoding or decoding a trie.
+    #[error("error (de)serializing trie: {0}")]
+    TrieEncoding(bytesrepr::Error),
+
+    /// The snapshot file has an unknown magic number or format version.
+    #[error("not a global state snapshot, or an unsupported snapshot version")]
+    InvalidHeader,
+
+    /// A chunk of the snapshot doesn't match its checksum.
+    #[error("checksum mismatch in snapshot chunk {index}")]
+    ChecksumMismatch {
+        /// The index of the corrupt chunk.
+        index: u64,
+    },
+
+    /// The imported snapshot doesn't contain the full trie under its state root hash.
+    #[error("snapshot is incomplete: {missing} tries are missing")]
+    Incomplete {
+        /// The number of tries still missing after the import.
+        missing: usize,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotHeader {
+    magic: [u8; 8],
+    version: u32,
+    state_root_hash: Digest,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotChunk {
+    /// The bincode-encoded `Vec<Vec<u8>>` of bytesrepr-encoded tries.
+    payload: Vec<u8>,
+    checksum: Digest,
+}
+
+/// Writes every trie reachable from `state_root_hash` to a snapshot at `path`.
+///
+/// Returns the number of tries written.
+pub fn export_state(
+    config: WithDir<Config>,
+    state_root_hash: Digest,
+    path: &Path,
+) -> Result<u64, Error> {
+    let engine_state = open_engine_state(&config)?;
+    let correlation_id = CorrelationId::new();
+
+    let file = File::create(path).map_err(|error| io_error(path, error))?;
+    let mut writer = BufWriter::new(file);
+    let header = SnapshotHeader {
+        magic: MAGIC,
+        version: FORMAT_VERSION,
+        state_root_hash,
+    };
+    bincode::serialize_into(&mut writer, &header).map_err(Error::Serialization)?;
+
+    // Depth-first walk, so that every trie is written before any of its descendants.
+    let root: Blake2bHash = state_root_hash.into();
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![root];
+    let mut batch = Vec::with_capacity(TRIES_PER_CHUNK);
+    let mut batch_size = 0;
+    let mut written = 0_u64;
+    while let Some(trie_key) = to_visit.pop() {
+        if !visited.insert(trie_key) {
+            continue;
+        }
+        let trie = match engine_state
+            .read_trie(correlation_id, trie_key)
+            .map_err(Error::GlobalState)?
+        {
+            Some(trie) => trie,
+            None if trie_key == root => return Err(Error::RootNotFound(state_root_hash)),
+            None => return Err(Error::MissingTrie(trie_key.into())),
+        };
+
+        match &trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => to_visit.extend(
+                pointer_block
+                    .to_indexed_pointers()
+                    .map(|(_, pointer)| pointer.into_hash()),
+            ),
+            Trie::Extension { pointer, .. } => to_visit.push(*pointer.hash()),
+        }
+
+        let trie_bytes = trie.to_bytes().map_err(Error::TrieEncoding)?;
+        batch_size += trie_bytes.len();
+        batch.push(trie_bytes);
+        written += 1;
+        if batch.len() == TRIES_PER_CHUNK || batch_size >= CHUNK_PAYLOAD_SIZE {
+            write_chunk(&mut writer, &batch)?;
+            batch.clear();
+            batch_size = 0;
+            debug!(%written, "exported tries");
+        }
+    }
+    if !batch.is_empty() {
+        write_chunk(&mut writer, &batch)?;
+    }
+    bincode::serialize_into(&mut writer, &Option::<SnapshotChunk>::None)
+        .map_err(Error::Serialization)?;
+    writer.flush().map_err(|error| io_error(path, error))?;
+
+    info!(%state_root_hash, %written, path = %path.display(), "exported global state");
+    Ok(written)
+}
+
+/// Restores the global state held in the snapshot at `path`.
+///
+/// Returns the state root hash of the snapshot once the full trie under it has been verified to
+/// be present in global state.
+pub fn import_state(config: WithDir<Config>, path: &Path) -> Result<Digest, Error> {
+    let engine_state = open_engine_state(&config)?;
+    let correlation_id = CorrelationId::new();
+
+    let file = File::open(path).map_err(|error| io_error(path, error))?;
+    let mut reader = BufReader::new(file);
+    let header: SnapshotHeader = bincode_options(MAX_HEADER_SIZE)
+        .deserialize_from(&mut reader)
+        .map_err(Error::Serialization)?;
+    if header.magic != MAGIC || header.version != FORMAT_VERSION {
+        return Err(Error::InvalidHeader);
+    }
+    let state_root_hash = header.state_root_hash;
+    let root: Blake2bHash = state_root_hash.into();
+
+    // The tries we know to be referenced, but which haven't been imported yet.
+    let mut pending: HashSet<Blake2bHash> = HashSet::new();
+    pending.insert(root);
+    let mut imported = 0_u64;
+    let mut index = 0_u64;
+    while let Some(tries_bytes) = read_chunk(&mut reader, index)? {
+        let mut tries = Vec::with_capacity(tries_bytes.len());
+        for trie_bytes in tries_bytes {
+            pending.remove(&Blake2bHash::new(&trie_bytes));
+            let trie: Trie<Key, StoredValue> =
+                bytesrepr::deserialize(trie_bytes).map_err(Error::TrieEncoding)?;
+            tries.push(trie);
+        }
+        let missing_descendants = engine_state
+            .put_tries_and_find_missing_descendant_trie_keys(correlation_id, &tries)
+            .map_err(Error::GlobalState)?;
+        pending.extend(missing_descendants);
+        imported += tries.len() as u64;
+        index += 1;
+        debug!(%imported, "imported tries");
+    }
+
+    let missing = engine_state
+        .missing_trie_keys(correlation_id, vec![root])
+        .map_err(Error::GlobalState)?;
+    if !pending.is_empty() || !missing.is_empty() {
+        return Err(Error::Incomplete {
+            missing: pending.len().max(missing.len()),
+        });
+    }
+
+    info!(%state_root_hash, %imported, path = %path.display(), "imported global state");
+    Ok(state_root_hash)
+}
+
+fn open_engine_state(config: &WithDir<Config>) -> Result<EngineState<LmdbGlobalState>, Error> {
+    let storage_config = config.map_ref(|cfg| cfg.storage.clone());
+    contract_runtime::open_engine_state(&storage_config, &config.value().contract_runtime)
+        .map_err(Error::OpenGlobalState)
+}
+
+/// Returns the bincode options used for the snapshot, which match those of `bincode::serialize`
+/// apart from the size limit applied when deserializing.
+fn bincode_options(limit: u64) -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(limit)
+}
+
+fn write_chunk<W: Write>(writer: &mut W, tries: &[Vec<u8>]) -> Result<(), Error> {
+    let payload = bincode::serialize(tries).map_err(Error::Serialization)?;
+    let checksum = hash::hash(&payload);
+    let chunk = SnapshotChunk { payload, checksum };
+    bincode::serialize_into(writer, &Some(chunk)).map_err(Error::Serialization)
+}
+
+/// Reads the next chunk, returning `None` once the end-of-snapshot marker has been reached.
+fn read_chunk<R: Read>(reader: &mut R, index: u64) -> Result<Option<Vec<Vec<u8>>>, Error> {
+    let maybe_chunk: Option<SnapshotChunk> = bincode_options(MAX_CHUNK_SIZE)
+        .deserialize_from(reader)
+        .map_err(Error::Serialization)?;
+    let chunk = match maybe_chunk {
+        Some(chunk) => chunk,
+        None => return Ok(None),
+    };
+    if hash::hash(&chunk.payload) != chunk.checksum {
+        return Err(Error::ChecksumMismatch { index });
+    }
+    bincode_options(chunk.payload.len() as u64)
+        .deserialize(&chunk.payload)
+        .map(Some)
+        .map_err(Error::Serialization)
+}
+
+fn io_error(path: &Path, error: io::Error) -> Error {
+    Error::SnapshotIo {
+        path: path.display().to_string(),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_execution_engine::{
+        shared::{additive_map::AdditiveMap, transform::Transform},
+        storage::global_state::CommitResult,
+    };
+    use casper_types::{AccessRights, CLValue, URef};
+    use rand::Rng;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{components::storage, testing::TestRng};
+
+    /// Returns a node config with its global state stored in `dir`.
+    fn config_in(dir: &TempDir) -> WithDir<Config> {
+        let config = Config {
+            storage: storage::Config {
+                path: dir.path().to_path_buf(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        WithDir::new(dir.path(), config)
+    }
+
+    #[test]
+    fn should_round_trip_global_state() {
+        let mut rng = TestRng::new();
+        let correlation_id = CorrelationId::new();
+
+        // Commit some state to the exporting node's global state.
+        let export_dir = tempfile::tempdir().unwrap();
+        let state_root_hash = {
+            let engine_state = open_engine_state(&config_in(&export_dir)).unwrap();
+            let empty_root = Trie::<Key, StoredValue>::node(&[]);
+            let empty_root_hash = Blake2bHash::new(&empty_root.to_bytes().unwrap());
+            engine_state
+                .put_trie_and_find_missing_descendant_trie_keys(correlation_id, &empty_root)
+                .unwrap();
+            let mut effects = AdditiveMap::new();
+            for value in 0..500_u64 {
+                let key = Key::URef(URef::new(rng.gen(), AccessRights::READ_ADD_WRITE));
+                let value = StoredValue::CLValue(CLValue::from_t(value).unwrap());
+                effects.insert(key, Transform::Write(value));
+            }
+            match engine_state
+                .apply_effect(correlation_id, empty_root_hash, effects)
+                .unwrap()
+            {
+                CommitResult::Success { state_root } => Digest::from(state_root),
+                other => panic!("failed to commit: {:?}", other),
+            }
+        };
+
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = snapshot_dir.path().join("snapshot");
+        let exported =
+            export_state(config_in(&export_dir), state_root_hash, &snapshot_path).unwrap();
+        assert!(exported > 500);
+
+        let import_dir = tempfile::tempdir().unwrap();
+        let imported_root_hash = import_state(config_in(&import_dir), &snapshot_path).unwrap();
+        assert_eq!(imported_root_hash, state_root_hash);
+
+        let engine_state = open_engine_state(&config_in(&import_dir)).unwrap();
+        let missing = engine_state
+            .missing_trie_keys(correlation_id, vec![state_root_hash.into()])
+            .unwrap();
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn should_reject_oversized_chunk() {
+        // The `Some` tag, followed by a payload length exceeding the limit.
+        let mut buffer = vec![1_u8];
+        bincode::serialize_into(&mut buffer, &(MAX_CHUNK_SIZE + 1)).unwrap();
+        buffer.extend(vec![0; 1024]);
+
+        let mut reader = buffer.as_slice();
+        match read_chunk(&mut reader, 0) {
+            Err(Error::Serialization(_)) => (),
+            other => panic!("expected serialization error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_round_trip_chunks() {
+        let tries = vec![vec![1, 2, 3], vec![], vec![4; 100]];
+        let mut buffer = vec![];
+        write_chunk(&mut buffer, &tries).unwrap();
+        bincode::serialize_into(&mut buffer, &Option::<SnapshotChunk>::None).unwrap();
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(read_chunk(&mut reader, 0).unwrap(), Some(tries));
+        assert_eq!(read_chunk(&mut reader, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn should_detect_corrupt_chunk() {
+        let tries = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let mut buffer = vec![];
+        write_chunk(&mut buffer, &tries).unwrap();
+
+        // Flip the last byte of the payload, which precedes the 32-byte checksum.
+        let index = buffer.len() - Digest::LENGTH - 1;
+        buffer[index] ^= 0xff;
+
+        let mut reader = buffer.as_slice();
+        match read_chunk(&mut reader, 3) {
+            Err(Error::ChecksumMismatch { index }) => assert_eq!(index, 3),
+            other => panic!("expected checksum mismatch, got {:?}", other),
+        }
+    }
+}
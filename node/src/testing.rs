@@ -4,6 +4,7 @@
 //! `casper-node` library.
 
 mod condition_check_reactor;
+pub mod fault_model;
 mod multi_stage_test_reactor;
 pub mod network;
 pub mod test_clock;
@@ -1,7 +1,13 @@
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    mem,
+    time::Duration,
+};
 
 use futures::future::BoxFuture;
 use prometheus::Registry;
+use tokio::time::Instant;
+use tracing::debug;
 
 use super::network::NetworkedReactor;
 use crate::{
@@ -10,18 +16,38 @@ use crate::{
     NodeRng,
 };
 
-/// A reactor wrapping an inner reactor, and which has an optional hook into
+/// The fate of an event, as decided by a fault injector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventFate {
+    /// The event is dispatched right away.
+    Dispatch,
+    /// The event is discarded.
+    Drop,
+    /// The event is dispatched once the given duration has elapsed.
+    Delay(Duration),
+    /// The event is held back and its fate is decided again later.
+    Hold,
+}
+
+/// A reactor wrapping an inner reactor, and which has optional hooks into
 /// `Reactor::dispatch_event()`.
 ///
-/// While the hook is not `None`, it's called on every call to `dispatch_event()`, taking a
-/// reference to the current `Event`, and setting a boolean result to true when the condition has
-/// been met.
+/// While the condition checker hook is not `None`, it's called on every event dispatched to the
+/// inner reactor, taking a reference to the current `Event`, and setting a boolean result to true
+/// when the condition has been met.
 ///
 /// Once the condition is met, the hook is reset to `None`.
+///
+/// While the fault injector hook is not `None`, it decides the fate of every event before it
+/// reaches the inner reactor. Delayed and held events are revisited whenever another event is
+/// dispatched, so they are only dispatched once the reactor receives some event afterwards.
 pub struct ConditionCheckReactor<R: Reactor> {
     reactor: R,
     condition_checker: Option<Box<dyn Fn(&R::Event) -> bool + Send>>,
     condition_result: bool,
+    fault_injector: Option<Box<dyn Fn(&R::Event) -> EventFate + Send>>,
+    delayed_events: Vec<(Instant, R::Event)>,
+    held_events: Vec<R::Event>,
 }
 
 impl<R: Reactor> ConditionCheckReactor<R> {
@@ -33,6 +59,14 @@ impl<R: Reactor> ConditionCheckReactor<R> {
         self.condition_checker = Some(condition_checker);
     }
 
+    /// Sets the fault injector hook.
+    pub fn set_fault_injector(
+        &mut self,
+        fault_injector: Box<dyn Fn(&R::Event) -> EventFate + Send>,
+    ) {
+        self.fault_injector = Some(fault_injector);
+    }
+
     /// Returns the result of the last execution of the condition checker hook.
     pub fn condition_result(&self) -> bool {
         self.condition_result
@@ -47,6 +81,65 @@ impl<R: Reactor> ConditionCheckReactor<R> {
     pub fn inner_mut(&mut self) -> &mut R {
         &mut self.reactor
     }
+
+    /// Dispatches `event` to the inner reactor, checking the condition on it.
+    fn dispatch_inner(
+        &mut self,
+        effect_builder: EffectBuilder<R::Event>,
+        rng: &mut NodeRng,
+        event: R::Event,
+    ) -> Effects<R::Event> {
+        if let Some(condition_checker) = self.condition_checker.as_ref() {
+            if condition_checker(&event) {
+                self.condition_result = true;
+                self.condition_checker = None;
+            }
+        }
+        self.reactor.dispatch_event(effect_builder, rng, event)
+    }
+
+    /// Dispatches `event`, or drops, delays or holds it as decided by the fault injector.
+    fn inject_faults(
+        &mut self,
+        effect_builder: EffectBuilder<R::Event>,
+        rng: &mut NodeRng,
+        event: R::Event,
+    ) -> Effects<R::Event> {
+        let fate = self
+            .fault_injector
+            .as_ref()
+            .map_or(EventFate::Dispatch, |fault_injector| fault_injector(&event));
+        match fate {
+            EventFate::Dispatch => return self.dispatch_inner(effect_builder, rng, event),
+            EventFate::Drop => debug!(%event, "dropping event due to injected fault"),
+            EventFate::Delay(delay) => self.delayed_events.push((Instant::now() + delay, event)),
+            EventFate::Hold => self.held_events.push(event),
+        }
+        Effects::new()
+    }
+
+    /// Dispatches all held events which are no longer held back and all delayed events which are
+    /// due.
+    fn release_events(
+        &mut self,
+        effect_builder: EffectBuilder<R::Event>,
+        rng: &mut NodeRng,
+    ) -> Effects<R::Event> {
+        let mut effects = Effects::new();
+        for event in mem::take(&mut self.held_events) {
+            effects.extend(self.inject_faults(effect_builder, rng, event));
+        }
+
+        let now = Instant::now();
+        let (due_events, delayed_events) = mem::take(&mut self.delayed_events)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(due, _)| *due <= now);
+        self.delayed_events = delayed_events;
+        for (_, event) in due_events {
+            effects.extend(self.dispatch_inner(effect_builder, rng, event));
+        }
+        effects
+    }
 }
 
 impl<R: Reactor> Reactor for ConditionCheckReactor<R> {
@@ -66,6 +159,9 @@ impl<R: Reactor> Reactor for ConditionCheckReactor<R> {
                 reactor,
                 condition_checker: None,
                 condition_result: false,
+                fault_injector: None,
+                delayed_events: Vec::new(),
+                held_events: Vec::new(),
             },
             effects,
         ))
@@ -77,15 +173,10 @@ impl<R: Reactor> Reactor for ConditionCheckReactor<R> {
         rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
-        self.condition_result = self
-            .condition_checker
-            .as_ref()
-            .map(|condition_checker| condition_checker(&event))
-            .unwrap_or_default();
-        if self.condition_result {
-            self.condition_checker = None;
-        }
-        self.reactor.dispatch_event(effect_builder, rng, event)
+        self.condition_result = false;
+        let mut effects = self.release_events(effect_builder, rng);
+        effects.extend(self.inject_faults(effect_builder, rng, event));
+        effects
     }
 
     fn maybe_exit(&self) -> Option<ReactorExit> {
@@ -105,6 +196,10 @@ impl<R: Reactor + NetworkedReactor> NetworkedReactor for ConditionCheckReactor<R
     fn node_id(&self) -> Self::NodeId {
         self.reactor.node_id()
     }
+
+    fn message_sender(event: &R::Event) -> Option<Self::NodeId> {
+        R::message_sender(event)
+    }
 }
 
 impl<R: Reactor + Debug> Debug for ConditionCheckReactor<R> {
@@ -113,6 +208,8 @@ impl<R: Reactor + Debug> Debug for ConditionCheckReactor<R> {
             .debug_struct("ConditionCheckReactor")
             .field("reactor", &self.reactor)
             .field("condition_check_result", &self.condition_result)
+            .field("delayed_events", &self.delayed_events.len())
+            .field("held_events", &self.held_events.len())
             .finish()
     }
 }
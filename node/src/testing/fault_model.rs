@@ -0,0 +1,318 @@
+//! A model of network faults to inject into networks of test reactors.
+//!
+//! A [`FaultModel`] decides the fate of every message sent from one node to another: it is either
+//! dropped or delivered after some latency. Messages are dropped if
+//!
+//! * the sender or the recipient is paused,
+//! * the sender and the recipient are in different groups of an active partition, or
+//! * the link between the two randomly loses it, according to its drop probability.
+//!
+//! All random decisions are taken by an RNG seeded from the test's `TestRng`, so that failures can
+//! be reproduced using the seed printed by the `TestRng`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use tokio::time::Instant;
+
+use super::TestRng;
+
+/// A fault model shared between all the nodes of a network.
+pub type SharedFaultModel<I> = Arc<Mutex<FaultModel<I>>>;
+
+/// The distribution of the latency of a link.
+#[derive(Clone, Debug)]
+pub enum Latency {
+    /// Every message is delayed by the same duration.
+    Fixed(Duration),
+    /// Every message is delayed by a duration chosen uniformly at random from `min..=max`.
+    Uniform {
+        /// The shortest delay.
+        min: Duration,
+        /// The longest delay.
+        max: Duration,
+    },
+}
+
+impl Latency {
+    fn sample<R: Rng>(&self, rng: &mut R) -> Duration {
+        match self {
+            Latency::Fixed(latency) => *latency,
+            Latency::Uniform { min, max } => rng.gen_range(*min..=*max),
+        }
+    }
+}
+
+impl Default for Latency {
+    fn default() -> Self {
+        Latency::Fixed(Duration::from_secs(0))
+    }
+}
+
+/// The faults of a directed link between two nodes.
+#[derive(Clone, Debug, Default)]
+pub struct LinkFaults {
+    /// The distribution of the latency of every message sent over the link.
+    pub latency: Latency,
+    /// The probability of any message sent over the link being lost, between 0 and 1.
+    pub drop_probability: f64,
+}
+
+/// The fate of a single message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    /// The message is lost.
+    Drop,
+    /// The message is delivered once the given duration has elapsed.
+    After(Duration),
+}
+
+/// A partition of the network into groups of nodes which can't reach each other.
+#[derive(Debug)]
+struct Partition<I> {
+    /// The groups of nodes, nodes not in any group are unaffected by the partition.
+    groups: Vec<HashSet<I>>,
+    /// When the partition takes effect.
+    start: Instant,
+    /// When the partition heals, if ever.
+    end: Option<Instant>,
+}
+
+impl<I: Eq + Hash> Partition<I> {
+    fn separates(&self, now: Instant, node1: &I, node2: &I) -> bool {
+        if now < self.start || self.end.map_or(false, |end| now >= end) {
+            return false;
+        }
+        let group_of = |node: &I| self.groups.iter().position(|group| group.contains(node));
+        match (group_of(node1), group_of(node2)) {
+            (Some(group1), Some(group2)) => group1 != group2,
+            _ => false,
+        }
+    }
+}
+
+/// A configurable model of network faults.
+///
+/// Without any configuration, every message is delivered immediately.
+#[derive(Debug)]
+pub struct FaultModel<I> {
+    /// The RNG used for all random decisions.
+    rng: Pcg64Mcg,
+    /// The faults of links without faults of their own.
+    default_link: LinkFaults,
+    /// The faults of individual links, keyed by sender and recipient.
+    links: HashMap<(I, I), LinkFaults>,
+    /// The current and scheduled partitions.
+    partitions: Vec<Partition<I>>,
+    /// The nodes which have been paused and not resumed yet.
+    paused: HashSet<I>,
+}
+
+impl<I> FaultModel<I>
+where
+    I: Clone + Eq + Hash + Debug,
+{
+    /// Creates a fault model without any faults, seeded from `rng`.
+    pub fn new(rng: &mut TestRng) -> Self {
+        FaultModel {
+            rng: Pcg64Mcg::from_seed(rng.gen()),
+            default_link: LinkFaults::default(),
+            links: HashMap::new(),
+            partitions: Vec::new(),
+            paused: HashSet::new(),
+        }
+    }
+
+    /// Wraps the fault model so it can be shared between the nodes of a network.
+    pub fn into_shared(self) -> SharedFaultModel<I> {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Sets the faults of all links which have no faults of their own.
+    pub fn set_default_link_faults(&mut self, link_faults: LinkFaults) {
+        self.default_link = link_faults;
+    }
+
+    /// Sets the faults of the link from `sender` to `recipient`.
+    pub fn set_link_faults(&mut self, sender: I, recipient: I, link_faults: LinkFaults) {
+        self.links.insert((sender, recipient), link_faults);
+    }
+
+    /// Partitions the network into `groups` from now on, until healed.
+    pub fn partition(&mut self, groups: Vec<HashSet<I>>) {
+        self.schedule_partition(groups, Duration::from_secs(0), None);
+    }
+
+    /// Partitions the network into `groups` once `start_in` has elapsed, healing the partition
+    /// after it lasted for `duration`, or never if `duration` is `None`.
+    pub fn schedule_partition(
+        &mut self,
+        groups: Vec<HashSet<I>>,
+        start_in: Duration,
+        duration: Option<Duration>,
+    ) {
+        let start = Instant::now() + start_in;
+        self.partitions.push(Partition {
+            groups,
+            start,
+            end: duration.map(|duration| start + duration),
+        });
+    }
+
+    /// Heals all current and scheduled partitions.
+    pub fn heal(&mut self) {
+        self.partitions.clear();
+    }
+
+    /// Returns `true` if `node1` and `node2` are currently separated by a partition.
+    pub fn is_partitioned(&self, node1: &I, node2: &I) -> bool {
+        let now = Instant::now();
+        self.partitions
+            .iter()
+            .any(|partition| partition.separates(now, node1, node2))
+    }
+
+    /// Pauses `node`: it loses every message sent to it and processes nothing until resumed.
+    ///
+    /// Unlike a crash (see `Network::crash_node`), the node keeps its in-memory state and resumes
+    /// where it left off.
+    pub fn pause(&mut self, node: I) {
+        self.paused.insert(node);
+    }
+
+    /// Resumes `node` after it was paused.
+    pub fn resume(&mut self, node: &I) {
+        self.paused.remove(node);
+    }
+
+    /// Returns `true` if `node` has been paused and not resumed yet.
+    pub fn is_paused(&self, node: &I) -> bool {
+        self.paused.contains(node)
+    }
+
+    /// Decides the fate of a message sent from `sender` to `recipient`.
+    pub fn delivery(&mut self, sender: &I, recipient: &I) -> Delivery {
+        if self.is_paused(sender)
+            || self.is_paused(recipient)
+            || self.is_partitioned(sender, recipient)
+        {
+            return Delivery::Drop;
+        }
+
+        let link_faults = self
+            .links
+            .get(&(sender.clone(), recipient.clone()))
+            .unwrap_or(&self.default_link);
+        if link_faults.drop_probability > 0.0 && self.rng.gen_bool(link_faults.drop_probability) {
+            return Delivery::Drop;
+        }
+        Delivery::After(link_faults.latency.sample(&mut self.rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time;
+
+    use super::*;
+
+    const NO_DELAY: Delivery = Delivery::After(Duration::from_secs(0));
+
+    #[test]
+    fn should_deliver_immediately_without_faults() {
+        let mut rng = crate::new_rng();
+        let mut fault_model = FaultModel::new(&mut rng);
+
+        assert_eq!(fault_model.delivery(&1, &2), NO_DELAY);
+        assert_eq!(fault_model.delivery(&2, &1), NO_DELAY);
+    }
+
+    #[test]
+    fn should_apply_link_faults() {
+        let mut rng = crate::new_rng();
+        let mut fault_model = FaultModel::new(&mut rng);
+        fault_model.set_default_link_faults(LinkFaults {
+            latency: Latency::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(20),
+            },
+            drop_probability: 0.0,
+        });
+        fault_model.set_link_faults(
+            1,
+            2,
+            LinkFaults {
+                latency: Latency::default(),
+                drop_probability: 1.0,
+            },
+        );
+
+        assert_eq!(fault_model.delivery(&1, &2), Delivery::Drop);
+        for _ in 0..100 {
+            match fault_model.delivery(&2, &1) {
+                Delivery::After(latency) => assert!(
+                    latency >= Duration::from_millis(10) && latency <= Duration::from_millis(20)
+                ),
+                Delivery::Drop => panic!("should not drop message"),
+            }
+        }
+    }
+
+    #[test]
+    fn should_drop_messages_across_partition() {
+        let mut rng = crate::new_rng();
+        let mut fault_model = FaultModel::new(&mut rng);
+        fault_model.partition(vec![
+            vec![1, 2].into_iter().collect(),
+            vec![3].into_iter().collect(),
+        ]);
+
+        assert_eq!(fault_model.delivery(&1, &2), NO_DELAY);
+        assert_eq!(fault_model.delivery(&1, &3), Delivery::Drop);
+        assert_eq!(fault_model.delivery(&3, &2), Delivery::Drop);
+        // Nodes outside of any group are unaffected.
+        assert_eq!(fault_model.delivery(&4, &3), NO_DELAY);
+
+        fault_model.heal();
+        assert_eq!(fault_model.delivery(&1, &3), NO_DELAY);
+    }
+
+    #[tokio::test]
+    async fn should_heal_scheduled_partition() {
+        time::pause();
+        let mut rng = crate::new_rng();
+        let mut fault_model = FaultModel::new(&mut rng);
+        fault_model.schedule_partition(
+            vec![vec![1].into_iter().collect(), vec![2].into_iter().collect()],
+            Duration::from_secs(1),
+            Some(Duration::from_secs(2)),
+        );
+
+        assert!(!fault_model.is_partitioned(&1, &2));
+        time::advance(Duration::from_secs(1)).await;
+        assert!(fault_model.is_partitioned(&1, &2));
+        time::advance(Duration::from_secs(2)).await;
+        assert!(!fault_model.is_partitioned(&1, &2));
+    }
+
+    #[test]
+    fn should_drop_messages_of_paused_node() {
+        let mut rng = crate::new_rng();
+        let mut fault_model = FaultModel::new(&mut rng);
+        fault_model.pause(1);
+
+        assert!(fault_model.is_paused(&1));
+        assert_eq!(fault_model.delivery(&1, &2), Delivery::Drop);
+        assert_eq!(fault_model.delivery(&2, &1), Delivery::Drop);
+
+        fault_model.resume(&1);
+        assert_eq!(fault_model.delivery(&2, &1), NO_DELAY);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 
 use log::info;
 use num::Zero;
@@ -10,7 +10,7 @@ use casper_execution_engine::shared::motes::Motes;
 use casper_types::{system::auction::DelegationRate, EraId, PublicKey, SecretKey, U512};
 
 use crate::{
-    components::{gossiper, small_network, storage, storage::Storage},
+    components::{gossiper, small_network, state_sync, storage, storage::Storage},
     crypto::AsymmetricKeyExt,
     reactor::participating,
     testing::{
@@ -42,12 +42,22 @@ impl PartialEq for SecretKeyWithStake {
 
 impl Eq for SecretKeyWithStake {}
 
+/// The settings a node was started with, which it is restarted with after crashing.
+struct NodeSettings {
+    first_node: bool,
+    secret_key: Arc<SecretKey>,
+    sync_global_state: bool,
+    storage_config: storage::Config,
+    unit_hashes_folder: PathBuf,
+}
+
 struct TestChain {
     // Keys that validator instances will use, can include duplicates
     storages: Vec<TempDir>,
     chainspec: Arc<Chainspec>,
     first_node_port: u16,
     network: Network<MultiStageTestReactor>,
+    node_settings: HashMap<NodeId, NodeSettings>,
 }
 
 impl TestChain {
@@ -136,16 +146,23 @@ impl TestChain {
             storages: Vec::new(),
             first_node_port,
             network,
+            node_settings: HashMap::new(),
         };
 
         // Add the nodes to the chain
         test_chain
-            .add_node(true, first_node_secret_key_with_stake.secret_key, None, rng)
+            .add_node(
+                true,
+                first_node_secret_key_with_stake.secret_key,
+                None,
+                false,
+                rng,
+            )
             .await;
 
         for secret_key_with_stake in other_secret_keys_with_stakes {
             test_chain
-                .add_node(false, secret_key_with_stake.secret_key, None, rng)
+                .add_node(false, secret_key_with_stake.secret_key, None, false, rng)
                 .await;
         }
 
@@ -158,10 +175,66 @@ impl TestChain {
         first_node: bool,
         secret_key: Arc<SecretKey>,
         trusted_hash: Option<BlockHash>,
+        sync_global_state: bool,
+        rng: &mut NodeRng,
+    ) -> NodeId {
+        // Set up storage in a temporary directory.
+        let (storage_config, temp_dir) = storage::Config::default_for_tests();
+        let unit_hashes_folder = temp_dir.path().to_path_buf();
+        self.storages.push(temp_dir);
+
+        let node_settings = NodeSettings {
+            first_node,
+            secret_key,
+            sync_global_state,
+            storage_config,
+            unit_hashes_folder,
+        };
+        self.start_node(node_settings, trusted_hash, rng).await
+    }
+
+    /// Crashes the node `node_id` and starts it again from its storage, returning its new ID.
+    ///
+    /// The node is restarted with the latest switch block in its storage as the trusted hash.
+    async fn restart_node(&mut self, node_id: &NodeId, rng: &mut NodeRng) -> NodeId {
+        let trusted_hash = {
+            let reactor = self.network.nodes()[node_id].reactor().inner();
+            let current_era = reactor
+                .consensus()
+                .expect("node should be participating")
+                .current_era();
+            let switch_block = reactor
+                .storage()
+                .expect("node should have storage")
+                .transactional_get_switch_block_by_era_id(current_era.value() - 1)
+                .expect("node should have the latest switch block");
+            *switch_block.hash()
+        };
+        let node_settings = self
+            .node_settings
+            .remove(node_id)
+            .expect("should have node settings");
+
+        info!("Crashing node {}", node_id);
+        self.network.crash_node(node_id).await;
+
+        info!(
+            "Restarting node {} with trusted hash {}",
+            node_id, trusted_hash
+        );
+        self.start_node(node_settings, Some(trusted_hash), rng)
+            .await
+    }
+
+    /// Starts a node with the given settings, returning its ID.
+    async fn start_node(
+        &mut self,
+        node_settings: NodeSettings,
+        trusted_hash: Option<BlockHash>,
         rng: &mut NodeRng,
     ) -> NodeId {
         // Set the network configuration.
-        let network = if first_node {
+        let network = if node_settings.first_node {
             small_network::Config::default_local_net_first_node(self.first_node_port)
         } else {
             small_network::Config::default_local_net(self.first_node_port)
@@ -174,16 +247,19 @@ impl TestChain {
         };
 
         // ...and the secret key for our validator.
-        participating_config.consensus.secret_key_path = External::from_value(secret_key);
+        participating_config.consensus.secret_key_path =
+            External::from_value(Arc::clone(&node_settings.secret_key));
 
         // Set a trust hash if one has been provided.
         participating_config.node.trusted_hash = trusted_hash;
 
-        // Additionally set up storage in a temporary directory.
-        let (storage_config, temp_dir) = storage::Config::default_for_tests();
-        participating_config.consensus.highway.unit_hashes_folder = temp_dir.path().to_path_buf();
-        self.storages.push(temp_dir);
-        participating_config.storage = storage_config;
+        // Download the global state of the trusted block instead of executing the blocks before it.
+        participating_config.state_sync =
+            state_sync::Config::default().with_enable(node_settings.sync_global_state);
+
+        participating_config.consensus.highway.unit_hashes_folder =
+            node_settings.unit_hashes_folder.clone();
+        participating_config.storage = node_settings.storage_config.clone();
 
         // Bundle our config with a chainspec for creating a multi-stage reactor
         let config = InitializerReactorConfigWithChainspec {
@@ -192,11 +268,14 @@ impl TestChain {
         };
 
         // Add the node (a multi-stage reactor) with the specified config to the network
-        self.network
+        let node_id = self
+            .network
             .add_node_with_config(config, rng)
             .await
             .expect("could not add node to reactor")
-            .0
+            .0;
+        self.node_settings.insert(node_id, node_settings);
+        node_id
     }
 }
 
@@ -342,6 +421,7 @@ async fn test_joiner() {
             false,
             joiner_node_secret_key,
             Some(first_switch_block_hash),
+            false,
             &mut rng,
         )
         .await;
@@ -389,6 +469,7 @@ async fn test_joiner_network() {
             false,
             joiner_node_secret_key,
             Some(first_switch_block_hash),
+            false,
             &mut rng,
         )
         .await;
@@ -402,3 +483,104 @@ async fn test_joiner_network() {
         .settle_on(&mut rng, is_in_era(era_num), Duration::from_secs(600))
         .await;
 }
+
+/// Test a node joining a network by downloading the global state of the trusted block
+#[tokio::test]
+async fn test_joiner_with_state_sync() {
+    testing::init_logging();
+
+    const INITIAL_NETWORK_SIZE: usize = 3;
+
+    let mut rng = crate::new_rng();
+
+    let mut chain = TestChain::new(INITIAL_NETWORK_SIZE, &mut rng).await;
+
+    // Get the first switch block hash
+    let first_switch_block_hash = get_switch_block_hash(1, &mut chain.network, &mut rng).await;
+
+    // Have a node join the network with that hash, synchronizing global state
+    info!(
+        "Joining with trusted hash {} and global state synchronization",
+        first_switch_block_hash
+    );
+    let joiner_node_secret_key = Arc::new(SecretKey::random(&mut rng));
+    let joiner_node_id = chain
+        .add_node(
+            false,
+            joiner_node_secret_key,
+            Some(first_switch_block_hash),
+            true,
+            &mut rng,
+        )
+        .await;
+
+    // The joined node only participates once it has all of the global state of the trusted block
+    // and has executed the blocks following it.
+    let era_num = 3;
+    info!("Waiting for Era {} to end", era_num);
+    chain
+        .network
+        .settle_on(&mut rng, is_in_era(era_num), Duration::from_secs(600))
+        .await;
+
+    let storage = chain.network.nodes()[&joiner_node_id]
+        .reactor()
+        .inner()
+        .storage()
+        .expect("joined node should have storage");
+    assert!(
+        storage
+            .transactional_get_switch_block_by_era_id(1)
+            .is_some(),
+        "joined node should have stored the trusted switch block"
+    );
+}
+
+/// Test a validator crashing and being restarted from its storage
+#[tokio::test]
+async fn test_validator_crash_and_restart() {
+    testing::init_logging();
+
+    const NETWORK_SIZE: usize = 3;
+
+    let mut rng = crate::new_rng();
+
+    let mut chain = TestChain::new(NETWORK_SIZE, &mut rng).await;
+
+    let era_num = 2;
+    info!("Waiting for Era {} to begin", era_num);
+    chain
+        .network
+        .settle_on(&mut rng, is_in_era(era_num), Duration::from_secs(600))
+        .await;
+
+    // Crash a node other than the first one, whose address the other nodes connect to.
+    let node_id = *chain
+        .node_settings
+        .iter()
+        .find(|(_, node_settings)| !node_settings.first_node)
+        .expect("should have a node other than the first one")
+        .0;
+    let restarted_node_id = chain.restart_node(&node_id, &mut rng).await;
+
+    // The restarted node starts out with everything it stored before crashing.
+    let storage = chain.network.nodes()[&restarted_node_id]
+        .reactor()
+        .inner()
+        .storage()
+        .expect("restarted node should have storage");
+    assert!(
+        storage
+            .transactional_get_switch_block_by_era_id(era_num - 1)
+            .is_some(),
+        "restarted node should have kept its switch blocks"
+    );
+
+    // The restarted node catches up with the others and participates again.
+    let era_num = 4;
+    info!("Waiting for Era {} to begin", era_num);
+    chain
+        .network
+        .settle_on(&mut rng, is_in_era(era_num), Duration::from_secs(600))
+        .await;
+}
@@ -14,7 +14,11 @@ use tokio::time;
 use tracing::{debug, error_span};
 use tracing_futures::Instrument;
 
-use super::ConditionCheckReactor;
+use super::{
+    condition_check_reactor::EventFate,
+    fault_model::{Delivery, SharedFaultModel},
+    ConditionCheckReactor,
+};
 use crate::{
     effect::{EffectBuilder, Effects},
     reactor::{Finalize, Reactor, Runner},
@@ -30,10 +34,21 @@ pub type Nodes<R> = HashMap<<R as NetworkedReactor>::NodeId, Runner<ConditionChe
 /// A reactor with networking functionality.
 pub trait NetworkedReactor: Sized {
     /// The node ID on the networking level.
-    type NodeId: Eq + Hash + Clone + Display + Debug;
+    type NodeId: Eq + Ord + Hash + Clone + Display + Debug + Send + 'static;
 
     /// Returns the node ID assigned to this specific reactor instance.
     fn node_id(&self) -> Self::NodeId;
+
+    /// Returns the sender of `event` if it is a message received from another node.
+    ///
+    /// Faults are only injected into events for which a sender is returned, see
+    /// `Network::set_fault_model`.
+    fn message_sender(_event: &<Self as Reactor>::Event) -> Option<Self::NodeId>
+    where
+        Self: Reactor,
+    {
+        None
+    }
 }
 
 /// Time interval for which to poll an observed testing network when no events have occurred.
@@ -48,6 +63,9 @@ const POLL_INTERVAL: Duration = Duration::from_millis(10);
 pub struct Network<R: Reactor + NetworkedReactor> {
     /// Current network.
     nodes: HashMap<<R as NetworkedReactor>::NodeId, Runner<ConditionCheckReactor<R>>>,
+
+    /// Faults injected into the messages received by the nodes, if any.
+    fault_model: Option<SharedFaultModel<<R as NetworkedReactor>::NodeId>>,
 }
 
 impl<R> Network<R>
@@ -92,6 +110,7 @@ where
     pub fn new() -> Self {
         Network {
             nodes: HashMap::new(),
+            fault_model: None,
         }
     }
 
@@ -105,9 +124,14 @@ where
         cfg: R::Config,
         rng: &mut NodeRng,
     ) -> Result<(R::NodeId, &mut Runner<ConditionCheckReactor<R>>), R::Error> {
-        let runner: Runner<ConditionCheckReactor<R>> = Runner::new(cfg, rng).await?;
+        let mut runner: Runner<ConditionCheckReactor<R>> = Runner::new(cfg, rng).await?;
 
         let node_id = runner.reactor().node_id();
+        if let Some(fault_model) = &self.fault_model {
+            runner
+                .reactor_mut()
+                .set_fault_injector(fault_injector::<R>(fault_model.clone(), node_id.clone()));
+        }
 
         let node_ref = match self.nodes.entry(node_id.clone()) {
             Entry::Occupied(_) => {
@@ -121,11 +145,42 @@ where
         Ok((node_id, node_ref))
     }
 
+    /// Injects the faults of `fault_model` into the messages received by every current and future
+    /// node of the network.
+    ///
+    /// Messages are identified via `NetworkedReactor::message_sender`. While a node is paused, it
+    /// loses all messages sent to it and holds back all other events until it is resumed. Delayed
+    /// and held back events are only dispatched once the node receives another event, so this is
+    /// best suited to reactors with periodic timers, such as the participating reactor.
+    pub fn set_fault_model(&mut self, fault_model: SharedFaultModel<R::NodeId>) {
+        for (node_id, runner) in self.nodes.iter_mut() {
+            runner
+                .reactor_mut()
+                .set_fault_injector(fault_injector::<R>(fault_model.clone(), node_id.clone()));
+        }
+        self.fault_model = Some(fault_model);
+    }
+
     /// Removes a node from the network.
     pub fn remove_node(&mut self, node_id: &R::NodeId) -> Option<Runner<ConditionCheckReactor<R>>> {
         self.nodes.remove(node_id)
     }
 
+    /// Crashes a node: removes it from the network and drops it, losing its in-memory state and all
+    /// the events it hasn't processed yet.
+    ///
+    /// Whatever the node persisted is retained, so it can be restarted by adding a node configured
+    /// to use the same storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node with the given ID.
+    pub async fn crash_node(&mut self, node_id: &R::NodeId) {
+        let runner = self.nodes.remove(node_id).expect("should find node");
+        drop(runner.drain_into_inner().await);
+        debug!(%node_id, "crashed node");
+    }
+
     /// Crank the specified runner once, returning the number of events processed.
     pub async fn crank(&mut self, node_id: &R::NodeId, rng: &mut TestRng) -> usize {
         let runner = self.nodes.get_mut(node_id).expect("should find node");
@@ -188,20 +243,16 @@ where
     }
 
     /// Crank all runners once, returning the number of events processed.
+    ///
+    /// The runners are cranked in the order of their node IDs, so that the values drawn from `rng`
+    /// are reproducible.
     pub async fn crank_all(&mut self, rng: &mut TestRng) -> usize {
+        let mut node_ids: Vec<_> = self.nodes.keys().cloned().collect();
+        node_ids.sort();
+
         let mut event_count = 0;
-        for node in self.nodes.values_mut() {
-            let node_id = node.reactor().node_id();
-            event_count += if node
-                .try_crank(rng)
-                .instrument(error_span!("crank", node_id = %node_id))
-                .await
-                .is_some()
-            {
-                1
-            } else {
-                0
-            }
+        for node_id in &node_ids {
+            event_count += self.crank(node_id, rng).await;
         }
 
         event_count
@@ -313,6 +364,31 @@ where
     }
 }
 
+/// Creates a fault injector for the node `node_id`, deciding the fate of its events according to
+/// `fault_model`.
+fn fault_injector<R>(
+    fault_model: SharedFaultModel<R::NodeId>,
+    node_id: R::NodeId,
+) -> Box<dyn Fn(&R::Event) -> EventFate + Send>
+where
+    R: Reactor + NetworkedReactor,
+{
+    Box::new(move |event| {
+        let mut fault_model = fault_model.lock().expect("fault model lock poisoned");
+        match R::message_sender(event) {
+            Some(sender) => match fault_model.delivery(&sender, &node_id) {
+                Delivery::Drop => EventFate::Drop,
+                Delivery::After(latency) if latency == Duration::from_secs(0) => {
+                    EventFate::Dispatch
+                }
+                Delivery::After(latency) => EventFate::Delay(latency),
+            },
+            None if fault_model.is_paused(&node_id) => EventFate::Hold,
+            None => EventFate::Dispatch,
+        }
+    })
+}
+
 impl<R> Finalize for Network<R>
 where
     R: Finalize + NetworkedReactor + Reactor + Send + 'static,
@@ -1,5 +1,6 @@
 //! Common types used across multiple components.
 
+mod account_history;
 pub(crate) mod appendable_block;
 mod block;
 pub mod chainspec;
@@ -10,6 +11,7 @@ pub mod json_compatibility;
 mod node_config;
 mod node_id;
 mod peers_map;
+mod pending_deploy;
 mod shared_object;
 mod status_feed;
 mod timestamp;
@@ -18,6 +20,8 @@ use rand::{CryptoRng, RngCore};
 #[cfg(not(test))]
 use rand_chacha::ChaCha20Rng;
 
+pub(crate) use account_history::transfer_accounts;
+pub use account_history::{AccountHistoryEntry, AccountHistoryPage};
 pub use block::{
     json_compatibility::JsonBlock, Block, BlockBody, BlockHash, BlockHeader, BlockSignatures,
     BlockValidationError, FinalitySignature,
@@ -31,10 +35,11 @@ pub use deploy::{
     DeployValidationFailure, Error as DeployError, ExcessiveSizeError as ExcessiveSizeDeployError,
 };
 pub use exit_code::ExitCode;
-pub use item::{Item, Tag};
+pub use item::{Item, Tag, TrieResponse};
 pub use node_config::NodeConfig;
 pub(crate) use node_id::NodeId;
 pub use peers_map::PeersMap;
+pub use pending_deploy::{PendingDeploy, PendingDeployStatus};
 pub(crate) use shared_object::SharedObject;
 pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
 pub use timestamp::{TimeDiff, Timestamp};
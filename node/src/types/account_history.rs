@@ -0,0 +1,76 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{account::AccountHash, Transfer};
+
+use crate::types::{BlockHash, DeployHash};
+
+/// A single deploy which touched an account, along with the transfers of that deploy which moved
+/// funds from or to the account.
+#[derive(Clone, DataSize, Serialize, Deserialize, Debug, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountHistoryEntry {
+    /// The hash of the block in which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+    /// The transfers of the deploy whose sender or recipient is the account.
+    ///
+    /// Transfers made directly to a purse rather than to an account don't identify the owner of
+    /// the target purse, so they're only included in the sender's history.
+    #[data_size(skip)]
+    pub transfers: Vec<Transfer>,
+}
+
+impl AccountHistoryEntry {
+    /// Creates an entry without any transfers.
+    pub(crate) fn new(block_hash: BlockHash, deploy_hash: DeployHash) -> Self {
+        AccountHistoryEntry {
+            block_hash,
+            deploy_hash,
+            transfers: vec![],
+        }
+    }
+
+    /// Adds the given transfers, skipping those already recorded.
+    ///
+    /// Returns `true` if any transfer was added.
+    pub(crate) fn add_transfers(&mut self, transfers: impl IntoIterator<Item = Transfer>) -> bool {
+        let mut modified = false;
+        for transfer in transfers {
+            if !self.transfers.contains(&transfer) {
+                self.transfers.push(transfer);
+                modified = true;
+            }
+        }
+        modified
+    }
+}
+
+/// A page of an account's history.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountHistoryPage {
+    /// The total number of entries in the account's history.
+    pub total: u64,
+    /// The entries in the requested range, oldest first.
+    pub entries: Vec<AccountHistoryEntry>,
+}
+
+/// Returns the accounts involved in the given transfer.
+///
+/// The recipient is only known if the transfer was made to an account; the owner of a target purse
+/// isn't resolved, as that would require a reverse lookup of purses in global state.
+pub(crate) fn transfer_accounts(transfer: &Transfer) -> impl Iterator<Item = AccountHash> {
+    let mut accounts = vec![transfer.from];
+    if let Some(to) = transfer.to {
+        if to != transfer.from {
+            accounts.push(to);
+        }
+    }
+    accounts.into_iter()
+}
@@ -1532,6 +1532,14 @@ pub(crate) mod json_compatibility {
         pub fn transfer_hashes(&self) -> &Vec<DeployHash> {
             &self.body.transfer_hashes
         }
+
+        /// Returns the finality signatures of the `Block`, keyed by the signing validators.
+        pub fn proofs(&self) -> BTreeMap<PublicKey, Signature> {
+            self.proofs
+                .iter()
+                .map(|proof| (proof.public_key.clone(), proof.signature))
+                .collect()
+        }
     }
 
     impl DocExample for JsonBlock {
@@ -1645,7 +1653,9 @@ impl Display for FinalitySignature {
 
 #[cfg(test)]
 mod tests {
-    use casper_types::bytesrepr;
+    use num_rational::Ratio;
+
+    use casper_types::{bytesrepr, light_client};
 
     use super::*;
     use crate::testing::TestRng;
@@ -1779,4 +1789,47 @@ mod tests {
         // Test should fail b/c `signature` is over `era_id=1` and here we're using `era_id=2`.
         assert!(fs_manufactured.verify().is_err());
     }
+    #[test]
+    fn light_client_header_and_finality_signature() {
+        let mut rng = TestRng::new();
+        let block = Block::random_with_specifics(
+            &mut rng,
+            EraId::from(3),
+            31,
+            ProtocolVersion::V1_0_0,
+            true,
+        );
+        let light_header = light_client::LightBlockHeader::from_serialized_header(
+            &block.header.to_bytes().unwrap(),
+        )
+        .expect("should parse header");
+        assert_eq!(light_header.block_hash(), &block.hash().inner().to_array());
+        assert_eq!(
+            light_header.state_root_hash(),
+            &block.header.state_root_hash().to_array()
+        );
+        assert_eq!(light_header.era_id(), block.header.era_id());
+        assert_eq!(light_header.height(), block.header.height());
+        assert_eq!(
+            light_header.next_era_validator_weights(),
+            block.header.next_era_validator_weights()
+        );
+
+        let (secret_key, public_key) = generate_ed25519_keypair();
+        let fs = FinalitySignature::new(
+            *block.hash(),
+            block.header.era_id(),
+            &Rc::new(secret_key),
+            public_key.clone(),
+        );
+        let signatures = iter::once((public_key.clone(), fs.signature)).collect();
+        let validator_weights = iter::once((public_key, U512::one())).collect();
+        light_client::verify_finality_signatures(
+            &light_header,
+            &signatures,
+            &validator_weights,
+            Ratio::new(1, 3),
+        )
+        .expect("finality signature should be accepted by light client");
+    }
 }
@@ -21,8 +21,8 @@ use serde::Serialize;
 use tracing::{error, warn};
 
 use casper_execution_engine::{
-    core::engine_state::genesis::ExecConfig,
-    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+    core::engine_state::{genesis::ExecConfig, upgrade::UpgradeConfig},
+    shared::{stored_value::StoredValue, system_config::SystemConfig, wasm_config::WasmConfig},
 };
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
@@ -70,6 +70,23 @@ pub struct Chainspec {
 impl Chainspec {
     /// Returns `false` and logs errors if the values set in the config don't make sense.
     pub(crate) fn is_valid(&self) -> bool {
+        for warning in self.validation_warnings() {
+            warn!("{}", warning);
+        }
+        self.protocol_config.is_valid() && self.highway_config.is_valid()
+    }
+
+    /// Returns a description of every value set in the config which doesn't make sense.
+    pub(crate) fn validation_errors(&self) -> Vec<String> {
+        let mut errors = self.protocol_config.validation_errors();
+        errors.extend(self.highway_config.validation_errors());
+        errors
+    }
+
+    /// Returns a description of every value set in the config which is allowed, but likely to be a
+    /// mistake.
+    pub(crate) fn validation_warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
         let min_era_ms = 1u64 << self.highway_config.minimum_round_exponent;
         // If the era duration is set to zero, we will treat it as explicitly stating that eras
         // should be defined by height only.
@@ -77,10 +94,65 @@ impl Chainspec {
             && self.core_config.era_duration.millis()
                 < self.core_config.minimum_era_height * min_era_ms
         {
-            warn!("era duration is less than minimum era height * round length!");
+            warnings
+                .push("era duration is less than minimum era height * round length!".to_string());
         }
+        warnings
+    }
 
-        self.protocol_config.is_valid() && self.highway_config.is_valid()
+    /// Returns the config for upgrading the global state under `pre_state_hash`, written by a node
+    /// running `previous_version`, to this chainspec.
+    ///
+    /// Panics if a value in the global state update is not a valid `StoredValue`.
+    pub(crate) fn upgrade_config(
+        &self,
+        pre_state_hash: Digest,
+        previous_version: ProtocolVersion,
+    ) -> UpgradeConfig {
+        let global_state_update = self
+            .protocol_config
+            .global_state_update
+            .as_ref()
+            .map(|state_update| {
+                state_update
+                    .0
+                    .iter()
+                    .map(|(key, stored_value_bytes)| {
+                        let stored_value = StoredValue::from_bytes(stored_value_bytes)
+                            .unwrap_or_else(|error| {
+                                panic!(
+                                "failed to parse global state value as StoredValue for upgrade: {}",
+                                error
+                            )
+                            })
+                            .0;
+                        (*key, stored_value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        UpgradeConfig::new(
+            pre_state_hash.into(),
+            previous_version,
+            self.protocol_config.version,
+            Some(self.wasm_config),
+            Some(self.system_costs_config),
+            Some(self.protocol_config.activation_point.era_id()),
+            Some(self.core_config.validator_slots),
+            Some(self.core_config.auction_delay),
+            Some(self.core_config.locked_funds_period.millis()),
+            Some(self.core_config.round_seigniorage_rate),
+            Some(self.core_config.unbonding_delay),
+            Some(self.core_config.minimum_delegation_amount),
+            Some(self.core_config.max_delegators_per_validator),
+            global_state_update,
+        )
+    }
+
+    /// Parses the chainspec file at the given path, along with the accounts and global state
+    /// update files in the same directory.
+    pub(crate) fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        parse_toml::parse_toml(path)
     }
 
     /// Serializes `self` and hashes the resulting bytes.
@@ -177,7 +249,7 @@ impl Loadable for Chainspec {
     type Error = Error;
 
     fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
-        parse_toml::parse_toml(path.as_ref().join(CHAINSPEC_NAME))
+        Chainspec::from_file(path.as_ref().join(CHAINSPEC_NAME))
     }
 }
 
@@ -197,6 +269,8 @@ impl From<&Chainspec> for ExecConfig {
                 .activation_point
                 .genesis_timestamp()
                 .map_or(0, |timestamp| timestamp.millis()),
+            chainspec.core_config.minimum_delegation_amount,
+            chainspec.core_config.max_delegators_per_validator,
         )
     }
 }
@@ -268,6 +342,10 @@ mod tests {
             remove_contract_user_group_urefs: HostFunction::new(131, [0, 1, 2, 3, 4, 5]),
             print: HostFunction::new(123, [0, 1]),
             blake2b: HostFunction::new(133, [0, 1, 2, 3]),
+            emit_event: HostFunction::new(142, [0, 1, 2, 3]),
+            verify_signature: HostFunction::new(143, [0, 1, 2, 3, 4, 5]),
+            dictionary_remove: HostFunction::new(144, [0, 1, 2, 3]),
+            dictionary_keys: HostFunction::new(145, [0, 1, 2, 3, 4]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         WasmConfig::new(
@@ -28,6 +28,12 @@ pub struct CoreConfig {
     /// Round seigniorage rate represented as a fractional number.
     #[data_size(skip)]
     pub(crate) round_seigniorage_rate: Ratio<u64>,
+    /// Minimum number of motes required to become a new delegator of a validator.
+    #[serde(default)]
+    pub(crate) minimum_delegation_amount: u64,
+    /// Maximum number of delegators per validator, where zero means no limit.
+    #[serde(default)]
+    pub(crate) max_delegators_per_validator: u32,
 }
 
 #[cfg(test)]
@@ -44,6 +50,8 @@ impl CoreConfig {
             rng.gen_range(1..1_000_000_000),
             rng.gen_range(1..1_000_000_000),
         );
+        let minimum_delegation_amount = rng.gen::<u32>() as u64;
+        let max_delegators_per_validator = rng.gen();
 
         CoreConfig {
             era_duration,
@@ -53,6 +61,8 @@ impl CoreConfig {
             locked_funds_period,
             unbonding_delay,
             round_seigniorage_rate,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
         }
     }
 }
@@ -67,6 +77,8 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.locked_funds_period.to_bytes()?);
         buffer.extend(self.unbonding_delay.to_bytes()?);
         buffer.extend(self.round_seigniorage_rate.to_bytes()?);
+        buffer.extend(self.minimum_delegation_amount.to_bytes()?);
+        buffer.extend(self.max_delegators_per_validator.to_bytes()?);
         Ok(buffer)
     }
 
@@ -78,6 +90,8 @@ impl ToBytes for CoreConfig {
             + self.locked_funds_period.serialized_length()
             + self.unbonding_delay.serialized_length()
             + self.round_seigniorage_rate.serialized_length()
+            + self.minimum_delegation_amount.serialized_length()
+            + self.max_delegators_per_validator.serialized_length()
     }
 }
 
@@ -90,6 +104,8 @@ impl FromBytes for CoreConfig {
         let (locked_funds_period, remainder) = TimeDiff::from_bytes(remainder)?;
         let (unbonding_delay, remainder) = u64::from_bytes(remainder)?;
         let (round_seigniorage_rate, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (minimum_delegation_amount, remainder) = u64::from_bytes(remainder)?;
+        let (max_delegators_per_validator, remainder) = u32::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -98,6 +114,8 @@ impl FromBytes for CoreConfig {
             locked_funds_period,
             unbonding_delay,
             round_seigniorage_rate,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
         };
         Ok((config, remainder))
     }
@@ -28,34 +28,40 @@ pub(crate) struct HighwayConfig {
 impl HighwayConfig {
     /// Checks whether the values set in the config make sense and returns `false` if they don't.
     pub(super) fn is_valid(&self) -> bool {
+        let errors = self.validation_errors();
+        for error in &errors {
+            error!("{}", error);
+        }
+        errors.is_empty()
+    }
+
+    /// Returns a description of every value set in the config which doesn't make sense.
+    pub(super) fn validation_errors(&self) -> Vec<String> {
+        let mut errors = vec![];
         if self.minimum_round_exponent > self.maximum_round_exponent {
-            error!(
-                min = %self.minimum_round_exponent,
-                max = %self.maximum_round_exponent,
-                "minimum round exponent is greater than the maximum round exponent",
-            );
-            return false;
+            errors.push(format!(
+                "minimum round exponent ({}) is greater than the maximum round exponent ({})",
+                self.minimum_round_exponent, self.maximum_round_exponent
+            ));
         }
 
         if self.finality_threshold_fraction <= Ratio::new(0, 1)
             || self.finality_threshold_fraction >= Ratio::new(1, 1)
         {
-            error!(
-                ftf = %self.finality_threshold_fraction,
-                "finality threshold fraction is not in the range (0, 1)",
-            );
-            return false;
+            errors.push(format!(
+                "finality threshold fraction ({}) is not in the range (0, 1)",
+                self.finality_threshold_fraction
+            ));
         }
 
         if self.reduced_reward_multiplier > Ratio::new(1, 1) {
-            error!(
-                rrm = %self.reduced_reward_multiplier,
-                "reduced reward multiplier is not in the range [0, 1]",
-            );
-            return false;
+            errors.push(format!(
+                "reduced reward multiplier ({}) is not in the range [0, 1]",
+                self.reduced_reward_multiplier
+            ));
         }
 
-        true
+        errors
     }
 
     /// Returns the length of the shortest allowed round.
@@ -152,6 +158,18 @@ mod tests {
         assert!(!highway_config.is_valid());
     }
 
+    #[test]
+    fn should_report_every_invalid_value() {
+        let mut rng = crate::new_rng();
+        let mut highway_config = HighwayConfig::random(&mut rng);
+        assert!(highway_config.validation_errors().is_empty());
+
+        highway_config.minimum_round_exponent = highway_config.maximum_round_exponent + 1;
+        highway_config.finality_threshold_fraction = Ratio::new(0, 1);
+        highway_config.reduced_reward_multiplier = Ratio::new(2, 1);
+        assert_eq!(highway_config.validation_errors().len(), 3);
+    }
+
     #[test]
     fn should_validate_for_finality_threshold() {
         let mut rng = crate::new_rng();
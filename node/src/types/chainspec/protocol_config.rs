@@ -9,6 +9,7 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
+use casper_execution_engine::shared::stored_value::StoredValue;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     EraId, ProtocolVersion,
@@ -37,49 +38,61 @@ pub struct ProtocolConfig {
 impl ProtocolConfig {
     /// Checks whether the values set in the config make sense and returns `false` if they don't.
     pub(super) fn is_valid(&self) -> bool {
-        // If this is not an emergency restart config, assert the `last_emergency_restart` is `None`
-        // or less than `activation_point`.
-        if self.global_state_update.is_none() {
-            if let Some(last_emergency_restart) = self.last_emergency_restart {
-                let activation_point = self.activation_point.era_id();
-                if last_emergency_restart >= activation_point {
-                    error!(
-                        %activation_point,
-                        %last_emergency_restart,
-                        "[protocol.last_emergency_restart] must be lower than \
-                        [protocol.activation_point] in the chainspec."
-                    );
-                    return false;
-                };
+        let errors = self.validation_errors();
+        for error in &errors {
+            error!("{}", error);
+        }
+        errors.is_empty()
+    }
+
+    /// Returns a description of every value set in the config which doesn't make sense.
+    pub(super) fn validation_errors(&self) -> Vec<String> {
+        let mut errors = vec![];
+        let activation_point = self.activation_point.era_id();
+        match (&self.global_state_update, self.last_emergency_restart) {
+            // If this is not an emergency restart config, the `last_emergency_restart` must be
+            // `None` or less than `activation_point`.
+            (None, Some(last_emergency_restart)) if last_emergency_restart >= activation_point => {
+                errors.push(format!(
+                    "[protocol.last_emergency_restart] ({}) must be lower than \
+                    [protocol.activation_point] ({}) in the chainspec.",
+                    last_emergency_restart, activation_point
+                ));
             }
-            return true;
+            (None, _) => (),
+            // If this IS an emergency restart config, the `last_emergency_restart` must be `Some`
+            // and equal to `activation_point`.
+            (Some(_), None) => errors.push(
+                "[protocol.last_emergency_restart] must exist in the chainspec since a global \
+                state update was provided, implying this upgrade is an emergency restart."
+                    .to_string(),
+            ),
+            (Some(_), Some(last_emergency_restart))
+                if last_emergency_restart != activation_point =>
+            {
+                errors.push(format!(
+                    "[protocol.last_emergency_restart] ({}) must equal [protocol.activation_point] \
+                    ({}) in the chainspec since a global state update was provided, implying this \
+                    upgrade is an emergency restart.",
+                    last_emergency_restart, activation_point
+                ))
+            }
+            (Some(_), Some(_)) => (),
         }
 
-        // If this IS an emergency restart config, assert the `last_emergency_restart` is `Some` and
-        // equal to `activation_point`.
-        let last_emergency_restart = match self.last_emergency_restart {
-            Some(era_id) => era_id,
-            None => {
-                error!(
-                    "[protocol.last_emergency_restart] must exist in the chainspec since a global \
-                    state update was provided, implying this upgrade is an emergency restart."
-                );
-                return false;
+        // Every value in the global state update must be applicable during the upgrade.
+        if let Some(global_state_update) = &self.global_state_update {
+            for (key, value_bytes) in &global_state_update.0 {
+                if let Err(error) = StoredValue::from_bytes(value_bytes) {
+                    errors.push(format!(
+                        "global state update value for {} is not a valid stored value: {}",
+                        key, error
+                    ));
+                }
             }
-        };
-        let activation_point = self.activation_point.era_id();
-        if activation_point != last_emergency_restart {
-            error!(
-                %activation_point,
-                %last_emergency_restart,
-                "[protocol.last_emergency_restart] must equal [protocol.activation_point] in the \
-                chainspec since a global state update was provided, implying this upgrade is an \
-                emergency restart."
-            );
-            return false;
         }
 
-        true
+        errors
     }
 
     /// Generates a random instance using a `TestRng`.
@@ -4,7 +4,7 @@ use std::{
 };
 
 use derive_more::Display;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::types::{BlockHash, BlockHeader, BlockHeaderWithMetadata};
@@ -43,6 +43,8 @@ pub enum Tag {
     BlockHeaderByHash,
     /// A block header and its finality signatures requested by its height in the linear chain.
     BlockHeaderAndFinalitySignaturesByHeight,
+    /// A global state trie node requested by its hash.
+    Trie,
 }
 
 /// A trait which allows an implementing type to be used by the gossiper and fetcher components, and
@@ -62,7 +64,7 @@ pub trait Item: Clone + Serialize + DeserializeOwned + Send + Sync + Debug + Dis
 
 impl Item for Trie<Key, StoredValue> {
     type Id = Blake2bHash;
-    const TAG: Tag = Tag::Deploy;
+    const TAG: Tag = Tag::Trie;
     const ID_IS_COMPLETE_ITEM: bool = false;
 
     fn id(&self) -> Self::Id {
@@ -71,6 +73,16 @@ impl Item for Trie<Key, StoredValue> {
     }
 }
 
+/// The response to a request for a trie, sent in place of the bare trie so that a peer which
+/// doesn't have the requested trie can say so.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TrieResponse {
+    /// The requested trie is not in the peer's trie store.
+    Absent(Blake2bHash),
+    /// The requested trie.
+    Trie(Box<Trie<Key, StoredValue>>),
+}
+
 impl Item for BlockHeader {
     type Id = BlockHash;
     const TAG: Tag = Tag::BlockHeaderByHash;
@@ -8,7 +8,7 @@ mod stored_value;
 pub use account::Account;
 pub use auction_state::AuctionState;
 use casper_types::{contracts::NamedKeys, NamedKey};
-pub use contracts::{Contract, ContractPackage};
+pub use contracts::{Contract, ContractPackage, ContractSchema};
 pub use stored_value::StoredValue;
 
 /// A helper function to change NamedKeys into a Vec<NamedKey>
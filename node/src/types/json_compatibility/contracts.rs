@@ -2,15 +2,55 @@
 #![allow(clippy::field_reassign_with_default)]
 
 use datasize::DataSize;
+use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::types::json_compatibility::vectorize;
+use crate::{rpcs::docs::DocExample, types::json_compatibility::vectorize};
 use casper_types::{
-    Contract as DomainContract, ContractHash, ContractPackage as DomainContractPackage,
-    ContractPackageHash, ContractWasmHash, EntryPoint, NamedKey, ProtocolVersion, URef,
+    CLType, Contract as DomainContract, ContractHash, ContractPackage as DomainContractPackage,
+    ContractPackageHash, ContractWasmHash, EntryPoint, EntryPointAccess, EntryPointType, NamedKey,
+    Parameter, ProtocolVersion, URef,
 };
 
+static CONTRACT_SCHEMA: Lazy<ContractSchema> = Lazy::new(|| {
+    let contract_hash = ContractHash::new([11; 32]);
+    let transfer = EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", CLType::Key),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    let mint = EntryPoint::new(
+        "mint",
+        vec![
+            Parameter::new("owner", CLType::Key),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["minters"]),
+        EntryPointType::Contract,
+    );
+    ContractSchema {
+        contract_hash,
+        contract_package_hash: ContractPackageHash::new([12; 32]),
+        protocol_version: ProtocolVersion::from_parts(1, 3, 2),
+        versions: vec![ContractVersion {
+            protocol_version_major: 1,
+            contract_version: 1,
+            contract_hash,
+        }],
+        disabled_versions: vec![],
+        groups: vec!["minters".to_string()],
+        locked: false,
+        entry_points: vec![mint, transfer],
+    }
+});
+
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, DataSize, JsonSchema,
 )]
@@ -111,3 +151,156 @@ impl From<&DomainContractPackage> for ContractPackage {
         }
     }
 }
+
+/// The machine-readable interface of a stored contract.
+///
+/// The entry points are those of the contract itself, sorted by name, while the versions and user
+/// groups are those of the contract package it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContractSchema {
+    contract_hash: ContractHash,
+    contract_package_hash: ContractPackageHash,
+    #[schemars(with = "String")]
+    protocol_version: ProtocolVersion,
+    versions: Vec<ContractVersion>,
+    disabled_versions: Vec<DisabledVersion>,
+    groups: Vec<String>,
+    locked: bool,
+    entry_points: Vec<EntryPoint>,
+}
+
+impl ContractSchema {
+    /// Constructs the schema of the contract stored under `contract_hash`, where
+    /// `contract_package` is the package identified by the contract's package hash.
+    pub fn new(
+        contract_hash: ContractHash,
+        contract: &DomainContract,
+        contract_package: &DomainContractPackage,
+    ) -> Self {
+        let ContractPackage {
+            versions,
+            disabled_versions,
+            groups,
+            ..
+        } = ContractPackage::from(contract_package);
+        ContractSchema {
+            contract_hash,
+            contract_package_hash: contract.contract_package_hash(),
+            protocol_version: contract.protocol_version(),
+            versions,
+            disabled_versions,
+            groups: groups.into_iter().map(|groups| groups.group).collect(),
+            locked: contract_package.is_locked(),
+            entry_points: contract.entry_points().clone().take_entry_points(),
+        }
+    }
+
+    /// Returns the hash of the contract.
+    pub fn contract_hash(&self) -> ContractHash {
+        self.contract_hash
+    }
+
+    /// Returns the hash of the contract package.
+    pub fn contract_package_hash(&self) -> ContractPackageHash {
+        self.contract_package_hash
+    }
+
+    /// Returns the entry points of the contract, sorted by name.
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    /// Returns the entry point with the given name, if any.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name() == name)
+    }
+}
+
+impl DocExample for ContractSchema {
+    fn doc_example() -> &'static Self {
+        &*CONTRACT_SCHEMA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        contracts::{
+            ContractPackageStatus, ContractVersions, DisabledVersions, Groups as DomainGroups,
+        },
+        AccessRights, ContractVersionKey, EntryPoints, Group,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_derive_schema_from_contract_and_package() {
+        let contract_hash = ContractHash::new([1; 32]);
+        let contract_package_hash = ContractPackageHash::new([2; 32]);
+
+        let mut versions = ContractVersions::new();
+        versions.insert(ContractVersionKey::new(1, 1), ContractHash::new([3; 32]));
+        versions.insert(ContractVersionKey::new(1, 2), contract_hash);
+        let mut disabled_versions = DisabledVersions::new();
+        disabled_versions.insert(ContractVersionKey::new(1, 1));
+        let mut groups = DomainGroups::new();
+        groups.insert(Group::new("admins"), Default::default());
+        let contract_package = DomainContractPackage::new(
+            URef::new([4; 32], AccessRights::READ_ADD_WRITE),
+            versions,
+            disabled_versions,
+            groups,
+            ContractPackageStatus::Locked,
+        );
+
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "withdraw",
+            vec![Parameter::new("amount", CLType::U512)],
+            CLType::Unit,
+            EntryPointAccess::groups(&["admins"]),
+            EntryPointType::Contract,
+        ));
+        entry_points.add_entry_point(EntryPoint::new(
+            "balance",
+            vec![],
+            CLType::U512,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        let contract = DomainContract::new(
+            contract_package_hash,
+            ContractWasmHash::new([5; 32]),
+            Default::default(),
+            entry_points,
+            ProtocolVersion::V1_0_0,
+        );
+
+        let schema = ContractSchema::new(contract_hash, &contract, &contract_package);
+        assert_eq!(schema.contract_hash(), contract_hash);
+        assert_eq!(schema.contract_package_hash(), contract_package_hash);
+        assert_eq!(schema.versions.len(), 2);
+        assert_eq!(schema.disabled_versions.len(), 1);
+        assert_eq!(schema.groups, vec!["admins".to_string()]);
+        assert!(schema.locked);
+
+        let names: Vec<_> = schema
+            .entry_points()
+            .iter()
+            .map(|entry_point| entry_point.name())
+            .collect();
+        assert_eq!(names, vec!["balance", "withdraw"]);
+        assert_eq!(
+            schema.entry_point("withdraw").unwrap().args(),
+            &[Parameter::new("amount", CLType::U512)]
+        );
+        assert!(schema.entry_point("deposit").is_none());
+
+        let json = serde_json::to_string(&schema).unwrap();
+        let decoded: ContractSchema = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, schema);
+    }
+}
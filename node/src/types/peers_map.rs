@@ -13,6 +13,9 @@ use crate::types::NodeId;
 struct PeerEntry {
     node_id: String,
     address: String,
+    /// The reputation score of the peer, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    score: Option<i64>,
 }
 
 /// Map of peer IDs to network addresses.
@@ -27,6 +30,27 @@ impl From<BTreeMap<NodeId, String>> for PeersMap {
             .map(|(node_id, address)| PeerEntry {
                 node_id: node_id.to_string(),
                 address,
+                score: None,
+            })
+            .collect();
+        PeersMap(ret)
+    }
+}
+
+impl PeersMap {
+    /// Constructs a map including the reputation score of each peer.
+    ///
+    /// Peers without an entry in `scores` have a neutral score of zero.
+    pub(crate) fn with_scores(
+        peers: BTreeMap<NodeId, String>,
+        scores: &BTreeMap<NodeId, i64>,
+    ) -> Self {
+        let ret = peers
+            .into_iter()
+            .map(|(node_id, address)| PeerEntry {
+                score: Some(scores.get(&node_id).copied().unwrap_or_default()),
+                node_id: node_id.to_string(),
+                address,
             })
             .collect();
         PeersMap(ret)
@@ -0,0 +1,51 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::PublicKey;
+
+use crate::types::{DeployHash, Timestamp};
+
+/// The reason a pending deploy is, or isn't, currently eligible for inclusion in a proposed block.
+#[derive(Clone, DataSize, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum PendingDeployStatus {
+    /// The deploy can be included in the next block proposed by this node.
+    Ready,
+    /// The deploy depends on deploys which haven't been included in a finalized block yet.
+    MissingDependencies {
+        /// The dependencies which haven't been finalized yet.
+        dependencies: Vec<DeployHash>,
+    },
+    /// The deploy's timestamp is later than the current time.
+    TimestampInFuture,
+    /// The deploy was received too recently to be proposed, see the block proposer's
+    /// `deploy_delay` setting.
+    AwaitingDeployDelay {
+        /// The earliest time at which the deploy can be proposed.
+        eligible_from: Timestamp,
+    },
+}
+
+/// A deploy or transfer held by the block proposer, awaiting inclusion in a block.
+#[derive(Clone, DataSize, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PendingDeploy {
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+    /// The account which sent the deploy.
+    pub account: PublicKey,
+    /// `true` if the deploy is a native transfer.
+    pub is_transfer: bool,
+    /// The gas price of the deploy.
+    pub gas_price: u64,
+    /// The time at which this node received the deploy.
+    pub received: Timestamp,
+    /// The time at which the deploy expires and will be pruned if it is still pending.
+    pub expires: Timestamp,
+    /// Whether the deploy can currently be proposed, and if not, why not.
+    pub status: PendingDeployStatus,
+}
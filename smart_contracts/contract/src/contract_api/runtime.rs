@@ -9,11 +9,12 @@ use core::mem::MaybeUninit;
 use casper_types::{
     account::AccountHash,
     api_error,
-    bytesrepr::{self, FromBytes},
+    bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractVersion, NamedKeys},
     system::CallStackElement,
     ApiError, BlockTime, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Phase,
-    RuntimeArgs, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    PublicKey, RuntimeArgs, Signature, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH,
+    PHASE_SERIALIZED_LENGTH,
 };
 
 use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -321,6 +322,30 @@ pub fn blake2b<T: AsRef<[u8]>>(input: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
     ret
 }
 
+/// Returns `true` if `signature` is a valid signature of `message` by `public_key`.
+///
+/// Both Ed25519 and secp256k1 keys are supported.  A signature never verifies against a public
+/// key of a different algorithm, nor against the system public key.
+pub fn verify_signature<T: AsRef<[u8]>>(
+    message: T,
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> bool {
+    let signature_bytes = signature.to_bytes().unwrap_or_revert();
+    let public_key_bytes = public_key.to_bytes().unwrap_or_revert();
+    let result = unsafe {
+        ext_ffi::casper_verify_signature(
+            message.as_ref().as_ptr(),
+            message.as_ref().len(),
+            signature_bytes.as_ptr(),
+            signature_bytes.len(),
+            public_key_bytes.as_ptr(),
+            public_key_bytes.len(),
+        )
+    };
+    result != 0
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
@@ -344,6 +369,19 @@ pub(crate) fn read_host_buffer(size: usize) -> Result<Vec<u8>, ApiError> {
     Ok(dest)
 }
 
+/// Emits an event named `name` with the given [`CLValue`] as its payload.
+///
+/// The event is recorded in the execution results of the deploy, attributed to the hash of the
+/// currently executing contract.  Events are only recorded if the deploy executes successfully.
+///
+/// Note this function is only relevant to contracts stored on chain which are invoked via
+/// [`call_contract`] or [`call_versioned_contract`]; calling it from session code is an error.
+pub fn emit_event(name: &str, value: CLValue) {
+    let (name_ptr, name_size, _name_bytes) = contract_api::to_ptr(name);
+    let (value_ptr, value_size, _value_bytes) = contract_api::to_ptr(value);
+    unsafe { ext_ffi::casper_emit_event(name_ptr, name_size, value_ptr, value_size) }
+}
+
 /// Returns the call stack.
 pub fn get_call_stack() -> Vec<CallStackElement> {
     let (call_stack_len, result_size) = {
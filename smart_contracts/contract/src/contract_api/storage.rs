@@ -355,6 +355,28 @@ pub fn new_dictionary(dictionary_name: &str) -> Result<URef, ApiError> {
     Ok(uref)
 }
 
+/// Creates new [`URef`] that represents a seed for a dictionary partition of the global state whose
+/// keys can be read with [`dictionary_keys`], and puts it under named keys.
+///
+/// Writing a new item to an indexed dictionary costs more than writing it to one created with
+/// [`new_dictionary`], as an index entry is stored along with the item.
+pub fn new_indexed_dictionary(dictionary_name: &str) -> Result<URef, ApiError> {
+    if dictionary_name.is_empty() || runtime::has_key(dictionary_name) {
+        return Err(ApiError::InvalidArgument);
+    }
+
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::casper_new_indexed_dictionary(value_size.as_mut_ptr()) };
+        api_error::result_from(ret)?;
+        unsafe { value_size.assume_init() }
+    };
+    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
+    let uref: URef = bytesrepr::deserialize(value_bytes).unwrap_or_revert();
+    runtime::put_key(dictionary_name, Key::from(uref));
+    Ok(uref)
+}
+
 /// Retrieve `value` stored under `dictionary_item_key` in the dictionary accessed by
 /// `dictionary_seed_uref`.
 pub fn dictionary_get<V: CLTyped + FromBytes>(
@@ -422,3 +444,63 @@ pub fn dictionary_put<V: CLTyped + ToBytes>(
 
     result.unwrap_or_revert()
 }
+
+/// Removes `dictionary_item_key` and its value from the dictionary accessed by
+/// `dictionary_seed_uref`.
+///
+/// Returns `false` if there was no such item in the dictionary.
+pub fn dictionary_remove(dictionary_seed_uref: URef, dictionary_item_key: &str) -> bool {
+    let (uref_ptr, uref_size, _bytes1) = contract_api::to_ptr(dictionary_seed_uref);
+    let (dictionary_item_key_ptr, dictionary_item_key_size) =
+        contract_api::dictionary_item_key_to_ptr(dictionary_item_key);
+
+    if dictionary_item_key_size > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+        revert(ApiError::DictionaryItemKeyExceedsLength)
+    }
+
+    let ret = unsafe {
+        ext_ffi::casper_dictionary_remove(
+            uref_ptr,
+            uref_size,
+            dictionary_item_key_ptr,
+            dictionary_item_key_size,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(()) => true,
+        Err(ApiError::ValueNotFound) => false,
+        Err(e) => runtime::revert(e),
+    }
+}
+
+/// Returns at most `limit` item keys of the dictionary accessed by `dictionary_seed_uref`,
+/// skipping the first `offset` ones.
+///
+/// The dictionary must have been created with [`new_indexed_dictionary`], otherwise the call
+/// reverts with [`ApiError::DictionaryNotIndexed`].
+///
+/// The keys are returned in a stable order as long as the dictionary isn't modified, so all the
+/// keys of a dictionary can be read in pages of `limit` keys.  The cost of the call grows with
+/// the number of items in the dictionary, as all of them are scanned to find the requested ones,
+/// and with `limit`.
+pub fn dictionary_keys(dictionary_seed_uref: URef, offset: u32, limit: u32) -> Vec<String> {
+    let (uref_ptr, uref_size, _bytes1) = contract_api::to_ptr(dictionary_seed_uref);
+
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::casper_dictionary_keys(
+                uref_ptr,
+                uref_size,
+                offset,
+                limit,
+                value_size.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { value_size.assume_init() }
+    };
+
+    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
+    bytesrepr::deserialize(value_bytes).unwrap_or_revert()
+}
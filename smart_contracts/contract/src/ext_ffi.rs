@@ -719,6 +719,13 @@ extern "C" {
     ///
     /// * `output_size` - pointer to a value where host will write size of bytes of created URef.
     pub fn casper_new_dictionary(output_size_ptr: *mut usize) -> i32;
+    /// Creates new URef that points to a dictionary partition of global state, whose keys can be
+    /// read with [`casper_dictionary_keys`].
+    ///
+    /// # Arguments
+    ///
+    /// * `output_size` - pointer to a value where host will write size of bytes of created URef.
+    pub fn casper_new_indexed_dictionary(output_size_ptr: *mut usize) -> i32;
     /// The bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`
     /// will be used together with the current context’s seed to form a dictionary.
     /// The value at that dictionary is read from the global state, serialized and
@@ -762,4 +769,77 @@ extern "C" {
         value_ptr: *const u8,
         value_size: usize,
     ) -> i32;
+    /// The bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`
+    /// will be used together with the passed URef's seed to form a dictionary.
+    /// The value under that dictionary in the global state is removed.  Returns
+    /// [`ApiError::ValueNotFound`](casper_types::ApiError::ValueNotFound) if there is no such
+    /// value.
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer to bytes representing the user-defined key
+    /// * `uref_size` - size of the key (in bytes)
+    /// * `key_ptr` - pointer to bytes representing the user-defined key to remove
+    /// * `key_size` - size of the key (in bytes)
+    pub fn casper_dictionary_remove(
+        uref_ptr: *const u8,
+        uref_size: usize,
+        key_ptr: *const u8,
+        key_size: usize,
+    ) -> i32;
+    /// Reads at most `limit` keys of the dictionary accessed by the passed URef, skipping the
+    /// first `offset` ones.  The keys are serialized as a list of strings and buffered in the
+    /// runtime.  This result can be obtained via the [`casper_read_host_buffer`] function.
+    /// Returns [`ApiError::DictionaryNotIndexed`](casper_types::ApiError::DictionaryNotIndexed)
+    /// if the dictionary wasn't created with [`casper_new_indexed_dictionary`].
+    ///
+    /// # Arguments
+    ///
+    /// * `uref_ptr` - pointer to bytes representing the user-defined key
+    /// * `uref_size` - size of the key (in bytes)
+    /// * `offset` - number of dictionary keys to skip
+    /// * `limit` - maximum number of dictionary keys to read
+    /// * `output_size` - pointer to a value where host will write size of bytes of the keys
+    pub fn casper_dictionary_keys(
+        uref_ptr: *const u8,
+        uref_size: usize,
+        offset: u32,
+        limit: u32,
+        output_size: *mut usize,
+    ) -> i32;
+    /// Emits an event on behalf of the currently executing contract.  The event is recorded in
+    /// the execution results of the deploy, attributed to the contract's hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_ptr` - pointer to serialized name of the event
+    /// * `name_size` - size of serialized name of the event
+    /// * `value_ptr` - pointer to serialized `CLValue` which is the payload of the event
+    /// * `value_size` - size of serialized `CLValue`
+    pub fn casper_emit_event(
+        name_ptr: *const u8,
+        name_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    /// Checks whether the given signature of the given message was made by the given public key.
+    /// Returns a non-zero value if the signature is valid.  This function causes a `Trap` if the
+    /// signature or public key bytes cannot be deserialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_ptr` - pointer to the signed message bytes
+    /// * `message_size` - size of the signed message bytes
+    /// * `signature_ptr` - pointer to serialized `Signature`
+    /// * `signature_size` - size of serialized `Signature`
+    /// * `public_key_ptr` - pointer to serialized `PublicKey`
+    /// * `public_key_size` - size of serialized `PublicKey`
+    pub fn casper_verify_signature(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
 }
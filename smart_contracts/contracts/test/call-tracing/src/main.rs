@@ -0,0 +1,77 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::ToString;
+
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{
+    contracts::{EntryPoint, EntryPoints},
+    runtime_args, ApiError, CLType, CLTyped, ContractHash, EntryPointAccess, EntryPointType,
+    Parameter, RuntimeArgs,
+};
+
+const OUTER_ENTRY_POINT: &str = "outer";
+const INNER_ENTRY_POINT: &str = "inner";
+const HASH_KEY_NAME: &str = "call_tracing_hash";
+const PACKAGE_HASH_KEY_NAME: &str = "call_tracing_package_hash";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_REVERT: &str = "revert";
+const INNER_REVERT_CODE: u16 = 7;
+
+#[no_mangle]
+pub extern "C" fn outer() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    let revert: bool = runtime::get_named_arg(ARG_REVERT);
+    runtime::call_contract::<()>(
+        contract_hash,
+        INNER_ENTRY_POINT,
+        runtime_args! {
+            ARG_REVERT => revert,
+        },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn inner() {
+    let revert: bool = runtime::get_named_arg(ARG_REVERT);
+    if revert {
+        runtime::revert(ApiError::User(INNER_REVERT_CODE));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            OUTER_ENTRY_POINT.to_string(),
+            vec![
+                Parameter::new(ARG_CONTRACT_HASH, ContractHash::cl_type()),
+                Parameter::new(ARG_REVERT, bool::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        entry_points.add_entry_point(EntryPoint::new(
+            INNER_ENTRY_POINT.to_string(),
+            vec![Parameter::new(ARG_REVERT, bool::cl_type())],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        entry_points
+    };
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(PACKAGE_HASH_KEY_NAME.to_string()),
+        None,
+    );
+
+    runtime::put_key(HASH_KEY_NAME, contract_hash.into());
+}
@@ -0,0 +1,79 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::ApiError;
+
+const ARG_INDEXED: &str = "indexed";
+const DICTIONARY_NAME: &str = "dictionary";
+const DICTIONARY_ITEM_KEYS: [&str; 5] = ["a", "b", "c", "d", "e"];
+const REMOVED_ITEM_KEY: &str = "c";
+const PAGE_SIZE: u32 = 2;
+
+#[repr(u16)]
+enum Error {
+    RemoveFailed = 0,
+    RemovedTwice = 1,
+    ValueNotRemoved = 2,
+    UnexpectedKeys = 3,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let indexed: bool = runtime::get_named_arg(ARG_INDEXED);
+    let dictionary_uref = if indexed {
+        storage::new_indexed_dictionary(DICTIONARY_NAME).unwrap_or_revert()
+    } else {
+        storage::new_dictionary(DICTIONARY_NAME).unwrap_or_revert()
+    };
+    for (index, item_key) in DICTIONARY_ITEM_KEYS.iter().enumerate() {
+        storage::dictionary_put(dictionary_uref, item_key, index as u64);
+    }
+
+    if !storage::dictionary_remove(dictionary_uref, REMOVED_ITEM_KEY) {
+        runtime::revert(Error::RemoveFailed);
+    }
+    if storage::dictionary_remove(dictionary_uref, REMOVED_ITEM_KEY) {
+        runtime::revert(Error::RemovedTwice);
+    }
+    let removed_value: Option<u64> =
+        storage::dictionary_get(dictionary_uref, REMOVED_ITEM_KEY).unwrap_or_revert();
+    if removed_value.is_some() {
+        runtime::revert(Error::ValueNotRemoved);
+    }
+
+    // Read all the keys in pages, which reverts if the dictionary isn't indexed.
+    let mut keys: Vec<String> = Vec::new();
+    loop {
+        let page = storage::dictionary_keys(dictionary_uref, keys.len() as u32, PAGE_SIZE);
+        let is_last_page = (page.len() as u32) < PAGE_SIZE;
+        keys.extend(page);
+        if is_last_page {
+            break;
+        }
+    }
+    keys.sort();
+
+    let mut expected_keys: Vec<String> = DICTIONARY_ITEM_KEYS
+        .iter()
+        .filter(|item_key| **item_key != REMOVED_ITEM_KEY)
+        .map(|item_key| String::from(*item_key))
+        .collect();
+    expected_keys.sort();
+    if keys != expected_keys {
+        runtime::revert(Error::UnexpectedKeys);
+    }
+}
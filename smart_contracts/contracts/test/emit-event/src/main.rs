@@ -0,0 +1,70 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::{EntryPoint, EntryPoints},
+    CLType, CLTyped, CLValue, EntryPointAccess, EntryPointType, Parameter,
+};
+
+const EMIT_ENTRY_POINT: &str = "emit";
+const EMIT_FROM_SESSION_ENTRY_POINT: &str = "emit_from_session";
+const HASH_KEY_NAME: &str = "emit_event_hash";
+const PACKAGE_HASH_KEY_NAME: &str = "emit_event_package_hash";
+const EVENT_NAME: &str = "message";
+const ARG_MESSAGE: &str = "message";
+
+fn emit_message() {
+    let message: String = runtime::get_named_arg(ARG_MESSAGE);
+    let value = CLValue::from_t(message).unwrap_or_revert();
+    runtime::emit_event(EVENT_NAME, value);
+}
+
+#[no_mangle]
+pub extern "C" fn emit() {
+    emit_message()
+}
+
+#[no_mangle]
+pub extern "C" fn emit_from_session() {
+    emit_message()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            EMIT_ENTRY_POINT.to_string(),
+            vec![Parameter::new(ARG_MESSAGE, String::cl_type())],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        entry_points.add_entry_point(EntryPoint::new(
+            EMIT_FROM_SESSION_ENTRY_POINT.to_string(),
+            vec![Parameter::new(ARG_MESSAGE, String::cl_type())],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Session,
+        ));
+        entry_points
+    };
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(PACKAGE_HASH_KEY_NAME.to_string()),
+        None,
+    );
+
+    runtime::put_key(HASH_KEY_NAME, contract_hash.into());
+}
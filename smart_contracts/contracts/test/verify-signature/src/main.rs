@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{bytesrepr, PublicKey, Signature};
+
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const VERIFY_RESULT: &str = "verify_result";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let message: Vec<u8> = runtime::get_named_arg(ARG_MESSAGE);
+    let signature_bytes: Vec<u8> = runtime::get_named_arg(ARG_SIGNATURE);
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+
+    let signature: Signature = bytesrepr::deserialize(signature_bytes).unwrap_or_revert();
+    let verified = runtime::verify_signature(message, &signature, &public_key);
+
+    let uref = storage::new_uref(verified);
+    runtime::put_key(VERIFY_RESULT, uref.into())
+}
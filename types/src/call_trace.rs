@@ -0,0 +1,137 @@
+//! Types describing the tree of contract calls made while executing a deploy.
+//!
+//! Call traces are only recorded by nodes and execution engines which have call tracing enabled.
+//! They are not part of the deploy's `ExecutionResult`, and hence play no part in consensus.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{ContractHash, ContractPackageHash, RuntimeArgs, U512};
+
+/// A call to a host function made by a [`CallFrame`].
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct HostFunctionCall {
+    /// The name of the host function.
+    pub name: String,
+    /// The gas charged for calling the host function.
+    pub cost: U512,
+}
+
+/// A single call of a contract's entry point, together with the calls it made in turn.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct CallFrame {
+    /// The hash of the called contract, or `None` if the frame is payment or session code sent as
+    /// module bytes.
+    pub contract_hash: Option<ContractHash>,
+    /// The hash of the called contract's package, or `None` if the frame is payment or session
+    /// code sent as module bytes.
+    pub contract_package_hash: Option<ContractPackageHash>,
+    /// The name of the called entry point.
+    pub entry_point: String,
+    /// The arguments passed to the entry point.
+    pub args: RuntimeArgs,
+    /// The gas used by the call, including the gas used by the calls it made.
+    pub gas_used: U512,
+    /// The host functions called directly by this frame, in the order they were called.
+    pub host_function_calls: Vec<HostFunctionCall>,
+    /// The contract calls made by this frame, in the order they were made.
+    pub calls: Vec<CallFrame>,
+    /// The error with which the call failed, if any.
+    pub error: Option<String>,
+}
+
+impl CallFrame {
+    /// Returns the innermost failed frame, i.e. the frame in which execution reverted, or `None`
+    /// if this frame succeeded.
+    pub fn failed_frame(&self) -> Option<&CallFrame> {
+        self.error.as_ref()?;
+        Some(
+            self.calls
+                .iter()
+                .find_map(CallFrame::failed_frame)
+                .unwrap_or(self),
+        )
+    }
+}
+
+/// The contract calls made while executing a deploy.
+#[derive(Clone, Default, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct CallTrace {
+    /// The call of the deploy's payment code, if it was executed as Wasm.
+    pub payment: Option<CallFrame>,
+    /// The call of the deploy's session code, if it was executed as Wasm.
+    pub session: Option<CallFrame>,
+    /// Whether some calls were left out as the trace reached its maximum size.
+    pub truncated: bool,
+}
+
+impl CallTrace {
+    /// Returns `true` if no calls were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.payment.is_none() && self.session.is_none()
+    }
+
+    /// Returns the innermost failed frame of the payment or session call, if any.
+    pub fn failed_frame(&self) -> Option<&CallFrame> {
+        self.payment
+            .as_ref()
+            .and_then(CallFrame::failed_frame)
+            .or_else(|| self.session.as_ref().and_then(CallFrame::failed_frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::*;
+
+    fn frame(entry_point: &str, error: Option<&str>, calls: Vec<CallFrame>) -> CallFrame {
+        CallFrame {
+            contract_hash: None,
+            contract_package_hash: None,
+            entry_point: entry_point.to_string(),
+            args: RuntimeArgs::new(),
+            gas_used: U512::zero(),
+            host_function_calls: Vec::new(),
+            calls,
+            error: error.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn should_find_innermost_failed_frame() {
+        let succeeded = frame("succeeded", None, Vec::new());
+        assert!(succeeded.failed_frame().is_none());
+
+        let reverted = frame("reverted", Some("User error: 1"), Vec::new());
+        let middle = frame(
+            "middle",
+            Some("User error: 1"),
+            vec![succeeded.clone(), reverted.clone()],
+        );
+        let call = frame("call", Some("User error: 1"), vec![middle]);
+        assert_eq!(call.failed_frame(), Some(&reverted));
+
+        let call_trace = CallTrace {
+            payment: Some(succeeded),
+            session: Some(call),
+            truncated: false,
+        };
+        assert_eq!(call_trace.failed_frame(), Some(&reverted));
+        assert!(!call_trace.is_empty());
+        assert!(CallTrace::default().is_empty());
+    }
+}
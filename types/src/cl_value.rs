@@ -19,6 +19,8 @@ use crate::{
 
 mod jsonrepr;
 
+pub use jsonrepr::{cl_value_from_json, cl_value_to_json};
+
 /// Error while converting a [`CLValue`] into a given type.
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct CLTypeMismatch {
@@ -1,10 +1,13 @@
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::convert::TryFrom;
 
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
 
 use crate::{
-    bytesrepr::{self, FromBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG},
+    bytesrepr::{
+        self, FromBytes, ToBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG,
+    },
     CLType, CLValue, Key, PublicKey, URef, U128, U256, U512,
 };
 
@@ -106,17 +109,145 @@ fn simple_type_to_json<T: FromBytes + Serialize>(bytes: &[u8]) -> Option<(Value,
     Some((json!(value), remainder))
 }
 
+/// Returns a best-effort attempt to convert a JSON value into a `CLValue` of the given type.
+///
+/// This is the inverse of [`cl_value_to_json`], so the JSON value is expected to have the form
+/// which that function produces for a `CLValue` of the given type.  Note that `null` is always
+/// parsed as `None` for `CLType::Option`, so e.g. `Some(())` can't be represented.
+///
+/// Returns `None` if the JSON value doesn't match the given type, or if the type is
+/// `CLType::Any`.
+pub fn cl_value_from_json(cl_type: &CLType, json_value: &Value) -> Option<CLValue> {
+    let mut bytes = Vec::new();
+    from_json(cl_type, json_value, &mut bytes)?;
+    Some(CLValue::from_components(cl_type.clone(), bytes))
+}
+
+fn from_json(cl_type: &CLType, json_value: &Value, bytes: &mut Vec<u8>) -> Option<()> {
+    match cl_type {
+        CLType::Bool => simple_type_from_json::<bool>(json_value, bytes),
+        CLType::I32 => simple_type_from_json::<i32>(json_value, bytes),
+        CLType::I64 => simple_type_from_json::<i64>(json_value, bytes),
+        CLType::U8 => simple_type_from_json::<u8>(json_value, bytes),
+        CLType::U32 => simple_type_from_json::<u32>(json_value, bytes),
+        CLType::U64 => simple_type_from_json::<u64>(json_value, bytes),
+        CLType::U128 => simple_type_from_json::<U128>(json_value, bytes),
+        CLType::U256 => simple_type_from_json::<U256>(json_value, bytes),
+        CLType::U512 => simple_type_from_json::<U512>(json_value, bytes),
+        CLType::Unit => simple_type_from_json::<()>(json_value, bytes),
+        CLType::String => simple_type_from_json::<String>(json_value, bytes),
+        CLType::Key => simple_type_from_json::<Key>(json_value, bytes),
+        CLType::URef => simple_type_from_json::<URef>(json_value, bytes),
+        CLType::PublicKey => simple_type_from_json::<PublicKey>(json_value, bytes),
+        CLType::Option(inner_cl_type) => {
+            if json_value.is_null() {
+                bytes.push(OPTION_NONE_TAG);
+                Some(())
+            } else {
+                bytes.push(OPTION_SOME_TAG);
+                from_json(inner_cl_type, json_value, bytes)
+            }
+        }
+        CLType::List(inner_cl_type) => {
+            let items = json_value.as_array()?;
+            let count = u32::try_from(items.len()).ok()?;
+            bytes.extend(count.to_bytes().ok()?);
+            for item in items {
+                from_json(inner_cl_type, item, bytes)?;
+            }
+            Some(())
+        }
+        CLType::ByteArray(length) => {
+            let array = hex::decode(json_value.as_str()?).ok()?;
+            if array.len() != *length as usize {
+                return None;
+            }
+            bytes.extend(array);
+            Some(())
+        }
+        CLType::Result { ok, err } => {
+            let object = json_value.as_object()?;
+            if object.len() != 1 {
+                return None;
+            }
+            if let Some(value) = object.get("Ok") {
+                bytes.push(RESULT_OK_TAG);
+                from_json(ok, value, bytes)
+            } else {
+                let value = object.get("Err")?;
+                bytes.push(RESULT_ERR_TAG);
+                from_json(err, value, bytes)
+            }
+        }
+        CLType::Map { key, value } => {
+            let entries = json_value.as_array()?;
+            let num_keys = u32::try_from(entries.len()).ok()?;
+            bytes.extend(num_keys.to_bytes().ok()?);
+            // The entries are sorted by their serialized keys, so that the encoding doesn't depend
+            // on the order of the JSON array.
+            let mut sorted_entries = BTreeMap::new();
+            for entry in entries {
+                let entry = entry.as_object()?;
+                if entry.len() != 2 {
+                    return None;
+                }
+                let mut key_bytes = Vec::new();
+                from_json(key, entry.get("key")?, &mut key_bytes)?;
+                let mut value_bytes = Vec::new();
+                from_json(value, entry.get("value")?, &mut value_bytes)?;
+                // Duplicate keys would be silently dropped when deserializing into a map.
+                if sorted_entries.insert(key_bytes, value_bytes).is_some() {
+                    return None;
+                }
+            }
+            for (key_bytes, value_bytes) in sorted_entries {
+                bytes.extend(key_bytes);
+                bytes.extend(value_bytes);
+            }
+            Some(())
+        }
+        CLType::Tuple1(arr) => tuple_from_json(arr, json_value, bytes),
+        CLType::Tuple2(arr) => tuple_from_json(arr, json_value, bytes),
+        CLType::Tuple3(arr) => tuple_from_json(arr, json_value, bytes),
+        CLType::Any => None,
+    }
+}
+
+fn simple_type_from_json<T: DeserializeOwned + ToBytes>(
+    json_value: &Value,
+    bytes: &mut Vec<u8>,
+) -> Option<()> {
+    let value: T = serde_json::from_value(json_value.clone()).ok()?;
+    bytes.extend(value.into_bytes().ok()?);
+    Some(())
+}
+
+fn tuple_from_json(
+    cl_types: &[Box<CLType>],
+    json_value: &Value,
+    bytes: &mut Vec<u8>,
+) -> Option<()> {
+    let items = json_value.as_array()?;
+    if items.len() != cl_types.len() {
+        return None;
+    }
+    for (cl_type, item) in cl_types.iter().zip(items) {
+        from_json(cl_type, item, bytes)?;
+    }
+    Some(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{bytesrepr::ToBytes, AsymmetricType, CLTyped, SecretKey};
-    use alloc::collections::BTreeMap;
+    use crate::{AccessRights, AsymmetricType, CLTyped, SecretKey};
 
     fn test_value<T: ToBytes + Serialize + Clone + CLTyped>(value: T) {
         let cl_value = CLValue::from_t(value.clone()).unwrap();
         let cl_value_as_json: Value = cl_value_to_json(&cl_value).unwrap();
         let expected = json!(value);
         assert_eq!(cl_value_as_json, expected);
+        assert_eq!(cl_value_from_json(&T::cl_type(), &expected), Some(cl_value));
     }
 
     #[test]
@@ -149,6 +280,10 @@ mod tests {
         let cl_value_as_json: Value = cl_value_to_json(&cl_value).unwrap();
         let expected = json!([a_hex, b_hex]);
         assert_eq!(cl_value_as_json, expected);
+        assert_eq!(
+            cl_value_from_json(cl_value.cl_type(), &expected),
+            Some(cl_value)
+        );
     }
 
     #[test]
@@ -169,6 +304,10 @@ mod tests {
         let cl_value_as_json: Value = cl_value_to_json(&cl_value).unwrap();
         let expected = json!([[a_hex, b_hex], [c_hex]]);
         assert_eq!(cl_value_as_json, expected);
+        assert_eq!(
+            cl_value_from_json(cl_value.cl_type(), &expected),
+            Some(cl_value)
+        );
     }
 
     #[test]
@@ -187,6 +326,20 @@ mod tests {
             { "key": key2, "value": value2 }
         ]);
         assert_eq!(cl_value_as_json, expected);
+        assert_eq!(
+            cl_value_from_json(cl_value.cl_type(), &expected),
+            Some(cl_value.clone())
+        );
+
+        // The order of the entries in the JSON array doesn't affect the encoding.
+        let reversed = json!([
+            { "key": key2, "value": value2 },
+            { "key": key1, "value": value1 }
+        ]);
+        assert_eq!(
+            cl_value_from_json(cl_value.cl_type(), &reversed),
+            Some(cl_value)
+        );
     }
 
     #[test]
@@ -206,6 +359,10 @@ mod tests {
         let cl_value_as_json = cl_value_to_json(&cl_value).unwrap();
         let expected = json!(hex::encode(&bytes));
         assert_eq!(cl_value_as_json, expected);
+        assert_eq!(
+            cl_value_from_json(cl_value.cl_type(), &expected),
+            Some(cl_value)
+        );
     }
 
     #[test]
@@ -228,4 +385,36 @@ mod tests {
         test_value((v1.clone(), v2.clone()));
         test_value((v1, v2, v3));
     }
+
+    #[test]
+    fn system_types_to_json_value() {
+        let uref = URef::new([6; 32], AccessRights::READ_ADD_WRITE);
+        test_value(Key::Hash([5; 32]));
+        test_value(Key::URef(uref));
+        test_value(uref);
+    }
+
+    #[test]
+    fn should_not_convert_mismatched_json_value() {
+        // Wrong JSON type for the `CLType`.
+        assert!(cl_value_from_json(&CLType::U64, &json!("1")).is_none());
+        assert!(cl_value_from_json(&CLType::String, &json!(1)).is_none());
+        // Out of range for the `CLType`.
+        assert!(cl_value_from_json(&CLType::U8, &json!(256)).is_none());
+        // Wrong length for a byte array.
+        assert!(cl_value_from_json(&CLType::ByteArray(3), &json!("0102")).is_none());
+        // Wrong number of tuple elements.
+        let tuple_type = <(u8, u8)>::cl_type();
+        assert!(cl_value_from_json(&tuple_type, &json!([1])).is_none());
+        assert!(cl_value_from_json(&tuple_type, &json!([1, 2, 3])).is_none());
+        // Neither `Ok` nor `Err`.
+        let result_type = Result::<u8, u8>::cl_type();
+        assert!(cl_value_from_json(&result_type, &json!({ "Maybe": 1 })).is_none());
+        // Duplicate map keys.
+        let map_type = BTreeMap::<String, u8>::cl_type();
+        let map = json!([{ "key": "a", "value": 1 }, { "key": "a", "value": 2 }]);
+        assert!(cl_value_from_json(&map_type, &map).is_none());
+        // `CLType::Any` can't be constructed.
+        assert!(cl_value_from_json(&CLType::Any, &json!(null)).is_none());
+    }
 }
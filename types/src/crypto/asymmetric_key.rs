@@ -21,7 +21,7 @@ use ed25519_dalek::{
 };
 use hex_fmt::HexFmt;
 use k256::ecdsa::{
-    Signature as Secp256k1Signature, SigningKey as Secp256k1SecretKey,
+    signature::Verifier, Signature as Secp256k1Signature, SigningKey as Secp256k1SecretKey,
     VerifyingKey as Secp256k1PublicKey,
 };
 #[cfg(feature = "std")]
@@ -662,6 +662,34 @@ impl JsonSchema for Signature {
     }
 }
 
+/// Verifies the signature of the given message against the given public key.
+pub fn verify<T: AsRef<[u8]>>(
+    message: T,
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> Result<(), Error> {
+    match (signature, public_key) {
+        (Signature::System, _) => Err(Error::AsymmetricKey(String::from(
+            "signatures based on the system key cannot be verified",
+        ))),
+        (Signature::Ed25519(signature), PublicKey::Ed25519(public_key)) => public_key
+            .verify_strict(message.as_ref(), signature)
+            .map_err(|_| Error::AsymmetricKey(String::from("failed to verify Ed25519 signature"))),
+        (Signature::Secp256k1(signature), PublicKey::Secp256k1(public_key)) => {
+            let verifier: &Secp256k1PublicKey = public_key;
+            verifier
+                .verify(message.as_ref(), signature)
+                .map_err(|error| {
+                    Error::AsymmetricKey(format!("failed to verify secp256k1 signature: {}", error))
+                })
+        }
+        _ => Err(Error::AsymmetricKey(format!(
+            "type mismatch between {} and {}",
+            signature, public_key
+        ))),
+    }
+}
+
 mod detail {
     use alloc::{string::String, vec::Vec};
 
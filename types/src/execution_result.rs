@@ -32,7 +32,7 @@ use crate::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     system::auction::{Bid, EraInfo, UnbondingPurse},
-    CLValue, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
+    CLValue, ContractHash, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
 };
 
 /// Constants to track ExecutionResult serialization.
@@ -64,6 +64,7 @@ const TRANSFORM_ADD_UINT256_TAG: u8 = 14;
 const TRANSFORM_ADD_UINT512_TAG: u8 = 15;
 const TRANSFORM_ADD_KEYS_TAG: u8 = 16;
 const TRANSFORM_FAILURE_TAG: u8 = 17;
+const TRANSFORM_PRUNE_TAG: u8 = 18;
 
 #[cfg(feature = "std")]
 static EXECUTION_RESULT: Lazy<ExecutionResult> = Lazy::new(|| {
@@ -323,6 +324,59 @@ impl FromBytes for ExecutionEffect {
     }
 }
 
+/// An event emitted by a contract while executing a deploy.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ContractEvent {
+    /// The hash of the contract which emitted the event.
+    pub contract_hash: ContractHash,
+    /// The name of the event.
+    pub name: String,
+    /// The payload of the event.
+    pub value: CLValue,
+}
+
+impl ToBytes for ContractEvent {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.contract_hash.to_bytes()?);
+        buffer.extend(self.name.to_bytes()?);
+        buffer.extend(self.value.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.contract_hash.serialized_length()
+            + self.name.serialized_length()
+            + self.value.serialized_length()
+    }
+}
+
+impl FromBytes for ContractEvent {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (contract_hash, remainder) = ContractHash::from_bytes(bytes)?;
+        let (name, remainder) = String::from_bytes(remainder)?;
+        let (value, remainder) = CLValue::from_bytes(remainder)?;
+        let contract_event = ContractEvent {
+            contract_hash,
+            name,
+            value,
+        };
+        Ok((contract_event, remainder))
+    }
+}
+
+impl Distribution<ContractEvent> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ContractEvent {
+        ContractEvent {
+            contract_hash: ContractHash::new(rng.gen()),
+            name: format!("event-{}", rng.gen::<u16>()),
+            value: CLValue::from_t(rng.gen::<u64>()).unwrap(),
+        }
+    }
+}
+
 /// An operation performed while executing a deploy.
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "std", derive(JsonSchema))]
@@ -473,6 +527,8 @@ pub enum Transform {
     AddKeys(Vec<NamedKey>),
     /// A failed transformation, containing an error message.
     Failure(String),
+    /// Removes the value from global state.
+    Prune,
 }
 
 impl ToBytes for Transform {
@@ -541,6 +597,7 @@ impl ToBytes for Transform {
                 buffer.insert(0, TRANSFORM_FAILURE_TAG);
                 buffer.extend(value.to_bytes()?);
             }
+            Transform::Prune => buffer.insert(0, TRANSFORM_PRUNE_TAG),
         }
         Ok(buffer)
     }
@@ -622,6 +679,7 @@ impl FromBytes for Transform {
                 let (value, remainder) = String::from_bytes(remainder)?;
                 Ok((Transform::Failure(value), remainder))
             }
+            TRANSFORM_PRUNE_TAG => Ok((Transform::Prune, remainder)),
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -630,7 +688,7 @@ impl FromBytes for Transform {
 impl Distribution<Transform> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Transform {
         // TODO - include WriteDeployInfo and WriteTransfer as options
-        match rng.gen_range(0..13) {
+        match rng.gen_range(0..14) {
             0 => Transform::Identity,
             1 => Transform::WriteCLValue(CLValue::from_t(true).unwrap()),
             2 => Transform::WriteAccount(AccountHash::new(rng.gen())),
@@ -653,6 +711,7 @@ impl Distribution<Transform> for Standard {
                 Transform::AddKeys(named_keys)
             }
             12 => Transform::Failure(rng.gen::<u64>().to_string()),
+            13 => Transform::Prune,
             _ => unreachable!(),
         }
     }
@@ -677,10 +736,50 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&transform);
     }
 
+    #[test]
+    fn bytesrepr_test_contract_event() {
+        let mut rng = get_rng();
+        let contract_event: ContractEvent = rng.gen();
+        bytesrepr::test_serialization_roundtrip(&contract_event);
+    }
+
     #[test]
     fn bytesrepr_test_execution_result() {
         let mut rng = get_rng();
         let execution_result: ExecutionResult = rng.gen();
         bytesrepr::test_serialization_roundtrip(&execution_result);
     }
+
+    #[test]
+    fn bytesrepr_should_decode_previously_stored_execution_result() {
+        // An `ExecutionResult::Failure` as encoded by casper-types 1.3.2, as held in storage.
+        const STORED_BYTES: [u8; 34] = [
+            0, // failure tag
+            1, 0, 0, 0, 1, 0, 0, 0, b'a', 1, // operations
+            1, 0, 0, 0, 1, 0, 0, 0, b'a', 0, // transforms
+            0, 0, 0, 0, // transfers
+            1, 3, // cost
+            1, 0, 0, 0, b'x', // error message
+        ];
+
+        let expected = ExecutionResult::Failure {
+            effect: ExecutionEffect {
+                operations: vec![Operation {
+                    key: "a".to_string(),
+                    kind: OpKind::Write,
+                }],
+                transforms: vec![TransformEntry {
+                    key: "a".to_string(),
+                    transform: Transform::Identity,
+                }],
+            },
+            transfers: vec![],
+            cost: U512::from(3),
+            error_message: "x".to_string(),
+        };
+
+        let decoded: ExecutionResult = bytesrepr::deserialize(STORED_BYTES.to_vec()).unwrap();
+        assert_eq!(decoded, expected);
+        assert_eq!(expected.to_bytes().unwrap(), STORED_BYTES.to_vec());
+    }
 }
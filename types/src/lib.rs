@@ -30,6 +30,7 @@ pub mod account;
 pub mod api_error;
 mod block_time;
 pub mod bytesrepr;
+mod call_trace;
 mod cl_type;
 mod cl_value;
 mod contract_wasm;
@@ -42,6 +43,7 @@ mod execution_result;
 pub mod gens;
 mod json_pretty_printer;
 mod key;
+pub mod light_client;
 mod named_key;
 mod phase;
 mod protocol_version;
@@ -58,8 +60,9 @@ pub use access_rights::{AccessRights, ACCESS_RIGHTS_SERIALIZED_LENGTH};
 #[doc(inline)]
 pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
+pub use call_trace::{CallFrame, CallTrace, HostFunctionCall};
 pub use cl_type::{named_key_type, CLType, CLTyped};
-pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
+pub use cl_value::{cl_value_from_json, cl_value_to_json, CLTypeMismatch, CLValue, CLValueError};
 pub use contract_wasm::{ContractWasm, ContractWasmHash};
 #[doc(inline)]
 pub use contracts::{
@@ -70,7 +73,7 @@ pub use contracts::{
 pub use crypto::*;
 pub use deploy_info::DeployInfo;
 pub use execution_result::{
-    ExecutionEffect, ExecutionResult, OpKind, Operation, Transform, TransformEntry,
+    ContractEvent, ExecutionEffect, ExecutionResult, OpKind, Operation, Transform, TransformEntry,
 };
 pub use json_pretty_printer::json_pretty_print;
 #[doc(inline)]
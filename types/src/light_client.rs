@@ -0,0 +1,610 @@
+//! Verification of global state query results by light clients.
+//!
+//! A light client which trusts the validator set of an era can use the functions in this module to
+//! check a value returned by an untrusted node: the block header must carry valid finality
+//! signatures from validators holding more than the given fraction of the era's total weight, and
+//! the value's Merkle proof must lead to the state root hash of that block header.
+//!
+//! The types here mirror the serialized forms of the node's block header and of the execution
+//! engine's trie Merkle proofs, so they can be deserialized directly from the bytes returned by the
+//! node's JSON-RPC API.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use displaydoc::Display;
+use num_rational::Ratio;
+#[cfg(feature = "std")]
+use thiserror::Error;
+
+use crate::{
+    account,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    crypto, EraId, ProtocolVersion, PublicKey, Signature, BLAKE2B_DIGEST_LENGTH, U512,
+};
+
+/// The number of children of a trie node.
+const RADIX: usize = 256;
+
+const TRIE_LEAF_TAG: u8 = 0;
+const TRIE_NODE_TAG: u8 = 1;
+const TRIE_EXTENSION_TAG: u8 = 2;
+
+const POINTER_LEAF_TAG: u8 = 0;
+const POINTER_NODE_TAG: u8 = 1;
+
+const PROOF_STEP_NODE_TAG: u8 = 0;
+const PROOF_STEP_EXTENSION_TAG: u8 = 1;
+
+/// A 32-byte BLAKE2b hash digest, as used for block hashes and trie hashes.
+pub type Digest = [u8; BLAKE2B_DIGEST_LENGTH];
+
+/// Errors returned when verifying a query result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Error))]
+#[cfg_attr(not(feature = "std"), derive(Display))]
+pub enum Error {
+    /// A finality signature is invalid.
+    #[cfg_attr(feature = "std", error("invalid finality signature by {}", _0))]
+    InvalidFinalitySignature(PublicKey),
+    /// The finality signatures don't exceed the required fraction of the validators' weight.
+    #[cfg_attr(
+        feature = "std",
+        error("finality signatures don't exceed the required fraction of the validators' weight")
+    )]
+    InsufficientFinalitySignatures,
+    /// No Merkle proofs were provided.
+    #[cfg_attr(feature = "std", error("no Merkle proofs provided"))]
+    NoProofs,
+    /// The Merkle proof is for a different key than the one queried.
+    #[cfg_attr(feature = "std", error("Merkle proof is for a different key"))]
+    UnexpectedKey,
+    /// The Merkle proof holds a different value than the one returned.
+    #[cfg_attr(feature = "std", error("Merkle proof holds a different value"))]
+    UnexpectedValue,
+    /// The Merkle proof doesn't lead to the state root hash of the block header.
+    #[cfg_attr(
+        feature = "std",
+        error("Merkle proof doesn't lead to the state root hash of the block")
+    )]
+    StateRootHashMismatch,
+    /// {0:?}
+    #[cfg_attr(feature = "std", error("{}", _0))]
+    BytesRepr(bytesrepr::Error),
+}
+
+impl From<bytesrepr::Error> for Error {
+    fn from(error: bytesrepr::Error) -> Self {
+        Error::BytesRepr(error)
+    }
+}
+
+/// A pointer from a trie node or extension to one of its children.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TriePointer {
+    /// Pointer to a leaf.
+    LeafPointer(Digest),
+    /// Pointer to a node or extension.
+    NodePointer(Digest),
+}
+
+impl ToBytes for TriePointer {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        match self {
+            TriePointer::LeafPointer(hash) => {
+                ret.push(POINTER_LEAF_TAG);
+                ret.extend_from_slice(hash);
+            }
+            TriePointer::NodePointer(hash) => {
+                ret.push(POINTER_NODE_TAG);
+                ret.extend_from_slice(hash);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH + BLAKE2B_DIGEST_LENGTH
+    }
+}
+
+impl FromBytes for TriePointer {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        let (hash, rem) = Digest::from_bytes(rem)?;
+        match tag {
+            POINTER_LEAF_TAG => Ok((TriePointer::LeafPointer(hash), rem)),
+            POINTER_NODE_TAG => Ok((TriePointer::NodePointer(hash), rem)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A component of a proof that an entry exists in the Merkle trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieMerkleProofStep {
+    /// Corresponds to a trie node, with the pointer to the proven entry's branch omitted.
+    Node {
+        /// The index of the omitted pointer.
+        hole_index: u8,
+        /// The remaining pointers of the node.
+        indexed_pointers_with_hole: Vec<(u8, TriePointer)>,
+    },
+    /// Corresponds to a trie extension.
+    Extension {
+        /// The affix of the extension.
+        affix: Bytes,
+    },
+}
+
+impl ToBytes for TrieMerkleProofStep {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        match self {
+            TrieMerkleProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                ret.push(PROOF_STEP_NODE_TAG);
+                ret.push(*hole_index);
+                ret.append(&mut indexed_pointers_with_hole.to_bytes()?);
+            }
+            TrieMerkleProofStep::Extension { affix } => {
+                ret.push(PROOF_STEP_EXTENSION_TAG);
+                ret.append(&mut affix.to_bytes()?);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                TrieMerkleProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    hole_index.serialized_length() + indexed_pointers_with_hole.serialized_length()
+                }
+                TrieMerkleProofStep::Extension { affix } => affix.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for TrieMerkleProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            PROOF_STEP_NODE_TAG => {
+                let (hole_index, rem) = u8::from_bytes(rem)?;
+                let (indexed_pointers_with_hole, rem) = FromBytes::from_bytes(rem)?;
+                Ok((
+                    TrieMerkleProofStep::Node {
+                        hole_index,
+                        indexed_pointers_with_hole,
+                    },
+                    rem,
+                ))
+            }
+            PROOF_STEP_EXTENSION_TAG => {
+                let (affix, rem) = Bytes::from_bytes(rem)?;
+                Ok((TrieMerkleProofStep::Extension { affix }, rem))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A proof that an entry with a specified `key` and `value` is present in the Merkle trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    value: V,
+    proof_steps: Vec<TrieMerkleProofStep>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    /// Constructs a new `TrieMerkleProof`.
+    pub fn new(key: K, value: V, proof_steps: Vec<TrieMerkleProofStep>) -> Self {
+        TrieMerkleProof {
+            key,
+            value,
+            proof_steps,
+        }
+    }
+
+    /// Returns the key of the proven entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the value of the proven entry.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Returns the proof steps, ordered from the leaf up to the root.
+    pub fn proof_steps(&self) -> &[TrieMerkleProofStep] {
+        &self.proof_steps
+    }
+}
+
+impl<K: ToBytes, V: ToBytes> TrieMerkleProof<K, V> {
+    /// Recomputes the state root hash committed to by this proof.
+    ///
+    /// The hash of the leaf holding `key` and `value` is computed first, then each proof step is
+    /// applied in turn to compute the hash of its parent, up to the root.
+    pub fn compute_state_hash(&self) -> Result<Digest, bytesrepr::Error> {
+        let mut leaf_bytes = vec![TRIE_LEAF_TAG];
+        leaf_bytes.append(&mut self.key.to_bytes()?);
+        leaf_bytes.append(&mut self.value.to_bytes()?);
+        let mut hash = account::blake2b(&leaf_bytes);
+
+        for (index, proof_step) in self.proof_steps.iter().enumerate() {
+            let pointer = if index == 0 {
+                TriePointer::LeafPointer(hash)
+            } else {
+                TriePointer::NodePointer(hash)
+            };
+            let step_bytes = match proof_step {
+                TrieMerkleProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    let mut pointer_block: Vec<Option<TriePointer>> = vec![None; RADIX];
+                    for (index, pointer) in indexed_pointers_with_hole {
+                        pointer_block[*index as usize] = Some(*pointer);
+                    }
+                    pointer_block[*hole_index as usize] = Some(pointer);
+                    let mut bytes = vec![TRIE_NODE_TAG];
+                    for maybe_pointer in pointer_block {
+                        bytes.append(&mut maybe_pointer.to_bytes()?);
+                    }
+                    bytes
+                }
+                TrieMerkleProofStep::Extension { affix } => {
+                    let mut bytes = vec![TRIE_EXTENSION_TAG];
+                    bytes.append(&mut affix.to_bytes()?);
+                    bytes.append(&mut pointer.to_bytes()?);
+                    bytes
+                }
+            };
+            hash = account::blake2b(&step_bytes);
+        }
+        Ok(hash)
+    }
+}
+
+impl<K: ToBytes, V: ToBytes> ToBytes for TrieMerkleProof<K, V> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.key.to_bytes()?);
+        ret.append(&mut self.value.to_bytes()?);
+        ret.append(&mut self.proof_steps.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.value.serialized_length()
+            + self.proof_steps.serialized_length()
+    }
+}
+
+impl<K: FromBytes, V: FromBytes> FromBytes for TrieMerkleProof<K, V> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem) = K::from_bytes(bytes)?;
+        let (value, rem) = V::from_bytes(rem)?;
+        let (proof_steps, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            TrieMerkleProof {
+                key,
+                value,
+                proof_steps,
+            },
+            rem,
+        ))
+    }
+}
+
+/// The parts of a block header needed by a light client, parsed from the header's serialized form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightBlockHeader {
+    block_hash: Digest,
+    parent_hash: Digest,
+    state_root_hash: Digest,
+    era_id: EraId,
+    height: u64,
+    protocol_version: ProtocolVersion,
+    next_era_validator_weights: Option<BTreeMap<PublicKey, U512>>,
+}
+
+impl LightBlockHeader {
+    /// Parses the serialized form of a block header, computing the block hash from it.
+    pub fn from_serialized_header(serialized_header: &[u8]) -> Result<Self, bytesrepr::Error> {
+        let (parent_hash, rem) = Digest::from_bytes(serialized_header)?;
+        let (state_root_hash, rem) = Digest::from_bytes(rem)?;
+        let (_body_hash, rem) = Digest::from_bytes(rem)?;
+        let (_random_bit, rem) = bool::from_bytes(rem)?;
+        let (_accumulated_seed, rem) = Digest::from_bytes(rem)?;
+        let (tag, mut rem) = u8::from_bytes(rem)?;
+        let next_era_validator_weights = match tag {
+            0 => None,
+            1 => {
+                let (_equivocators, era_end_rem) = Vec::<PublicKey>::from_bytes(rem)?;
+                let (_rewards, era_end_rem) = BTreeMap::<PublicKey, u64>::from_bytes(era_end_rem)?;
+                let (_inactive_validators, era_end_rem) =
+                    Vec::<PublicKey>::from_bytes(era_end_rem)?;
+                let (weights, era_end_rem) = FromBytes::from_bytes(era_end_rem)?;
+                rem = era_end_rem;
+                Some(weights)
+            }
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        let (_timestamp, rem) = u64::from_bytes(rem)?;
+        let (era_id, rem) = EraId::from_bytes(rem)?;
+        let (height, rem) = u64::from_bytes(rem)?;
+        let (protocol_version, rem) = ProtocolVersion::from_bytes(rem)?;
+        if !rem.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+
+        Ok(LightBlockHeader {
+            block_hash: account::blake2b(serialized_header),
+            parent_hash,
+            state_root_hash,
+            era_id,
+            height,
+            protocol_version,
+            next_era_validator_weights,
+        })
+    }
+
+    /// Returns the hash of the block.
+    pub fn block_hash(&self) -> &Digest {
+        &self.block_hash
+    }
+
+    /// Returns the hash of the parent block.
+    pub fn parent_hash(&self) -> &Digest {
+        &self.parent_hash
+    }
+
+    /// Returns the root hash of the global state after executing the block.
+    pub fn state_root_hash(&self) -> &Digest {
+        &self.state_root_hash
+    }
+
+    /// Returns the era in which the block was created.
+    pub fn era_id(&self) -> EraId {
+        self.era_id
+    }
+
+    /// Returns the height of the block.
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Returns the protocol version of the block.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Returns the validator weights of the next era if this is a switch block, otherwise `None`.
+    pub fn next_era_validator_weights(&self) -> Option<&BTreeMap<PublicKey, U512>> {
+        self.next_era_validator_weights.as_ref()
+    }
+}
+
+/// Verifies that the block has been finalized by the given validators.
+///
+/// Every signature must be a valid finality signature of the block, and the signatures by members
+/// of `validator_weights` must together account for more than `finality_threshold` of the total
+/// weight.  Signatures by keys which are not in `validator_weights` are ignored.
+pub fn verify_finality_signatures(
+    header: &LightBlockHeader,
+    signatures: &BTreeMap<PublicKey, Signature>,
+    validator_weights: &BTreeMap<PublicKey, U512>,
+    finality_threshold: Ratio<u64>,
+) -> Result<(), Error> {
+    let mut signed_message = header.block_hash.to_vec();
+    signed_message.extend_from_slice(&header.era_id.to_le_bytes());
+
+    let mut signed_weight = U512::zero();
+    for (public_key, signature) in signatures {
+        crypto::verify(&signed_message, signature, public_key)
+            .map_err(|_| Error::InvalidFinalitySignature(public_key.clone()))?;
+        if let Some(weight) = validator_weights.get(public_key) {
+            signed_weight += *weight;
+        }
+    }
+
+    let total_weight = validator_weights
+        .values()
+        .fold(U512::zero(), |total, weight| total + *weight);
+    if signed_weight * U512::from(*finality_threshold.denom())
+        <= total_weight * U512::from(*finality_threshold.numer())
+    {
+        return Err(Error::InsufficientFinalitySignatures);
+    }
+    Ok(())
+}
+
+/// Verifies that `proof` shows `key` to have held `value` in the global state of the block.
+pub fn verify_trie_merkle_proof<K, V>(
+    header: &LightBlockHeader,
+    proof: &TrieMerkleProof<K, V>,
+    key: &K,
+    value: &V,
+) -> Result<(), Error>
+where
+    K: ToBytes + PartialEq,
+    V: ToBytes + PartialEq,
+{
+    if proof.key() != key {
+        return Err(Error::UnexpectedKey);
+    }
+    if proof.value() != value {
+        return Err(Error::UnexpectedValue);
+    }
+    if &proof.compute_state_hash()? != header.state_root_hash() {
+        return Err(Error::StateRootHashMismatch);
+    }
+    Ok(())
+}
+
+/// Verifies the result of a global state query against a finalized block.
+///
+/// The block header must carry sufficient finality signatures as per
+/// [`verify_finality_signatures`], and `proof` must show `key` to have held `value` in the global
+/// state of the block as per [`verify_trie_merkle_proof`].
+pub fn verify_query_result<K, V>(
+    header: &LightBlockHeader,
+    signatures: &BTreeMap<PublicKey, Signature>,
+    validator_weights: &BTreeMap<PublicKey, U512>,
+    finality_threshold: Ratio<u64>,
+    proof: &TrieMerkleProof<K, V>,
+    key: &K,
+    value: &V,
+) -> Result<(), Error>
+where
+    K: ToBytes + PartialEq,
+    V: ToBytes + PartialEq,
+{
+    verify_finality_signatures(header, signatures, validator_weights, finality_threshold)?;
+    verify_trie_merkle_proof(header, proof, key, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::ExpandedSecretKey;
+
+    use super::*;
+    use crate::{AsymmetricType, Key, SecretKey};
+
+    fn leaf_hash(key: &Key, value: &u64) -> Digest {
+        let mut bytes = vec![TRIE_LEAF_TAG];
+        bytes.append(&mut key.to_bytes().unwrap());
+        bytes.append(&mut value.to_bytes().unwrap());
+        account::blake2b(&bytes)
+    }
+
+    fn header(state_root_hash: Digest, era_id: EraId) -> LightBlockHeader {
+        LightBlockHeader {
+            block_hash: [7; BLAKE2B_DIGEST_LENGTH],
+            parent_hash: [6; BLAKE2B_DIGEST_LENGTH],
+            state_root_hash,
+            era_id,
+            height: 11,
+            protocol_version: ProtocolVersion::V1_0_0,
+            next_era_validator_weights: None,
+        }
+    }
+
+    fn sign(secret_key: &SecretKey, header: &LightBlockHeader) -> (PublicKey, Signature) {
+        let public_key = PublicKey::from(secret_key);
+        let (secret_key, ed25519_public_key) = match (secret_key, &public_key) {
+            (SecretKey::Ed25519(secret_key), PublicKey::Ed25519(public_key)) => {
+                (secret_key, public_key)
+            }
+            _ => unreachable!(),
+        };
+        let mut message = header.block_hash().to_vec();
+        message.extend_from_slice(&header.era_id().to_le_bytes());
+        let signature = ExpandedSecretKey::from(secret_key).sign(&message, ed25519_public_key);
+        (public_key, Signature::Ed25519(signature))
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let proof = TrieMerkleProof::new(
+            Key::Hash([1; 32]),
+            5_u64,
+            vec![
+                TrieMerkleProofStep::Node {
+                    hole_index: 3,
+                    indexed_pointers_with_hole: vec![
+                        (1, TriePointer::LeafPointer([2; 32])),
+                        (200, TriePointer::NodePointer([3; 32])),
+                    ],
+                },
+                TrieMerkleProofStep::Extension {
+                    affix: vec![1, 2, 3].into(),
+                },
+            ],
+        );
+        bytesrepr::test_serialization_roundtrip(&proof);
+    }
+
+    #[test]
+    fn should_verify_proof_of_single_leaf() {
+        let key = Key::Hash([1; 32]);
+        let value = 5_u64;
+        let proof = TrieMerkleProof::new(key, value, vec![]);
+        let header = header(leaf_hash(&key, &value), EraId::new(1));
+
+        assert_eq!(
+            verify_trie_merkle_proof(&header, &proof, &key, &value),
+            Ok(())
+        );
+        assert_eq!(
+            verify_trie_merkle_proof(&header, &proof, &Key::Hash([2; 32]), &value),
+            Err(Error::UnexpectedKey)
+        );
+        assert_eq!(
+            verify_trie_merkle_proof(&header, &proof, &key, &6),
+            Err(Error::UnexpectedValue)
+        );
+    }
+
+    #[test]
+    fn should_reject_forged_value() {
+        let key = Key::Hash([1; 32]);
+        let header = header(leaf_hash(&key, &5), EraId::new(1));
+        let forged_proof = TrieMerkleProof::new(key, 6_u64, vec![]);
+
+        assert_eq!(
+            verify_trie_merkle_proof(&header, &forged_proof, &key, &6),
+            Err(Error::StateRootHashMismatch)
+        );
+    }
+
+    #[test]
+    fn should_verify_finality_signatures() {
+        let header = header([1; 32], EraId::new(3));
+        let secret_keys: Vec<SecretKey> = (1..=3_u8)
+            .map(|seed| SecretKey::ed25519_from_bytes([seed; 32]).unwrap())
+            .collect();
+        let validator_weights: BTreeMap<PublicKey, U512> = secret_keys
+            .iter()
+            .map(|secret_key| (PublicKey::from(secret_key), U512::from(100)))
+            .collect();
+        let one_third = Ratio::new(1, 3);
+
+        // A single signature holds exactly one third of the weight, which is not enough.
+        let mut signatures = BTreeMap::new();
+        let (public_key, signature) = sign(&secret_keys[0], &header);
+        signatures.insert(public_key, signature);
+        assert_eq!(
+            verify_finality_signatures(&header, &signatures, &validator_weights, one_third),
+            Err(Error::InsufficientFinalitySignatures)
+        );
+
+        let (public_key, signature) = sign(&secret_keys[1], &header);
+        signatures.insert(public_key, signature);
+        assert_eq!(
+            verify_finality_signatures(&header, &signatures, &validator_weights, one_third),
+            Ok(())
+        );
+
+        // A signature of a different era is invalid.
+        let other_era_header = self::header([1; 32], EraId::new(4));
+        let (public_key, signature) = sign(&secret_keys[2], &other_era_header);
+        signatures.insert(public_key.clone(), signature);
+        assert_eq!(
+            verify_finality_signatures(&header, &signatures, &validator_weights, one_third),
+            Err(Error::InvalidFinalitySignature(public_key))
+        );
+    }
+}
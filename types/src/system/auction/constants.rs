@@ -26,6 +26,8 @@ pub const ARG_PUBLIC_KEY: &str = "public_key";
 pub const ARG_VALIDATOR: &str = "validator";
 /// Named constant for `delegator`.
 pub const ARG_DELEGATOR: &str = "delegator";
+/// Named constant for `new_validator`.
+pub const ARG_NEW_VALIDATOR: &str = "new_validator";
 /// Named constant for `validator_purse`.
 pub const ARG_VALIDATOR_PURSE: &str = "validator_purse";
 /// Named constant for `validator_keys`.
@@ -67,6 +69,8 @@ pub const METHOD_WITHDRAW_BID: &str = "withdraw_bid";
 pub const METHOD_DELEGATE: &str = "delegate";
 /// Named constant for method `undelegate`.
 pub const METHOD_UNDELEGATE: &str = "undelegate";
+/// Named constant for method `redelegate`.
+pub const METHOD_REDELEGATE: &str = "redelegate";
 /// Named constant for method `run_auction`.
 pub const METHOD_RUN_AUCTION: &str = "run_auction";
 /// Named constant for method `slash`.
@@ -92,3 +96,10 @@ pub const AUCTION_DELAY_KEY: &str = "auction_delay";
 pub const LOCKED_FUNDS_PERIOD_KEY: &str = "locked_funds_period";
 /// Unbonding delay expressed in eras.
 pub const UNBONDING_DELAY_KEY: &str = "unbonding_delay";
+/// Minimum amount of motes required to become a new delegator of a validator.
+pub const MINIMUM_DELEGATION_AMOUNT_KEY: &str = "minimum_delegation_amount";
+/// Maximum number of delegators per validator, where zero means no limit.
+pub const MAX_DELEGATORS_PER_VALIDATOR_KEY: &str = "max_delegators_per_validator";
+/// Unbonding requests created through `redelegate`, along with the validators they are
+/// redelegated to.
+pub const REDELEGATIONS_KEY: &str = "redelegations";
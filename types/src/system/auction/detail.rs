@@ -7,8 +7,9 @@ use crate::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        constants::*, Auction, Bids, EraId, Error, RuntimeProvider, SeigniorageAllocation,
-        SeigniorageRecipientsSnapshot, StorageProvider, UnbondingPurse, UnbondingPurses,
+        constants::*, Auction, Bids, Delegator, EraId, Error, RuntimeProvider,
+        SeigniorageAllocation, SeigniorageRecipientsSnapshot, StorageProvider, UnbondingPurse,
+        UnbondingPurses,
     },
     CLTyped, Key, KeyTag, PublicKey, URef, U512,
 };
@@ -156,6 +157,49 @@ where
     write_to(provider, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, snapshot)
 }
 
+/// Returns the unbonding purses created through `redelegate`, each paired with the validator its
+/// amount is redelegated to.
+///
+/// Auction contracts installed before redelegation was introduced have no such purses.
+fn get_redelegations<P>(provider: &mut P) -> Result<Vec<(UnbondingPurse, PublicKey)>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider.named_keys_get(REDELEGATIONS_KEY).is_none() {
+        return Ok(Vec::new());
+    }
+    read_from(provider, REDELEGATIONS_KEY)
+}
+
+fn set_redelegations<P>(
+    provider: &mut P,
+    redelegations: Vec<(UnbondingPurse, PublicKey)>,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    write_to(provider, REDELEGATIONS_KEY, redelegations)
+}
+
+/// Removes the redelegations of all unbonding purses of the given validators.
+pub(crate) fn remove_redelegations<P>(
+    provider: &mut P,
+    validator_public_keys: &[PublicKey],
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let mut redelegations = get_redelegations(provider)?;
+    let redelegation_count = redelegations.len();
+    redelegations.retain(|(unbonding_purse, _)| {
+        !validator_public_keys.contains(unbonding_purse.validator_public_key())
+    });
+    if redelegations.len() != redelegation_count {
+        set_redelegations(provider, redelegations)?;
+    }
+    Ok(())
+}
+
 pub fn get_validator_slots<P>(provider: &mut P) -> Result<usize, Error>
 where
     P: StorageProvider + RuntimeProvider + ?Sized,
@@ -182,6 +226,64 @@ where
     read_from(provider, UNBONDING_DELAY_KEY)
 }
 
+/// Returns the minimum amount required to become a new delegator of a validator.
+///
+/// Auction contracts installed before this limit was introduced have no minimum.
+fn get_minimum_delegation_amount<P>(provider: &mut P) -> Result<U512, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider
+        .named_keys_get(MINIMUM_DELEGATION_AMOUNT_KEY)
+        .is_none()
+    {
+        return Ok(U512::zero());
+    }
+    let minimum_delegation_amount: u64 = read_from(provider, MINIMUM_DELEGATION_AMOUNT_KEY)?;
+    Ok(U512::from(minimum_delegation_amount))
+}
+
+/// Returns the maximum number of delegators per validator, or `None` if there is no limit.
+///
+/// Auction contracts installed before this limit was introduced have no limit.
+fn get_max_delegators_per_validator<P>(provider: &mut P) -> Result<Option<u32>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider
+        .named_keys_get(MAX_DELEGATORS_PER_VALIDATOR_KEY)
+        .is_none()
+    {
+        return Ok(None);
+    }
+    let max_delegators_per_validator: u32 = read_from(provider, MAX_DELEGATORS_PER_VALIDATOR_KEY)?;
+    if max_delegators_per_validator == 0 {
+        return Ok(None);
+    }
+    Ok(Some(max_delegators_per_validator))
+}
+
+/// Checks whether a new delegator of `amount` motes can be added to a validator which currently
+/// has `delegator_count` delegators.
+pub(crate) fn check_new_delegator<P>(
+    provider: &mut P,
+    delegator_count: usize,
+    amount: U512,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if amount < get_minimum_delegation_amount(provider)? {
+        return Err(Error::DelegationAmountTooSmall);
+    }
+    if let Some(max_delegators_per_validator) = get_max_delegators_per_validator(provider)? {
+        if delegator_count >= max_delegators_per_validator as usize {
+            return Err(Error::ExceededDelegatorSizeLimit);
+        }
+    }
+    Ok(())
+}
+
 /// Iterates over unbonding entries and checks if a locked amount can be paid already if
 /// a specific era is reached.
 ///
@@ -198,6 +300,9 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(provider: &mut P) ->
 
     let unbonding_delay = get_unbonding_delay(provider)?;
 
+    let mut redelegations = get_redelegations(provider)?;
+    let redelegation_count = redelegations.len();
+
     for unbonding_list in unbonding_purses.values_mut() {
         let mut new_unbonding_list = Vec::new();
         for unbonding_purse in unbonding_list.iter() {
@@ -205,10 +310,22 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(provider: &mut P) ->
             // current era id + unbonding delay is equal or greater than the `era_of_creation` that
             // was calculated on `unbond` attempt.
             if current_era_id >= unbonding_purse.era_of_creation() + unbonding_delay {
-                // Move funds from bid purse to unbonding purse
-                provider
-                    .unbond(unbonding_purse)
-                    .map_err(|_| Error::TransferToUnbondingPurse)?;
+                let redelegation_index = redelegations
+                    .iter()
+                    .position(|(redelegated_purse, _)| redelegated_purse == unbonding_purse);
+                let redelegated = match redelegation_index {
+                    Some(index) => {
+                        let (_, new_validator) = redelegations.swap_remove(index);
+                        redelegate_unbonding_purse(provider, unbonding_purse, &new_validator)?
+                    }
+                    None => false,
+                };
+                if !redelegated {
+                    // Move funds from bid purse to unbonding purse
+                    provider
+                        .unbond(unbonding_purse)
+                        .map_err(|_| Error::TransferToUnbondingPurse)?;
+                }
             } else {
                 new_unbonding_list.push(unbonding_purse.clone());
             }
@@ -217,17 +334,92 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(provider: &mut P) ->
     }
 
     set_unbonding_purses(provider, unbonding_purses)?;
+    if redelegations.len() != redelegation_count {
+        set_redelegations(provider, redelegations)?;
+    }
     Ok(())
 }
 
+/// Moves the funds of a matured unbonding purse into a delegation with `new_validator`.
+///
+/// Returns `false` without moving any funds if `new_validator` no longer has an active bid or can't
+/// accept the unbonder as a new delegator, in which case the unbonding purse should be paid out to
+/// the unbonder instead.
+fn redelegate_unbonding_purse<P: Auction + ?Sized>(
+    provider: &mut P,
+    unbonding_purse: &UnbondingPurse,
+    new_validator: &PublicKey,
+) -> Result<bool, Error> {
+    let new_validator_account_hash = AccountHash::from(new_validator);
+    let mut bid = match provider.read_bid(&new_validator_account_hash)? {
+        Some(bid) if !bid.inactive() => bid,
+        _ => return Ok(false),
+    };
+
+    let delegator_public_key = unbonding_purse.unbonder_public_key();
+    let amount = *unbonding_purse.amount();
+    let delegators = bid.delegators_mut();
+
+    match delegators.get_mut(delegator_public_key) {
+        Some(delegator) => {
+            provider
+                .mint_transfer_direct(
+                    Some(PublicKey::System.to_account_hash()),
+                    *unbonding_purse.bonding_purse(),
+                    *delegator.bonding_purse(),
+                    amount,
+                    None,
+                )
+                .map_err(|_| Error::TransferToDelegatorPurse)?
+                .map_err(|_| Error::TransferToDelegatorPurse)?;
+            delegator.increase_stake(amount)?;
+        }
+        None => {
+            match check_new_delegator(provider, delegators.len(), amount) {
+                Ok(()) => {}
+                Err(Error::DelegationAmountTooSmall) | Err(Error::ExceededDelegatorSizeLimit) => {
+                    return Ok(false)
+                }
+                Err(error) => return Err(error),
+            }
+            let bonding_purse = provider.create_purse()?;
+            provider
+                .mint_transfer_direct(
+                    Some(PublicKey::System.to_account_hash()),
+                    *unbonding_purse.bonding_purse(),
+                    bonding_purse,
+                    amount,
+                    None,
+                )
+                .map_err(|_| Error::TransferToDelegatorPurse)?
+                .map_err(|_| Error::TransferToDelegatorPurse)?;
+            let delegator = Delegator::unlocked(
+                delegator_public_key.clone(),
+                amount,
+                bonding_purse,
+                new_validator.clone(),
+            );
+            delegators.insert(delegator_public_key.clone(), delegator);
+        }
+    }
+
+    provider.write_bid(new_validator_account_hash, bid)?;
+
+    Ok(true)
+}
+
 /// Creates a new purse in unbonding_purses given a validator's key, amount, and a destination
 /// unbonding purse. Returns the amount of motes remaining in the validator's bid purse.
+///
+/// If `new_validator` is given, the amount is redelegated to that validator instead of being paid
+/// out to the unbonder once the unbonding delay elapses.
 pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     provider: &mut P,
     validator_public_key: PublicKey,
     unbonder_public_key: PublicKey,
     bonding_purse: URef,
     amount: U512,
+    new_validator: Option<PublicKey>,
 ) -> Result<(), Error> {
     if provider.get_balance(bonding_purse)?.unwrap_or_default() < amount {
         return Err(Error::UnbondTooLarge);
@@ -243,6 +435,11 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
         era_of_creation,
         amount,
     );
+    if let Some(new_validator) = new_validator {
+        let mut redelegations = get_redelegations(provider)?;
+        redelegations.push((new_unbonding_purse.clone(), new_validator));
+        set_redelegations(provider, redelegations)?;
+    }
     unbonding_purses.push(new_unbonding_purse);
     provider.write_withdraw(validator_account_hash, unbonding_purses)?;
 
@@ -152,6 +152,15 @@ pub enum Error {
     /// An arithmetic overflow has occurred.
     #[cfg_attr(feature = "std", error("Arithmetic overflow"))]
     ArithmeticOverflow = 39,
+    /// The validator to redelegate to does not have an active bid.
+    #[cfg_attr(feature = "std", error("Redelegation validator not found"))]
+    RedelegationValidatorNotFound = 40,
+    /// The amount of a new delegation is below the minimum delegation amount.
+    #[cfg_attr(feature = "std", error("Delegation amount too small"))]
+    DelegationAmountTooSmall = 41,
+    /// The validator already has the maximum number of delegators.
+    #[cfg_attr(feature = "std", error("Exceeded delegator size limit"))]
+    ExceededDelegatorSizeLimit = 42,
 
     // NOTE: These variants below and related plumbing will be removed once support for WASM
     // system contracts will be dropped.
@@ -233,6 +242,13 @@ impl TryFrom<u8> for Error {
             d if d == Error::DelegatorFundsLocked as u8 => Ok(Error::DelegatorFundsLocked),
             d if d == Error::GasLimit as u8 => Ok(Error::GasLimit),
             d if d == Error::ArithmeticOverflow as u8 => Ok(Error::ArithmeticOverflow),
+            d if d == Error::RedelegationValidatorNotFound as u8 => {
+                Ok(Error::RedelegationValidatorNotFound)
+            }
+            d if d == Error::DelegationAmountTooSmall as u8 => Ok(Error::DelegationAmountTooSmall),
+            d if d == Error::ExceededDelegatorSizeLimit as u8 => {
+                Ok(Error::ExceededDelegatorSizeLimit)
+            }
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -230,6 +230,9 @@ pub trait Auction:
     /// Adds a new delegator to delegators, or tops off a current one. If the target validator is
     /// not in founders, the function call returns an error and does nothing.
     ///
+    /// A new delegator must delegate at least the minimum delegation amount, and can't be added
+    /// to a validator which already has the maximum number of delegators.
+    ///
     /// The function calls bond in the Mint contract to transfer motes to the validator's purse and
     /// returns a tuple of that purse and the amount of motes contained in it after the transfer.
     fn delegate(
@@ -286,6 +289,7 @@ pub trait Auction:
                 *delegator.staked_amount()
             }
             None => {
+                detail::check_new_delegator(self, delegators.len(), amount)?;
                 let bonding_purse = self.create_purse()?;
                 self.mint_transfer_direct(
                     Some(PublicKey::System.to_account_hash()),
@@ -355,6 +359,78 @@ pub trait Auction:
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
+                    None,
+                )?;
+
+                let era_end_timestamp_millis = detail::get_era_end_timestamp_millis(self)?;
+                let updated_stake = delegator.decrease_stake(amount, era_end_timestamp_millis)?;
+                if updated_stake == U512::zero() {
+                    delegators.remove(&delegator_public_key);
+                };
+                updated_stake
+            }
+            None => return Err(Error::DelegatorNotFound),
+        };
+
+        self.write_bid(validator_account_hash, bid)?;
+
+        Ok(new_amount)
+    }
+
+    /// Removes an amount of motes (or the entry altogether, if the remaining amount is 0) from
+    /// the entry in delegators and creates a new unbonding purse which, once the unbonding delay
+    /// elapses, is delegated to `new_validator` rather than paid out to the delegator.
+    ///
+    /// If `new_validator` no longer has an active bid by the time the unbonding delay elapses, or
+    /// can't accept the delegator as a new delegator due to the delegation limits, the funds are
+    /// paid out to the delegator as with [`Auction::undelegate`].
+    ///
+    /// Returns the remaining bid amount with the current validator.
+    fn redelegate(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+        new_validator: PublicKey,
+    ) -> Result<U512, Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+        match self.get_immediate_caller() {
+            Some(&CallStackElement::Session { account_hash })
+                if account_hash != provided_account_hash =>
+            {
+                return Err(Error::InvalidContext)
+            }
+            Some(&CallStackElement::StoredSession { .. }) => {
+                // stored session code is not allowed to call this method
+                return Err(Error::InvalidContext);
+            }
+            _ => {}
+        };
+
+        let new_validator_account_hash = AccountHash::from(&new_validator);
+        match self.read_bid(&new_validator_account_hash)? {
+            Some(bid) if !bid.inactive() => {}
+            _ => return Err(Error::RedelegationValidatorNotFound),
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+        let mut bid = match self.read_bid(&validator_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        let delegators = bid.delegators_mut();
+
+        let new_amount = match delegators.get_mut(&delegator_public_key) {
+            Some(delegator) => {
+                detail::create_unbonding_purse(
+                    self,
+                    validator_public_key,
+                    delegator_public_key.clone(),
+                    *delegator.bonding_purse(),
+                    amount,
+                    Some(new_validator),
                 )?;
 
                 let era_end_timestamp_millis = detail::get_era_end_timestamp_millis(self)?;
@@ -382,9 +458,9 @@ pub trait Auction:
 
         let mut burned_amount: U512 = U512::zero();
 
-        for validator_public_key in validator_public_keys {
+        for validator_public_key in &validator_public_keys {
             // Burn stake, deactivate
-            let validator_account_hash = AccountHash::from(&validator_public_key);
+            let validator_account_hash = AccountHash::from(validator_public_key);
             if let Some(mut bid) = self.read_bid(&validator_account_hash)? {
                 burned_amount += *bid.staked_amount();
                 *bid.staked_amount_mut() = U512::zero();
@@ -396,7 +472,7 @@ pub trait Auction:
                 self.write_bid(validator_account_hash, bid)?;
             };
 
-            let validator_account_hash = AccountHash::from(&validator_public_key);
+            let validator_account_hash = AccountHash::from(validator_public_key);
             // Update unbonding entries for given validator
             let unbonding_purses = self.read_withdraw(&validator_account_hash)?;
             if !unbonding_purses.is_empty() {
@@ -408,6 +484,8 @@ pub trait Auction:
             }
         }
 
+        detail::remove_redelegations(self, &validator_public_keys)?;
+
         self.reduce_total_supply(burned_amount)?;
 
         Ok(())
@@ -120,7 +120,7 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if amount > source_balance {
             return Err(Error::InsufficientFunds);
         }
-        if self.read_balance(target)?.is_none() {
+        if !self.balance_exists(target)? {
             return Err(Error::DestNotFound);
         }
         self.write_balance(source, source_balance - amount)?;